@@ -1,8 +1,11 @@
 use cairo_vm::{
-    types::{layout_name::LayoutName, program::Program},
-    vm::runners::cairo_runner::CairoRunner,
+    crypto::{keccak_builtin_permutation, keccak_f1600},
+    types::{layout_name::LayoutName, program::Program, relocatable::MaybeRelocatable},
+    vm::{runners::cairo_runner::CairoRunner, vm_core::VirtualMachine},
+    Felt252,
 };
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use num_bigint::BigUint;
 
 use mimalloc::MiMalloc;
 
@@ -62,5 +65,78 @@ fn load_program_data(c: &mut Criterion) {
     });
 }
 
+// Byte-oriented deduction of the keccak builtin outputs, going through a BigUint to build the
+// state lanes, kept as a baseline for the lane-packing one used by the builtin
+fn naive_keccak_builtin_permutation(cells: &[Felt252; 8]) -> [Felt252; 8] {
+    let input_message: Vec<u8> = cells
+        .iter()
+        .flat_map(|x| {
+            let mut bytes = x.to_bytes_le().to_vec();
+            bytes.resize(25, 0);
+            bytes
+        })
+        .collect();
+    let mut state = BigUint::from_bytes_le(&input_message).to_u64_digits();
+    state.resize(25, 0);
+    let mut state: [u64; 25] = state.try_into().unwrap();
+    keccak_f1600(&mut state);
+    let output_message: Vec<u8> = state.iter().flat_map(|x| x.to_le_bytes()).collect();
+    core::array::from_fn(|i| {
+        let mut bytes = output_message[i * 25..(i + 1) * 25].to_vec();
+        bytes.resize(32, 0);
+        Felt252::from_bytes_le_slice(&bytes)
+    })
+}
+
+fn keccak_builtin_deduction(c: &mut Criterion) {
+    let max = Felt252::TWO.pow(200_u32) - Felt252::ONE;
+    let cells: [Felt252; 8] =
+        core::array::from_fn(|i| max - Felt252::from((i as u64).wrapping_mul(0x9e3779b97f4a7c15)));
+    assert_eq!(
+        keccak_builtin_permutation(&cells),
+        naive_keccak_builtin_permutation(&cells)
+    );
+    let mut group = c.benchmark_group("keccak builtin deduction");
+    group.bench_function("lanes", |b| {
+        b.iter(|| keccak_builtin_permutation(black_box(&cells)))
+    });
+    group.bench_function("naive", |b| {
+        b.iter(|| naive_keccak_builtin_permutation(black_box(&cells)))
+    });
+    group.finish();
+}
+
+// Reading a blake2s message (16 felts) the way hash hints do, collecting the values into a vector
+// vs iterating over them straight from memory
+fn memory_felt_range(c: &mut Criterion) {
+    let mut vm = VirtualMachine::new(false);
+    let base = vm.add_memory_segment();
+    let data: Vec<MaybeRelocatable> = (0..16_u64)
+        .map(|i| Felt252::from(u32::MAX as u64 - i).into())
+        .collect();
+    vm.load_data(base, &data).unwrap();
+    let mut group = c.benchmark_group("memory felt range");
+    group.bench_function("get_integer_range", |b| {
+        b.iter(|| {
+            black_box(&vm)
+                .get_integer_range(black_box(base), 16)
+                .unwrap()
+                .iter()
+                .fold(Felt252::ZERO, |acc, x| acc + x.as_ref())
+        })
+    });
+    group.bench_function("get_felt_range", |b| {
+        b.iter(|| {
+            black_box(&vm)
+                .get_felt_range(black_box(base), 16)
+                .unwrap()
+                .fold(Felt252::ZERO, |acc, x| acc + x)
+        })
+    });
+    group.finish();
+}
+
 criterion_group!(runner, build_many_runners, load_program_data, parse_program);
-criterion_main!(runner);
+criterion_group!(builtins, keccak_builtin_deduction);
+criterion_group!(memory, memory_felt_range);
+criterion_main!(runner, builtins, memory);
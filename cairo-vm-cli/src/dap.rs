@@ -0,0 +1,415 @@
+//! A minimal Debug Adapter Protocol server, so that editors such as VS Code can debug a run.
+//!
+//! The server talks DAP over the given reader and writer (stdin/stdout when launched with
+//! `--dap`) and supports a single thread with a single stack frame: breakpoints on source lines,
+//! continuing, stepping one instruction at a time, and inspecting the registers, the current
+//! frame's memory and the ids visible to the hints at the current pc.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+use cairo_vm::types::layout::CairoLayoutParams;
+use cairo_vm::types::layout_name::LayoutName;
+use cairo_vm::types::program::Program;
+use cairo_vm::types::relocatable::Relocatable;
+use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
+use cairo_vm::vm::runners::cairo_runner::{CairoRunner, DebugSession};
+use serde_json::{json, Value};
+
+use crate::Error;
+
+const THREAD_ID: u64 = 1;
+const REGISTERS_REFERENCE: u64 = 1;
+const MEMORY_REFERENCE: u64 = 2;
+const IDS_REFERENCE: u64 = 3;
+
+struct Session {
+    program: Program,
+    runner: CairoRunner,
+    hint_processor: BuiltinHintProcessor,
+    debug_session: DebugSession,
+    end: Relocatable,
+    breakpoints: HashSet<Relocatable>,
+}
+
+impl Session {
+    fn new(
+        program: Program,
+        layout: LayoutName,
+        dynamic_layout_params: Option<CairoLayoutParams>,
+    ) -> Result<Self, CairoRunError> {
+        let mut runner = CairoRunner::new(&program, layout, dynamic_layout_params, false, false)?;
+        let end = runner.initialize(false)?;
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let debug_session = runner.start_debug_session(&mut hint_processor)?;
+        Ok(Session {
+            program,
+            runner,
+            hint_processor,
+            debug_session,
+            end,
+            breakpoints: HashSet::new(),
+        })
+    }
+
+    fn finished(&self) -> bool {
+        self.runner.vm.get_pc() == self.end
+    }
+
+    /// Sets the breakpoints of a source file, mapping each line to the first instruction on it.
+    /// Returns the breakpoints as reported to the client.
+    fn set_breakpoints(&mut self, path: &str, lines: &[u64]) -> Vec<Value> {
+        let path = canonical_path(path);
+        // Each source file is resolved once, instead of once per instruction
+        let mut is_source: HashMap<&str, bool> = HashMap::new();
+        let source_pcs: Vec<usize> = (0..self.program.data_len())
+            .filter(|pc| {
+                self.program
+                    .get_instruction_location(*pc)
+                    .is_some_and(|location| {
+                        let filename = location.inst.input_file.filename.as_str();
+                        *is_source
+                            .entry(filename)
+                            .or_insert_with(|| canonical_path(filename) == path)
+                    })
+            })
+            .collect();
+        self.breakpoints
+            .retain(|pc| !source_pcs.contains(&pc.offset));
+        lines
+            .iter()
+            .map(|line| {
+                let pc = source_pcs.iter().find(|pc| {
+                    self.program
+                        .get_instruction_location(**pc)
+                        .is_some_and(|location| u64::from(location.inst.start_line) == *line)
+                });
+                if let Some(pc) = pc {
+                    self.breakpoints.insert(Relocatable::from((0, *pc)));
+                }
+                json!({ "verified": pc.is_some(), "line": line })
+            })
+            .collect()
+    }
+
+    fn stack_frame(&self) -> Value {
+        let pc = self.runner.vm.get_pc();
+        let location = (pc.segment_index == 0)
+            .then(|| self.program.get_instruction_location(pc.offset))
+            .flatten();
+        match location {
+            Some(location) => json!({
+                "id": 0,
+                "name": pc.to_string(),
+                "source": { "path": location.inst.input_file.filename },
+                "line": location.inst.start_line,
+                "column": location.inst.start_col,
+            }),
+            None => json!({ "id": 0, "name": pc.to_string(), "line": 0, "column": 0 }),
+        }
+    }
+
+    fn variables(&self, reference: u64) -> Vec<Value> {
+        let vm = &self.runner.vm;
+        match reference {
+            REGISTERS_REFERENCE => {
+                let registers = vm.get_registers();
+                vec![
+                    variable("pc".to_string(), registers.pc.to_string()),
+                    variable("ap".to_string(), registers.ap.to_string()),
+                    variable("fp".to_string(), registers.fp.to_string()),
+                ]
+            }
+            MEMORY_REFERENCE => {
+                let (fp, ap) = (vm.get_fp(), vm.get_ap());
+                (fp.offset..ap.offset)
+                    .map(|offset| {
+                        let addr = Relocatable::from((fp.segment_index, offset));
+                        let value = vm
+                            .get_maybe(&addr)
+                            .map_or_else(|| "unknown".to_string(), |value| value.to_string());
+                        variable(addr.to_string(), value)
+                    })
+                    .collect()
+            }
+            IDS_REFERENCE => self
+                .runner
+                .get_ids_at_pc()
+                .into_iter()
+                .map(|(name, value)| {
+                    let value =
+                        value.map_or_else(|| "unknown".to_string(), |value| value.to_string());
+                    variable(name, value)
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Resolves `path` to an absolute path without symlinks, so that a source file matches however
+/// the client and the compiler spelled it. Relative paths are resolved from the current directory,
+/// and paths that can't be resolved are kept as they are.
+fn canonical_path(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Serves a debugging session of the program until the client disconnects.
+pub(crate) fn serve(
+    program: Program,
+    layout: LayoutName,
+    dynamic_layout_params: Option<CairoLayoutParams>,
+    input: impl BufRead,
+    output: impl Write,
+) -> Result<(), Error> {
+    DapServer {
+        input,
+        output,
+        seq: 0,
+    }
+    .serve(program, layout, dynamic_layout_params)
+}
+
+struct DapServer<R, W> {
+    input: R,
+    output: W,
+    seq: u64,
+}
+
+impl<R: BufRead, W: Write> DapServer<R, W> {
+    fn serve(
+        &mut self,
+        program: Program,
+        layout: LayoutName,
+        dynamic_layout_params: Option<CairoLayoutParams>,
+    ) -> Result<(), Error> {
+        let mut session = Session::new(program, layout, dynamic_layout_params)?;
+        while let Some(request) = self.read_message()? {
+            let command = request["command"].as_str().unwrap_or_default();
+            let arguments = &request["arguments"];
+            let mut body = json!({});
+            let mut events = Vec::new();
+            match command {
+                "initialize" => {
+                    body = json!({ "supportsConfigurationDoneRequest": true });
+                    events.push(("initialized", json!({})));
+                }
+                "launch" => events.push(("stopped", stopped_body("entry"))),
+                "setBreakpoints" => {
+                    let path = arguments["source"]["path"].as_str().unwrap_or_default();
+                    let lines: Vec<u64> = arguments["breakpoints"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                        .collect();
+                    body = json!({ "breakpoints": session.set_breakpoints(path, &lines) });
+                }
+                "threads" => {
+                    body = json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] });
+                }
+                "continue" => {
+                    let hit = session.runner.run_until_breakpoint(
+                        session.end,
+                        &session.breakpoints,
+                        &mut session.hint_processor,
+                        &mut session.debug_session,
+                    );
+                    events.push(match hit {
+                        Ok(Some(_)) => ("stopped", stopped_body("breakpoint")),
+                        Ok(None) => ("terminated", json!({})),
+                        Err(error) => ("stopped", exception_body(error)),
+                    });
+                }
+                "next" | "stepIn" | "stepOut" => {
+                    let step = if session.finished() {
+                        Ok(())
+                    } else {
+                        session
+                            .runner
+                            .single_step(&mut session.hint_processor, &mut session.debug_session)
+                            .map(|_| ())
+                    };
+                    events.push(match step {
+                        Ok(()) if session.finished() => ("terminated", json!({})),
+                        Ok(()) => ("stopped", stopped_body("step")),
+                        Err(error) => ("stopped", exception_body(error)),
+                    });
+                }
+                "stackTrace" => {
+                    body = json!({ "stackFrames": [session.stack_frame()], "totalFrames": 1 });
+                }
+                "scopes" => {
+                    body = json!({ "scopes": [
+                        scope("Registers", REGISTERS_REFERENCE),
+                        scope("Frame", MEMORY_REFERENCE),
+                        scope("Ids", IDS_REFERENCE),
+                    ]});
+                }
+                "variables" => {
+                    let reference = arguments["variablesReference"].as_u64().unwrap_or_default();
+                    body = json!({ "variables": session.variables(reference) });
+                }
+                "disconnect" => {
+                    self.respond(&request, true, body)?;
+                    return Ok(());
+                }
+                "configurationDone" => {}
+                _ => {
+                    self.respond(&request, false, json!({}))?;
+                    continue;
+                }
+            }
+            self.respond(&request, true, body)?;
+            for (event, body) in events {
+                self.send(json!({ "type": "event", "event": event, "body": body }))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a message framed by a `Content-Length` header.
+    /// Returns `None` once the client closes the stream.
+    fn read_message(&mut self) -> io::Result<Option<Value>> {
+        let mut content_length = None;
+        loop {
+            let mut header = String::new();
+            if self.input.read_line(&mut header)? == 0 {
+                return Ok(None);
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(length) = header.strip_prefix("Content-Length:") {
+                content_length = length.trim().parse::<usize>().ok();
+            }
+        }
+        let content_length = content_length.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+        })?;
+        let mut content = vec![0; content_length];
+        self.input.read_exact(&mut content)?;
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+
+    fn respond(&mut self, request: &Value, success: bool, body: Value) -> io::Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": success,
+            "body": body,
+        }))
+    }
+
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let content = serde_json::to_vec(&message)?;
+        write!(self.output, "Content-Length: {}\r\n\r\n", content.len())?;
+        self.output.write_all(&content)?;
+        self.output.flush()
+    }
+}
+
+fn variable(name: String, value: String) -> Value {
+    json!({ "name": name, "value": value, "variablesReference": 0 })
+}
+
+fn scope(name: &str, reference: u64) -> Value {
+    json!({ "name": name, "variablesReference": reference, "expensive": false })
+}
+
+fn stopped_body(reason: &str) -> Value {
+    json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true })
+}
+
+fn exception_body(error: impl ToString) -> Value {
+    json!({
+        "reason": "exception",
+        "description": error.to_string(),
+        "threadId": THREAD_ID,
+        "allThreadsStopped": true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: Value) -> String {
+        let content = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{content}", content.len())
+    }
+
+    #[test]
+    fn step_and_continue_to_the_end() {
+        let program =
+            Program::from_file("../cairo_programs/fibonacci.json".as_ref(), Some("main")).unwrap();
+        let input: String = [
+            json!({ "seq": 1, "type": "request", "command": "initialize" }),
+            json!({ "seq": 2, "type": "request", "command": "launch" }),
+            json!({ "seq": 3, "type": "request", "command": "next" }),
+            json!({
+                "seq": 4,
+                "type": "request",
+                "command": "variables",
+                "arguments": { "variablesReference": REGISTERS_REFERENCE },
+            }),
+            json!({ "seq": 5, "type": "request", "command": "continue" }),
+            json!({ "seq": 6, "type": "request", "command": "disconnect" }),
+        ]
+        .into_iter()
+        .map(frame)
+        .collect();
+
+        let mut output = Vec::new();
+        serve(
+            program,
+            LayoutName::plain,
+            None,
+            input.as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(r#""event":"initialized""#));
+        assert!(output.contains(r#""reason":"entry""#));
+        assert!(output.contains(r#""reason":"step""#));
+        assert!(output.contains(r#""name":"pc""#));
+        assert!(output.contains(r#""event":"terminated""#));
+        assert!(!output.contains(r#""success":false"#));
+    }
+
+    #[test]
+    fn canonical_path_tells_same_named_files_apart() {
+        assert_eq!(
+            canonical_path("../cairo-vm-cli/src/dap.rs"),
+            canonical_path("src/dap.rs")
+        );
+        assert_ne!(
+            canonical_path("../vm/Cargo.toml"),
+            canonical_path("Cargo.toml")
+        );
+        assert_eq!(
+            canonical_path("missing/foo.cairo"),
+            PathBuf::from("missing/foo.cairo")
+        );
+    }
+
+    #[test]
+    fn dynamic_layout_params_are_used() {
+        let program =
+            Program::from_file("../cairo_programs/fibonacci.json".as_ref(), Some("main")).unwrap();
+        let params =
+            CairoLayoutParams::from_file("../vm/src/tests/cairo_layout_params_file.json".as_ref())
+                .unwrap();
+        assert!(Session::new(program.clone(), LayoutName::dynamic, Some(params)).is_ok());
+        assert!(Session::new(program, LayoutName::dynamic, None).is_err());
+    }
+}
@@ -3,11 +3,14 @@
 use bincode::enc::write::Writer;
 use cairo_vm::air_public_input::PublicInputError;
 use cairo_vm::cairo_run::{self, EncodeTraceError};
+use cairo_vm::coverage::Coverage;
 use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
 #[cfg(feature = "with_tracer")]
 use cairo_vm::serde::deserialize_program::DebugInfo;
 use cairo_vm::types::layout::CairoLayoutParams;
 use cairo_vm::types::layout_name::LayoutName;
+#[cfg(feature = "dap")]
+use cairo_vm::types::program::Program;
 use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_vm::vm::errors::trace_errors::TraceError;
 use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
@@ -24,6 +27,9 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+#[cfg(feature = "dap")]
+mod dap;
+
 #[cfg(feature = "with_mimalloc")]
 use mimalloc::MiMalloc;
 
@@ -44,6 +50,12 @@ struct Args {
     entrypoint: String,
     #[structopt(long = "memory_file")]
     memory_file: Option<PathBuf>,
+    /// Write the source coverage of the run in the lcov format
+    #[clap(long = "lcov_file", value_parser)]
+    lcov_file: Option<PathBuf>,
+    /// Write the source coverage of the run in the Cobertura XML format
+    #[clap(long = "cobertura_file", value_parser)]
+    cobertura_file: Option<PathBuf>,
     /// When using dynamic layout, it's parameters must be specified through a layout params file.
     #[clap(long = "layout", default_value = "plain", value_enum)]
     layout: LayoutName,
@@ -55,6 +67,9 @@ struct Args {
     proof_mode: bool,
     #[structopt(long = "secure_run")]
     secure_run: Option<bool>,
+    /// Fill the unused builtin instances with valid values at the end of the run
+    #[clap(long = "fill_builtin_holes", requires = "proof_mode")]
+    fill_builtin_holes: bool,
     #[clap(long = "air_public_input", requires = "proof_mode")]
     air_public_input: Option<String>,
     #[clap(
@@ -81,6 +96,10 @@ struct Args {
         conflicts_with_all = ["proof_mode", "air_private_input", "air_public_input"]
     )]
     run_from_cairo_pie: bool,
+    /// Debug the program with a Debug Adapter Protocol client over stdin/stdout instead of running it
+    #[structopt(long = "dap")]
+    #[cfg(feature = "dap")]
+    dap: bool,
 }
 
 #[derive(Debug, Error)]
@@ -99,6 +118,8 @@ enum Error {
     Trace(#[from] TraceError),
     #[error(transparent)]
     PublicInput(#[from] PublicInputError),
+    #[error("Failed to format the coverage report")]
+    Coverage(std::fmt::Error),
     #[error(transparent)]
     #[cfg(feature = "with_tracer")]
     TraceData(#[from] TraceDataError),
@@ -166,13 +187,29 @@ fn start_tracer(cairo_runner: &CairoRunner) -> Result<(), TraceDataError> {
 fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
     let args = Args::try_parse_from(args)?;
 
-    let trace_enabled = args.trace_file.is_some() || args.air_public_input.is_some();
+    let trace_enabled = args.trace_file.is_some()
+        || args.air_public_input.is_some()
+        || args.lcov_file.is_some()
+        || args.cobertura_file.is_some();
 
     let cairo_layout_params = match args.cairo_layout_params_file {
         Some(file) => Some(CairoLayoutParams::from_file(&file)?),
         None => None,
     };
 
+    #[cfg(feature = "dap")]
+    if args.dap {
+        let program = Program::from_file(&args.filename, Some(&args.entrypoint))
+            .map_err(CairoRunError::Program)?;
+        return dap::serve(
+            program,
+            args.layout,
+            cairo_layout_params,
+            io::stdin().lock(),
+            io::stdout().lock(),
+        );
+    }
+
     let cairo_run_config = cairo_run::CairoRunConfig {
         entrypoint: &args.entrypoint,
         trace_enabled,
@@ -182,6 +219,7 @@ fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
         secure_run: args.secure_run,
         allow_missing_builtins: args.allow_missing_builtins,
         dynamic_layout_params: cairo_layout_params,
+        fill_builtin_holes: args.fill_builtin_holes,
         ..Default::default()
     };
 
@@ -233,6 +271,22 @@ fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
         memory_writer.flush()?;
     }
 
+    if args.lcov_file.is_some() || args.cobertura_file.is_some() {
+        let coverage = Coverage::from_runner(&cairo_runner)?;
+        if let Some(ref lcov_path) = args.lcov_file {
+            let mut lcov = String::new();
+            coverage.write_lcov(&mut lcov).map_err(Error::Coverage)?;
+            std::fs::write(lcov_path, lcov)?;
+        }
+        if let Some(ref cobertura_path) = args.cobertura_file {
+            let mut cobertura = String::new();
+            coverage
+                .write_cobertura(&mut cobertura)
+                .map_err(Error::Coverage)?;
+            std::fs::write(cobertura_path, cobertura)?;
+        }
+    }
+
     if let Some(file_path) = args.air_public_input {
         let json = cairo_runner.get_air_public_input()?.serialize_json()?;
         std::fs::write(file_path, json)?;
@@ -406,6 +460,21 @@ mod tests {
         assert_matches!(run(args), Err(Error::IO(_)));
     }
 
+    #[test]
+    fn test_run_coverage() {
+        let args = [
+            "cairo-vm-cli",
+            "../cairo_programs/fibonacci.json",
+            "--lcov_file",
+            "/dev/null",
+            "--cobertura_file",
+            "/dev/null",
+        ]
+        .into_iter()
+        .map(String::from);
+        assert_matches!(run(args), Ok(_));
+    }
+
     #[rstest]
     #[case("../cairo_programs/manually_compiled/invalid_even_length_hex.json")]
     #[case("../cairo_programs/manually_compiled/invalid_memory.json")]
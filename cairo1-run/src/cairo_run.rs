@@ -43,7 +43,7 @@ use cairo_vm::{
         program::Program, relocatable::MaybeRelocatable,
     },
     vm::{
-        errors::{runner_errors::RunnerError, vm_errors::VirtualMachineError},
+        errors::vm_errors::VirtualMachineError,
         runners::cairo_runner::{CairoRunner, RunResources, RunnerMode},
         vm_core::VirtualMachine,
     },
@@ -77,8 +77,11 @@ impl From<Vec<Felt252>> for FuncArg {
 /// Configuration parameters for a cairo run
 #[derive(Debug)]
 pub struct Cairo1RunConfig<'a> {
-    /// Input arguments for the `main` function in the cairo progran
+    /// Input arguments for the entrypoint function in the cairo progran
     pub args: &'a [FuncArg],
+    /// Name of the function to run, matched as a suffix of the full function path.
+    /// Defaults to `::main`
+    pub entrypoint: &'a str,
     /// Serialize program output into a user-friendly format
     pub serialize_output: bool,
     /// Compute cairo trace during execution
@@ -101,6 +104,7 @@ impl Default for Cairo1RunConfig<'_> {
     fn default() -> Self {
         Self {
             args: Default::default(),
+            entrypoint: "::main",
             serialize_output: false,
             trace_enabled: false,
             relocate_mem: false,
@@ -141,7 +145,7 @@ pub fn cairo_run_program(
     let casm_program =
         cairo_lang_sierra_to_casm::compiler::compile(sierra_program, &metadata, config)?;
 
-    let main_func = find_function(sierra_program, "::main")?;
+    let main_func = find_function(sierra_program, cairo_run_config.entrypoint)?;
 
     let initial_gas = 9999999999999_usize;
 
@@ -377,7 +381,7 @@ fn build_hints_vec<'b>(
 fn find_function<'a>(
     sierra_program: &'a SierraProgram,
     name_suffix: &'a str,
-) -> Result<&'a Function, RunnerError> {
+) -> Result<&'a Function, Error> {
     sierra_program
         .funcs
         .iter()
@@ -388,7 +392,7 @@ fn find_function<'a>(
                 false
             }
         })
-        .ok_or_else(|| RunnerError::MissingMain)
+        .ok_or_else(|| Error::MissingFunction(name_suffix.to_string()))
 }
 
 // Function derived from the cairo-lang-runner crate.
@@ -1568,6 +1572,7 @@ mod tests {
     use std::path::Path;
 
     use super::*;
+    use assert_matches::assert_matches;
     use cairo_lang_compiler::{
         compile_prepared_db, db::RootDatabase, project::setup_project, CompilerConfig,
     };
@@ -1699,6 +1704,40 @@ mod tests {
         assert_eq!(hash_a, hash_b)
     }
 
+    #[test]
+    fn run_custom_entrypoint() {
+        let sierra_program =
+            compile_to_sierra("../cairo_programs/cairo-1-programs/fibonacci.cairo");
+        let cairo_run_config = Cairo1RunConfig {
+            entrypoint: "::fib",
+            args: &[
+                FuncArg::Single(Felt252::ONE),
+                FuncArg::Single(Felt252::ONE),
+                FuncArg::Single(Felt252::from(10)),
+            ],
+            ..Default::default()
+        };
+        let (_, return_values, _) = cairo_run_program(&sierra_program, cairo_run_config).unwrap();
+        assert_eq!(
+            return_values,
+            vec![MaybeRelocatable::from(Felt252::from(89))]
+        );
+    }
+
+    #[test]
+    fn run_missing_entrypoint() {
+        let sierra_program =
+            compile_to_sierra("../cairo_programs/cairo-1-programs/fibonacci.cairo");
+        let cairo_run_config = Cairo1RunConfig {
+            entrypoint: "::fibonacci",
+            ..Default::default()
+        };
+        assert_matches!(
+            cairo_run_program(&sierra_program, cairo_run_config),
+            Err(Error::MissingFunction(name)) if name == "::fibonacci"
+        );
+    }
+
     #[rstest]
     fn check_output_segment_contains_program_ouput_and_input(
         #[values(true, false)] proof_mode: bool,
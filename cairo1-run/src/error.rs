@@ -4,6 +4,7 @@ use cairo_vm::{
     air_public_input::PublicInputError,
     cairo_run::EncodeTraceError,
     types::errors::program_errors::ProgramError,
+    utils::FeltWithShortString,
     vm::errors::{
         memory_errors::MemoryError, runner_errors::RunnerError, trace_errors::TraceError,
         vm_errors::VirtualMachineError,
@@ -40,8 +41,10 @@ pub enum Error {
     Program(#[from] ProgramError),
     #[error(transparent)]
     Memory(#[from] MemoryError),
-    #[error("Program panicked with {0:?}")]
+    #[error("Program panicked with [{}]", format_panic_data(.0))]
     RunPanic(Vec<Felt252>),
+    #[error("Missing function {0} in sierra program")]
+    MissingFunction(String),
     #[error("Function signature has no return types")]
     NoRetTypesInSignature,
     #[error("No size for concrete type id: {0}")]
@@ -64,3 +67,11 @@ pub enum Error {
     #[error("Only programs with `Array<Felt252>` as an input can be currently proven. Try inputing the serialized version of the input and deserializing it on main")]
     IlegalInputValue,
 }
+
+fn format_panic_data(panic_data: &[Felt252]) -> String {
+    panic_data
+        .iter()
+        .map(|felt| FeltWithShortString(felt).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
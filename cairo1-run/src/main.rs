@@ -6,7 +6,7 @@ use cairo_lang_compiler::{
 };
 use cairo_vm::types::layout::CairoLayoutParams;
 use cairo_vm::{
-    air_public_input::PublicInputError, types::layout_name::LayoutName,
+    air_public_input::PublicInputError, types::layout_name::LayoutName, utils::FeltWithShortString,
     vm::errors::trace_errors::TraceError, Felt252,
 };
 use clap::{Parser, ValueHint};
@@ -55,6 +55,9 @@ struct Args {
     // Same rules from `args` apply here
     #[clap(long = "args_file", value_parser, value_hint=ValueHint::FilePath, conflicts_with = "args")]
     args_file: Option<PathBuf>,
+    /// Name of the function to run, matched as a suffix of the full function path.
+    #[clap(long = "entrypoint", default_value = "::main")]
+    entrypoint: String,
     #[clap(long = "print_output", value_parser)]
     print_output: bool,
     #[clap(
@@ -171,6 +174,7 @@ fn run(args: impl Iterator<Item = String>) -> Result<Option<String>, Error> {
         layout: args.layout,
         trace_enabled: args.trace_file.is_some() || args.air_public_input.is_some(),
         args: &args.args.0,
+        entrypoint: &args.entrypoint,
         finalize_builtins: args.air_public_input.is_some() || args.cairo_pie_output.is_some(),
         append_return_values: args.append_return_values,
         dynamic_layout_params: cairo_layout_params,
@@ -273,15 +277,7 @@ fn main() -> Result<(), Error> {
             if !panic_data.is_empty() {
                 let panic_data_string_list = panic_data
                     .iter()
-                    .map(|m| {
-                        // Try to parse to utf8 string
-                        let msg = String::from_utf8(m.to_bytes_be().to_vec());
-                        if let Ok(msg) = msg {
-                            format!("{} ('{}')", m, msg)
-                        } else {
-                            m.to_string()
-                        }
-                    })
+                    .map(|m| FeltWithShortString(m).to_string())
                     .join(", ");
                 println!("Run panicked with: [{}]", panic_data_string_list);
             }
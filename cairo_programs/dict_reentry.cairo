@@ -0,0 +1,26 @@
+from starkware.cairo.common.dict import dict_read, dict_write
+from starkware.cairo.common.default_dict import default_dict_new
+from starkware.cairo.common.dict_access import DictAccess
+
+// Creates a dictionary and writes a value to it.
+func create_and_write(key: felt, value: felt) -> (dict_ptr: DictAccess*) {
+    alloc_locals;
+    let (local dict_ptr: DictAccess*) = default_dict_new(17);
+    dict_write{dict_ptr=dict_ptr}(key=key, new_value=value);
+    return (dict_ptr=dict_ptr);
+}
+
+// Reads a value from a dictionary created by a previous run, then overwrites it.
+func read_and_write{dict_ptr: DictAccess*}(key: felt, new_value: felt) -> (value: felt) {
+    alloc_locals;
+    let (local value: felt) = dict_read(key=key);
+    dict_write(key=key, new_value=new_value);
+    return (value=value);
+}
+
+func main() {
+    let (dict_ptr) = create_and_write(12, 34);
+    let (value) = read_and_write{dict_ptr=dict_ptr}(12, 35);
+    assert value = 34;
+    return ();
+}
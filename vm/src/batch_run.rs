@@ -0,0 +1,151 @@
+//! Runs many independent programs across a pool of threads.
+
+use crate::stdlib::{
+    num::NonZeroUsize,
+    prelude::*,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::thread;
+
+use crate::{
+    cairo_run::{cairo_run_program, CairoRunConfig},
+    hint_processor::hint_processor_definition::HintProcessor,
+    types::program::Program,
+    vm::{
+        errors::cairo_run_errors::CairoRunError,
+        runners::cairo_runner::{CairoRunner, ExecutionResources},
+    },
+};
+
+/// Runs a batch of programs with the same [CairoRunConfig] on a fixed number of threads.
+///
+/// The programs are borrowed by every worker, so each one is only deserialized once.
+/// Runners are not `Send`, so each run is turned into an output of the caller's choice in the
+/// worker thread that ran it.
+pub struct BatchRunner<'a> {
+    config: CairoRunConfig<'a>,
+    n_threads: NonZeroUsize,
+}
+
+/// The outputs of a [BatchRunner::run], in the same order as the programs.
+#[derive(Debug)]
+pub struct BatchResult<T> {
+    pub outputs: Vec<T>,
+    /// The sum of the execution resources of the successful runs.
+    pub total_resources: ExecutionResources,
+}
+
+impl<'a> BatchRunner<'a> {
+    pub fn new(config: CairoRunConfig<'a>, n_threads: NonZeroUsize) -> Self {
+        BatchRunner { config, n_threads }
+    }
+
+    /// Runs every program with a hint processor built by `new_hint_processor`, and turns each
+    /// result into an output with `process`, which receives the index of the program.
+    pub fn run<H, T>(
+        &self,
+        programs: &[Program],
+        new_hint_processor: impl Fn() -> H + Sync,
+        process: impl Fn(usize, Result<CairoRunner, CairoRunError>) -> T + Sync,
+    ) -> BatchResult<T>
+    where
+        H: HintProcessor,
+        T: Send,
+    {
+        let next_program = AtomicUsize::new(0);
+        let worker = || {
+            let mut outputs = Vec::new();
+            let mut resources = ExecutionResources::default();
+            loop {
+                let index = next_program.fetch_add(1, Ordering::Relaxed);
+                let Some(program) = programs.get(index) else {
+                    break;
+                };
+                let mut hint_processor = new_hint_processor();
+                let result = cairo_run_program(program, &self.config, &mut hint_processor);
+                if let Some(run_resources) = result
+                    .as_ref()
+                    .ok()
+                    .and_then(|runner| runner.get_execution_resources().ok())
+                {
+                    resources += &run_resources;
+                }
+                outputs.push((index, process(index, result)));
+            }
+            (outputs, resources)
+        };
+
+        let n_threads = self.n_threads.get().min(programs.len());
+        let mut outputs = Vec::with_capacity(programs.len());
+        let mut total_resources = ExecutionResources::default();
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..n_threads).map(|_| scope.spawn(worker)).collect();
+            for handle in handles {
+                let (worker_outputs, worker_resources) =
+                    handle.join().expect("batch runner worker panicked");
+                outputs.extend(worker_outputs);
+                total_resources += &worker_resources;
+            }
+        });
+        outputs.sort_unstable_by_key(|(index, _)| *index);
+
+        BatchResult {
+            outputs: outputs.into_iter().map(|(_, output)| output).collect(),
+            total_resources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+    use crate::types::layout_name::LayoutName;
+
+    #[test]
+    fn run_batch_of_programs() {
+        let fibonacci = Program::from_bytes(
+            include_bytes!("../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let factorial = Program::from_bytes(
+            include_bytes!("../../cairo_programs/factorial.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let programs = vec![fibonacci.clone(), factorial, fibonacci.clone(), fibonacci];
+        let config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            ..Default::default()
+        };
+
+        let batch_runner = BatchRunner::new(config, NonZeroUsize::new(3).unwrap());
+        let result = batch_runner.run(&programs, BuiltinHintProcessor::new_empty, |index, run| {
+            (
+                index,
+                run.unwrap().get_execution_resources().unwrap().n_steps,
+            )
+        });
+
+        let fibonacci_steps = result.outputs[0].1;
+        assert_eq!(fibonacci_steps, 80);
+        assert_eq!(
+            result
+                .outputs
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(result.outputs[2].1, fibonacci_steps);
+        assert_eq!(
+            result.total_resources.n_steps,
+            result
+                .outputs
+                .iter()
+                .map(|(_, n_steps)| n_steps)
+                .sum::<usize>()
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Building blocks to run tasks under cairo-lang's simple bootloader.
+//!
+//! The bootloader runs a list of tasks (programs or Cairo PIEs) and writes, for each of them,
+//! its program hash and output to the bootloader's output, so that a single proof attests to all
+//! of them. This module holds the bootloader input, the tasks, and the fact topologies and
+//! per-task outputs computed from a bootloader run, as defined in
+//! `starkware.cairo.bootloaders.simple_bootloader`.
+
+use crate::stdlib::prelude::*;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::program_hash::{
+    compute_program_hash_chain, compute_program_hash_poseidon, ProgramHashError,
+};
+use crate::serde::deserialize_program::{parse_program_json, ProgramJson};
+use crate::types::errors::program_errors::ProgramError;
+use crate::types::program::Program;
+use crate::vm::runners::cairo_pie::{Attributes, CairoPie, Pages, StrippedProgram};
+use crate::Felt252;
+
+/// Name of the output builtin attribute holding the tree structure of a task's fact topology.
+pub const GPS_FACT_TOPOLOGY: &str = "gps_fact_topology";
+
+#[derive(thiserror_no_std::Error, Debug)]
+#[non_exhaustive]
+pub enum BootloaderError {
+    #[error(transparent)]
+    Program(#[from] ProgramError),
+    #[error(transparent)]
+    ProgramHash(#[from] ProgramHashError),
+    #[cfg(feature = "std")]
+    #[error("Failed to read the Cairo PIE: {0}")]
+    Pie(#[from] std::io::Error),
+    #[cfg(not(feature = "std"))]
+    #[error("Cairo PIE tasks can only be loaded with the std feature")]
+    PieNotSupported,
+    #[error("Expected page id {expected}, found {found}")]
+    UnexpectedPageId { expected: usize, found: usize },
+    #[error("Invalid start {start} for page {page_id}")]
+    InvalidPageStart { page_id: usize, start: usize },
+    #[error("Invalid size {size} for page {page_id}")]
+    InvalidPageSize { page_id: usize, size: usize },
+    #[error("The output pages don't cover the entire output")]
+    IncompletePages,
+    #[error("The task has output pages but no {GPS_FACT_TOPOLOGY} attribute")]
+    MissingFactTopology,
+    #[error("The bootloader output is truncated")]
+    TruncatedOutput,
+}
+
+/// The input of the simple bootloader, as read by its hints from the `simple_bootloader_input`
+/// scope variable.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SimpleBootloaderInput {
+    pub tasks: Vec<TaskSpec>,
+    /// Where to write the fact topologies of the tasks, see [FactTopologiesFile]
+    #[serde(default)]
+    pub fact_topologies_path: Option<String>,
+    /// Whether to put all the outputs in a single page
+    #[serde(default)]
+    pub single_page: bool,
+}
+
+/// A task of the bootloader input, before its program or PIE is loaded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum TaskSpec {
+    RunProgramTask {
+        /// The compiled program, as output by `cairo-compile`
+        program: serde_json::Value,
+        #[serde(default)]
+        program_input: serde_json::Value,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+    CairoPiePath {
+        path: String,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+}
+
+/// A task run by the bootloader.
+#[derive(Debug, Clone)]
+pub enum Task {
+    Program {
+        program: Program,
+        /// The input of the program, made available to its hints as `program_input`
+        program_input: serde_json::Value,
+    },
+    Pie(CairoPie),
+}
+
+impl TaskSpec {
+    /// Loads the task's program (with its `main` entrypoint) or reads its Cairo PIE.
+    pub fn load_task(&self) -> Result<Task, BootloaderError> {
+        match self {
+            TaskSpec::RunProgramTask {
+                program,
+                program_input,
+                ..
+            } => {
+                let program_json = ProgramJson::deserialize(program).map_err(ProgramError::from)?;
+                Ok(Task::Program {
+                    program: parse_program_json(program_json, Some("main"))?,
+                    program_input: program_input.clone(),
+                })
+            }
+            #[cfg(feature = "std")]
+            TaskSpec::CairoPiePath { path, .. } => Ok(Task::Pie(CairoPie::read_zip_file(
+                std::path::Path::new(path),
+            )?)),
+            #[cfg(not(feature = "std"))]
+            TaskSpec::CairoPiePath { .. } => Err(BootloaderError::PieNotSupported),
+        }
+    }
+
+    pub fn use_poseidon(&self) -> bool {
+        match self {
+            TaskSpec::RunProgramTask { use_poseidon, .. }
+            | TaskSpec::CairoPiePath { use_poseidon, .. } => *use_poseidon,
+        }
+    }
+}
+
+impl Task {
+    /// Returns the program run by the task.
+    pub fn get_program(&self) -> Result<StrippedProgram, ProgramError> {
+        match self {
+            Task::Program { program, .. } => program.get_stripped_program(),
+            Task::Pie(pie) => Ok(pie.metadata.program.clone()),
+        }
+    }
+
+    /// Computes the hash of the task's program, as written by the bootloader next to the task's
+    /// output. The program is hashed with Poseidon if `use_poseidon` is set, and with a Pedersen
+    /// hash chain otherwise.
+    pub fn program_hash(
+        &self,
+        bootloader_version: usize,
+        use_poseidon: bool,
+    ) -> Result<Felt252, ProgramHashError> {
+        let program = self.get_program()?;
+        if use_poseidon {
+            compute_program_hash_poseidon(&program, bootloader_version)
+        } else {
+            compute_program_hash_chain(&program, bootloader_version)
+        }
+    }
+}
+
+/// Describes how the output of a task is split into pages and how the pages are hashed into the
+/// task's fact.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FactTopology {
+    pub tree_structure: Vec<usize>,
+    pub page_sizes: Vec<usize>,
+}
+
+/// The contents of the file written at [SimpleBootloaderInput::fact_topologies_path].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FactTopologiesFile {
+    pub fact_topologies: Vec<FactTopology>,
+}
+
+/// Computes the fact topology of a task from the state its output builtin was left in.
+/// Page starts are relative to the start of the task's output.
+pub fn get_task_fact_topology(
+    output_size: usize,
+    pages: &Pages,
+    attributes: &Attributes,
+) -> Result<FactTopology, BootloaderError> {
+    let tree_structure = match attributes.get(GPS_FACT_TOPOLOGY) {
+        Some(tree_structure) => tree_structure.clone(),
+        None if pages.is_empty() => vec![1, 0],
+        None => return Err(BootloaderError::MissingFactTopology),
+    };
+    Ok(FactTopology {
+        tree_structure,
+        page_sizes: get_page_sizes(output_size, pages)?,
+    })
+}
+
+/// Returns the size of every page of the output, starting with page 0 (the part of the output
+/// before page 1).
+/// Pages must be numbered from 1 and cover the rest of the output without gaps.
+pub fn get_page_sizes(output_size: usize, pages: &Pages) -> Result<Vec<usize>, BootloaderError> {
+    let mut page_ids: Vec<usize> = pages.keys().copied().collect();
+    page_ids.sort_unstable();
+
+    let mut page_sizes = vec![output_size];
+    let mut expected_start = None;
+    for (expected_page_id, page_id) in (1..).zip(page_ids) {
+        if page_id != expected_page_id {
+            return Err(BootloaderError::UnexpectedPageId {
+                expected: expected_page_id,
+                found: page_id,
+            });
+        }
+        let page = &pages[&page_id];
+        match expected_start {
+            None if page.start <= output_size => page_sizes[0] = page.start,
+            Some(start) if page.start == start => {}
+            _ => {
+                return Err(BootloaderError::InvalidPageStart {
+                    page_id,
+                    start: page.start,
+                })
+            }
+        }
+        if page.size == 0 || page.size > output_size {
+            return Err(BootloaderError::InvalidPageSize {
+                page_id,
+                size: page.size,
+            });
+        }
+        expected_start = Some(page.start + page.size);
+        page_sizes.push(page.size);
+    }
+    if expected_start.is_some_and(|end| end != output_size) {
+        return Err(BootloaderError::IncompletePages);
+    }
+    Ok(page_sizes)
+}
+
+/// The output of a task, as written by the simple bootloader.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskOutput {
+    pub program_hash: Felt252,
+    pub output: Vec<Felt252>,
+}
+
+/// Splits the output of the simple bootloader into the outputs of its tasks.
+///
+/// The output starts with the number of tasks, followed by the output of each task prefixed by
+/// its size (including the two prefix cells) and its program hash.
+pub fn parse_simple_bootloader_output(
+    output: &[Felt252],
+) -> Result<Vec<TaskOutput>, BootloaderError> {
+    let to_usize = |felt: &Felt252| -> Result<usize, BootloaderError> {
+        felt.to_usize().ok_or(BootloaderError::TruncatedOutput)
+    };
+    let (n_tasks, mut output) = output
+        .split_first()
+        .ok_or(BootloaderError::TruncatedOutput)?;
+    let n_tasks = to_usize(n_tasks)?;
+    let mut tasks = Vec::new();
+    for _ in 0..n_tasks {
+        let size = match output {
+            [size, ..] => to_usize(size)?,
+            [] => return Err(BootloaderError::TruncatedOutput),
+        };
+        if size < 2 || size > output.len() {
+            return Err(BootloaderError::TruncatedOutput);
+        }
+        let (task, rest) = output.split_at(size);
+        tasks.push(TaskOutput {
+            program_hash: task[1],
+            output: task[2..].to_vec(),
+        });
+        output = rest;
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stdlib::collections::HashMap;
+    use crate::vm::runners::cairo_pie::PublicMemoryPage;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn pages(pages: &[(usize, usize, usize)]) -> Pages {
+        pages
+            .iter()
+            .map(|(id, start, size)| {
+                (
+                    *id,
+                    PublicMemoryPage {
+                        start: *start,
+                        size: *size,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn fact_topology_without_pages() {
+        assert_eq!(
+            get_task_fact_topology(5, &HashMap::new(), &HashMap::new()).unwrap(),
+            FactTopology {
+                tree_structure: vec![1, 0],
+                page_sizes: vec![5],
+            }
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn fact_topology_with_pages() {
+        let attributes = HashMap::from([(GPS_FACT_TOPOLOGY.to_string(), vec![3, 2, 0, 1])]);
+        assert_eq!(
+            get_task_fact_topology(10, &pages(&[(1, 2, 5), (2, 7, 3)]), &attributes).unwrap(),
+            FactTopology {
+                tree_structure: vec![3, 2, 0, 1],
+                page_sizes: vec![2, 5, 3],
+            }
+        );
+        assert_matches!(
+            get_task_fact_topology(10, &pages(&[(1, 2, 8)]), &HashMap::new()),
+            Err(BootloaderError::MissingFactTopology)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn page_sizes_errors() {
+        assert_matches!(
+            get_page_sizes(10, &pages(&[(2, 0, 10)])),
+            Err(BootloaderError::UnexpectedPageId {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_matches!(
+            get_page_sizes(10, &pages(&[(1, 2, 3), (2, 6, 4)])),
+            Err(BootloaderError::InvalidPageStart {
+                page_id: 2,
+                start: 6
+            })
+        );
+        assert_matches!(
+            get_page_sizes(10, &pages(&[(1, 2, 0)])),
+            Err(BootloaderError::InvalidPageSize {
+                page_id: 1,
+                size: 0
+            })
+        );
+        assert_matches!(
+            get_page_sizes(10, &pages(&[(1, 2, 3)])),
+            Err(BootloaderError::IncompletePages)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_bootloader_output() {
+        let output: Vec<Felt252> = [2, 4, 100, 7, 8, 2, 200]
+            .into_iter()
+            .map(Felt252::from)
+            .collect();
+        assert_eq!(
+            parse_simple_bootloader_output(&output).unwrap(),
+            vec![
+                TaskOutput {
+                    program_hash: Felt252::from(100),
+                    output: vec![Felt252::from(7), Felt252::from(8)],
+                },
+                TaskOutput {
+                    program_hash: Felt252::from(200),
+                    output: vec![],
+                },
+            ]
+        );
+        assert_matches!(
+            parse_simple_bootloader_output(&output[..6]),
+            Err(BootloaderError::TruncatedOutput)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_program_task() {
+        let program: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../cairo_programs/fibonacci.json")).unwrap();
+        let input: SimpleBootloaderInput = serde_json::from_value(serde_json::json!({
+            "tasks": [{
+                "type": "RunProgramTask",
+                "program": program,
+                "program_input": { "n": 10 },
+            }],
+            "single_page": true,
+        }))
+        .unwrap();
+        assert!(!input.tasks[0].use_poseidon());
+
+        let task = input.tasks[0].load_task().unwrap();
+        let expected = Program::from_bytes(
+            include_bytes!("../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        assert_matches!(
+            &task,
+            Task::Program { program, program_input }
+                if *program == expected && *program_input == serde_json::json!({ "n": 10 })
+        );
+        assert_eq!(
+            task.program_hash(0, false).unwrap(),
+            expected.compute_program_hash(0).unwrap()
+        );
+        assert_eq!(
+            task.program_hash(0, true).unwrap(),
+            compute_program_hash_poseidon(&expected.get_stripped_program().unwrap(), 0).unwrap()
+        );
+    }
+}
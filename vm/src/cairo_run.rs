@@ -1,15 +1,17 @@
 use crate::{
     hint_processor::hint_processor_definition::HintProcessor,
-    types::{
-        builtin_name::BuiltinName, layout::CairoLayoutParams, layout_name::LayoutName,
-        program::Program,
-    },
+    types::{layout::CairoLayoutParams, layout_name::LayoutName, program::Program},
     vm::{
         errors::{
-            cairo_run_errors::CairoRunError, runner_errors::RunnerError, vm_exception::VmException,
+            cairo_run_errors::CairoRunError, memory_errors::MemoryError,
+            runner_errors::RunnerError, trace_errors::TraceError, vm_exception::VmException,
+        },
+        runners::{
+            cairo_pie::CairoPie,
+            cairo_runner::{CairoRunner, RunnerConfig, RunnerMode},
         },
-        runners::{cairo_pie::CairoPie, cairo_runner::CairoRunner},
         security::verify_secure_runner,
+        vm_core::VmConfig,
     },
 };
 
@@ -36,6 +38,9 @@ pub struct CairoRunConfig<'a> {
     pub secure_run: Option<bool>,
     pub disable_trace_padding: bool,
     pub allow_missing_builtins: Option<bool>,
+    /// Fill the unused builtin instances with valid values at the end of a proof mode run,
+    /// see [CairoRunner::fill_builtin_holes]. It is ignored otherwise.
+    pub fill_builtin_holes: bool,
 }
 
 impl<'a> Default for CairoRunConfig<'a> {
@@ -50,6 +55,7 @@ impl<'a> Default for CairoRunConfig<'a> {
             disable_trace_padding: false,
             allow_missing_builtins: None,
             dynamic_layout_params: None,
+            fill_builtin_holes: false,
         }
     }
 }
@@ -99,6 +105,9 @@ pub fn cairo_run_program_with_initial_scope(
     cairo_runner.read_return_values(allow_missing_builtins)?;
     if cairo_run_config.proof_mode {
         cairo_runner.finalize_segments()?;
+        if cairo_run_config.fill_builtin_holes {
+            cairo_runner.fill_builtin_holes()?;
+        }
     }
     if secure_run {
         verify_secure_runner(&cairo_runner, true, None)?;
@@ -150,45 +159,24 @@ pub fn cairo_run_pie(
     {
         return Err(RunnerError::PieNStepsVsRunResourcesNStepsMismatch.into());
     }
-    pie.run_validity_checks()?;
     let secure_run = cairo_run_config.secure_run.unwrap_or(true);
 
     let allow_missing_builtins = cairo_run_config.allow_missing_builtins.unwrap_or_default();
 
-    let program = Program::from_stripped_program(&pie.metadata.program);
-    let mut cairo_runner = CairoRunner::new(
-        &program,
-        cairo_run_config.layout,
-        cairo_run_config.dynamic_layout_params.clone(),
-        false,
-        cairo_run_config.trace_enabled,
+    // Data is not trusted in secure_run, therefore we skip extending the hash builtin's data
+    let (mut cairo_runner, end) = CairoRunner::from_cairo_pie(
+        pie,
+        RunnerConfig {
+            layout: cairo_run_config.layout,
+            dynamic_layout_params: cairo_run_config.dynamic_layout_params.clone(),
+            mode: RunnerMode::ExecutionMode,
+            vm_config: VmConfig::new().trace_enabled(cairo_run_config.trace_enabled),
+            ..Default::default()
+        },
+        allow_missing_builtins,
+        !secure_run,
     )?;
 
-    let end = cairo_runner.initialize(allow_missing_builtins)?;
-    cairo_runner.vm.finalize_segments_by_cairo_pie(pie);
-    // Load builtin additional data
-    for (name, data) in pie.additional_data.0.iter() {
-        // Data is not trusted in secure_run, therefore we skip extending the hash builtin's data
-        if matches!(name, BuiltinName::pedersen) && secure_run {
-            continue;
-        }
-        if let Some(builtin) = cairo_runner
-            .vm
-            .builtin_runners
-            .iter_mut()
-            .find(|b| b.name() == *name)
-        {
-            builtin.extend_additional_data(data)?;
-        }
-    }
-    // Load previous execution memory
-    let has_zero_segment = cairo_runner.vm.segments.has_zero_segment() as usize;
-    let n_extra_segments = pie.metadata.extra_segments.len() - has_zero_segment;
-    cairo_runner
-        .vm
-        .segments
-        .load_pie_memory(&pie.memory, n_extra_segments)?;
-
     cairo_runner
         .run_until_pc(end, hint_processor)
         .map_err(|err| VmException::from_vm_error(&cairo_runner, err))?;
@@ -265,6 +253,38 @@ pub fn cairo_run_fuzzed_program(
 #[error("Failed to encode trace at position {0}, serialize error: {1}")]
 pub struct EncodeTraceError(usize, bincode::error::EncodeError);
 
+impl EncodeTraceError {
+    pub(crate) fn new(position: usize, error: bincode::error::EncodeError) -> Self {
+        EncodeTraceError(position, error)
+    }
+
+    /// Returns the position of the trace entry or memory cell that couldn't be encoded.
+    pub fn position(&self) -> usize {
+        self.0
+    }
+
+    /// Returns the error returned by the writer.
+    pub fn encode_error(&self) -> &bincode::error::EncodeError {
+        &self.1
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum EncodeMemoryError {
+    #[error(transparent)]
+    Memory(#[from] MemoryError),
+    #[error("Failed to encode memory at address {0}, serialize error: {1}")]
+    Encode(usize, bincode::error::EncodeError),
+}
+
+#[derive(Debug, Error)]
+pub enum EncodeRelocatedTraceError {
+    #[error(transparent)]
+    Trace(#[from] TraceError),
+    #[error(transparent)]
+    Encode(#[from] EncodeTraceError),
+}
+
 /// Writes the trace binary representation.
 ///
 /// Bincode encodes to little endian by default and each trace entry is composed of
@@ -322,6 +342,7 @@ mod tests {
         },
         utils::test_utils::*,
     };
+    use assert_matches::assert_matches;
     use bincode::enc::write::SliceWriter;
 
     use rstest::rstest;
@@ -434,6 +455,103 @@ mod tests {
         assert_eq!(buffer, *expected_encoded_trace);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_binary_trace_file_without_relocated_trace() {
+        let program_content = include_bytes!("../../cairo_programs/struct.json");
+        let expected_encoded_trace =
+            include_bytes!("../../cairo_programs/trace_memory/cairo_trace_struct");
+
+        // run test program until the end
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = run_test_program(program_content, &mut hint_processor).unwrap();
+
+        let mut buffer = [0; 24];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        // relocate the trace straight into the writer
+        cairo_runner.relocate_trace_into(&mut buff_writer).unwrap();
+
+        assert!(cairo_runner.relocated_trace.is_none());
+        assert_eq!(buffer, *expected_encoded_trace);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_filtered_trace() {
+        let program = Program::from_bytes(
+            include_bytes!("../../cairo_programs/struct.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let end = cairo_runner.initialize(false).unwrap();
+        // Drops every entry
+        cairo_runner.vm.set_trace_pc_filter(Vec::new());
+        assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_ok());
+
+        let mut buffer = [0; 24];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        assert_matches!(
+            cairo_runner.relocate_trace_into(&mut buff_writer),
+            Err(EncodeRelocatedTraceError::Trace(TraceError::FilteredTrace))
+        );
+        assert_matches!(cairo_runner.relocate(false), Err(TraceError::FilteredTrace));
+        assert!(cairo_runner.relocated_trace.is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_trace_into_trace_not_enabled() {
+        let program = Program::from_bytes(
+            include_bytes!("../../cairo_programs/struct.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_ok());
+
+        let mut buffer = [0; 24];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        assert_matches!(
+            cairo_runner.relocate_trace_into(&mut buff_writer),
+            Err(EncodeRelocatedTraceError::Trace(
+                TraceError::TraceNotEnabled
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_binary_trace_file_compact_trace() {
+        let program_content = include_bytes!("../../cairo_programs/struct.json");
+        let expected_encoded_trace =
+            include_bytes!("../../cairo_programs/trace_memory/cairo_trace_struct");
+
+        // run test program until the end, storing the trace in compact mode
+        let program = Program::from_bytes(program_content, Some("main")).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, false);
+        cairo_runner.vm.enable_compact_trace();
+        let end = cairo_runner.initialize(false).unwrap();
+        assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_ok());
+        assert_eq!(
+            cairo_runner.vm.get_compact_trace().map(|t| t.len()),
+            Some(1)
+        );
+
+        assert!(cairo_runner.relocate(false).is_ok());
+
+        let trace_entries = cairo_runner.relocated_trace.unwrap();
+        let mut buffer = [0; 24];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        write_encoded_trace(&trace_entries, &mut buff_writer).unwrap();
+
+        assert_eq!(buffer, *expected_encoded_trace);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn write_binary_memory_file() {
@@ -457,6 +575,44 @@ mod tests {
         assert_eq!(*expected_encoded_memory, buffer);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_binary_memory_file_without_relocated_memory() {
+        let program_content = include_bytes!("../../cairo_programs/struct.json");
+        let expected_encoded_memory =
+            include_bytes!("../../cairo_programs/trace_memory/cairo_memory_struct");
+
+        // run test program until the end
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = run_test_program(program_content, &mut hint_processor).unwrap();
+
+        let mut buffer = [0; 120];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+        // relocate the memory straight into the writer
+        cairo_runner.relocate_memory_into(&mut buff_writer).unwrap();
+
+        assert!(cairo_runner.relocated_memory.is_empty());
+        assert_eq!(*expected_encoded_memory, buffer);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_binary_memory_file_without_relocated_memory_buffer_too_small() {
+        let program_content = include_bytes!("../../cairo_programs/struct.json");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = run_test_program(program_content, &mut hint_processor).unwrap();
+
+        // Only fits the address of the first cell
+        let mut buffer = [0; 8];
+        let mut buff_writer = SliceWriter::new(&mut buffer);
+
+        assert_matches!(
+            cairo_runner.relocate_memory_into(&mut buff_writer),
+            Err(EncodeMemoryError::Encode(1, _))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_with_no_trace() {
@@ -0,0 +1,279 @@
+//! Source coverage of a run, built from the executed pcs and the program's debug info.
+//!
+//! The coverage can be written in the [lcov](https://github.com/linux-test-project/lcov) and
+//! [Cobertura](https://cobertura.github.io/cobertura/) formats understood by most coverage
+//! tools.
+
+use crate::stdlib::{collections::BTreeMap, fmt, prelude::*};
+use crate::types::program::Program;
+use crate::vm::{errors::trace_errors::TraceError, runners::cairo_runner::CairoRunner};
+
+/// The coverage of the source files of a program, keyed by file name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// Number of times each line with instructions was executed
+    pub lines: BTreeMap<u32, usize>,
+    pub functions: BTreeMap<String, FunctionCoverage>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FunctionCoverage {
+    /// Line where the function's first instruction is
+    pub line: u32,
+    /// Number of times the function was entered
+    pub hits: usize,
+}
+
+impl FileCoverage {
+    fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+
+    fn functions_hit(&self) -> usize {
+        self.functions
+            .values()
+            .filter(|function| function.hits > 0)
+            .count()
+    }
+}
+
+impl Coverage {
+    /// Computes the coverage from the pc offsets (in the program segment) of the executed
+    /// instructions, one per step.
+    ///
+    /// A line is executed as many times as its most executed instruction. Instructions without
+    /// debug info are ignored.
+    pub fn new(program: &Program, executed_pcs: impl IntoIterator<Item = usize>) -> Self {
+        let mut pc_hits = vec![0_usize; program.data_len()];
+        for pc in executed_pcs {
+            if let Some(hits) = pc_hits.get_mut(pc) {
+                *hits += 1;
+            }
+        }
+
+        let mut files = BTreeMap::<String, FileCoverage>::new();
+        for (pc, hits) in pc_hits.iter().enumerate() {
+            let Some(location) = program.get_instruction_location(pc) else {
+                continue;
+            };
+            let file = files
+                .entry(location.inst.input_file.filename.clone())
+                .or_default();
+            let line_hits = file.lines.entry(location.inst.start_line).or_default();
+            *line_hits = (*line_hits).max(*hits);
+        }
+        for (name, identifier) in program.iter_identifiers() {
+            if identifier.type_.as_deref() != Some("function") {
+                continue;
+            }
+            let Some(pc) = identifier.pc else {
+                continue;
+            };
+            let Some(location) = program.get_instruction_location(pc) else {
+                continue;
+            };
+            let function = FunctionCoverage {
+                line: location.inst.start_line,
+                hits: pc_hits.get(pc).copied().unwrap_or_default(),
+            };
+            files
+                .entry(location.inst.input_file.filename.clone())
+                .or_default()
+                .functions
+                .insert(name.to_string(), function);
+        }
+        Coverage { files }
+    }
+
+    /// Computes the coverage of a run from its trace.
+    /// Fails if the trace wasn't enabled, and is incomplete if it was filtered by pc.
+    pub fn from_runner(runner: &CairoRunner) -> Result<Self, TraceError> {
+        let decoded_trace;
+        let trace = match (&runner.vm.trace, &runner.vm.compact_trace) {
+            (Some(trace), _) => trace,
+            (None, Some(compact_trace)) => {
+                decoded_trace = compact_trace.to_trace_entries();
+                &decoded_trace
+            }
+            (None, None) => return Err(TraceError::TraceNotEnabled),
+        };
+        let program_segment = runner
+            .program_base
+            .map(|base| base.segment_index)
+            .unwrap_or_default();
+        let executed_pcs = trace
+            .iter()
+            .filter(|entry| entry.pc.segment_index == program_segment)
+            .map(|entry| entry.pc.offset);
+        Ok(Self::new(runner.get_program(), executed_pcs))
+    }
+
+    /// Writes the coverage as an lcov tracefile.
+    pub fn write_lcov(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(out, "TN:")?;
+        for (filename, file) in &self.files {
+            writeln!(out, "SF:{filename}")?;
+            for (name, function) in &file.functions {
+                writeln!(out, "FN:{},{name}", function.line)?;
+            }
+            for (name, function) in &file.functions {
+                writeln!(out, "FNDA:{},{name}", function.hits)?;
+            }
+            writeln!(out, "FNF:{}", file.functions.len())?;
+            writeln!(out, "FNH:{}", file.functions_hit())?;
+            for (line, hits) in &file.lines {
+                writeln!(out, "DA:{line},{hits}")?;
+            }
+            writeln!(out, "LF:{}", file.lines.len())?;
+            writeln!(out, "LH:{}", file.lines_hit())?;
+            writeln!(out, "end_of_record")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the coverage as a Cobertura XML report, with one class per source file.
+    pub fn write_cobertura(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let lines_valid: usize = self.files.values().map(|file| file.lines.len()).sum();
+        let lines_covered: usize = self.files.values().map(FileCoverage::lines_hit).sum();
+        writeln!(out, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            out,
+            r#"<coverage line-rate="{}" branch-rate="0" lines-covered="{lines_covered}" lines-valid="{lines_valid}" branches-covered="0" branches-valid="0" complexity="0" version="cairo-vm" timestamp="0">"#,
+            rate(lines_covered, lines_valid)
+        )?;
+        writeln!(out, "  <packages>")?;
+        writeln!(
+            out,
+            r#"    <package name="" line-rate="{}" branch-rate="0" complexity="0">"#,
+            rate(lines_covered, lines_valid)
+        )?;
+        writeln!(out, "      <classes>")?;
+        for (filename, file) in &self.files {
+            let filename = escape_xml(filename);
+            writeln!(
+                out,
+                r#"        <class name="{filename}" filename="{filename}" line-rate="{}" branch-rate="0" complexity="0">"#,
+                rate(file.lines_hit(), file.lines.len())
+            )?;
+            writeln!(out, "          <methods>")?;
+            for (name, function) in &file.functions {
+                writeln!(
+                    out,
+                    r#"            <method name="{}" signature="" line-rate="{}" branch-rate="0">"#,
+                    escape_xml(name),
+                    rate(usize::from(function.hits > 0), 1)
+                )?;
+                writeln!(
+                    out,
+                    r#"              <lines><line number="{}" hits="{}"/></lines>"#,
+                    function.line, function.hits
+                )?;
+                writeln!(out, "            </method>")?;
+            }
+            writeln!(out, "          </methods>")?;
+            writeln!(out, "          <lines>")?;
+            for (line, hits) in &file.lines {
+                writeln!(out, r#"            <line number="{line}" hits="{hits}"/>"#)?;
+            }
+            writeln!(out, "          </lines>")?;
+            writeln!(out, "        </class>")?;
+        }
+        writeln!(out, "      </classes>")?;
+        writeln!(out, "    </package>")?;
+        writeln!(out, "  </packages>")?;
+        writeln!(out, "</coverage>")
+    }
+}
+
+fn rate(covered: usize, valid: usize) -> f64 {
+    if valid == 0 {
+        return 1.0;
+    }
+    covered as f64 / valid as f64
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cairo_run::{cairo_run, CairoRunConfig};
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn fibonacci_coverage() -> Coverage {
+        let cairo_run_config = CairoRunConfig {
+            trace_enabled: true,
+            ..Default::default()
+        };
+        let runner = cairo_run(
+            include_bytes!("../../cairo_programs/fibonacci.json"),
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+        Coverage::from_runner(&runner).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn coverage_from_runner() {
+        let coverage = fibonacci_coverage();
+        assert_eq!(coverage.files.len(), 1);
+        let file = coverage.files.values().next().unwrap();
+        assert!(file.lines_hit() > 0);
+        assert_eq!(file.lines_hit(), file.lines.len());
+        assert_eq!(file.functions["__main__.main"].hits, 1);
+        assert!(file.functions["__main__.fib"].hits > 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn coverage_from_runner_without_trace() {
+        let runner = cairo_run(
+            include_bytes!("../../cairo_programs/fibonacci.json"),
+            &CairoRunConfig::default(),
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+        assert_eq!(
+            Coverage::from_runner(&runner),
+            Err(TraceError::TraceNotEnabled)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn write_reports() {
+        let coverage = fibonacci_coverage();
+
+        let mut lcov = String::new();
+        coverage.write_lcov(&mut lcov).unwrap();
+        assert!(lcov.starts_with("TN:\nSF:"));
+        assert!(lcov.contains("FNDA:1,__main__.main\n"));
+        assert!(lcov.ends_with("end_of_record\n"));
+
+        let mut cobertura = String::new();
+        coverage.write_cobertura(&mut cobertura).unwrap();
+        assert!(cobertura.contains(r#"<coverage line-rate="1""#));
+        assert!(cobertura.contains(r#"<method name="__main__.fib""#));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn escape_xml_special_characters() {
+        assert_eq!(escape_xml(r#"a<b>&"c""#), "a&lt;b&gt;&amp;&quot;c&quot;");
+    }
+}
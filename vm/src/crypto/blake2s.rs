@@ -0,0 +1,230 @@
+use crate::stdlib::prelude::*;
+
+pub const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Applies the blake2s compression function to the state `h`, as `blake2s_compress` from
+/// `starkware.cairo.common.cairo_blake2s.blake2s_utils`.
+///
+/// `t0` and `t1` are the low and high words of the byte counter, `f0` and `f1` the finalization
+/// flags. The working state is kept on the stack, as this is called once per block (and per
+/// padding instance) by the finalize hints.
+///
+/// When the `blake2s-vectorized` feature is enabled, the four mixes of each half round are
+/// computed together over rows of the working state, which the compiler lowers to SIMD
+/// instructions on targets supporting them.
+pub fn blake2s_compress_array(
+    h: &[u32; 8],
+    message: &[u32; 16],
+    t0: u32,
+    t1: u32,
+    f0: u32,
+    f1: u32,
+) -> [u32; 8] {
+    #[cfg(feature = "blake2s-vectorized")]
+    let compress = vectorized::compress;
+    #[cfg(not(feature = "blake2s-vectorized"))]
+    let compress = scalar::compress;
+    compress(h, message, t0, t1, f0, f1)
+}
+
+/// Same as [blake2s_compress_array], returning the new state as a `Vec`.
+pub fn blake2s_compress(
+    h: &[u32; 8],
+    message: &[u32; 16],
+    t0: u32,
+    t1: u32,
+    f0: u32,
+    f1: u32,
+) -> Vec<u32> {
+    blake2s_compress_array(h, message, t0, t1, f0, f1).to_vec()
+}
+
+#[cfg(any(test, not(feature = "blake2s-vectorized")))]
+mod scalar {
+    use super::{IV, SIGMA};
+
+    fn mix(state: &mut [u32; 16], [a, b, c, d]: [usize; 4], m0: u32, m1: u32) {
+        state[a] = state[a].wrapping_add(state[b]).wrapping_add(m0);
+        state[d] = (state[d] ^ state[a]).rotate_right(16);
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] = (state[b] ^ state[c]).rotate_right(12);
+        state[a] = state[a].wrapping_add(state[b]).wrapping_add(m1);
+        state[d] = (state[d] ^ state[a]).rotate_right(8);
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] = (state[b] ^ state[c]).rotate_right(7);
+    }
+
+    fn blake_round(state: &mut [u32; 16], message: &[u32; 16], sigma: &[usize; 16]) {
+        mix(state, [0, 4, 8, 12], message[sigma[0]], message[sigma[1]]);
+        mix(state, [1, 5, 9, 13], message[sigma[2]], message[sigma[3]]);
+        mix(state, [2, 6, 10, 14], message[sigma[4]], message[sigma[5]]);
+        mix(state, [3, 7, 11, 15], message[sigma[6]], message[sigma[7]]);
+        mix(state, [0, 5, 10, 15], message[sigma[8]], message[sigma[9]]);
+        mix(
+            state,
+            [1, 6, 11, 12],
+            message[sigma[10]],
+            message[sigma[11]],
+        );
+        mix(state, [2, 7, 8, 13], message[sigma[12]], message[sigma[13]]);
+        mix(state, [3, 4, 9, 14], message[sigma[14]], message[sigma[15]]);
+    }
+
+    pub(super) fn compress(
+        h: &[u32; 8],
+        message: &[u32; 16],
+        t0: u32,
+        t1: u32,
+        f0: u32,
+        f1: u32,
+    ) -> [u32; 8] {
+        let mut state = [0; 16];
+        state[..8].copy_from_slice(h);
+        state[8..12].copy_from_slice(&IV[..4]);
+        state[12] = IV[4] ^ t0;
+        state[13] = IV[5] ^ t1;
+        state[14] = IV[6] ^ f0;
+        state[15] = IV[7] ^ f1;
+        for sigma in &SIGMA {
+            blake_round(&mut state, message, sigma);
+        }
+        core::array::from_fn(|i| h[i] ^ state[i] ^ state[8 + i])
+    }
+}
+
+#[cfg(any(test, feature = "blake2s-vectorized"))]
+mod vectorized {
+    use super::{IV, SIGMA};
+
+    /// Four consecutive words of the working state, processed lane-wise.
+    type Row = [u32; 4];
+
+    #[inline(always)]
+    fn add(x: Row, y: Row) -> Row {
+        core::array::from_fn(|i| x[i].wrapping_add(y[i]))
+    }
+
+    #[inline(always)]
+    fn xor_rotate(x: Row, y: Row, n: u32) -> Row {
+        core::array::from_fn(|i| (x[i] ^ y[i]).rotate_right(n))
+    }
+
+    /// Applies the mixing function to the four lanes of `rows` at once, lane `i` holding the
+    /// words `a`, `b`, `c` and `d` of the `i`-th mix.
+    #[inline(always)]
+    fn mix(rows: &mut [Row; 4], m0: Row, m1: Row) {
+        let [a, b, c, d] = rows;
+        *a = add(add(*a, *b), m0);
+        *d = xor_rotate(*d, *a, 16);
+        *c = add(*c, *d);
+        *b = xor_rotate(*b, *c, 12);
+        *a = add(add(*a, *b), m1);
+        *d = xor_rotate(*d, *a, 8);
+        *c = add(*c, *d);
+        *b = xor_rotate(*b, *c, 7);
+    }
+
+    fn blake_round(rows: &mut [Row; 4], message: &[u32; 16], sigma: &[usize; 16]) {
+        let word = |i: usize| message[sigma[i]];
+        // Columns
+        mix(
+            rows,
+            [word(0), word(2), word(4), word(6)],
+            [word(1), word(3), word(5), word(7)],
+        );
+        // Diagonals, moved into the columns by rotating the last three rows
+        rows[1].rotate_left(1);
+        rows[2].rotate_left(2);
+        rows[3].rotate_left(3);
+        mix(
+            rows,
+            [word(8), word(10), word(12), word(14)],
+            [word(9), word(11), word(13), word(15)],
+        );
+        rows[1].rotate_right(1);
+        rows[2].rotate_right(2);
+        rows[3].rotate_right(3);
+    }
+
+    pub(super) fn compress(
+        h: &[u32; 8],
+        message: &[u32; 16],
+        t0: u32,
+        t1: u32,
+        f0: u32,
+        f1: u32,
+    ) -> [u32; 8] {
+        let mut rows = [
+            [h[0], h[1], h[2], h[3]],
+            [h[4], h[5], h[6], h[7]],
+            [IV[0], IV[1], IV[2], IV[3]],
+            [IV[4] ^ t0, IV[5] ^ t1, IV[6] ^ f0, IV[7] ^ f1],
+        ];
+        for sigma in &SIGMA {
+            blake_round(&mut rows, message, sigma);
+        }
+        core::array::from_fn(|i| h[i] ^ rows[i / 4][i % 4] ^ rows[2 + i / 4][i % 4])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// Deterministic pseudo-random words (xorshift32), to compare both implementations over
+    /// inputs covering every bit.
+    fn words<const N: usize>(seed: &mut u32) -> [u32; N] {
+        core::array::from_fn(|_| {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 17;
+            *seed ^= *seed << 5;
+            *seed
+        })
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn vectorized_compress_matches_scalar() {
+        let mut seed = 0x2545F491;
+        for _ in 0..100 {
+            let h = words::<8>(&mut seed);
+            let message = words::<16>(&mut seed);
+            let [t0, t1, f0, f1] = words::<4>(&mut seed);
+            assert_eq!(
+                vectorized::compress(&h, &message, t0, t1, f0, f1),
+                scalar::compress(&h, &message, t0, t1, f0, f1)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn vectorized_compress_matches_scalar_padding_instance() {
+        // The dummy instance written by the finalize hints
+        let mut modified_iv = IV;
+        modified_iv[0] ^= 0x01010020;
+        let message = [0; 16];
+        assert_eq!(
+            vectorized::compress(&modified_iv, &message, 0, 0, 0xffffffff, 0),
+            scalar::compress(&modified_iv, &message, 0, 0, 0xffffffff, 0)
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::Felt252;
+
+/// Applies the Keccak-f[1600] permutation to `state` in place.
+///
+/// This is the permutation used by the keccak builtin and by the `cairo_keccak` hints (in place
+/// of `keccak_func` from `keccak_utils.py`). The state is made of 25 little-endian 64-bit lanes.
+/// When the `keccak-asm` feature is enabled, the ARMv8 SHA3 instructions are used on targets
+/// supporting them, falling back to the unrolled software implementation otherwise.
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    ::keccak::f1600(state)
+}
+
+/// Number of bits held by each input and output cell of the keccak builtin.
+const KECCAK_BUILTIN_CELL_BITS: usize = 200;
+
+/// Packs the eight 200-bit input cells of a keccak builtin instance into the 25 lanes of a
+/// Keccak-f[1600] state, cell `i` occupying bits `[200 * i, 200 * (i + 1))` of the state.
+///
+/// Each cell is moved as four 64-bit limbs, so no per-bit or per-byte felt arithmetic is
+/// involved. The cells are expected to be lower than 2^200, as checked by the builtin.
+pub fn keccak_builtin_state_from_felts(cells: &[Felt252; 8]) -> [u64; 25] {
+    let mut state = [0_u64; 25];
+    for (i, cell) in cells.iter().enumerate() {
+        let limbs = cell.to_le_digits();
+        for (j, limb) in limbs.iter().enumerate() {
+            let start = i * KECCAK_BUILTIN_CELL_BITS + j * 64;
+            let (lane, shift) = (start / 64, start % 64);
+            state[lane] |= limb << shift;
+            if shift > 0 && lane + 1 < state.len() {
+                state[lane + 1] |= limb >> (64 - shift);
+            }
+        }
+    }
+    state
+}
+
+/// Splits a Keccak-f[1600] state into the eight 200-bit output cells of a keccak builtin
+/// instance. This is the inverse of [`keccak_builtin_state_from_felts`].
+pub fn keccak_builtin_felts_from_state(state: &[u64; 25]) -> [Felt252; 8] {
+    core::array::from_fn(|i| {
+        let mut bytes = [0_u8; 32];
+        for j in 0..4 {
+            let start = i * KECCAK_BUILTIN_CELL_BITS + j * 64;
+            let (lane, shift) = (start / 64, start % 64);
+            let mut limb = state[lane] >> shift;
+            if shift > 0 && lane + 1 < state.len() {
+                limb |= state[lane + 1] << (64 - shift);
+            }
+            // Only the lowest 8 bits of the last limb belong to this cell
+            if j == 3 {
+                limb &= 0xff;
+            }
+            bytes[j * 8..(j + 1) * 8].copy_from_slice(&limb.to_le_bytes());
+        }
+        Felt252::from_bytes_le(&bytes)
+    })
+}
+
+/// Computes the eight output cells of a keccak builtin instance from its eight input cells.
+///
+/// This is the same function used by the keccak builtin to deduce its output cells. The input
+/// cells are expected to be lower than 2^200.
+pub fn keccak_builtin_permutation(cells: &[Felt252; 8]) -> [Felt252; 8] {
+    let mut state = keccak_builtin_state_from_felts(cells);
+    keccak_f1600(&mut state);
+    keccak_builtin_felts_from_state(&state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// Textbook Keccak-f[1600] (FIPS 202, section 3), used as the scalar reference for the
+    /// implementation selected by the `keccak-asm` feature.
+    fn keccak_f1600_reference(state: &mut [u64; 25]) {
+        const ROUND_CONSTANTS: [u64; 24] = [
+            0x0000000000000001,
+            0x0000000000008082,
+            0x800000000000808A,
+            0x8000000080008000,
+            0x000000000000808B,
+            0x0000000080000001,
+            0x8000000080008081,
+            0x8000000000008009,
+            0x000000000000008A,
+            0x0000000000000088,
+            0x0000000080008009,
+            0x000000008000000A,
+            0x000000008000808B,
+            0x800000000000008B,
+            0x8000000000008089,
+            0x8000000000008003,
+            0x8000000000008002,
+            0x8000000000000080,
+            0x000000000000800A,
+            0x800000008000000A,
+            0x8000000080008081,
+            0x8000000000008080,
+            0x0000000080000001,
+            0x8000000080008008,
+        ];
+        // Rotation offsets of lane (x, y), at index x + 5 * y
+        const RHO: [u32; 25] = [
+            0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61,
+            56, 14,
+        ];
+        for round_constant in ROUND_CONSTANTS {
+            // Theta
+            let columns: [u64; 5] = core::array::from_fn(|x| {
+                state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20]
+            });
+            for x in 0..5 {
+                let d = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+                for y in 0..5 {
+                    state[x + 5 * y] ^= d;
+                }
+            }
+            // Rho and pi
+            let mut moved = [0_u64; 25];
+            for x in 0..5 {
+                for y in 0..5 {
+                    moved[y + 5 * ((2 * x + 3 * y) % 5)] =
+                        state[x + 5 * y].rotate_left(RHO[x + 5 * y]);
+                }
+            }
+            // Chi
+            for x in 0..5 {
+                for y in 0..5 {
+                    state[x + 5 * y] = moved[x + 5 * y]
+                        ^ (!moved[(x + 1) % 5 + 5 * y] & moved[(x + 2) % 5 + 5 * y]);
+                }
+            }
+            // Iota
+            state[0] ^= round_constant;
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn keccak_f1600_matches_reference() {
+        // Deterministic pseudo-random lanes (xorshift64)
+        let mut seed = 0x9E3779B97F4A7C15_u64;
+        for _ in 0..20 {
+            let state: [u64; 25] = core::array::from_fn(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            });
+            let (mut optimized, mut reference) = (state, state);
+            keccak_f1600(&mut optimized);
+            keccak_f1600_reference(&mut reference);
+            assert_eq!(optimized, reference);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn keccak_f1600_zero_state() {
+        let mut state = [0; 25];
+        keccak_f1600(&mut state);
+        assert_eq!(state[0], 0xF1258F7940E1DDE7);
+        assert_eq!(state[1], 0x84D5CCF933C0478A);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn keccak_builtin_state_round_trip() {
+        let max = Felt252::TWO.pow(200_u32) - Felt252::ONE;
+        let cells = [
+            Felt252::ZERO,
+            Felt252::ONE,
+            max,
+            Felt252::from(u64::MAX),
+            Felt252::TWO.pow(199_u32),
+            Felt252::from(0x1234567890abcdef_u64) * Felt252::TWO.pow(100_u32),
+            max,
+            Felt252::from(255),
+        ];
+        let state = keccak_builtin_state_from_felts(&cells);
+        assert_eq!(keccak_builtin_felts_from_state(&state), cells);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn keccak_builtin_state_layout() {
+        let mut cells = [Felt252::ZERO; 8];
+        // Bit 200 of the state is the lowest bit of the second cell
+        cells[1] = Felt252::ONE;
+        // Bit 1599 of the state is the highest bit of the last cell
+        cells[7] = Felt252::TWO.pow(199_u32);
+        let state = keccak_builtin_state_from_felts(&cells);
+        let mut expected = [0_u64; 25];
+        expected[3] = 1 << 8;
+        expected[24] = 1 << 63;
+        assert_eq!(state, expected);
+    }
+}
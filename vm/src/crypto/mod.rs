@@ -0,0 +1,112 @@
+//! Hash functions and permutations used by the VM builtins and hints, exposed so that
+//! integrators reuse the same implementation (e.g. when computing program hashes or commitments).
+
+pub(crate) mod blake2s;
+mod keccak;
+
+pub use self::blake2s::{blake2s_compress, blake2s_compress_array};
+pub use self::keccak::{
+    keccak_builtin_felts_from_state, keccak_builtin_permutation, keccak_builtin_state_from_felts,
+    keccak_f1600,
+};
+
+use crate::Felt252;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
+
+/// Computes the Pedersen hash of `x` and `y`.
+///
+/// This is the same function used by the pedersen builtin, where `x` is the first input cell
+/// and `y` the second one.
+pub fn pedersen_hash(x: &Felt252, y: &Felt252) -> Felt252 {
+    Pedersen::hash(x, y)
+}
+
+/// Applies the Hades permutation used by Poseidon to `state` in place.
+///
+/// This is the same function used by the poseidon builtin, where `state` holds the three
+/// input cells and is overwritten with the three output cells.
+pub fn poseidon_permutation(state: &mut [Felt252; 3]) {
+    Poseidon::hades_permutation(state)
+}
+
+/// Computes the Poseidon hash of `x` and `y`, as `poseidon_hash` in Cairo's
+/// `starkware.cairo.common.builtin_poseidon.poseidon`.
+pub fn poseidon_hash(x: &Felt252, y: &Felt252) -> Felt252 {
+    Poseidon::hash(x, y)
+}
+
+/// Computes the Poseidon hash of an arbitrary amount of elements, as `poseidon_hash_many` in
+/// Cairo's `starkware.cairo.common.builtin_poseidon.poseidon`.
+pub fn poseidon_hash_many(values: &[Felt252]) -> Felt252 {
+    Poseidon::hash_array(values)
+}
+
+/// Computes the public key of `private_key` over the STARK curve.
+#[cfg(feature = "test_utils")]
+pub fn get_public_key(private_key: &Felt252) -> Felt252 {
+    starknet_crypto::get_public_key(private_key)
+}
+
+/// Signs `message_hash` with `private_key` over the STARK curve, producing a signature `(r, s)`
+/// accepted by the ecdsa builtin. The nonce is derived deterministically as in RFC 6979.
+///
+/// Meant to generate valid signatures in tests, it is not audited for production use.
+#[cfg(feature = "test_utils")]
+pub fn sign(
+    message_hash: &Felt252,
+    private_key: &Felt252,
+) -> Result<(Felt252, Felt252), starknet_crypto::SignError> {
+    let k = starknet_crypto::rfc6979_generate_k(message_hash, private_key, None);
+    let signature = starknet_crypto::sign(private_key, message_hash, &k)?;
+    Ok((signature.r, signature.s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::felt_hex;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pedersen_hash_matches_builtin() {
+        assert_eq!(
+            pedersen_hash(&Felt252::from(32), &Felt252::from(72)),
+            felt_hex!("0x73b3ec210cccbb970f80c6826fb1c40ae9f487617696234ff147451405c339f")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn poseidon_hash_is_permutation_with_capacity_two() {
+        let (x, y) = (Felt252::from(1), Felt252::from(2));
+        let mut state = [x, y, Felt252::TWO];
+        poseidon_permutation(&mut state);
+        assert_eq!(poseidon_hash(&x, &y), state[0]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn poseidon_hash_many_pads_input() {
+        // An even amount of elements gets padded with [1, 0]
+        let values = [Felt252::from(7), Felt252::from(11)];
+        let mut state = [values[0], values[1], Felt252::ZERO];
+        poseidon_permutation(&mut state);
+        state[0] += Felt252::ONE;
+        poseidon_permutation(&mut state);
+        assert_eq!(poseidon_hash_many(&values), state[0]);
+    }
+
+    #[test]
+    #[cfg(feature = "test_utils")]
+    fn sign_produces_verifiable_signature() {
+        let private_key = Felt252::from(0x1234567890_u64);
+        let message_hash = pedersen_hash(&Felt252::from(1), &Felt252::from(2));
+        let (r, s) = sign(&message_hash, &private_key).unwrap();
+        assert!(
+            starknet_crypto::verify(&get_public_key(&private_key), &message_hash, &r, &s).unwrap()
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Helpers to convert between big integers and their representation as limbs of felts, as done
+//! by the `uint256`, `uint384` and `cairo_secp` Cairo libraries.
+//!
+//! These are the same primitives used by the builtin hint processor, exposed so that custom hints
+//! can reuse them.
+
+use crate::math_utils::signed_felt;
+use crate::stdlib::ops::Shl;
+use crate::types::errors::math_errors::MathError;
+use crate::Felt252;
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// Splits `num` into `N` limbs of `num_bits_shift` bits each, least significant limb first.
+///
+/// Bits that don't fit in the `N` limbs are discarded.
+///
+/// # Examples
+///
+/// ```
+/// # use cairo_vm::hint_processor::builtin_hint_processor::biguint_limbs::split;
+/// # use cairo_vm::Felt252;
+/// # use num_bigint::BigUint;
+/// let limbs: [Felt252; 2] = split(&((BigUint::from(3_u32) << 128) + 5_u32), 128);
+/// assert_eq!(limbs, [Felt252::from(5), Felt252::from(3)]);
+/// ```
+pub fn split<const N: usize>(num: &BigUint, num_bits_shift: u32) -> [Felt252; N] {
+    let mut num = num.clone();
+    let bitmask = &((BigUint::one() << num_bits_shift) - 1_u32);
+    [0; N].map(|_| {
+        let a = &num & bitmask;
+        num >>= num_bits_shift;
+        Felt252::from(&a)
+    })
+}
+
+/// Packs `N` limbs of `num_bits_shift` bits each, least significant limb first, into a
+/// single integer. This is the inverse of [split].
+pub fn pack<const N: usize>(limbs: [impl AsRef<Felt252>; N], num_bits_shift: usize) -> BigUint {
+    limbs
+        .into_iter()
+        .enumerate()
+        .map(|(i, limb)| limb.as_ref().to_biguint() << (i * num_bits_shift))
+        .sum()
+}
+
+/// Packs the first three limbs, interpreted as signed felts, into an integer using base 2**86.
+///
+/// This is `pack` from `starkware.cairo.common.cairo_secp.secp_utils`.
+pub fn pack86<const N: usize>(limbs: [impl AsRef<Felt252>; N]) -> BigInt {
+    limbs
+        .into_iter()
+        .take(3)
+        .enumerate()
+        .map(|(idx, value)| signed_felt(*value.as_ref()).shl(idx * 86))
+        .sum()
+}
+
+/// Computes the carries of adding `a` and `b` limb by limb, where a limb overflows when it
+/// reaches `shift`.
+pub fn add_carries<const N: usize>(
+    a: &[impl AsRef<Felt252>; N],
+    b: &[impl AsRef<Felt252>; N],
+    shift: &BigUint,
+) -> [bool; N] {
+    let mut carry = false;
+    let mut carries = [false; N];
+    for (i, c) in carries.iter_mut().enumerate() {
+        let sum = a[i].as_ref().to_biguint() + b[i].as_ref().to_biguint() + carry as u32;
+        carry = &sum >= shift;
+        *c = carry;
+    }
+    carries
+}
+
+/// Computes the quotient and remainder of `a` divided by `div`.
+///
+/// # Errors
+///
+/// Returns [`MathError::DividedByZero`] if `div` is zero.
+pub fn div_rem(a: &BigUint, div: &BigUint) -> Result<(BigUint, BigUint), MathError> {
+    if div.is_zero() {
+        return Err(MathError::DividedByZero);
+    }
+    Ok(a.div_mod_floor(div))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn split_and_pack_roundtrip() {
+        let num = (BigUint::from(7_u32) << 256) + (BigUint::from(2_u32) << 128) + 1_u32;
+        let limbs: [Felt252; 3] = split(&num, 128);
+        assert_eq!(
+            limbs,
+            [Felt252::from(1), Felt252::from(2), Felt252::from(7)]
+        );
+        assert_eq!(pack(limbs, 128), num);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pack86_negative_limbs() {
+        let limbs = [Felt252::from(-1), Felt252::ONE, Felt252::ZERO];
+        assert_eq!(pack86(limbs), (BigInt::one() << 86) - 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn add_carries_propagate() {
+        let shift = BigUint::one() << 128;
+        let max = Felt252::from(u128::MAX);
+        let a = [max, max, Felt252::ONE];
+        let b = [Felt252::ONE, Felt252::ZERO, Felt252::ONE];
+        assert_eq!(add_carries(&a, &b, &shift), [true, true, false]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_rem_by_zero() {
+        assert_matches!(
+            div_rem(&BigUint::one(), &BigUint::zero()),
+            Err(MathError::DividedByZero)
+        );
+        assert_eq!(
+            div_rem(&BigUint::from(7_u32), &BigUint::from(2_u32)),
+            Ok((BigUint::from(3_u32), BigUint::one()))
+        );
+    }
+}
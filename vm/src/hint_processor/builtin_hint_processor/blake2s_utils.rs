@@ -1,11 +1,11 @@
-use crate::stdlib::{borrow::Cow, collections::HashMap, prelude::*};
+use crate::stdlib::{collections::HashMap, prelude::*};
 
 use crate::types::errors::math_errors::MathError;
 use crate::Felt252;
 use crate::{
     hint_processor::{
         builtin_hint_processor::{
-            blake2s_hash::{blake2s_compress, IV},
+            blake2s_hash::{blake2s_compress, blake2s_compress_array, IV},
             hint_utils::{get_ptr_from_var_name, get_relocatable_from_var_name},
         },
         hint_processor_definition::HintReference,
@@ -22,18 +22,19 @@ use num_traits::ToPrimitive;
 use super::hint_utils::get_integer_from_var_name;
 
 fn get_fixed_size_u32_array<const T: usize>(
-    h_range: &Vec<Cow<Felt252>>,
+    h_range: impl ExactSizeIterator<Item = Felt252>,
 ) -> Result<[u32; T], HintError> {
-    let mut u32_vec = Vec::<u32>::with_capacity(h_range.len());
-    for num in h_range {
-        u32_vec.push(num.to_u32().ok_or(HintError::BigintToU32Fail)?);
+    if h_range.len() != T {
+        return Err(HintError::FixedSizeArrayFail(T));
     }
-    u32_vec
-        .try_into()
-        .map_err(|_| HintError::FixedSizeArrayFail(T))
+    let mut u32_array = [0; T];
+    for (element, num) in u32_array.iter_mut().zip(h_range) {
+        *element = num.to_u32().ok_or(HintError::BigintToU32Fail)?;
+    }
+    Ok(u32_array)
 }
 
-fn get_maybe_relocatable_array_from_u32(array: &Vec<u32>) -> Vec<MaybeRelocatable> {
+fn get_maybe_relocatable_array_from_u32(array: &[u32]) -> Vec<MaybeRelocatable> {
     let mut new_array = Vec::<MaybeRelocatable>::with_capacity(array.len());
     for element in array {
         new_array.push(MaybeRelocatable::from(Felt252::from(*element)));
@@ -47,12 +48,12 @@ output_ptr should point to the middle of an instance, right after initial_state,
 which should all have a value at this point, and right before the output portion which will be
 written by this function.*/
 fn compute_blake2s_func(vm: &mut VirtualMachine, output_ptr: Relocatable) -> Result<(), HintError> {
-    let h = get_fixed_size_u32_array::<8>(&vm.get_integer_range((output_ptr - 26)?, 8)?)?;
-    let message = get_fixed_size_u32_array::<16>(&vm.get_integer_range((output_ptr - 18)?, 16)?)?;
+    let h = get_fixed_size_u32_array::<8>(vm.get_felt_range((output_ptr - 26)?, 8)?)?;
+    let message = get_fixed_size_u32_array::<16>(vm.get_felt_range((output_ptr - 18)?, 16)?)?;
     let t = felt_to_u32(vm.get_integer((output_ptr - 2)?)?.as_ref())?;
     let f = felt_to_u32(vm.get_integer((output_ptr - 1)?)?.as_ref())?;
     let new_state =
-        get_maybe_relocatable_array_from_u32(&blake2s_compress(&h, &message, t, 0, f, 0));
+        get_maybe_relocatable_array_from_u32(&blake2s_compress_array(&h, &message, t, 0, f, 0));
     vm.load_data(output_ptr, &new_state)
         .map_err(HintError::Memory)?;
     Ok(())
@@ -275,7 +276,7 @@ pub fn example_blake2s_compress(
             .ok_or_else(|| HintError::Math(MathError::Felt252ToU32Conversion(Box::new(x))))
     })??;
 
-    let message = get_fixed_size_u32_array::<16>(&vm.get_integer_range(blake2s_start, 16)?)?;
+    let message = get_fixed_size_u32_array::<16>(vm.get_felt_range(blake2s_start, 16)?)?;
     let mut modified_iv = IV;
     modified_iv[0] = IV[0] ^ 0x01010020;
     let new_state = blake2s_compress(&modified_iv, &message, n_bytes, 0, 0xffffffff, 0);
@@ -458,13 +459,9 @@ mod tests {
             2491453561, 3491828193, 2085238082, 1219908895, 514171180, 4245497115, 4193177630,
         ];
         //Get data from memory
-        let data = get_fixed_size_u32_array::<204>(
-            &vm.segments
-                .memory
-                .get_integer_range(relocatable!(2, 0), 204)
-                .unwrap(),
-        )
-        .unwrap();
+        let data =
+            get_fixed_size_u32_array::<204>(vm.get_felt_range(relocatable!(2, 0), 204).unwrap())
+                .unwrap();
         assert_eq!(expected_data, data);
     }
 
@@ -0,0 +1,2153 @@
+//! Hints of the simple bootloader (`starkware.cairo.bootloaders.simple_bootloader`) that load
+//! its input, pick the current task, load its program into memory, validate its hash, call it,
+//! write back the builtin pointers it returned and configure the output pages from the fact
+//! topologies of the tasks.
+//!
+//! The bootloader input is parsed from the `program_input` scope variable into the
+//! `simple_bootloader_input` scope variable, as a [SimpleBootloaderInput].
+//!
+//! Program tasks are called with their hints loaded along with them when the `extensive_hints`
+//! feature is enabled, and run with the constants of the bootloader. Without that feature, only
+//! programs without hints can be run as tasks. Cairo PIE tasks aren't run: their memory is
+//! relocated into the bootloader run instead.
+
+#[cfg(feature = "extensive_hints")]
+use crate::hint_processor::hint_processor_definition::{HintExtension, HintProcessorLogic};
+use crate::{
+    any_box,
+    bootloader::{get_task_fact_topology, FactTopology, SimpleBootloaderInput, Task},
+    hint_processor::{
+        builtin_hint_processor::hint_utils::{
+            get_integer_from_var_name, get_member_address_from_var_name, get_ptr_from_var_name,
+            insert_value_from_var_name, insert_value_into_ap,
+        },
+        hint_processor_definition::HintReference,
+    },
+    serde::deserialize_program::{ApTracking, Identifier},
+    stdlib::{collections::HashMap, prelude::*},
+    types::{
+        builtin_name::BuiltinName,
+        exec_scope::ExecutionScopes,
+        program::Program,
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+        runners::{
+            builtin_runner::OutputBuiltinState,
+            cairo_pie::{BuiltinAdditionalData, CairoPie, Pages, PublicMemoryPage},
+        },
+        vm_core::VirtualMachine,
+    },
+    Felt252,
+};
+use num_traits::ToPrimitive;
+use serde::Deserialize;
+
+/// Version of the bootloader, written in the program headers and hashed with the programs.
+pub const BOOTLOADER_VERSION: usize = 0;
+
+/// The struct holding a pointer for each builtin the bootloader supports, in the order the
+/// bootloader lays them out.
+pub const BUILTIN_DATA: &str =
+    "starkware.cairo.bootloaders.simple_bootloader.execute_task.BuiltinData";
+
+/// The function of the bootloader that runs a task, whose labels locate the return pc of the
+/// Cairo PIE tasks.
+pub const EXECUTE_TASK: &str =
+    "starkware.cairo.bootloaders.simple_bootloader.execute_task.execute_task";
+
+pub const SIMPLE_BOOTLOADER_LOAD_INPUT: &str = r#"from starkware.cairo.bootloaders.simple_bootloader.objects import SimpleBootloaderInput
+simple_bootloader_input = SimpleBootloaderInput.Schema().load(program_input)"#;
+
+pub const SIMPLE_BOOTLOADER_PREPARE_TASK_RANGE_CHECKS: &str = r#"n_tasks = len(simple_bootloader_input.tasks)
+memory[ids.output_ptr] = n_tasks
+
+# Task range checks are located right after simple bootloader validation range checks, and
+# this is validated later in this function.
+ids.task_range_check_ptr = ids.range_check_ptr + ids.BuiltinData.SIZE * n_tasks
+
+# A list of fact_toplogies that instruct how to generate the fact from the program output
+# for each task.
+fact_topologies = []"#;
+
+pub const SIMPLE_BOOTLOADER_SET_TASKS_VARIABLE: &str = "tasks = simple_bootloader_input.tasks";
+
+pub const SIMPLE_BOOTLOADER_SET_CURRENT_TASK: &str = r#"from starkware.cairo.bootloaders.simple_bootloader.objects import Task
+
+# Pass current task to execute_task.
+task_id = len(simple_bootloader_input.tasks) - ids.n_tasks
+task = simple_bootloader_input.tasks[task_id].load_task()"#;
+
+pub const SIMPLE_BOOTLOADER_USE_POSEIDON: &str =
+    "memory[ap] = to_felt_or_relocatable(1 if task.use_poseidon else 0)";
+
+pub const SIMPLE_BOOTLOADER_CONFIGURE_FACT_TOPOLOGIES: &str = r#"# Dump fact topologies to a json file.
+from starkware.cairo.bootloaders.simple_bootloader.utils import (
+    configure_fact_topologies,
+    write_to_fact_topologies_file,
+)
+
+# The task-related output is prefixed by a single word that contains the number of tasks.
+tasks_output_start = output_builtin.base + 1
+
+if not simple_bootloader_input.single_page:
+    # Configure the memory pages in the output builtin, based on fact_topologies.
+    configure_fact_topologies(
+        fact_topologies=fact_topologies, output_start=tasks_output_start,
+        output_builtin=output_builtin,
+    )
+
+if simple_bootloader_input.fact_topologies_path is not None:
+    write_to_fact_topologies_file(
+        fact_topologies_path=simple_bootloader_input.fact_topologies_path,
+        fact_topologies=fact_topologies,
+    )"#;
+
+pub const EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT: &str =
+    "ids.program_data_ptr = program_data_base = segments.add()";
+
+pub const EXECUTE_TASK_LOAD_PROGRAM: &str = r#"from starkware.cairo.bootloaders.simple_bootloader.utils import load_program
+
+# Call load_program to load the program header and code to memory.
+program_address, program_data_size = load_program(
+    task=task, memory=memory, program_header=ids.program_header,
+    builtins_offset=ids.ProgramHeader.builtin_list)
+segments.finalize(program_data_base.segment_index, program_data_size)"#;
+
+pub const EXECUTE_TASK_VALIDATE_HASH: &str = r#"# Validate hash.
+from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+
+assert memory[ids.output_ptr + 1] == compute_program_hash_chain(
+    program=task.get_program(),
+    use_poseidon=bool(ids.use_poseidon)), 'Computed hash does not match input.'"#;
+
+pub const EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS: &str = r#"# Sanity check.
+assert ids.program_address == program_address"#;
+
+pub const EXECUTE_TASK_CALL_TASK: &str = r#"from starkware.cairo.bootloaders.simple_bootloader.objects import (
+    CairoPieTask,
+    RunProgramTask,
+    Task,
+)
+from starkware.cairo.bootloaders.simple_bootloader.utils import (
+    load_cairo_pie,
+    prepare_output_runner,
+)
+
+assert isinstance(task, Task)
+n_builtins = len(task.get_program().builtins)
+new_task_locals = {}
+if isinstance(task, RunProgramTask):
+    new_task_locals['program_input'] = task.program_input
+    new_task_locals['WITH_BOOTLOADER'] = True
+
+    vm_load_program(task.program, program_address)
+elif isinstance(task, CairoPieTask):
+    ret_pc = ids.ret_pc_label.instruction_offset_ - ids.call_task.instruction_offset_ + pc
+    load_cairo_pie(
+        task=task.cairo_pie, memory=memory, segments=segments,
+        program_address=program_address, execution_segment_address= ap - n_builtins,
+        builtin_runners=builtin_runners, ret_fp=fp, ret_pc=ret_pc)
+else:
+    raise NotImplementedError(f'Unexpected task type: {type(task).__name__}.')
+
+output_runner_data = prepare_output_runner(
+    task=task,
+    output_builtin=output_builtin,
+    output_ptr=ids.pre_execution_builtin_ptrs.output)
+vm_enter_scope(new_task_locals)"#;
+
+pub const EXECUTE_TASK_EXIT_SCOPE: &str = r#"vm_exit_scope()
+# Note that bootloader_input will only be available in the next hint."#;
+
+pub const EXECUTE_TASK_WRITE_RETURN_BUILTINS: &str = r#"from starkware.cairo.bootloaders.simple_bootloader.utils import write_return_builtins
+
+# Fill the values of all builtin pointers after executing the task.
+builtins = task.get_program().builtins
+write_return_builtins(
+    memory=memory, return_builtins_addr=ids.return_builtin_ptrs.address_,
+    used_builtins=builtins, used_builtins_addr=ids.used_builtins_addr,
+    pre_execution_builtins_addr=ids.pre_execution_builtin_ptrs.address_, task=task)
+
+vm_enter_scope({'n_selected_builtins': n_builtins})"#;
+
+pub const EXECUTE_TASK_APPEND_FACT_TOPOLOGIES: &str = r#"from starkware.cairo.bootloaders.simple_bootloader.utils import get_task_fact_topology
+
+# Add the fact topology of the current task to 'fact_topologies'.
+output_start = ids.pre_execution_builtin_ptrs.output
+output_end = ids.return_builtin_ptrs.output
+fact_topologies.append(get_task_fact_topology(
+    output_size=output_end - output_start,
+    task=task,
+    output_builtin=output_builtin,
+    output_runner_data=output_runner_data,
+))"#;
+
+pub const SELECT_BUILTINS_ENTER_SCOPE: &str =
+    "vm_enter_scope({'n_selected_builtins': ids.n_selected_builtins})";
+
+pub const SELECT_BUILTINS_SELECT_BUILTIN: &str = r#"# A builtin should be selected iff its encoding appears in the selected encodings list
+# and the list wasn't exhausted.
+# Note that testing inclusion by a single comparison is possible since the lists are sorted.
+ids.select_builtin = int(
+  n_selected_builtins > 0 and memory[ids.selected_encodings] == memory[ids.all_encodings])
+if ids.select_builtin:
+  n_selected_builtins = n_selected_builtins - 1"#;
+
+/// Parses the `program_input` scope variable into the `simple_bootloader_input` scope variable.
+pub fn simple_bootloader_load_input(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
+    let program_input = exec_scopes.get_ref::<serde_json::Value>("program_input")?;
+    let input = SimpleBootloaderInput::deserialize(program_input).map_err(|err| {
+        HintError::CustomHint(format!("Invalid simple bootloader input: {err}").into())
+    })?;
+    exec_scopes.insert_value("simple_bootloader_input", input);
+    Ok(())
+}
+
+/// Writes the number of tasks at `ids.output_ptr` and starts an empty `fact_topologies` list.
+///
+/// The range checks of the tasks start at `ids.task_range_check_ptr`, right after the ones the
+/// bootloader uses to validate the builtin pointers of each task (one per `BuiltinData` member).
+pub fn simple_bootloader_prepare_task_range_checks(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<(), HintError> {
+    let n_tasks = exec_scopes
+        .get_ref::<SimpleBootloaderInput>("simple_bootloader_input")?
+        .tasks
+        .len();
+    let output_ptr = get_ptr_from_var_name("output_ptr", vm, ids_data, ap_tracking)?;
+    vm.insert_value(output_ptr, Felt252::from(n_tasks))?;
+
+    let builtin_data_size = identifiers
+        .get(BUILTIN_DATA)
+        .and_then(|identifier| identifier.size)
+        .ok_or_else(|| HintError::UnknownIdentifier(BUILTIN_DATA.into()))?;
+    let range_check_ptr = get_ptr_from_var_name("range_check_ptr", vm, ids_data, ap_tracking)?;
+    insert_value_from_var_name(
+        "task_range_check_ptr",
+        (range_check_ptr + builtin_data_size * n_tasks)?,
+        vm,
+        ids_data,
+        ap_tracking,
+    )?;
+    exec_scopes.insert_value("fact_topologies", Vec::<FactTopology>::new());
+    Ok(())
+}
+
+/// Implements hint:
+/// %{ tasks = simple_bootloader_input.tasks %}
+pub fn simple_bootloader_set_tasks_variable(
+    exec_scopes: &mut ExecutionScopes,
+) -> Result<(), HintError> {
+    let tasks = exec_scopes
+        .get_ref::<SimpleBootloaderInput>("simple_bootloader_input")?
+        .tasks
+        .clone();
+    exec_scopes.insert_value("tasks", tasks);
+    Ok(())
+}
+
+/// Loads the task the bootloader is about to run into the `task` scope variable, and its index
+/// in the input into `task_id`.
+/// Tasks are run in order, with `ids.n_tasks` being the number of tasks left.
+pub fn simple_bootloader_set_current_task(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let input = exec_scopes.get_ref::<SimpleBootloaderInput>("simple_bootloader_input")?;
+    let n_tasks = get_integer_from_var_name("n_tasks", vm, ids_data, ap_tracking)?;
+    let (task_id, task_spec) = n_tasks
+        .to_usize()
+        .and_then(|n_tasks| input.tasks.len().checked_sub(n_tasks))
+        .and_then(|task_id| {
+            input
+                .tasks
+                .get(task_id)
+                .map(|task_spec| (task_id, task_spec))
+        })
+        .ok_or_else(|| HintError::CustomHint(format!("Invalid n_tasks: {n_tasks}").into()))?;
+    let task = task_spec
+        .load_task()
+        .map_err(|err| HintError::CustomHint(err.to_string().into()))?;
+    exec_scopes.insert_value("task_id", task_id);
+    exec_scopes.insert_value("task", task);
+    Ok(())
+}
+
+/// Implements hint:
+/// %{ 1 if task.use_poseidon else 0 %}
+///
+/// The flag is read from the input task at `task_id`, as [Task] doesn't keep it.
+pub fn simple_bootloader_use_poseidon(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+) -> Result<(), HintError> {
+    let input = exec_scopes.get_ref::<SimpleBootloaderInput>("simple_bootloader_input")?;
+    let task_id = exec_scopes.get::<usize>("task_id")?;
+    let task_spec = input
+        .tasks
+        .get(task_id)
+        .ok_or_else(|| HintError::CustomHint(format!("Invalid task_id: {task_id}").into()))?;
+    let use_poseidon = if task_spec.use_poseidon() {
+        Felt252::ONE
+    } else {
+        Felt252::ZERO
+    };
+    insert_value_into_ap(vm, use_poseidon)
+}
+
+/// Adds an output page for each page of the fact topologies of the tasks (unless the input asks
+/// for a single page), and writes the fact topologies to the input's `fact_topologies_path`.
+///
+/// The pages of the tasks are laid out one after the other, skipping the number of tasks at the
+/// start of the output and the size and program hash written before the output of each task.
+pub fn simple_bootloader_configure_fact_topologies(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+) -> Result<(), HintError> {
+    let input = exec_scopes.get_ref::<SimpleBootloaderInput>("simple_bootloader_input")?;
+    let fact_topologies = exec_scopes.get_ref::<Vec<FactTopology>>("fact_topologies")?;
+    let output_builtin = vm.get_output_builtin_mut()?;
+
+    if !input.single_page {
+        let mut output_start = Relocatable::from((output_builtin.base() as isize, 1));
+        // Page 0 is reserved for the bootloader
+        let mut page_id = 1;
+        for fact_topology in fact_topologies {
+            output_start = (output_start + 2)?;
+            for &page_size in &fact_topology.page_sizes {
+                output_builtin
+                    .add_page(page_id, output_start, page_size)
+                    .map_err(VirtualMachineError::from)?;
+                output_start = (output_start + page_size)?;
+                page_id += 1;
+            }
+        }
+    }
+
+    if let Some(path) = &input.fact_topologies_path {
+        write_fact_topologies(path, fact_topologies)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+fn write_fact_topologies(path: &str, fact_topologies: &[FactTopology]) -> Result<(), HintError> {
+    let fact_topologies_file = crate::bootloader::FactTopologiesFile {
+        fact_topologies: fact_topologies.to_vec(),
+    };
+    serde_json::to_vec(&fact_topologies_file)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| std::fs::write(path, bytes).map_err(|err| err.to_string()))
+        .map_err(|err| HintError::CustomHint(err.into()))
+}
+
+#[cfg(not(feature = "std"))]
+fn write_fact_topologies(_path: &str, _fact_topologies: &[FactTopology]) -> Result<(), HintError> {
+    Err(HintError::CustomHint(
+        "Fact topologies can only be written with the std feature".into(),
+    ))
+}
+
+/// Implements hint:
+/// %{ ids.program_data_ptr = program_data_base = segments.add() %}
+pub fn execute_task_allocate_program_data_segment(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let program_data_base = vm.add_memory_segment();
+    insert_value_from_var_name(
+        "program_data_ptr",
+        program_data_base,
+        vm,
+        ids_data,
+        ap_tracking,
+    )?;
+    exec_scopes.insert_value("program_data_base", program_data_base);
+    Ok(())
+}
+
+/// Writes the program header of the current task at `ids.program_header`, followed by its
+/// program's code, and finalizes the program data segment with their size.
+/// The address of the code is stored in the `program_address` scope variable.
+///
+/// The header consists of the data length (not counting itself), the bootloader version, the
+/// program's main, and the number and names of its builtins. Its fields are located through the
+/// `ProgramHeader` struct of the bootloader.
+pub fn execute_task_load_program(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<(), HintError> {
+    let program = exec_scopes
+        .get_ref::<Task>("task")?
+        .get_program()
+        .map_err(|err| HintError::CustomHint(err.to_string().into()))?;
+    let program_data_base = exec_scopes.get::<Relocatable>("program_data_base")?;
+    let header_address = get_ptr_from_var_name("program_header", vm, ids_data, ap_tracking)?;
+    let member_address = |member: &str| {
+        get_member_address_from_var_name(
+            &format!("program_header.{member}"),
+            vm,
+            ids_data,
+            ap_tracking,
+            identifiers,
+        )
+    };
+
+    let builtins_address = member_address("builtin_list")?;
+    let n_builtins = program.builtins.len();
+    let header_size = (builtins_address - header_address)? + n_builtins;
+    let header_fields = [
+        (
+            member_address("data_length")?,
+            header_size - 1 + program.data.len(),
+        ),
+        (member_address("bootloader_version")?, BOOTLOADER_VERSION),
+        (member_address("program_main")?, program.main),
+        (member_address("n_builtins")?, n_builtins),
+    ];
+    for (address, value) in header_fields {
+        vm.insert_value(address, Felt252::from(value))?;
+    }
+    let builtin_list: Vec<MaybeRelocatable> = program
+        .builtins
+        .iter()
+        .map(|builtin| Felt252::from_bytes_be_slice(builtin.to_str().as_bytes()).into())
+        .collect();
+    vm.load_data(builtins_address, &builtin_list)?;
+    let program_address = (header_address + header_size)?;
+    vm.load_data(program_address, &program.data)?;
+
+    vm.segments.finalize(
+        Some(header_size + program.data.len()),
+        program_data_base.segment_index as usize,
+        None,
+    );
+    exec_scopes.insert_value("program_address", program_address);
+    Ok(())
+}
+
+/// Checks that the program hash the task's output starts with (after its size) is the hash of
+/// the task's program.
+pub fn execute_task_validate_hash(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let use_poseidon =
+        get_integer_from_var_name("use_poseidon", vm, ids_data, ap_tracking)? != Felt252::ZERO;
+    let output_ptr = get_ptr_from_var_name("output_ptr", vm, ids_data, ap_tracking)?;
+    let program_hash = exec_scopes
+        .get_ref::<Task>("task")?
+        .program_hash(BOOTLOADER_VERSION, use_poseidon)
+        .map_err(|err| HintError::CustomHint(err.to_string().into()))?;
+    if vm.get_integer((output_ptr + 1)?)?.as_ref() != &program_hash {
+        return Err(HintError::AssertionFailed(
+            "Computed hash does not match input.".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `ids.program_address` is the address the task's program was loaded at.
+pub fn execute_task_assert_program_address(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let program_address = exec_scopes.get::<Relocatable>("program_address")?;
+    if get_ptr_from_var_name("program_address", vm, ids_data, ap_tracking)? != program_address {
+        return Err(HintError::AssertionFailed(
+            format!("ids.program_address doesn't match the program address {program_address}")
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reads the output pointer of a `BuiltinData` ids variable.
+fn get_output_ptr_from_var_name(
+    var_name: &str,
+    vm: &VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<Relocatable, HintError> {
+    let address = get_member_address_from_var_name(
+        &format!("{var_name}.output"),
+        vm,
+        ids_data,
+        ap_tracking,
+        identifiers,
+    )?;
+    Ok(vm.get_relocatable(address)?)
+}
+
+/// Returns the pc of a label of the bootloader's `execute_task` function.
+fn get_execute_task_label_pc(
+    label: &str,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<usize, HintError> {
+    identifiers
+        .get(&format!("{EXECUTE_TASK}.{label}"))
+        .and_then(|identifier| identifier.pc)
+        .ok_or_else(|| HintError::UnknownIdentifier(label.into()))
+}
+
+/// Prepares the call to the current task, whose program was loaded at `program_address`.
+///
+/// For a program task, the output builtin state of the bootloader is saved in the
+/// `output_runner_data` scope variable and replaced by an empty one, so the pages and attributes
+/// added by the task can be told apart. The task then runs in a new scope holding its
+/// `program_input`.
+///
+/// A Cairo PIE task has its memory loaded by [load_cairo_pie] instead, and runs in an empty
+/// scope. Its output pages are taken from its additional data, so `output_runner_data` is
+/// `None`.
+pub fn execute_task_call_task(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<(), HintError> {
+    // Checked now so that a missing pointer doesn't surface after the task ran
+    get_output_ptr_from_var_name(
+        "pre_execution_builtin_ptrs",
+        vm,
+        ids_data,
+        ap_tracking,
+        identifiers,
+    )?;
+
+    let (output_runner_data, new_task_locals) = match exec_scopes.get_ref::<Task>("task")? {
+        Task::Program {
+            program,
+            program_input,
+        } => {
+            check_task_hints(program)?;
+            let output_builtin = vm.get_output_builtin_mut()?;
+            let output_runner_data = output_builtin.get_state();
+            output_builtin.set_state(OutputBuiltinState {
+                base: output_runner_data.base,
+                pages: Default::default(),
+                attributes: Default::default(),
+            });
+            (
+                Some(output_runner_data),
+                HashMap::from([
+                    ("program_input".to_string(), any_box!(program_input.clone())),
+                    ("WITH_BOOTLOADER".to_string(), any_box!(true)),
+                ]),
+            )
+        }
+        Task::Pie(cairo_pie) => {
+            let program_address = exec_scopes.get::<Relocatable>("program_address")?;
+            let n_builtins = cairo_pie.metadata.program.builtins.len();
+            let ret_pc_offset = get_execute_task_label_pc("ret_pc_label", identifiers)?
+                .checked_sub(get_execute_task_label_pc("call_task", identifiers)?)
+                .ok_or_else(|| {
+                    HintError::CustomHint("ret_pc_label must come after call_task".into())
+                })?;
+            let ret_pc = (vm.get_pc() + ret_pc_offset)?;
+            let execution_segment_address = (vm.get_ap() - n_builtins)?;
+            let ret_fp = vm.get_fp();
+            load_cairo_pie(
+                cairo_pie,
+                vm,
+                program_address,
+                execution_segment_address,
+                ret_fp,
+                ret_pc,
+            )?;
+            (None, HashMap::new())
+        }
+    };
+    exec_scopes.insert_value("output_runner_data", output_runner_data);
+    exec_scopes.enter_scope(new_task_locals);
+    Ok(())
+}
+
+/// Loads the memory of a Cairo PIE into the bootloader run, in place of running its program.
+///
+/// The segments of the PIE are relocated to the program data segment, the execution segment
+/// starting with the builtin pointers passed to the task, the return fp and pc of the task call,
+/// the segments of the builtins the task was given, and new segments for the extra segments.
+/// The signatures of the PIE are added to the signature builtin before its memory is written,
+/// as the builtin validates the signed messages when they are written.
+pub fn load_cairo_pie(
+    cairo_pie: &CairoPie,
+    vm: &mut VirtualMachine,
+    program_address: Relocatable,
+    execution_segment_address: Relocatable,
+    ret_fp: Relocatable,
+    ret_pc: Relocatable,
+) -> Result<(), HintError> {
+    let metadata = &cairo_pie.metadata;
+    let mut segment_offsets = HashMap::from([
+        (metadata.program_segment.index, program_address),
+        (metadata.execution_segment.index, execution_segment_address),
+        (metadata.ret_fp_segment.index, ret_fp),
+        (metadata.ret_pc_segment.index, ret_pc),
+    ]);
+
+    // The execution segment of the PIE starts with the pointers to its builtin segments
+    for (idx, builtin) in metadata.program.builtins.iter().enumerate() {
+        let pie_address = (metadata.execution_segment.index as usize, idx);
+        let builtin_segment = match cairo_pie
+            .memory
+            .0
+            .iter()
+            .find(|(address, _)| *address == pie_address)
+        {
+            Some((_, MaybeRelocatable::RelocatableValue(ptr))) if ptr.offset == 0 => {
+                ptr.segment_index
+            }
+            _ => {
+                return Err(HintError::CustomHint(
+                    format!(
+                        "Input builtin pointer {} is not a relocatable with a zero offset",
+                        builtin.to_str()
+                    )
+                    .into(),
+                ))
+            }
+        };
+        let builtin_ptr = vm.get_relocatable((execution_segment_address + idx)?)?;
+        segment_offsets.insert(builtin_segment, builtin_ptr);
+    }
+
+    for segment in &metadata.extra_segments {
+        let base = vm.add_memory_segment();
+        vm.segments
+            .finalize(Some(segment.size), base.segment_index as usize, None);
+        segment_offsets.insert(segment.index, base);
+    }
+
+    let relocate = |address: Relocatable| -> Result<Relocatable, HintError> {
+        let base = segment_offsets.get(&address.segment_index).ok_or_else(|| {
+            HintError::CustomHint(
+                format!(
+                    "The Cairo PIE uses the unknown segment {}",
+                    address.segment_index
+                )
+                .into(),
+            )
+        })?;
+        Ok((*base + address.offset)?)
+    };
+
+    match cairo_pie.additional_data.0.get(&BuiltinName::ecdsa) {
+        Some(BuiltinAdditionalData::Signature(signatures)) => {
+            let signature_builtin = vm.get_signature_builtin()?;
+            for (address, signature) in signatures {
+                signature_builtin.add_signature(relocate(*address)?, signature)?;
+            }
+        }
+        Some(BuiltinAdditionalData::Empty(_)) | None => {}
+        Some(_) => {
+            return Err(HintError::CustomHint(
+                "Invalid additional data for the ecdsa builtin".into(),
+            ))
+        }
+    }
+
+    for ((segment_index, offset), value) in &cairo_pie.memory.0 {
+        let address = relocate(Relocatable::from((*segment_index as isize, *offset)))?;
+        let value = match value {
+            MaybeRelocatable::RelocatableValue(ptr) => MaybeRelocatable::from(relocate(*ptr)?),
+            MaybeRelocatable::Int(_) => value.clone(),
+        };
+        vm.insert_value(address, value)?;
+    }
+    Ok(())
+}
+
+/// Without the `extensive_hints` feature, the hints of a task can't be loaded along with its
+/// program.
+#[cfg(not(feature = "extensive_hints"))]
+fn check_task_hints(program: &Program) -> Result<(), HintError> {
+    match program
+        .shared_program_data
+        .hints_collection
+        .iter_hints()
+        .next()
+    {
+        Some(_) => Err(HintError::CustomHint(
+            "Running a task with hints requires the extensive_hints feature".into(),
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(feature = "extensive_hints")]
+fn check_task_hints(_program: &Program) -> Result<(), HintError> {
+    Ok(())
+}
+
+/// Compiles the hints of the current task's program, placing them at `program_address`, where
+/// its code was loaded.
+#[cfg(feature = "extensive_hints")]
+pub fn load_task_hints(
+    hint_processor: &dyn HintProcessorLogic,
+    exec_scopes: &ExecutionScopes,
+) -> Result<HintExtension, HintError> {
+    let Task::Program { program, .. } = exec_scopes.get_ref::<Task>("task")? else {
+        // The memory of a Cairo PIE is loaded instead of running its program
+        return Ok(HintExtension::default());
+    };
+    let program_address = exec_scopes.get::<Relocatable>("program_address")?;
+    let shared_program_data = &program.shared_program_data;
+
+    let mut hint_extension = HintExtension::default();
+    for (pc, (start, length)) in shared_program_data.hints_collection.hints_ranges.iter() {
+        let hints = shared_program_data.hints_collection.hints[*start..*start + length.get()]
+            .iter()
+            .map(|hint| {
+                hint_processor.compile_hint_with_identifiers(
+                    &hint.code,
+                    &hint.flow_tracking_data.ap_tracking,
+                    &hint.flow_tracking_data.reference_ids,
+                    &shared_program_data.reference_manager,
+                    &shared_program_data.identifiers,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        hint_extension.insert((program_address + pc.offset)?, hints);
+    }
+    Ok(hint_extension)
+}
+
+/// Returns the names of the builtins of the bootloader's `BuiltinData` struct, in order.
+fn get_builtin_data_members(
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<Vec<&str>, HintError> {
+    let members = identifiers
+        .get(BUILTIN_DATA)
+        .and_then(|identifier| identifier.members.as_ref())
+        .ok_or_else(|| HintError::UnknownIdentifier(BUILTIN_DATA.into()))?;
+    let mut members: Vec<_> = members
+        .iter()
+        .map(|(name, member)| (name.as_str(), member.offset))
+        .collect();
+    members.sort_by_key(|(_, offset)| *offset);
+    Ok(members.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Fills `ids.return_builtin_ptrs` with the builtin pointers after the task ran, and enters a
+/// scope to select the builtins of the task among them.
+///
+/// The builtins used by the task are read in order from the ones it returned, at
+/// `ids.used_builtins_addr`, while the others keep their `ids.pre_execution_builtin_ptrs`
+/// value. A Cairo PIE task must have used as many cells of each builtin as its PIE says.
+pub fn execute_task_write_return_builtins(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<(), HintError> {
+    let task = exec_scopes.get_ref::<Task>("task")?;
+    let used_builtins = task
+        .get_program()
+        .map_err(|err| HintError::CustomHint(err.to_string().into()))?
+        .builtins;
+    let used_builtins_addr =
+        get_ptr_from_var_name("used_builtins_addr", vm, ids_data, ap_tracking)?;
+    let member_address = |var_name: &str, builtin: &str| {
+        get_member_address_from_var_name(
+            &format!("{var_name}.{builtin}"),
+            vm,
+            ids_data,
+            ap_tracking,
+            identifiers,
+        )
+    };
+
+    let mut return_builtins = Vec::new();
+    let mut used_builtin_offset = 0;
+    for builtin in get_builtin_data_members(identifiers)? {
+        let pre_execution_ptr =
+            vm.get_relocatable(member_address("pre_execution_builtin_ptrs", builtin)?)?;
+        let return_ptr = match used_builtins.iter().find(|used| used.to_str() == builtin) {
+            Some(used_builtin) => {
+                let return_ptr = vm.get_relocatable((used_builtins_addr + used_builtin_offset)?)?;
+                used_builtin_offset += 1;
+                if let Task::Pie(cairo_pie) = task {
+                    let pie_size = cairo_pie
+                        .metadata
+                        .builtin_segments
+                        .get(used_builtin)
+                        .map(|segment| segment.size);
+                    if pie_size != Some((return_ptr - pre_execution_ptr)?) {
+                        return Err(HintError::AssertionFailed(
+                            "Builtin usage is inconsistent with the CairoPie.".into(),
+                        ));
+                    }
+                }
+                return_ptr
+            }
+            // The builtin is unused, hence its value is the same as before calling the program
+            None => pre_execution_ptr,
+        };
+        return_builtins.push((member_address("return_builtin_ptrs", builtin)?, return_ptr));
+    }
+    for (address, return_ptr) in return_builtins {
+        vm.insert_value(address, return_ptr)?;
+    }
+
+    let n_builtins = Felt252::from(used_builtins.len());
+    exec_scopes.enter_scope(HashMap::from([(
+        "n_selected_builtins".to_string(),
+        any_box!(n_builtins),
+    )]));
+    Ok(())
+}
+
+/// Computes the fact topology of the task that just ran and appends it to the `fact_topologies`
+/// scope variable.
+///
+/// The fact topology of a program task comes from the state of the output builtin, which is then
+/// restored to the one of the bootloader. The one of a Cairo PIE task comes from the output
+/// builtin additional data of its PIE.
+pub fn execute_task_append_fact_topologies(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<(), HintError> {
+    let output_start = get_output_ptr_from_var_name(
+        "pre_execution_builtin_ptrs",
+        vm,
+        ids_data,
+        ap_tracking,
+        identifiers,
+    )?;
+    let output_end = get_output_ptr_from_var_name(
+        "return_builtin_ptrs",
+        vm,
+        ids_data,
+        ap_tracking,
+        identifiers,
+    )?;
+    let output_size = (output_end - output_start)?;
+    let output_runner_data = exec_scopes.get::<Option<OutputBuiltinState>>("output_runner_data")?;
+
+    let fact_topology = match exec_scopes.get_ref::<Task>("task")? {
+        Task::Program { .. } => {
+            let output_runner_data = output_runner_data.ok_or_else(|| {
+                HintError::CustomHint(
+                    "The output builtin state of the bootloader wasn't saved".into(),
+                )
+            })?;
+            let output_builtin = vm.get_output_builtin_mut()?;
+            let task_state = output_builtin.get_state();
+            // The pages of the task start at its output
+            let pages = task_state
+                .pages
+                .into_iter()
+                .map(|(page_id, page)| {
+                    let start = page.start.checked_sub(output_start.offset).ok_or_else(|| {
+                        HintError::CustomHint(
+                            format!("Page {page_id} starts before the task output").into(),
+                        )
+                    })?;
+                    Ok((
+                        page_id,
+                        PublicMemoryPage {
+                            start,
+                            size: page.size,
+                        },
+                    ))
+                })
+                .collect::<Result<Pages, HintError>>()?;
+            let fact_topology = get_task_fact_topology(output_size, &pages, &task_state.attributes);
+            output_builtin.set_state(output_runner_data);
+            fact_topology
+        }
+        Task::Pie(cairo_pie) => match cairo_pie.additional_data.0.get(&BuiltinName::output) {
+            Some(BuiltinAdditionalData::Output(output)) => {
+                get_task_fact_topology(output_size, &output.pages, &output.attributes)
+            }
+            _ => {
+                return Err(HintError::CustomHint(
+                    "The Cairo PIE has no output builtin additional data".into(),
+                ))
+            }
+        },
+    }
+    .map_err(|err| HintError::CustomHint(err.to_string().into()))?;
+
+    exec_scopes
+        .get_mut_list_ref::<FactTopology>("fact_topologies")?
+        .push(fact_topology);
+    Ok(())
+}
+
+/// Implements hint:
+/// %{ vm_enter_scope({'n_selected_builtins': ids.n_selected_builtins}) %}
+pub fn select_builtins_enter_scope(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let n_selected_builtins =
+        get_integer_from_var_name("n_selected_builtins", vm, ids_data, ap_tracking)?;
+    exec_scopes.enter_scope(HashMap::from([(
+        "n_selected_builtins".to_string(),
+        any_box!(n_selected_builtins),
+    )]));
+    Ok(())
+}
+
+/// Selects the builtin at `ids.all_encodings` if it's the next one of the `n_selected_builtins`
+/// left at `ids.selected_encodings`. As both encoding lists are sorted, comparing the two
+/// encodings is enough.
+pub fn select_builtins_select_builtin(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let n_selected_builtins = exec_scopes.get_mut_ref::<Felt252>("n_selected_builtins")?;
+    let select_builtin = *n_selected_builtins != Felt252::ZERO && {
+        let selected_encodings =
+            get_ptr_from_var_name("selected_encodings", vm, ids_data, ap_tracking)?;
+        let all_encodings = get_ptr_from_var_name("all_encodings", vm, ids_data, ap_tracking)?;
+        vm.get_integer(selected_encodings)? == vm.get_integer(all_encodings)?
+    };
+    if select_builtin {
+        *n_selected_builtins -= Felt252::ONE;
+    }
+    insert_value_from_var_name(
+        "select_builtin",
+        if select_builtin {
+            Felt252::ONE
+        } else {
+            Felt252::ZERO
+        },
+        vm,
+        ids_data,
+        ap_tracking,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::any_box;
+    use crate::bootloader::TaskSpec;
+    use crate::bootloader::GPS_FACT_TOPOLOGY;
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::{
+        BuiltinHintProcessor, HintProcessorData,
+    };
+    use crate::hint_processor::builtin_hint_processor::hint_code;
+    use crate::hint_processor::hint_processor_definition::HintProcessorLogic;
+    use crate::serde::deserialize_program::{FlowTrackingData, HintParams, Member};
+    use crate::stdlib::{borrow::Cow, sync::Arc};
+    use crate::types::{builtin_name::BuiltinName, program::ProgramBuilder};
+    use crate::utils::test_utils::*;
+    use crate::vm::runners::builtin_runner::OutputBuiltinRunner;
+    use crate::vm::runners::cairo_pie::{
+        CairoPieAdditionalData, CairoPieMemory, CairoPieMetadata, CairoPieVersion,
+        OutputBuiltinAdditionalData, SegmentInfo, StrippedProgram,
+    };
+    use crate::vm::runners::cairo_runner::ExecutionResources;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn fibonacci_task_scopes() -> ExecutionScopes {
+        let program: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../../../cairo_programs/fibonacci.json"))
+                .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "simple_bootloader_input",
+            SimpleBootloaderInput {
+                tasks: vec![TaskSpec::RunProgramTask {
+                    program,
+                    program_input: serde_json::Value::Null,
+                    use_poseidon: false,
+                }],
+                fact_topologies_path: None,
+                single_page: false,
+            },
+        );
+        exec_scopes
+    }
+
+    const PROGRAM_HEADER: &str =
+        "starkware.cairo.bootloaders.simple_bootloader.execute_task.ProgramHeader";
+
+    fn fibonacci_program() -> Program {
+        Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap()
+    }
+
+    fn struct_identifier(members: &[(&str, &str, usize)]) -> Identifier {
+        Identifier {
+            pc: None,
+            type_: Some("struct".to_string()),
+            value: None,
+            full_name: None,
+            members: Some(
+                members
+                    .iter()
+                    .map(|(name, cairo_type, offset)| {
+                        (
+                            name.to_string(),
+                            Member {
+                                cairo_type: cairo_type.to_string(),
+                                offset: *offset,
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            cairo_type: None,
+            size: Some(members.len()),
+        }
+    }
+
+    fn label_identifier(pc: usize) -> Identifier {
+        Identifier {
+            pc: Some(pc),
+            type_: Some("label".to_string()),
+            value: None,
+            full_name: None,
+            members: None,
+            cairo_type: None,
+            size: None,
+        }
+    }
+
+    /// The identifiers of the bootloader read by the hints, with the given `ProgramHeader`
+    /// members
+    fn bootloader_identifiers(
+        program_header: &[(&str, &str, usize)],
+    ) -> HashMap<String, Identifier> {
+        HashMap::from([
+            (
+                PROGRAM_HEADER.to_string(),
+                struct_identifier(program_header),
+            ),
+            (
+                BUILTIN_DATA.to_string(),
+                struct_identifier(&[
+                    ("output", "felt", 0),
+                    ("pedersen", "felt", 1),
+                    ("range_check", "felt", 2),
+                ]),
+            ),
+            (format!("{EXECUTE_TASK}.call_task"), label_identifier(10)),
+            (format!("{EXECUTE_TASK}.ret_pc_label"), label_identifier(12)),
+        ])
+    }
+
+    const PROGRAM_HEADER_MEMBERS: [(&str, &str, usize); 5] = [
+        ("data_length", "felt", 0),
+        ("bootloader_version", "felt", 1),
+        ("program_main", "felt", 2),
+        ("n_builtins", "felt", 3),
+        ("builtin_list", "felt", 4),
+    ];
+
+    /// Sets the cairo type of an ids variable, so that its members can be accessed
+    fn set_cairo_type(ids_data: &mut HashMap<String, HintReference>, name: &str, cairo_type: &str) {
+        ids_data.get_mut(name).unwrap().cairo_type = Some(cairo_type.to_string());
+    }
+
+    fn run_bootloader_hint(
+        vm: &mut VirtualMachine,
+        ids_data: &HashMap<String, HintReference>,
+        identifiers: &HashMap<String, Identifier>,
+        hint_code: &str,
+        exec_scopes: &mut ExecutionScopes,
+    ) -> Result<(), HintError> {
+        let hint_data = HintProcessorData::new_default(hint_code.to_string(), ids_data.clone())
+            .with_identifiers(Arc::new(identifiers.clone()));
+        BuiltinHintProcessor::new_empty().execute_hint(
+            vm,
+            exec_scopes,
+            &any_box!(hint_data),
+            &HashMap::new(),
+        )
+    }
+
+    fn output_builtin(base: usize) -> OutputBuiltinRunner {
+        let mut output_builtin = OutputBuiltinRunner::new(true);
+        output_builtin.set_state(OutputBuiltinState {
+            base,
+            pages: Default::default(),
+            attributes: Default::default(),
+        });
+        output_builtin
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_load_input_and_set_current_task() {
+        let program: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../../../cairo_programs/fibonacci.json"))
+                .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "program_input",
+            serde_json::json!({
+                "tasks": [{
+                    "type": "RunProgramTask",
+                    "program": program,
+                    "use_poseidon": true,
+                }],
+            }),
+        );
+
+        // Frame: output_ptr, range_check_ptr, task_range_check_ptr, n_tasks
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), (3, 5)), ((1, 3), 1)];
+        vm.add_memory_segment();
+        vm.set_fp(4);
+        vm.set_ap(4);
+        let ids_data = ids_data![
+            "output_ptr",
+            "range_check_ptr",
+            "task_range_check_ptr",
+            "n_tasks"
+        ];
+        let identifiers = bootloader_identifiers(&PROGRAM_HEADER_MEMBERS);
+        for hint_code in [
+            SIMPLE_BOOTLOADER_LOAD_INPUT,
+            SIMPLE_BOOTLOADER_PREPARE_TASK_RANGE_CHECKS,
+            SIMPLE_BOOTLOADER_SET_TASKS_VARIABLE,
+            SIMPLE_BOOTLOADER_SET_CURRENT_TASK,
+            SIMPLE_BOOTLOADER_USE_POSEIDON,
+        ] {
+            assert_matches!(
+                run_bootloader_hint(
+                    &mut vm,
+                    &ids_data,
+                    &identifiers,
+                    hint_code,
+                    &mut exec_scopes
+                ),
+                Ok(())
+            );
+        }
+        // The task range checks start after the 3 builtin pointers checked for the task
+        check_memory![
+            vm.segments.memory,
+            ((2, 0), 1),
+            ((1, 2), (3, 8)),
+            ((1, 4), 1)
+        ];
+        assert!(exec_scopes
+            .get_list_ref::<FactTopology>("fact_topologies")
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            exec_scopes.get_list_ref::<TaskSpec>("tasks").unwrap().len(),
+            1
+        );
+        assert_eq!(exec_scopes.get::<usize>("task_id").unwrap(), 0);
+        assert_matches!(
+            exec_scopes.get_ref::<Task>("task"),
+            Ok(Task::Program { .. })
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_load_input_invalid() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_input", serde_json::json!({ "tasks": 1 }));
+        let mut vm = vm!();
+        assert_matches!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                SIMPLE_BOOTLOADER_LOAD_INPUT,
+                &mut exec_scopes
+            ),
+            Err(HintError::CustomHint(msg)) if msg.starts_with("Invalid simple bootloader input")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_prepare_task_range_checks_without_builtin_data() {
+        let mut exec_scopes = fibonacci_task_scopes();
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), (3, 5))];
+        vm.add_memory_segment();
+        vm.set_fp(3);
+        let ids_data = ids_data!["output_ptr", "range_check_ptr", "task_range_check_ptr"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                SIMPLE_BOOTLOADER_PREPARE_TASK_RANGE_CHECKS,
+                &mut exec_scopes
+            ),
+            Err(HintError::UnknownIdentifier(name)) if name.as_ref() == BUILTIN_DATA
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_set_current_task_invalid_n_tasks() {
+        let mut exec_scopes = fibonacci_task_scopes();
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 2)];
+        vm.set_fp(1);
+        let ids_data = ids_data!["n_tasks"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                SIMPLE_BOOTLOADER_SET_CURRENT_TASK,
+                &mut exec_scopes
+            ),
+            Err(HintError::CustomHint(_))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_load_program_and_validate_hash() {
+        let program = fibonacci_program();
+        let stripped_program = program.get_stripped_program().unwrap();
+        let program_hash = program.compute_program_hash(BOOTLOADER_VERSION).unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program,
+                program_input: serde_json::Value::Null,
+            },
+        );
+
+        let mut vm = vm!();
+        vm.segments = segments![((1, 2), (2, 0)), ((1, 3), 0), ((2, 0), 3)];
+        vm.insert_value((2, 1).into(), program_hash).unwrap();
+        vm.set_fp(4);
+        let mut ids_data = ids_data![
+            "program_data_ptr",
+            "program_header",
+            "output_ptr",
+            "use_poseidon"
+        ];
+        set_cairo_type(
+            &mut ids_data,
+            "program_header",
+            &format!("{PROGRAM_HEADER}*"),
+        );
+        let identifiers = bootloader_identifiers(&PROGRAM_HEADER_MEMBERS);
+
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data.clone(),
+                EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        let program_data_base = Relocatable::from((3, 0));
+        assert_eq!(
+            vm.get_relocatable((1, 0).into()).unwrap(),
+            program_data_base
+        );
+        vm.insert_value((1, 1).into(), program_data_base).unwrap();
+
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &identifiers,
+                EXECUTE_TASK_LOAD_PROGRAM,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        let data_len = stripped_program.data.len();
+        let mut expected_data: Vec<MaybeRelocatable> =
+            [3 + data_len, BOOTLOADER_VERSION, stripped_program.main, 0]
+                .into_iter()
+                .map(MaybeRelocatable::from)
+                .collect();
+        expected_data.extend(stripped_program.data);
+        assert_eq!(
+            vm.get_continuous_range(program_data_base, 4 + data_len)
+                .unwrap(),
+            expected_data
+        );
+        assert_eq!(vm.segments.segment_sizes[&3], 4 + data_len);
+        assert_eq!(
+            exec_scopes.get::<Relocatable>("program_address").unwrap(),
+            Relocatable::from((3, 4))
+        );
+
+        assert_matches!(
+            run_hint!(vm, ids_data, EXECUTE_TASK_VALIDATE_HASH, &mut exec_scopes),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_load_program_follows_program_header_layout() {
+        let program = ProgramBuilder::new()
+            .builtins([BuiltinName::output, BuiltinName::pedersen])
+            .data([Felt252::from(10), Felt252::from(20)])
+            .main(1)
+            .build()
+            .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_data_base", Relocatable::from((2, 0)));
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program,
+                program_input: serde_json::Value::Null,
+            },
+        );
+
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        vm.add_memory_segment();
+        vm.set_fp(1);
+        let mut ids_data = ids_data!["program_header"];
+        set_cairo_type(
+            &mut ids_data,
+            "program_header",
+            &format!("{PROGRAM_HEADER}*"),
+        );
+        // A header with its fields in another order and an extra field before the builtins
+        let identifiers = bootloader_identifiers(&[
+            ("bootloader_version", "felt", 0),
+            ("data_length", "felt", 1),
+            ("program_main", "felt", 2),
+            ("n_builtins", "felt", 3),
+            ("program_hash", "felt", 4),
+            ("builtin_list", "felt", 5),
+        ]);
+
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &identifiers,
+                EXECUTE_TASK_LOAD_PROGRAM,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_memory![
+            vm.segments.memory,
+            ((2, 0), BOOTLOADER_VERSION),
+            ((2, 1), 8),
+            ((2, 2), 1),
+            ((2, 3), 2),
+            ((2, 7), 10),
+            ((2, 8), 20)
+        ];
+        assert_eq!(
+            vm.get_integer_range((2, 5).into(), 2).unwrap(),
+            [
+                Cow::Owned(Felt252::from_bytes_be_slice(b"output")),
+                Cow::Owned(Felt252::from_bytes_be_slice(b"pedersen"))
+            ]
+        );
+        assert_eq!(vm.segments.segment_sizes[&2], 9);
+        assert_eq!(
+            exec_scopes.get::<Relocatable>("program_address").unwrap(),
+            Relocatable::from((2, 7))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_load_program_header_without_cairo_type() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_data_base", Relocatable::from((2, 0)));
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program: fibonacci_program(),
+                program_input: serde_json::Value::Null,
+            },
+        );
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        vm.add_memory_segment();
+        vm.set_fp(1);
+        let ids_data = ids_data!["program_header"];
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &bootloader_identifiers(&PROGRAM_HEADER_MEMBERS),
+                EXECUTE_TASK_LOAD_PROGRAM,
+                &mut exec_scopes
+            ),
+            Err(HintError::UnknownIdentifier(name)) if name.as_ref() == "program_header"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_validate_hash_with_poseidon() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let task = Task::Program {
+            program,
+            program_input: serde_json::Value::Null,
+        };
+        let program_hash = task.program_hash(BOOTLOADER_VERSION, true).unwrap();
+        let pedersen_program_hash = task.program_hash(BOOTLOADER_VERSION, false).unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("task", task);
+
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), 1), ((2, 0), 3)];
+        vm.insert_value((2, 1).into(), program_hash).unwrap();
+        vm.set_fp(2);
+        let ids_data = ids_data!["output_ptr", "use_poseidon"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data.clone(),
+                EXECUTE_TASK_VALIDATE_HASH,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+
+        // The Pedersen hash of the program doesn't match when use_poseidon is set
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), 1), ((2, 0), 3)];
+        vm.insert_value((2, 1).into(), pedersen_program_hash)
+            .unwrap();
+        vm.set_fp(2);
+        assert_matches!(
+            run_hint!(vm, ids_data, EXECUTE_TASK_VALIDATE_HASH, &mut exec_scopes),
+            Err(HintError::AssertionFailed(_))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_validate_hash_mismatch() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program,
+                program_input: serde_json::Value::Null,
+            },
+        );
+
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), 0), ((2, 0), 3), ((2, 1), 7)];
+        vm.set_fp(2);
+        let ids_data = ids_data!["output_ptr", "use_poseidon"];
+        assert_matches!(
+            run_hint!(vm, ids_data, EXECUTE_TASK_VALIDATE_HASH, &mut exec_scopes),
+            Err(HintError::AssertionFailed(msg)) if msg.as_ref() == "Computed hash does not match input."
+        );
+    }
+
+    /// Runs the hints of the simple bootloader for a single program task, in the order the
+    /// bootloader runs them, with the task adding an output page of its own.
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_program_task_hints() {
+        let program = fibonacci_program();
+        let program_hash = program.compute_program_hash(BOOTLOADER_VERSION).unwrap();
+        let data_len = program.data_len();
+        let mut exec_scopes = fibonacci_task_scopes();
+        // Started when preparing the task range checks
+        exec_scopes.insert_value("fact_topologies", Vec::<FactTopology>::new());
+
+        // Frame: n_tasks, program_data_ptr, program_header, output_ptr, use_poseidon,
+        // pre_execution_builtin_ptrs, return_builtin_ptrs
+        // The BuiltinData structs are at (1, 10) and (1, 11), the output segment is segment 2
+        // and starts with the number of tasks, then the size and program hash of the task
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 3), (2, 1)),
+            ((1, 4), 0),
+            ((1, 5), (1, 10)),
+            ((1, 6), (1, 11)),
+            ((1, 10), (2, 3)),
+            ((2, 0), 1),
+            ((2, 1), 5)
+        ];
+        vm.insert_value((2, 2).into(), program_hash).unwrap();
+        vm.set_fp(7);
+        vm.builtin_runners = vec![output_builtin(2).into()];
+        let mut ids_data = ids_data![
+            "n_tasks",
+            "program_data_ptr",
+            "program_header",
+            "output_ptr",
+            "use_poseidon",
+            "pre_execution_builtin_ptrs",
+            "return_builtin_ptrs"
+        ];
+        set_cairo_type(
+            &mut ids_data,
+            "program_header",
+            &format!("{PROGRAM_HEADER}*"),
+        );
+        set_cairo_type(
+            &mut ids_data,
+            "pre_execution_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        set_cairo_type(
+            &mut ids_data,
+            "return_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        let identifiers = bootloader_identifiers(&PROGRAM_HEADER_MEMBERS);
+        let run = |vm: &mut VirtualMachine, hint_code, exec_scopes: &mut ExecutionScopes| {
+            run_bootloader_hint(vm, &ids_data, &identifiers, hint_code, exec_scopes)
+        };
+
+        for hint_code in [
+            SIMPLE_BOOTLOADER_SET_CURRENT_TASK,
+            EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT,
+        ] {
+            assert_matches!(run(&mut vm, hint_code, &mut exec_scopes), Ok(()));
+        }
+        // let program_header = cast(program_data_ptr, ProgramHeader*)
+        vm.insert_value((1, 2).into(), Relocatable::from((3, 0)))
+            .unwrap();
+        for hint_code in [EXECUTE_TASK_LOAD_PROGRAM, EXECUTE_TASK_VALIDATE_HASH] {
+            assert_matches!(run(&mut vm, hint_code, &mut exec_scopes), Ok(()));
+        }
+        assert_eq!(vm.segments.segment_sizes[&3], 4 + data_len);
+
+        vm.get_output_builtin_mut()
+            .unwrap()
+            .add_page(7, (2, 20).into(), 1)
+            .unwrap();
+        assert_matches!(
+            run(&mut vm, EXECUTE_TASK_CALL_TASK, &mut exec_scopes),
+            Ok(())
+        );
+        assert_eq!(
+            exec_scopes
+                .get::<serde_json::Value>("program_input")
+                .unwrap(),
+            serde_json::Value::Null
+        );
+        assert!(exec_scopes.get::<bool>("WITH_BOOTLOADER").unwrap());
+        // The task starts with an empty output builtin state
+        let task_output_builtin = vm.get_output_builtin_mut().unwrap();
+        assert_eq!(
+            task_output_builtin.get_state(),
+            output_builtin(2).get_state()
+        );
+
+        // The task writes 3 words and puts the last 2 in a page
+        task_output_builtin.add_page(1, (2, 4).into(), 2).unwrap();
+        task_output_builtin.add_attribute(GPS_FACT_TOPOLOGY.to_string(), vec![2, 1, 0, 2]);
+        vm.insert_value((1, 11).into(), Relocatable::from((2, 6)))
+            .unwrap();
+
+        for hint_code in [EXECUTE_TASK_EXIT_SCOPE, EXECUTE_TASK_APPEND_FACT_TOPOLOGIES] {
+            assert_matches!(run(&mut vm, hint_code, &mut exec_scopes), Ok(()));
+        }
+        assert_eq!(
+            exec_scopes
+                .get::<Vec<FactTopology>>("fact_topologies")
+                .unwrap(),
+            vec![FactTopology {
+                tree_structure: vec![2, 1, 0, 2],
+                page_sizes: vec![1, 2],
+            }]
+        );
+        // The output builtin state of the bootloader is restored
+        let bootloader_pages = vm.get_output_builtin_mut().unwrap().get_state().pages;
+        assert_eq!(
+            bootloader_pages,
+            Pages::from([(7, PublicMemoryPage { start: 20, size: 1 })])
+        );
+
+        vm.get_output_builtin_mut()
+            .unwrap()
+            .set_state(output_builtin(2).get_state());
+        assert_matches!(
+            run(
+                &mut vm,
+                SIMPLE_BOOTLOADER_CONFIGURE_FACT_TOPOLOGIES,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            vm.get_output_builtin_mut().unwrap().get_state().pages,
+            Pages::from([
+                (1, PublicMemoryPage { start: 3, size: 1 }),
+                (2, PublicMemoryPage { start: 4, size: 2 })
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_configure_fact_topologies_single_page() {
+        let mut exec_scopes = fibonacci_task_scopes();
+        exec_scopes
+            .get_mut_ref::<SimpleBootloaderInput>("simple_bootloader_input")
+            .unwrap()
+            .single_page = true;
+        exec_scopes.insert_value(
+            "fact_topologies",
+            vec![FactTopology {
+                tree_structure: vec![1, 0],
+                page_sizes: vec![3],
+            }],
+        );
+        let mut vm = vm!();
+        vm.builtin_runners = vec![output_builtin(2).into()];
+        assert_matches!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                SIMPLE_BOOTLOADER_CONFIGURE_FACT_TOPOLOGIES,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert!(vm
+            .get_output_builtin_mut()
+            .unwrap()
+            .get_state()
+            .pages
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_append_fact_topologies_without_gps_fact_topology() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program: fibonacci_program(),
+                program_input: serde_json::Value::Null,
+            },
+        );
+        exec_scopes.insert_value("output_runner_data", Some(output_builtin(2).get_state()));
+        exec_scopes.insert_value("fact_topologies", Vec::<FactTopology>::new());
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), (2, 4))];
+        vm.set_fp(2);
+        let mut output = output_builtin(2);
+        output.add_page(1, (2, 1).into(), 3).unwrap();
+        vm.builtin_runners = vec![output.into()];
+        let mut ids_data = ids_data!["pre_execution_builtin_ptrs", "return_builtin_ptrs"];
+        set_cairo_type(&mut ids_data, "pre_execution_builtin_ptrs", BUILTIN_DATA);
+        set_cairo_type(&mut ids_data, "return_builtin_ptrs", BUILTIN_DATA);
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &bootloader_identifiers(&PROGRAM_HEADER_MEMBERS),
+                EXECUTE_TASK_APPEND_FACT_TOPOLOGIES,
+                &mut exec_scopes
+            ),
+            Err(HintError::CustomHint(msg)) if msg.contains(GPS_FACT_TOPOLOGY)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_append_fact_topologies_without_fact_topologies() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program: fibonacci_program(),
+                program_input: serde_json::Value::Null,
+            },
+        );
+        exec_scopes.insert_value("output_runner_data", Some(output_builtin(2).get_state()));
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((1, 1), (2, 4))];
+        vm.set_fp(2);
+        vm.builtin_runners = vec![output_builtin(2).into()];
+        let mut ids_data = ids_data!["pre_execution_builtin_ptrs", "return_builtin_ptrs"];
+        set_cairo_type(&mut ids_data, "pre_execution_builtin_ptrs", BUILTIN_DATA);
+        set_cairo_type(&mut ids_data, "return_builtin_ptrs", BUILTIN_DATA);
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &bootloader_identifiers(&PROGRAM_HEADER_MEMBERS),
+                EXECUTE_TASK_APPEND_FACT_TOPOLOGIES,
+                &mut exec_scopes
+            ),
+            Err(HintError::VariableNotInScopeError(name)) if name.as_ref() == "fact_topologies"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_assert_program_address() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_address", Relocatable::from((3, 4)));
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (3, 4))];
+        vm.set_fp(1);
+        let ids_data = ids_data!["program_address"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data.clone(),
+                EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+
+        exec_scopes.insert_value("program_address", Relocatable::from((3, 5)));
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS,
+                &mut exec_scopes
+            ),
+            Err(HintError::AssertionFailed(_))
+        );
+    }
+
+    /// Frame: pre_execution_builtin_ptrs, used_builtins_addr, return_builtin_ptrs (a struct).
+    /// The builtin pointers before the task are at (1, 10), and the ones returned by the task,
+    /// which uses the output and range check builtins, are at (1, 20).
+    fn return_builtins_vm() -> (VirtualMachine, HashMap<String, HintReference>) {
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), (1, 10)),
+            ((1, 1), (1, 20)),
+            ((1, 10), (2, 0)),
+            ((1, 11), (3, 0)),
+            ((1, 12), (4, 0)),
+            ((1, 20), (2, 3)),
+            ((1, 21), (4, 2))
+        ];
+        vm.set_fp(3);
+        let mut ids_data = ids_data![
+            "pre_execution_builtin_ptrs",
+            "used_builtins_addr",
+            "return_builtin_ptrs"
+        ];
+        set_cairo_type(
+            &mut ids_data,
+            "pre_execution_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        set_cairo_type(&mut ids_data, "return_builtin_ptrs", BUILTIN_DATA);
+        (vm, ids_data)
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_write_return_builtins() {
+        let program = ProgramBuilder::new()
+            .builtins([BuiltinName::output, BuiltinName::range_check])
+            .build()
+            .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program,
+                program_input: serde_json::Value::Null,
+            },
+        );
+        let (mut vm, ids_data) = return_builtins_vm();
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &bootloader_identifiers(&PROGRAM_HEADER_MEMBERS),
+                EXECUTE_TASK_WRITE_RETURN_BUILTINS,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        // The unused pedersen builtin keeps its pointer from before the task
+        check_memory![
+            vm.segments.memory,
+            ((1, 2), (2, 3)),
+            ((1, 3), (3, 0)),
+            ((1, 4), (4, 2))
+        ];
+        assert_eq!(exec_scopes.data.len(), 2);
+        assert_eq!(
+            exec_scopes.get::<Felt252>("n_selected_builtins").unwrap(),
+            Felt252::from(2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_write_return_builtins_inconsistent_with_cairo_pie() {
+        let mut cairo_pie = output_cairo_pie();
+        cairo_pie.metadata.program.builtins = vec![BuiltinName::output, BuiltinName::range_check];
+        cairo_pie.metadata.builtin_segments = HashMap::from([
+            (BuiltinName::output, SegmentInfo::from((4, 3))),
+            (BuiltinName::range_check, SegmentInfo::from((6, 1))),
+        ]);
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("task", Task::Pie(cairo_pie));
+        let (mut vm, ids_data) = return_builtins_vm();
+        // The task used 2 range checks, but its PIE only 1
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &bootloader_identifiers(&PROGRAM_HEADER_MEMBERS),
+                EXECUTE_TASK_WRITE_RETURN_BUILTINS,
+                &mut exec_scopes
+            ),
+            Err(HintError::AssertionFailed(msg))
+                if msg.as_ref() == "Builtin usage is inconsistent with the CairoPie."
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_select_builtins() {
+        let mut exec_scopes = ExecutionScopes::new();
+        // Frame: n_selected_builtins, selected_encodings, all_encodings, select_builtin
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), (2, 0)),
+            ((1, 2), (2, 1)),
+            ((2, 0), 5),
+            ((2, 1), 5)
+        ];
+        vm.set_fp(4);
+        let ids_data = ids_data![
+            "n_selected_builtins",
+            "selected_encodings",
+            "all_encodings",
+            "select_builtin"
+        ];
+        for hint_code in [SELECT_BUILTINS_ENTER_SCOPE, SELECT_BUILTINS_SELECT_BUILTIN] {
+            assert_matches!(
+                run_hint!(vm, ids_data.clone(), hint_code, &mut exec_scopes),
+                Ok(())
+            );
+        }
+        check_memory![vm.segments.memory, ((1, 3), 1)];
+        assert_eq!(
+            exec_scopes.get::<Felt252>("n_selected_builtins").unwrap(),
+            Felt252::ZERO
+        );
+
+        // Once all the builtins were selected, matching encodings aren't selected anymore
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), (2, 0)),
+            ((1, 2), (2, 1)),
+            ((2, 0), 5),
+            ((2, 1), 5)
+        ];
+        vm.set_fp(4);
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                SELECT_BUILTINS_SELECT_BUILTIN,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 3), 0)];
+    }
+
+    /// A Cairo PIE that writes 7 and 8 to its output, with the last word in a page of its own.
+    /// It uses the output builtin and an extra segment, holding 9.
+    fn output_cairo_pie() -> CairoPie {
+        let memory = [
+            ((0, 0), MaybeRelocatable::from(5)),
+            ((1, 0), MaybeRelocatable::from((4, 0))),
+            ((1, 1), MaybeRelocatable::from((2, 0))),
+            ((1, 2), MaybeRelocatable::from((3, 0))),
+            ((1, 3), MaybeRelocatable::from((5, 0))),
+            ((4, 0), MaybeRelocatable::from(7)),
+            ((4, 1), MaybeRelocatable::from(8)),
+            ((5, 0), MaybeRelocatable::from(9)),
+        ];
+        CairoPie {
+            metadata: CairoPieMetadata {
+                program: StrippedProgram {
+                    data: vec![MaybeRelocatable::from(5)],
+                    builtins: vec![BuiltinName::output],
+                    main: 0,
+                    prime: (),
+                },
+                program_segment: SegmentInfo::from((0, 1)),
+                execution_segment: SegmentInfo::from((1, 4)),
+                ret_fp_segment: SegmentInfo::from((2, 0)),
+                ret_pc_segment: SegmentInfo::from((3, 0)),
+                builtin_segments: HashMap::from([(BuiltinName::output, SegmentInfo::from((4, 2)))]),
+                extra_segments: vec![SegmentInfo::from((5, 1))],
+            },
+            memory: CairoPieMemory(memory.to_vec()),
+            execution_resources: ExecutionResources::default(),
+            additional_data: CairoPieAdditionalData(HashMap::from([(
+                BuiltinName::output,
+                BuiltinAdditionalData::Output(OutputBuiltinAdditionalData {
+                    pages: Pages::from([(1, PublicMemoryPage { start: 1, size: 1 })]),
+                    attributes: HashMap::from([(GPS_FACT_TOPOLOGY.to_string(), vec![2, 1, 0, 2])]),
+                }),
+            )])),
+            version: CairoPieVersion { cairo_pie: () },
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_cairo_pie_task_hints() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_address", Relocatable::from((0, 5)));
+        exec_scopes.insert_value("fact_topologies", Vec::<FactTopology>::new());
+        exec_scopes.insert_value("task", Task::Pie(output_cairo_pie()));
+
+        // Frame: pre_execution_builtin_ptrs, return_builtin_ptrs
+        // The task is given the output pointer (2, 3) at ap - 1
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), (1, 10)),
+            ((1, 1), (1, 11)),
+            ((1, 6), (2, 3)),
+            ((1, 10), (2, 3)),
+            ((2, 0), 1)
+        ];
+        vm.set_fp(2);
+        vm.set_ap(7);
+        let mut ids_data = ids_data!["pre_execution_builtin_ptrs", "return_builtin_ptrs"];
+        set_cairo_type(
+            &mut ids_data,
+            "pre_execution_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        set_cairo_type(
+            &mut ids_data,
+            "return_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        let identifiers = bootloader_identifiers(&PROGRAM_HEADER_MEMBERS);
+
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &identifiers,
+                EXECUTE_TASK_CALL_TASK,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        // The PIE runs from ap - 1, returning to fp and to the pc of ret_pc_label, and its
+        // extra segment is the new segment 3
+        check_memory![
+            vm.segments.memory,
+            ((0, 5), 5),
+            ((1, 6), (2, 3)),
+            ((1, 7), (1, 2)),
+            ((1, 8), (0, 2)),
+            ((1, 9), (3, 0)),
+            ((2, 3), 7),
+            ((2, 4), 8),
+            ((3, 0), 9)
+        ];
+        assert_eq!(vm.segments.segment_sizes[&3], 1);
+        assert!(exec_scopes.get_local_variables().unwrap().is_empty());
+
+        vm.insert_value((1, 11).into(), Relocatable::from((2, 5)))
+            .unwrap();
+        for hint_code in [EXECUTE_TASK_EXIT_SCOPE, EXECUTE_TASK_APPEND_FACT_TOPOLOGIES] {
+            assert_matches!(
+                run_bootloader_hint(
+                    &mut vm,
+                    &ids_data,
+                    &identifiers,
+                    hint_code,
+                    &mut exec_scopes
+                ),
+                Ok(())
+            );
+        }
+        assert_eq!(
+            exec_scopes
+                .get::<Option<OutputBuiltinState>>("output_runner_data")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            exec_scopes
+                .get::<Vec<FactTopology>>("fact_topologies")
+                .unwrap(),
+            vec![FactTopology {
+                tree_structure: vec![2, 1, 0, 2],
+                page_sizes: vec![1, 1],
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_call_task_cairo_pie_with_unknown_segment() {
+        let mut cairo_pie = output_cairo_pie();
+        cairo_pie
+            .memory
+            .0
+            .push(((1, 4), MaybeRelocatable::from((6, 0))));
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_address", Relocatable::from((0, 5)));
+        exec_scopes.insert_value("task", Task::Pie(cairo_pie));
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (1, 10)), ((1, 6), (2, 3)), ((1, 10), (2, 3))];
+        vm.add_memory_segment();
+        vm.set_fp(1);
+        vm.set_ap(7);
+        let mut ids_data = ids_data!["pre_execution_builtin_ptrs"];
+        set_cairo_type(
+            &mut ids_data,
+            "pre_execution_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        assert_matches!(
+            run_bootloader_hint(
+                &mut vm,
+                &ids_data,
+                &bootloader_identifiers(&PROGRAM_HEADER_MEMBERS),
+                EXECUTE_TASK_CALL_TASK,
+                &mut exec_scopes
+            ),
+            Err(HintError::CustomHint(msg)) if msg.contains("unknown segment 6")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_call_task_with_hints() {
+        let program = ProgramBuilder::new()
+            .data([Felt252::from(0x208b7fff7fff7ffe_u64)])
+            .hint(
+                0,
+                HintParams {
+                    code: hint_code::ADD_SEGMENT.to_string(),
+                    accessible_scopes: vec![],
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::default(),
+                        reference_ids: HashMap::new(),
+                    },
+                },
+            )
+            .build()
+            .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("program_address", Relocatable::from((3, 5)));
+        exec_scopes.insert_value(
+            "task",
+            Task::Program {
+                program,
+                program_input: serde_json::json!({ "n": 1 }),
+            },
+        );
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (1, 5)), ((1, 5), (2, 0))];
+        vm.set_fp(1);
+        vm.builtin_runners = vec![output_builtin(2).into()];
+        let mut ids_data = ids_data!["pre_execution_builtin_ptrs"];
+        set_cairo_type(
+            &mut ids_data,
+            "pre_execution_builtin_ptrs",
+            &format!("{BUILTIN_DATA}*"),
+        );
+        let hint_data =
+            HintProcessorData::new_default(EXECUTE_TASK_CALL_TASK.to_string(), ids_data)
+                .with_identifiers(Arc::new(bootloader_identifiers(&PROGRAM_HEADER_MEMBERS)));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+
+        #[cfg(not(feature = "extensive_hints"))]
+        assert_matches!(
+            hint_processor.execute_hint(
+                &mut vm,
+                &mut exec_scopes,
+                &any_box!(hint_data),
+                &HashMap::new()
+            ),
+            Err(HintError::CustomHint(msg)) if msg.contains("extensive_hints")
+        );
+        #[cfg(feature = "extensive_hints")]
+        {
+            let hint_extension = hint_processor
+                .execute_hint_extensive(
+                    &mut vm,
+                    &mut exec_scopes,
+                    &any_box!(hint_data),
+                    &HashMap::new(),
+                )
+                .unwrap();
+            // The hint of the task is placed at the start of its code
+            let task_hints = &hint_extension[&Relocatable::from((3, 5))];
+            assert_eq!(task_hints.len(), 1);
+            assert_eq!(
+                task_hints[0]
+                    .downcast_ref::<HintProcessorData>()
+                    .unwrap()
+                    .code,
+                hint_code::ADD_SEGMENT
+            );
+            assert_eq!(
+                exec_scopes
+                    .get::<serde_json::Value>("program_input")
+                    .unwrap(),
+                serde_json::json!({ "n": 1 })
+            );
+        }
+    }
+}
@@ -24,9 +24,12 @@ use super::{
         pack::*,
     },
 };
+#[cfg(all(feature = "extensive_hints", feature = "cairo-0-bootloader-hints"))]
+use crate::hint_processor::hint_processor_definition::HintExtension;
 use crate::Felt252;
 use crate::{
     hint_processor::{
+        builtin_hint_processor::hint_utils::{get_integer_from_var_name, get_ptr_from_var_name},
         builtin_hint_processor::secp::ec_utils::{
             ec_double_assign_new_x, ec_double_assign_new_x_v2,
         },
@@ -50,6 +53,7 @@ use crate::{
                 default_dict_new, dict_new, dict_read, dict_squash_copy_dict,
                 dict_squash_update_ptr, dict_update, dict_write,
             },
+            dict_manager::DictManager,
             ec_utils::{chained_ec_op_random_ec_point_hint, random_ec_point_hint, recover_y_hint},
             find_element_hint::{find_element, search_sorted_lower},
             garaga::get_felt_bitlenght,
@@ -111,8 +115,8 @@ use crate::{
         },
         hint_processor_definition::HintReference,
     },
-    serde::deserialize_program::ApTracking,
-    stdlib::{any::Any, collections::HashMap, prelude::*, rc::Rc},
+    serde::deserialize_program::{ApTracking, Identifier},
+    stdlib::{any::Any, cell::RefCell, collections::HashMap, prelude::*, rc::Rc, sync::Arc},
     types::exec_scope::ExecutionScopes,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
@@ -127,11 +131,58 @@ use crate::hint_processor::builtin_hint_processor::secp::secp_utils::{
 };
 
 use super::blake2s_utils::example_blake2s_compress;
+use num_traits::ToPrimitive;
+
+/// Latest cairo-lang version whose hints are implemented by the [`BuiltinHintProcessor`].
+pub const LATEST_SUPPORTED_COMPILER_VERSION: &str = "0.13.3";
+
+// Returns the amount of elements looped over by the hints whose work grows with their inputs,
+// read from the same ids as the hints. Ids that can't be read count as no iterations, as the
+// hint itself reports the error.
+fn hint_iterations(vm: &VirtualMachine, hint_data: &HintProcessorData) -> usize {
+    let (ids_data, ap_tracking) = (&hint_data.ids_data, &hint_data.ap_tracking);
+    let integer = |name| {
+        get_integer_from_var_name(name, vm, ids_data, ap_tracking)
+            .ok()
+            .and_then(|value| value.to_usize())
+            .unwrap_or_default()
+    };
+    match &*hint_data.code {
+        hint_code::FIND_ELEMENT | hint_code::SEARCH_SORTED_LOWER => integer("n_elms"),
+        hint_code::USORT_BODY => integer("input_len"),
+        hint_code::SQUASH_DICT => integer("n_accesses"),
+        hint_code::SET_ADD => {
+            let ptr = |name| get_ptr_from_var_name(name, vm, ids_data, ap_tracking).ok();
+            ptr("set_ptr")
+                .zip(ptr("set_end_ptr"))
+                .and_then(|(start, end)| (end - start).ok())
+                .unwrap_or_default()
+        }
+        _ => 0,
+    }
+}
+
+// Parses the numeric components of a version such as "0.13.1" or "0.13.2a0", ignoring any
+// pre-release suffix
+fn parse_compiler_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .split('.')
+        .map(|component| {
+            let digits_len = component
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(component.len());
+            component[..digits_len].parse().ok()
+        })
+        .collect()
+}
 
 pub struct HintProcessorData {
     pub code: String,
     pub ap_tracking: ApTracking,
     pub ids_data: HashMap<String, HintReference>,
+    // The identifiers of the program, used to resolve the members of the ids variables.
+    // Only filled in by compile_hint_with_identifiers or with_identifiers
+    pub(crate) identifiers: Arc<HashMap<String, Identifier>>,
 }
 
 impl HintProcessorData {
@@ -140,8 +191,21 @@ impl HintProcessorData {
             code,
             ap_tracking: ApTracking::default(),
             ids_data,
+            identifiers: Arc::default(),
         }
     }
+
+    /// Attaches the identifiers of the program, used by the hints that access the members of
+    /// their ids variables
+    pub fn with_identifiers(mut self, identifiers: Arc<HashMap<String, Identifier>>) -> Self {
+        self.identifiers = identifiers;
+        self
+    }
+
+    /// The identifiers of the program, empty unless attached
+    pub fn identifiers(&self) -> &HashMap<String, Identifier> {
+        &self.identifiers
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -160,12 +224,14 @@ pub struct HintFunc(
 pub struct BuiltinHintProcessor {
     pub extra_hints: HashMap<String, Rc<HintFunc>>,
     run_resources: RunResources,
+    dict_manager: Option<Rc<RefCell<DictManager>>>,
 }
 impl BuiltinHintProcessor {
     pub fn new_empty() -> Self {
         BuiltinHintProcessor {
             extra_hints: HashMap::new(),
             run_resources: RunResources::default(),
+            dict_manager: None,
         }
     }
 
@@ -173,9 +239,26 @@ impl BuiltinHintProcessor {
         BuiltinHintProcessor {
             extra_hints,
             run_resources,
+            dict_manager: None,
         }
     }
 
+    /// Makes the dict hints use the given [DictManager] instead of creating one in the
+    /// execution scopes of each run, so the state of the dictionaries outlives the run.
+    /// The same processor (or another one sharing the manager) can then be used for several
+    /// runs, for example with successive [run_from_entrypoint](crate::vm::runners::cairo_runner::CairoRunner::run_from_entrypoint) calls.
+    ///
+    /// Dictionaries are tracked by the index of their segment. Successive runs on the same runner
+    /// keep allocating new segments, so a later run can keep reading and writing the dictionaries
+    /// of the previous ones. A new runner allocates its segments from scratch instead: before
+    /// using the manager with it, detach the previous runner's dictionaries with
+    /// [DictManager::take_trackers], otherwise creating a dictionary on a segment that is still
+    /// tracked fails with [HintError::CantCreateDictionaryOnTakenSegment].
+    pub fn with_dict_manager(mut self, dict_manager: Rc<RefCell<DictManager>>) -> Self {
+        self.dict_manager = Some(dict_manager);
+        self
+    }
+
     pub fn add_hint(&mut self, hint_code: String, hint_func: Rc<HintFunc>) {
         self.extra_hints.insert(hint_code, hint_func);
     }
@@ -193,6 +276,12 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             .downcast_ref::<HintProcessorData>()
             .ok_or(HintError::WrongHintData)?;
 
+        if let Some(dict_manager) = &self.dict_manager {
+            if exec_scopes.get_dict_manager().is_err() {
+                exec_scopes.insert_value("dict_manager", Rc::clone(dict_manager));
+            }
+        }
+
         if let Some(hint_func) = self.extra_hints.get(&hint_data.code) {
             return hint_func.0(
                 vm,
@@ -202,6 +291,10 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 constants,
             );
         }
+        if self.run_resources.charges_hint_iterations() {
+            self.run_resources
+                .consume_steps(hint_iterations(vm, hint_data));
+        }
         match &*hint_data.code {
             hint_code::ADD_SEGMENT => add_segment(vm),
             hint_code::IS_NN => is_nn(vm, &hint_data.ids_data, &hint_data.ap_tracking),
@@ -266,6 +359,9 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::SPLIT_FELT => {
                 split_felt(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
             }
+            hint_code::SPLIT_FELT_V1 => {
+                split_felt_v1(vm, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
             hint_code::UNSIGNED_DIV_REM => {
                 unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -763,13 +859,24 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::NONDET_ELEMENTS_OVER_TWO => {
                 elements_over_x(vm, &hint_data.ids_data, &hint_data.ap_tracking, 2)
             }
-            hint_code::RANDOM_EC_POINT => {
-                random_ec_point_hint(vm, &hint_data.ids_data, &hint_data.ap_tracking)
-            }
-            hint_code::CHAINED_EC_OP_RANDOM_EC_POINT => {
-                chained_ec_op_random_ec_point_hint(vm, &hint_data.ids_data, &hint_data.ap_tracking)
-            }
-            hint_code::RECOVER_Y => recover_y_hint(vm, &hint_data.ids_data, &hint_data.ap_tracking),
+            hint_code::RANDOM_EC_POINT => random_ec_point_hint(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                &hint_data.identifiers,
+            ),
+            hint_code::CHAINED_EC_OP_RANDOM_EC_POINT => chained_ec_op_random_ec_point_hint(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                &hint_data.identifiers,
+            ),
+            hint_code::RECOVER_Y => recover_y_hint(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                &hint_data.identifiers,
+            ),
             hint_code::PACK_MODN_DIV_MODN => {
                 pack_modn_div_modn(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -974,10 +1081,160 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 constants,
             ),
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SIMPLE_BOOTLOADER_LOAD_INPUT => {
+                super::bootloader::simple_bootloader_load_input(exec_scopes)
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SIMPLE_BOOTLOADER_PREPARE_TASK_RANGE_CHECKS => {
+                super::bootloader::simple_bootloader_prepare_task_range_checks(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &hint_data.identifiers,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SIMPLE_BOOTLOADER_SET_TASKS_VARIABLE => {
+                super::bootloader::simple_bootloader_set_tasks_variable(exec_scopes)
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SIMPLE_BOOTLOADER_SET_CURRENT_TASK => {
+                super::bootloader::simple_bootloader_set_current_task(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SIMPLE_BOOTLOADER_USE_POSEIDON => {
+                super::bootloader::simple_bootloader_use_poseidon(vm, exec_scopes)
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT => {
+                super::bootloader::execute_task_allocate_program_data_segment(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_LOAD_PROGRAM => {
+                super::bootloader::execute_task_load_program(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &hint_data.identifiers,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_VALIDATE_HASH => {
+                super::bootloader::execute_task_validate_hash(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_ASSERT_PROGRAM_ADDRESS => {
+                super::bootloader::execute_task_assert_program_address(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_CALL_TASK => super::bootloader::execute_task_call_task(
+                vm,
+                exec_scopes,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                &hint_data.identifiers,
+            ),
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_EXIT_SCOPE => exit_scope(exec_scopes),
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_WRITE_RETURN_BUILTINS => {
+                super::bootloader::execute_task_write_return_builtins(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &hint_data.identifiers,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::EXECUTE_TASK_APPEND_FACT_TOPOLOGIES => {
+                super::bootloader::execute_task_append_fact_topologies(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &hint_data.identifiers,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SIMPLE_BOOTLOADER_CONFIGURE_FACT_TOPOLOGIES => {
+                super::bootloader::simple_bootloader_configure_fact_topologies(vm, exec_scopes)
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SELECT_BUILTINS_ENTER_SCOPE => {
+                super::bootloader::select_builtins_enter_scope(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                )
+            }
+            #[cfg(feature = "cairo-0-bootloader-hints")]
+            super::bootloader::SELECT_BUILTINS_SELECT_BUILTIN => {
+                super::bootloader::select_builtins_select_builtin(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                )
+            }
 
             code => Err(HintError::UnknownHint(code.to_string().into_boxed_str())),
         }
     }
+
+    #[cfg(all(feature = "extensive_hints", feature = "cairo-0-bootloader-hints"))]
+    fn execute_hint_extensive(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &Box<dyn Any>,
+        constants: &HashMap<String, Felt252>,
+    ) -> Result<HintExtension, HintError> {
+        // The hints of a task called by the bootloader are loaded along with its program
+        let hint_extension = match hint_data.downcast_ref::<HintProcessorData>() {
+            Some(data) if data.code == super::bootloader::EXECUTE_TASK_CALL_TASK => {
+                super::bootloader::load_task_hints(self, exec_scopes)?
+            }
+            _ => HintExtension::default(),
+        };
+        self.execute_hint(vm, exec_scopes, hint_data, constants)?;
+        Ok(hint_extension)
+    }
+
+    fn supports_compiler_version(&self, compiler_version: &str) -> bool {
+        match (
+            parse_compiler_version(compiler_version),
+            parse_compiler_version(LATEST_SUPPORTED_COMPILER_VERSION),
+        ) {
+            (Some(version), Some(latest)) => version <= latest,
+            // Versions we can't make sense of aren't blamed for unknown hints
+            _ => true,
+        }
+    }
 }
 
 impl ResourceTracker for BuiltinHintProcessor {
@@ -989,6 +1246,10 @@ impl ResourceTracker for BuiltinHintProcessor {
         self.run_resources.consumed()
     }
 
+    fn consume_steps(&mut self, n_steps: usize) {
+        self.run_resources.consume_steps(n_steps)
+    }
+
     fn get_n_steps(&self) -> Option<usize> {
         self.run_resources.get_n_steps()
     }
@@ -1435,4 +1696,62 @@ mod tests {
         );
         assert_eq!(exec_scopes.data.len(), 3);
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn supports_compiler_version() {
+        let hint_processor = BuiltinHintProcessor::new_empty();
+        assert!(hint_processor.supports_compiler_version("0.10.3"));
+        assert!(hint_processor.supports_compiler_version("0.13.2a0"));
+        assert!(hint_processor.supports_compiler_version(LATEST_SUPPORTED_COMPILER_VERSION));
+        assert!(!hint_processor.supports_compiler_version("0.13.4"));
+        assert!(!hint_processor.supports_compiler_version("0.14.0"));
+        assert!(!hint_processor.supports_compiler_version("1.0"));
+        assert!(hint_processor.supports_compiler_version("unknown"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compile_hint_with_identifiers_shares_identifiers() {
+        let hint_processor = BuiltinHintProcessor::new_empty();
+        let identifiers = Arc::new(HashMap::from([(
+            "__main__.main".to_string(),
+            Identifier {
+                pc: Some(0),
+                type_: Some("function".to_string()),
+                value: None,
+                full_name: None,
+                members: None,
+                cairo_type: None,
+                size: None,
+            },
+        )]));
+        let hint_data = hint_processor
+            .compile_hint_with_identifiers(
+                "memory[ap] = segments.add()",
+                &ApTracking::new(),
+                &HashMap::new(),
+                &[],
+                &identifiers,
+            )
+            .unwrap();
+        let hint_data = hint_data.downcast_ref::<HintProcessorData>().unwrap();
+        assert!(Arc::ptr_eq(&hint_data.identifiers, &identifiers));
+        assert_eq!(hint_data.identifiers(), &*identifiers);
+
+        // compile_hint alone leaves them out
+        let hint_data = hint_processor
+            .compile_hint(
+                "memory[ap] = segments.add()",
+                &ApTracking::new(),
+                &HashMap::new(),
+                &[],
+            )
+            .unwrap();
+        assert!(hint_data
+            .downcast_ref::<HintProcessorData>()
+            .unwrap()
+            .identifiers()
+            .is_empty());
+    }
 }
@@ -5,6 +5,7 @@ use crate::stdlib::{
     prelude::*,
 };
 use crate::{
+    crypto::keccak_f1600,
     hint_processor::{
         builtin_hint_processor::hint_utils::{
             get_integer_from_var_name, get_ptr_from_var_name, insert_value_from_var_name,
@@ -169,9 +170,8 @@ pub(crate) fn block_permutation_v1(
         .try_into()
         .map_err(|_| VirtualMachineError::SliceToArrayError)?;
 
-    // this function of the keccak crate is the one used instead of keccak_func from
-    // keccak_utils.py
-    keccak::f1600(&mut u64_values);
+    // this function is the one used instead of keccak_func from keccak_utils.py
+    keccak_f1600(&mut u64_values);
 
     let bigint_values = u64_array_to_mayberelocatable_vec(&u64_values);
 
@@ -233,9 +233,8 @@ pub(crate) fn block_permutation_v2(
         .try_into()
         .map_err(|_| VirtualMachineError::SliceToArrayError)?;
 
-    // this function of the keccak crate is the one used instead of keccak_func from
-    // keccak_utils.py
-    keccak::f1600(&mut u64_values);
+    // this function is the one used instead of keccak_func from keccak_utils.py
+    keccak_f1600(&mut u64_values);
 
     let bigint_values = u64_array_to_mayberelocatable_vec(&u64_values);
 
@@ -276,7 +275,7 @@ fn cairo_keccak_finalize(
     let mut inp = vec![0; keccak_state_size_felts]
         .try_into()
         .map_err(|_| VirtualMachineError::SliceToArrayError)?;
-    keccak::f1600(&mut inp);
+    keccak_f1600(&mut inp);
 
     let mut padding = vec![Felt252::ZERO.into(); keccak_state_size_felts];
     padding.extend(u64_array_to_mayberelocatable_vec(&inp));
@@ -1,4 +1,4 @@
-use crate::stdlib::{boxed::Box, collections::HashMap};
+use crate::stdlib::{boxed::Box, collections::HashMap, mem};
 
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},
@@ -103,6 +103,13 @@ impl DictManager {
         Ok(MaybeRelocatable::RelocatableValue(base))
     }
 
+    //Removes and returns all the trackers, leaving the manager empty.
+    //Used to detach the dictionaries of a finished runner before reusing the manager with a new
+    //runner, as the new runner's dictionaries would be allocated on the same segments.
+    pub fn take_trackers(&mut self) -> HashMap<isize, DictTracker> {
+        mem::take(&mut self.trackers)
+    }
+
     //Returns the tracker which's current_ptr matches with the given dict_ptr
     pub fn get_tracker_mut(
         &mut self,
@@ -329,6 +336,26 @@ mod tests {
         assert_eq!(vm.segments.num_segments(), 1);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn dict_manager_take_trackers() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        dict_manager.new_dict(&mut vm, HashMap::new()).unwrap();
+
+        let trackers = dict_manager.take_trackers();
+        assert_eq!(
+            trackers,
+            HashMap::from([(0, DictTracker::new_empty(relocatable!(0, 0)))])
+        );
+        assert!(dict_manager.trackers.is_empty());
+
+        // The segment can be used by a new run
+        let mut vm = vm!();
+        let base = dict_manager.new_dict(&mut vm, HashMap::new());
+        assert_matches!(base, Ok(x) if x == MaybeRelocatable::from((0, 0)));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn dict_manager_new_dict_empty_same_segment() {
@@ -1,20 +1,21 @@
+use crate::math_utils::is_quad_residue_felt;
 use crate::stdlib::{borrow::Cow, boxed::Box, collections::HashMap, prelude::*};
 use crate::utils::CAIRO_PRIME;
 use crate::Felt252;
 use crate::{
     hint_processor::{
         builtin_hint_processor::hint_utils::{
-            get_integer_from_var_name, get_relocatable_from_var_name,
+            get_integer_from_var_name, get_member_address_from_var_name,
         },
         hint_processor_definition::HintReference,
     },
-    serde::deserialize_program::ApTracking,
+    serde::deserialize_program::{ApTracking, Identifier},
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use lazy_static::lazy_static;
 use num_bigint::BigUint;
 use num_bigint::ToBigInt;
-use num_traits::{Num, One, Pow, ToPrimitive, Zero};
+use num_traits::{Num, Pow, ToPrimitive, Zero};
 use sha2::{Digest, Sha256};
 
 use super::hint_utils::get_ptr_from_var_name;
@@ -30,20 +31,49 @@ impl EcPoint<'_> {
         vm: &'a VirtualMachine,
         ids_data: &'a HashMap<String, HintReference>,
         ap_tracking: &'a ApTracking,
+        identifiers: &HashMap<String, Identifier>,
     ) -> Result<EcPoint<'a>, HintError> {
-        // Get first addr of EcPoint struct
-        let point_addr = get_relocatable_from_var_name(name, vm, ids_data, ap_tracking)?;
+        let get_member = |member: &str| {
+            let member_addr = get_member_address_from_var_name(
+                &format!("{name}.{member}"),
+                vm,
+                ids_data,
+                ap_tracking,
+                identifiers,
+            )?;
+            vm.get_integer(member_addr).map_err(|_| {
+                HintError::IdentifierHasNoMember(Box::new((name.to_string(), member.to_string())))
+            })
+        };
         Ok(EcPoint {
-            x: vm.get_integer(point_addr).map_err(|_| {
-                HintError::IdentifierHasNoMember(Box::new((name.to_string(), "x".to_string())))
-            })?,
-            y: vm.get_integer((point_addr + 1)?).map_err(|_| {
-                HintError::IdentifierHasNoMember(Box::new((name.to_string(), "y".to_string())))
-            })?,
+            x: get_member("x")?,
+            y: get_member("y")?,
         })
     }
 }
 
+// Writes the coordinates of an EcPoint variable
+fn insert_ec_point_from_var_name(
+    name: &str,
+    (x, y): (Felt252, Felt252),
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<(), HintError> {
+    for (member, value) in [("x", x), ("y", y)] {
+        let member_addr = get_member_address_from_var_name(
+            &format!("{name}.{member}"),
+            vm,
+            ids_data,
+            ap_tracking,
+            identifiers,
+        )?;
+        vm.insert_value(member_addr, value)?;
+    }
+    Ok(())
+}
+
 // Implements hint:
 // from starkware.crypto.signature.signature import ALPHA, BETA, FIELD_PRIME
 // from starkware.python.math_utils import random_ec_point
@@ -59,19 +89,17 @@ pub fn random_ec_point_hint(
     vm: &mut VirtualMachine,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
 ) -> Result<(), HintError> {
-    let p = EcPoint::from_var_name("p", vm, ids_data, ap_tracking)?;
-    let q = EcPoint::from_var_name("q", vm, ids_data, ap_tracking)?;
+    let p = EcPoint::from_var_name("p", vm, ids_data, ap_tracking, identifiers)?;
+    let q = EcPoint::from_var_name("q", vm, ids_data, ap_tracking, identifiers)?;
     let m = Cow::Owned(get_integer_from_var_name("m", vm, ids_data, ap_tracking)?);
     let bytes: Vec<u8> = [p.x, p.y, m, q.x, q.y]
         .iter()
         .flat_map(|x| x.to_bytes_be())
         .collect();
-    let (x, y) = random_ec_point_seeded(bytes)?;
-    let s_addr = get_relocatable_from_var_name("s", vm, ids_data, ap_tracking)?;
-    vm.insert_value(s_addr, x)?;
-    vm.insert_value((s_addr + 1)?, y)?;
-    Ok(())
+    let s = random_ec_point_seeded(bytes)?;
+    insert_ec_point_from_var_name("s", s, vm, ids_data, ap_tracking, identifiers)
 }
 
 // Implements hint:
@@ -106,13 +134,14 @@ pub fn chained_ec_op_random_ec_point_hint(
     vm: &mut VirtualMachine,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
 ) -> Result<(), HintError> {
     let n_elms = get_integer_from_var_name("len", vm, ids_data, ap_tracking)?;
     if n_elms.is_zero() || n_elms.to_usize().is_none() {
         return Err(HintError::InvalidLenValue(Box::new(n_elms)));
     }
     let n_elms = n_elms.to_usize().unwrap();
-    let p = EcPoint::from_var_name("p", vm, ids_data, ap_tracking)?;
+    let p = EcPoint::from_var_name("p", vm, ids_data, ap_tracking, identifiers)?;
     let m = get_ptr_from_var_name("m", vm, ids_data, ap_tracking)?;
     let q = get_ptr_from_var_name("q", vm, ids_data, ap_tracking)?;
     let m_range = vm.get_integer_range(m, n_elms)?;
@@ -123,11 +152,8 @@ pub fn chained_ec_op_random_ec_point_hint(
         .chain(q_range.iter())
         .flat_map(|x| x.to_bytes_be())
         .collect();
-    let (x, y) = random_ec_point_seeded(bytes)?;
-    let s_addr = get_relocatable_from_var_name("s", vm, ids_data, ap_tracking)?;
-    vm.insert_value(s_addr, x)?;
-    vm.insert_value((s_addr + 1)?, y)?;
-    Ok(())
+    let s = random_ec_point_seeded(bytes)?;
+    insert_ec_point_from_var_name("s", s, vm, ids_data, ap_tracking, identifiers)
 }
 
 // Implements hint:
@@ -140,15 +166,17 @@ pub fn recover_y_hint(
     vm: &mut VirtualMachine,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
 ) -> Result<(), HintError> {
     let p_x = get_integer_from_var_name("x", vm, ids_data, ap_tracking)?;
-    let p_addr = get_relocatable_from_var_name("p", vm, ids_data, ap_tracking)?;
-    vm.insert_value(p_addr, p_x)?;
+    let p_x_addr = get_member_address_from_var_name("p.x", vm, ids_data, ap_tracking, identifiers)?;
+    vm.insert_value(p_x_addr, p_x)?;
     let p_y = Felt252::from(
         &recover_y(&p_x.to_biguint())
             .ok_or_else(|| HintError::RecoverYPointNotOnCurve(Box::new(p_x)))?,
     );
-    vm.insert_value((p_addr + 1)?, p_y)?;
+    let p_y_addr = get_member_address_from_var_name("p.y", vm, ids_data, ap_tracking, identifiers)?;
+    vm.insert_value(p_y_addr, p_y)?;
     Ok(())
 }
 
@@ -188,7 +216,6 @@ lazy_static! {
         10
     )
     .unwrap();
-    static ref FELT_MAX_HALVED: BigUint = Felt252::MAX.to_biguint() / 2_u32;
 }
 
 // Recovers the corresponding y coordinate on the elliptic curve
@@ -197,21 +224,14 @@ lazy_static! {
 // Returns None if x is not the x coordinate of a point in the curve
 fn recover_y(x: &BigUint) -> Option<BigUint> {
     let y_squared: BigUint = x.modpow(&BigUint::from(3_u32), &CAIRO_PRIME) + ALPHA * x + &*BETA;
-    if is_quad_residue(&y_squared) {
-        Some(Felt252::from(&y_squared).sqrt()?.to_biguint())
+    let y_squared = Felt252::from(&y_squared);
+    if is_quad_residue_felt(&y_squared) {
+        Some(y_squared.sqrt()?.to_biguint())
     } else {
         None
     }
 }
 
-// Implementation adapted from sympy implementation
-// Conditions:
-// + prime is ommited as it will be CAIRO_PRIME
-// + a >= 0 < prime (other cases ommited)
-fn is_quad_residue(a: &BigUint) -> bool {
-    a.is_zero() || a.is_one() || a.modpow(&FELT_MAX_HALVED, &CAIRO_PRIME).is_one()
-}
-
 #[cfg(test)]
 mod tests {
     use crate::any_box;
@@ -220,6 +240,7 @@ mod tests {
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData;
     use crate::hint_processor::hint_processor_definition::HintProcessorLogic;
     use crate::relocatable;
+    use crate::serde::deserialize_program::Member;
     use crate::types::relocatable::Relocatable;
     use num_traits::Zero;
 
@@ -232,32 +253,77 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    const EC_POINT: &str = "starkware.cairo.common.ec_point.EcPoint";
+
+    fn ec_point_identifiers(x_offset: usize, y_offset: usize) -> HashMap<String, Identifier> {
+        let member = |offset| Member {
+            cairo_type: "felt".to_string(),
+            offset,
+        };
+        HashMap::from([(
+            EC_POINT.to_string(),
+            Identifier {
+                pc: None,
+                type_: Some("struct".to_string()),
+                value: None,
+                full_name: Some(EC_POINT.to_string()),
+                members: Some(HashMap::from([
+                    ("x".to_string(), member(x_offset)),
+                    ("y".to_string(), member(y_offset)),
+                ])),
+                cairo_type: None,
+                size: Some(2),
+            },
+        )])
+    }
+
+    // Runs a hint whose `ec_points` variables are EcPoint structs laid out as in `identifiers`
+    fn run_ec_point_hint(
+        vm: &mut VirtualMachine,
+        mut ids_data: HashMap<String, HintReference>,
+        ec_points: &[&str],
+        identifiers: HashMap<String, Identifier>,
+        hint_code: &str,
+    ) -> Result<(), HintError> {
+        for name in ec_points {
+            ids_data.get_mut(*name).unwrap().cairo_type = Some(EC_POINT.to_string());
+        }
+        let hint_data = HintProcessorData::new_default(hint_code.to_string(), ids_data)
+            .with_identifiers(Arc::new(identifiers));
+        BuiltinHintProcessor::new_empty().execute_hint(
+            vm,
+            exec_scopes_ref!(),
+            &any_box!(hint_data),
+            &HashMap::new(),
+        )
+    }
+
     #[test]
     fn test_is_quad_residue_less_than_2() {
-        assert!(is_quad_residue(&BigUint::one()));
-        assert!(is_quad_residue(&BigUint::zero()));
+        assert!(is_quad_residue_felt(&Felt252::ONE));
+        assert!(is_quad_residue_felt(&Felt252::ZERO));
     }
 
     #[test]
     fn test_is_quad_residue_false() {
-        assert!(!is_quad_residue(
+        assert!(!is_quad_residue_felt(&Felt252::from(
             &BigUint::from_str_radix(
                 "205857351767627712295703269674687767888261140702556021834663354704341414042",
                 10
             )
             .unwrap()
-        ));
+        )));
     }
 
     #[test]
     fn test_is_quad_residue_true() {
-        assert!(is_quad_residue(
+        assert!(is_quad_residue_felt(&Felt252::from(
             &BigUint::from_str_radix(
                 "99957092485221722822822221624080199277265330641980989815386842231144616633668",
                 10
             )
             .unwrap()
-        ));
+        )));
     }
 
     #[test]
@@ -352,7 +418,16 @@ mod tests {
         )
         .unwrap();
         //Execute the hint
-        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        assert_matches!(
+            run_ec_point_hint(
+                &mut vm,
+                ids_data,
+                &["p", "q", "s"],
+                ec_point_identifiers(0, 1),
+                hint_code
+            ),
+            Ok(())
+        );
         // Check post-hint memory values
         // s.x = 96578541406087262240552119423829615463800550101008760434566010168435227837635
         // s.y = 3412645436898503501401619513420382337734846074629040678138428701431530606439
@@ -458,7 +533,16 @@ mod tests {
         //len
         vm.insert_value((1, 4).into(), Felt252::from(3)).unwrap();
         //Execute the hint
-        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        assert_matches!(
+            run_ec_point_hint(
+                &mut vm,
+                ids_data,
+                &["p", "s"],
+                ec_point_identifiers(0, 1),
+                hint_code
+            ),
+            Ok(())
+        );
         // Check post-hint memory values
         // s.x = 1354562415074475070179359167082942891834423311678180448592849484844152837347
         // s.y = 907662328694455187848008017177970257426839229889571025406355869359245158736
@@ -495,7 +579,16 @@ mod tests {
         )
         .unwrap();
         //Execute the hint
-        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        assert_matches!(
+            run_ec_point_hint(
+                &mut vm,
+                ids_data,
+                &["p"],
+                ec_point_identifiers(0, 1),
+                hint_code
+            ),
+            Ok(())
+        );
         // Check post-hint memory values
         // p.x = 3004956058830981475544150447242655232275382685012344776588097793621230049020
         // p.y = 386236054595386575795345623791920124827519018828430310912260655089307618738
@@ -512,4 +605,44 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_recover_y_hint_follows_struct_layout() {
+        let hint_code = hint_code::RECOVER_Y;
+        let mut vm = vm!();
+        vm.run_context.fp = 3;
+        let ids_data = non_continuous_ids_data![("x", -3), ("p", -1)];
+        add_segments!(vm, 2);
+        vm.insert_value(
+            (1, 0).into(),
+            felt_str!(
+                "3004956058830981475544150447242655232275382685012344776588097793621230049020"
+            ),
+        )
+        .unwrap();
+        // An EcPoint whose members are declared as (y, x)
+        assert_matches!(
+            run_ec_point_hint(
+                &mut vm,
+                ids_data,
+                &["p"],
+                ec_point_identifiers(1, 0),
+                hint_code
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            vm.get_integer((1, 2).into()).unwrap().as_ref(),
+            &felt_str!(
+                "386236054595386575795345623791920124827519018828430310912260655089307618738"
+            )
+        );
+        assert_eq!(
+            vm.get_integer((1, 3).into()).unwrap().as_ref(),
+            &felt_str!(
+                "3004956058830981475544150447242655232275382685012344776588097793621230049020"
+            )
+        );
+    }
 }
@@ -6,7 +6,7 @@ use num_traits::Zero;
 use super::hint_utils::insert_value_from_var_name;
 use super::secp::bigint_utils::Uint384;
 use super::uint256_utils::Uint256;
-use crate::math_utils::{is_quad_residue, mul_inv, sqrt_prime_power};
+use crate::math_utils::{is_quad_residue, mul_inv, sqrt_mod_p};
 use crate::serde::deserialize_program::ApTracking;
 use crate::stdlib::{collections::HashMap, prelude::*};
 use crate::types::errors::math_errors::MathError;
@@ -73,7 +73,7 @@ pub fn u384_get_square_root(
     let success_x = is_quad_residue(&x, &p)?;
 
     let root_x = if success_x {
-        sqrt_prime_power(&x, &p).unwrap_or_default()
+        sqrt_mod_p(&x, &p).unwrap_or_default()
     } else {
         BigUint::zero()
     };
@@ -82,7 +82,7 @@ pub fn u384_get_square_root(
     let success_gx = is_quad_residue(&gx, &p)?;
 
     let root_gx = if success_gx {
-        sqrt_prime_power(&gx, &p).unwrap_or_default()
+        sqrt_mod_p(&gx, &p).unwrap_or_default()
     } else {
         BigUint::zero()
     };
@@ -165,7 +165,7 @@ pub fn u256_get_square_root(
     let success_x = is_quad_residue(&x, &p)?;
 
     let root_x = if success_x {
-        sqrt_prime_power(&x, &p).unwrap_or_default()
+        sqrt_mod_p(&x, &p).unwrap_or_default()
     } else {
         BigUint::zero()
     };
@@ -174,7 +174,7 @@ pub fn u256_get_square_root(
     let success_gx = is_quad_residue(&gx, &p)?;
 
     let root_gx = if success_gx {
-        sqrt_prime_power(&gx, &p).unwrap_or_default()
+        sqrt_mod_p(&gx, &p).unwrap_or_default()
     } else {
         BigUint::zero()
     };
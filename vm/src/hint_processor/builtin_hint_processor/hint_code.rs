@@ -113,6 +113,11 @@ assert res < ids.bound, f'split_int(): Limb {res} is out of range.'"#;
 pub const SPLIT_64: &str = r#"ids.low = ids.a & ((1<<64) - 1)
 ids.high = ids.a >> 64"#;
 
+pub const SPLIT_FELT_V1: &str = r#"from starkware.cairo.common.math_utils import assert_integer
+assert_integer(ids.value)
+ids.low = ids.value & ((1 << 128) - 1)
+ids.high = ids.value >> 128"#;
+
 pub const SPLIT_FELT: &str = r#"from starkware.cairo.common.math_utils import assert_integer
 assert ids.MAX_HIGH < 2**128 and ids.MAX_LOW < 2**128
 assert PRIME - 1 == ids.MAX_HIGH * 2**128 + ids.MAX_LOW
@@ -9,7 +9,7 @@ use crate::hint_processor::hint_processor_utils::{
 use crate::hint_processor::hint_processor_utils::{
     get_integer_from_reference, get_maybe_relocatable_from_reference,
 };
-use crate::serde::deserialize_program::ApTracking;
+use crate::serde::deserialize_program::{ApTracking, Identifier};
 use crate::types::relocatable::MaybeRelocatable;
 use crate::types::relocatable::Relocatable;
 use crate::vm::errors::hint_errors::HintError;
@@ -110,6 +110,56 @@ pub fn get_maybe_relocatable_from_var_name<'a>(
         .ok_or_else(|| HintError::UnknownIdentifier(Box::<str>::from(var_name)))
 }
 
+//Gets the address of a member of an ids variable, given its path (e.g. `point.x` for `ids.point.x`).
+//The member offsets are looked up in the struct definitions of the program by following the
+//cairo_type of the reference, dereferencing the pointers to structs found along the way
+pub fn get_member_address_from_var_name(
+    path: &str,
+    vm: &VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<Relocatable, HintError> {
+    let Some((var_name, members)) = path.split_once('.') else {
+        return get_relocatable_from_var_name(path, vm, ids_data, ap_tracking);
+    };
+    let mut cairo_type = get_reference_from_var_name(var_name, ids_data)?
+        .cairo_type
+        .as_deref()
+        .ok_or_else(|| HintError::UnknownIdentifier(Box::<str>::from(var_name)))?;
+    let mut address = match cairo_type.strip_suffix('*') {
+        // The value of a pointer is the address of the struct it points to
+        Some(pointee) => {
+            cairo_type = pointee;
+            get_ptr_from_var_name(var_name, vm, ids_data, ap_tracking)?
+        }
+        None => get_relocatable_from_var_name(var_name, vm, ids_data, ap_tracking)?,
+    };
+    let mut parent = var_name;
+    for member_name in members.split('.') {
+        if let Some(pointee) = cairo_type.strip_suffix('*') {
+            address = vm
+                .get_relocatable(address)
+                .map_err(|_| HintError::IdentifierNotRelocatable(Box::<str>::from(parent)))?;
+            cairo_type = pointee;
+        }
+        let member = identifiers
+            .get(cairo_type)
+            .and_then(|identifier| identifier.members.as_ref())
+            .and_then(|members| members.get(member_name))
+            .ok_or_else(|| {
+                HintError::IdentifierHasNoMember(Box::new((
+                    parent.to_string(),
+                    member_name.to_string(),
+                )))
+            })?;
+        address = (address + member.offset)?;
+        cairo_type = &member.cairo_type;
+        parent = &path[..parent.len() + 1 + member_name.len()];
+    }
+    Ok(address)
+}
+
 pub fn get_reference_from_var_name<'a>(
     var_name: &'a str,
     ids_data: &'a HashMap<String, HintReference>,
@@ -135,8 +185,10 @@ mod tests {
     use super::*;
 
     use crate::{
-        hint_processor::hint_processor_definition::HintReference, relocatable,
-        serde::deserialize_program::OffsetValue, utils::test_utils::*,
+        hint_processor::hint_processor_definition::HintReference,
+        relocatable,
+        serde::deserialize_program::{Member, OffsetValue},
+        utils::test_utils::*,
         vm::vm_memory::memory::Memory,
     };
     use assert_matches::assert_matches;
@@ -144,6 +196,113 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    fn struct_identifier(members: &[(&str, &str, usize)]) -> Identifier {
+        Identifier {
+            pc: None,
+            type_: Some("struct".to_string()),
+            value: None,
+            full_name: None,
+            members: Some(
+                members
+                    .iter()
+                    .map(|(name, cairo_type, offset)| {
+                        (
+                            name.to_string(),
+                            Member {
+                                cairo_type: cairo_type.to_string(),
+                                offset: *offset,
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+            cairo_type: None,
+            size: Some(members.len()),
+        }
+    }
+
+    fn member_access_test_data() -> (HashMap<String, HintReference>, HashMap<String, Identifier>) {
+        let line = HintReference {
+            cairo_type: Some("__main__.Line".to_string()),
+            ..HintReference::new_simple(0)
+        };
+        let point_ptr = HintReference {
+            cairo_type: Some("__main__.Point*".to_string()),
+            ..HintReference::new_simple(2)
+        };
+        let ids_data = HashMap::from([
+            ("line".to_string(), line),
+            ("point_ptr".to_string(), point_ptr),
+        ]);
+        let identifiers = HashMap::from([
+            (
+                "__main__.Point".to_string(),
+                struct_identifier(&[("x", "felt", 0), ("y", "felt", 1)]),
+            ),
+            (
+                "__main__.Line".to_string(),
+                struct_identifier(&[("a", "__main__.Point", 0), ("b", "__main__.Point*", 2)]),
+            ),
+        ]);
+        (ids_data, identifiers)
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_member_address_from_var_name_valid() {
+        let mut vm = vm!();
+        // line = Line(a=Point(1, 2), b=&Point(7, 8)), point_ptr = line.b
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), 2),
+            ((1, 2), (2, 0)),
+            ((2, 0), 7),
+            ((2, 1), 8)
+        ];
+        let (ids_data, identifiers) = member_access_test_data();
+        let get_member_address = |path| {
+            get_member_address_from_var_name(path, &vm, &ids_data, &ApTracking::new(), &identifiers)
+        };
+
+        assert_matches!(get_member_address("line"), Ok(x) if x == relocatable!(1, 0));
+        assert_matches!(get_member_address("line.a.y"), Ok(x) if x == relocatable!(1, 1));
+        assert_matches!(get_member_address("line.b"), Ok(x) if x == relocatable!(1, 2));
+        assert_matches!(get_member_address("line.b.x"), Ok(x) if x == relocatable!(2, 0));
+        assert_matches!(get_member_address("point_ptr.y"), Ok(x) if x == relocatable!(2, 1));
+        assert_eq!(
+            vm.get_integer(get_member_address("line.b.y").unwrap())
+                .unwrap()
+                .as_ref(),
+            &Felt252::from(8)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_member_address_from_var_name_invalid_member() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 1), ((1, 1), 2), ((1, 2), 3)];
+        let (ids_data, identifiers) = member_access_test_data();
+        let get_member_address = |path| {
+            get_member_address_from_var_name(path, &vm, &ids_data, &ApTracking::new(), &identifiers)
+        };
+
+        assert_matches!(
+            get_member_address("line.c"),
+            Err(HintError::IdentifierHasNoMember(bx))
+                if *bx == ("line".to_string(), "c".to_string())
+        );
+        assert_matches!(
+            get_member_address("line.a.x.z"),
+            Err(HintError::IdentifierHasNoMember(bx))
+                if *bx == ("line.a.x".to_string(), "z".to_string())
+        );
+        assert_matches!(
+            get_member_address("line.b.x"),
+            Err(HintError::IdentifierNotRelocatable(bx)) if bx.as_ref() == "line.b"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_ptr_from_var_name_immediate_value() {
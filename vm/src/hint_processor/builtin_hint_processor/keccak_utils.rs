@@ -1,6 +1,5 @@
 use crate::stdlib::{boxed::Box, cmp, collections::HashMap, prelude::*};
 
-use crate::types::errors::math_errors::MathError;
 use crate::Felt252;
 use crate::{
     hint_processor::{
@@ -9,12 +8,11 @@ use crate::{
         },
         hint_processor_definition::HintReference,
     },
-    math_utils::pow2_const_nz,
+    math_utils::{pow2_const_nz, safe_div_rem},
     serde::deserialize_program::ApTracking,
     types::{exec_scope::ExecutionScopes, relocatable::Relocatable},
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
-use num_integer::Integer;
 use num_traits::ToPrimitive;
 use sha3::{Digest, Keccak256};
 
@@ -158,9 +156,7 @@ pub fn unsafe_keccak_finalize(
     let n_elems = (end_ptr - start_ptr)?;
 
     let mut keccak_input = Vec::new();
-    let range = vm.get_integer_range(start_ptr, n_elems)?;
-
-    for word in range.into_iter() {
+    for word in vm.get_felt_range(start_ptr, n_elems)? {
         keccak_input.extend_from_slice(&word.to_bytes_be()[16..]);
     }
 
@@ -241,30 +237,13 @@ pub fn split_n_bytes(
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
 ) -> Result<(), HintError> {
-    let n_bytes =
-        get_integer_from_var_name("n_bytes", vm, ids_data, ap_tracking).and_then(|x| {
-            x.to_u64()
-                .ok_or_else(|| HintError::Math(MathError::Felt252ToU64Conversion(Box::new(x))))
-        })?;
+    let n_bytes = get_integer_from_var_name("n_bytes", vm, ids_data, ap_tracking)?;
     let bytes_in_word = constants
         .get(BYTES_IN_WORD)
-        .and_then(|x| x.to_u64())
         .ok_or_else(|| HintError::MissingConstant(Box::new(BYTES_IN_WORD)))?;
-    let (high, low) = n_bytes.div_mod_floor(&bytes_in_word);
-    insert_value_from_var_name(
-        "n_words_to_copy",
-        Felt252::from(high),
-        vm,
-        ids_data,
-        ap_tracking,
-    )?;
-    insert_value_from_var_name(
-        "n_bytes_left",
-        Felt252::from(low),
-        vm,
-        ids_data,
-        ap_tracking,
-    )
+    let (high, low) = safe_div_rem(&n_bytes, bytes_in_word)?;
+    insert_value_from_var_name("n_words_to_copy", high, vm, ids_data, ap_tracking)?;
+    insert_value_from_var_name("n_bytes_left", low, vm, ids_data, ap_tracking)
 }
 
 // Implements hint:
@@ -297,6 +276,7 @@ mod tests {
             },
             hint_processor_definition::{HintProcessorLogic, HintReference},
         },
+        types::errors::math_errors::MathError,
         utils::test_utils::*,
     };
     use assert_matches::assert_matches;
@@ -370,6 +350,24 @@ mod tests {
         check_memory!(vm.segments.memory, ((1, 0), 2), ((1, 1), 1));
     }
 
+    #[test]
+    fn split_n_bytes_zero_bytes_in_word() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 2), 17)];
+        vm.set_fp(3);
+        let ids_data = ids_data!["n_words_to_copy", "n_bytes_left", "n_bytes"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::SPLIT_N_BYTES,
+                exec_scopes_ref!(),
+                &HashMap::from([(String::from(BYTES_IN_WORD), Felt252::ZERO)])
+            ),
+            Err(HintError::Math(MathError::DividedByZeroFelt(bx))) if *bx == Felt252::from(17)
+        );
+    }
+
     #[test]
     fn split_output_mid_low_high() {
         let mut vm = vm!();
@@ -2,7 +2,6 @@ use crate::{
     hint_processor::builtin_hint_processor::hint_utils::get_constant_from_var_name,
     math_utils::signed_felt,
     stdlib::{boxed::Box, collections::HashMap, prelude::*},
-    types::errors::math_errors::MathError,
 };
 use lazy_static::lazy_static;
 use num_traits::{Signed, Zero};
@@ -19,7 +18,7 @@ use crate::{
         },
         hint_processor_definition::HintReference,
     },
-    math_utils::{isqrt, pow2_const},
+    math_utils::{is_quad_residue_felt, isqrt, pow2_const, pow2_const_nz, safe_div_rem, safe_mod},
     serde::deserialize_program::ApTracking,
     types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
     vm::{
@@ -334,14 +333,10 @@ pub fn split_int(
     let value = get_integer_from_var_name("value", vm, ids_data, ap_tracking)?;
     let base = get_integer_from_var_name("base", vm, ids_data, ap_tracking)?;
     let bound = get_integer_from_var_name("bound", vm, ids_data, ap_tracking)?;
-    let base = &base
-        .as_ref()
-        .try_into()
-        .map_err(|_| MathError::DividedByZero)?;
     let bound = bound.as_ref();
     let output = get_ptr_from_var_name("output", vm, ids_data, ap_tracking)?;
     //Main Logic
-    let res = value.mod_floor(base);
+    let res = safe_mod(&value, &base)?;
     if &res > bound {
         return Err(HintError::SplitIntLimbOutOfRange(Box::new(res)));
     }
@@ -401,13 +396,26 @@ pub fn split_felt(
         Felt252::MAX == max_high * bound + max_low,
         "assert PRIME - 1 == ids.MAX_HIGH * 2**128 + ids.MAX_LOW",
     )?;
+    split_felt_v1(vm, ids_data, ap_tracking)
+}
+
+// Implements the version of the hint emitted by older compilers, without the constant checks:
+//from starkware.cairo.common.math_utils import assert_integer
+//assert_integer(ids.value)
+//ids.low = ids.value & ((1 << 128) - 1)
+//ids.high = ids.value >> 128
+pub fn split_felt_v1(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
     let value = get_integer_from_var_name("value", vm, ids_data, ap_tracking)?;
     let value = value.as_ref();
     //Main logic
     //assert_integer(ids.value) (done by match)
     // ids.low = ids.value & ((1 << 128) - 1)
     // ids.high = ids.value >> 128
-    let (high, low) = value.div_rem(&bound.try_into().unwrap());
+    let (high, low) = value.div_rem(pow2_const_nz(128));
     insert_value_from_var_name("high", high, vm, ids_data, ap_tracking)?;
     insert_value_from_var_name("low", low, vm, ids_data, ap_tracking)
 }
@@ -506,7 +514,7 @@ pub fn unsigned_div_rem(
         return Err(HintError::OutOfValidRange(Box::new((div, *builtin_bound))));
     }
 
-    let (q, r) = value.div_rem(&(div).try_into().map_err(|_| MathError::DividedByZero)?);
+    let (q, r) = safe_div_rem(&value, &div)?;
     insert_value_from_var_name("r", r, vm, ids_data, ap_tracking)?;
     insert_value_from_var_name("q", q, vm, ids_data, ap_tracking)
 }
@@ -542,7 +550,7 @@ pub fn assert_250_bit(
     if &value > upper_bound {
         return Err(HintError::ValueOutside250BitRange(Box::new(value)));
     }
-    let (high, low) = value.div_rem(&shift.try_into().map_err(|_| MathError::DividedByZero)?);
+    let (high, low) = safe_div_rem(&value, &shift)?;
     insert_value_from_var_name("high", high, vm, ids_data, ap_tracking)?;
     insert_value_from_var_name("low", low, vm, ids_data, ap_tracking)
 }
@@ -647,9 +655,7 @@ pub fn is_quad_residue(
 
     if x.is_zero() || x == Felt252::ONE {
         insert_value_from_var_name("y", *x.as_ref(), vm, ids_data, ap_tracking)
-    // } else if Pow::pow(felt_to_biguint(x), &(&*CAIRO_PRIME >> 1_u32)).is_one() {
-    } else if x.pow_felt(&Felt252::MAX.div_rem(&Felt252::TWO.try_into().unwrap()).0) == Felt252::ONE
-    {
+    } else if is_quad_residue_felt(&x) {
         insert_value_from_var_name("y", x.sqrt().unwrap_or_default(), vm, ids_data, ap_tracking)
     } else {
         insert_value_from_var_name(
@@ -2087,6 +2093,92 @@ mod tests {
         ];
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_split_felt_v1_ok_without_constants() {
+        let hint_code = hint_code::SPLIT_FELT_V1;
+        let mut vm = vm_with_range_check!();
+        vm.segments = segments![
+            ((1, 3), ("335438970432432812899076431678123043273", 10)),
+            ((1, 4), (2, 0))
+        ];
+        add_segments!(vm, 1);
+        //Initialize fp
+        vm.run_context.fp = 7;
+        //Create ids
+        let ids_data = HashMap::from([
+            ("value".to_string(), HintReference::new_simple(-4)),
+            (
+                "low".to_string(),
+                HintReference::new(-3, 0, true, true, true),
+            ),
+            (
+                "high".to_string(),
+                HintReference::new(-3, 1, true, true, true),
+            ),
+        ]);
+        //Execute the hint
+        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        //Check hint memory inserts
+        check_memory![
+            vm.segments.memory,
+            ((2, 0), ("335438970432432812899076431678123043273", 10)),
+            ((2, 1), 0)
+        ];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_split_felt_v1_nonzero_high_and_boundaries() {
+        let two_pow_128 = Felt252::TWO.pow(128_u32);
+        // (value, high, low)
+        let cases = [
+            (Felt252::ZERO, Felt252::ZERO, Felt252::ZERO),
+            (
+                two_pow_128 - Felt252::ONE,
+                Felt252::ZERO,
+                Felt252::from(u128::MAX),
+            ),
+            (two_pow_128, Felt252::ONE, Felt252::ZERO),
+            (
+                felt_hex!("0x123456789abcdeffedcba9876543210fedcba9876543210"),
+                Felt252::from(0x123456789abcdef_u64),
+                Felt252::from(0xfedcba9876543210fedcba9876543210_u128),
+            ),
+            (
+                Felt252::MAX,
+                felt_hex!("0x8000000000000110000000000000000"),
+                Felt252::ZERO,
+            ),
+        ];
+        for (value, high, low) in cases {
+            let mut vm = vm_with_range_check!();
+            vm.segments = segments![((1, 4), (2, 0))];
+            vm.insert_value((1, 3).into(), value).unwrap();
+            add_segments!(vm, 1);
+            //Initialize fp
+            vm.run_context.fp = 7;
+            //Create ids
+            let ids_data = HashMap::from([
+                ("value".to_string(), HintReference::new_simple(-4)),
+                (
+                    "low".to_string(),
+                    HintReference::new(-3, 0, true, true, true),
+                ),
+                (
+                    "high".to_string(),
+                    HintReference::new(-3, 1, true, true, true),
+                ),
+            ]);
+            //Execute the hint
+            assert_matches!(run_hint!(vm, ids_data, hint_code::SPLIT_FELT_V1), Ok(()));
+            //Check hint memory inserts
+            assert_eq!(vm.get_integer((2, 0).into()).unwrap().as_ref(), &low);
+            assert_eq!(vm.get_integer((2, 1).into()).unwrap().as_ref(), &high);
+            assert_eq!(high * two_pow_128 + low, value);
+        }
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_split_felt_incorrect_ids() {
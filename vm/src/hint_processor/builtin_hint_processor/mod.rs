@@ -1,6 +1,10 @@
 pub mod bigint;
+pub mod biguint_limbs;
 pub mod blake2s_hash;
 pub mod blake2s_utils;
+#[cfg(feature = "cairo-0-bootloader-hints")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cairo-0-bootloader-hints")))]
+pub mod bootloader;
 pub mod builtin_hint_processor_definition;
 pub mod cairo_keccak;
 pub mod dict_hint_utils;
@@ -35,7 +39,6 @@ pub mod squash_dict_utils;
 pub mod uint256_utils;
 pub mod uint384;
 pub mod uint384_extension;
-pub mod uint_utils;
 pub mod usort;
 pub mod vrf;
 
@@ -1,7 +1,4 @@
-use core::ops::Shl;
-
-use crate::hint_processor::builtin_hint_processor::uint_utils::{pack, split};
-use crate::math_utils::signed_felt;
+use crate::hint_processor::builtin_hint_processor::biguint_limbs::{pack, pack86, split};
 use crate::stdlib::{borrow::Cow, boxed::Box, collections::HashMap, prelude::*};
 use crate::Felt252;
 use crate::{
@@ -87,12 +84,7 @@ impl<const NUM_LIMBS: usize> BigIntN<'_, NUM_LIMBS> {
     }
 
     pub(crate) fn pack86(self) -> BigInt {
-        self.limbs
-            .into_iter()
-            .take(3)
-            .enumerate()
-            .map(|(idx, value)| signed_felt(*value).shl(idx * 86))
-            .sum()
+        pack86(self.limbs)
     }
 
     pub(crate) fn split(num: &BigUint) -> Self {
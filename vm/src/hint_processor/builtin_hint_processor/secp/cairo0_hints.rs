@@ -1,18 +1,15 @@
-use crate::stdlib::{
-    collections::HashMap,
-    ops::Deref,
-    ops::{Add, Mul, Rem},
-    prelude::*,
-};
+use crate::stdlib::{collections::HashMap, ops::Deref, prelude::*};
 
 use crate::hint_processor::builtin_hint_processor::hint_utils::{
     get_constant_from_var_name, get_integer_from_var_name, insert_value_from_var_name,
 };
 use crate::hint_processor::builtin_hint_processor::uint256_utils::Uint256;
 use crate::hint_processor::hint_processor_definition::HintReference;
-use crate::math_utils::{div_mod, signed_felt};
+use crate::math_utils::{
+    curve::{ec_double_new_x, SECP256R1},
+    div_mod, safe_div_rem, signed_felt,
+};
 use crate::serde::deserialize_program::ApTracking;
-use crate::types::errors::math_errors::MathError;
 use crate::types::exec_scope::ExecutionScopes;
 use crate::vm::errors::hint_errors::HintError;
 use crate::vm::vm_core::VirtualMachine;
@@ -24,7 +21,7 @@ use num_traits::Zero;
 
 use super::bigint_utils::{BigInt3, Uint384};
 use super::ec_utils::EcPoint;
-use super::secp_utils::{SECP256R1_ALPHA, SECP256R1_B, SECP256R1_P};
+use super::secp_utils::SECP256R1_P;
 
 pub const SECP_REDUCE: &str = r#"from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_P
 from starkware.cairo.common.cairo_secp.secp_utils import pack
@@ -115,7 +112,7 @@ pub fn compute_ids_high_low(
         return Err(HintError::ValueOutside250BitRange(Box::new(value)));
     }
 
-    let (high, low) = value.div_rem(&shift.try_into().map_err(|_| MathError::DividedByZero)?);
+    let (high, low) = safe_div_rem(&value, &shift)?;
     insert_value_from_var_name("high", high, vm, ids_data, ap_tracking)?;
     insert_value_from_var_name("low", low, vm, ids_data, ap_tracking)?;
     Ok(())
@@ -152,23 +149,6 @@ pub fn r1_get_point_from_x(
 ) -> Result<(), HintError> {
     exec_scopes.insert_value::<BigInt>("SECP256R1_P", SECP256R1_P.clone());
 
-    // def y_squared_from_x(x: int, alpha: int, beta: int, field_prime: int) -> int:
-    // """
-    // Computes y^2 using the curve equation:
-    // y^2 = x^3 + alpha * x + beta (mod field_prime)
-    // """
-    // return (pow(x, 3, field_prime) + alpha * x + beta) % field_prime
-    fn y_squared_from_x(x: &BigInt, alpha: &BigInt, beta: &BigInt, field_prime: &BigInt) -> BigInt {
-        // Compute x^3 (mod field_prime)
-        let x_cubed = x.modpow(&BigInt::from(3), field_prime);
-
-        // Compute alpha * x
-        let alpha_x = alpha.mul(x);
-
-        // Compute y^2 = (x^3 + alpha * x + beta) % field_prime
-        x_cubed.add(&alpha_x).add(beta).rem(field_prime)
-    }
-
     // prime = curve.prime
     //     y_squared = y_squared_from_x(
     //         x=x,
@@ -185,7 +165,7 @@ pub fn r1_get_point_from_x(
         .pack86()
         .mod_floor(&SECP256R1_P);
 
-    let y_square_int = y_squared_from_x(&x, &SECP256R1_ALPHA, &SECP256R1_B, &SECP256R1_P);
+    let y_square_int = SECP256R1.y_squared(&x);
     exec_scopes.insert_value::<BigInt>("y_square_int", y_square_int.clone());
 
     // Calculate (prime + 1) // 4
@@ -254,8 +234,7 @@ pub fn secp_double_assign_new_x(
     let x = point.x.pack86().mod_floor(&SECP256R1_P);
     let y = point.y.pack86().mod_floor(&SECP256R1_P);
 
-    let value =
-        (slope.modpow(&(2usize.into()), &SECP256R1_P) - (&x << 1u32)).mod_floor(&SECP256R1_P);
+    let value = ec_double_new_x(&slope, &x, &SECP256R1_P);
 
     //Assign variables to vm scope
     exec_scopes.insert_value("slope", slope);
@@ -365,6 +344,9 @@ mod tests {
     use crate::utils::test_utils::*;
 
     use super::*;
+    use crate::hint_processor::builtin_hint_processor::secp::secp_utils::{
+        SECP256R1_ALPHA, SECP256R1_B,
+    };
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
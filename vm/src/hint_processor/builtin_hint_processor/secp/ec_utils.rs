@@ -13,7 +13,10 @@ use crate::{
         },
         hint_processor_definition::HintReference,
     },
-    math_utils::{ec_double_slope, line_slope},
+    math_utils::{
+        curve::{ec_add_new_x, ec_double_new_x, ec_new_y},
+        ec_double_slope, line_slope,
+    },
     serde::deserialize_program::ApTracking,
     stdlib::{collections::HashMap, prelude::*},
     types::exec_scope::ExecutionScopes,
@@ -256,7 +259,7 @@ pub fn square_slope_minus_xs(
     let x1 = point1.x.pack86();
     let y0 = point0.y.pack86();
 
-    let value = (slope.pow(2) - &x0 - &x1).mod_floor(&secp_p);
+    let value = ec_add_new_x(&slope, &x0, &x1, &secp_p);
 
     exec_scopes.insert_value("slope", slope);
     exec_scopes.insert_value("x0", x0);
@@ -309,7 +312,7 @@ pub fn ec_double_assign_new_x(
     let x = point.x.pack86().mod_floor(secp_p);
     let y = point.y.pack86().mod_floor(secp_p);
 
-    let value = (slope.pow(2) - (&x << 1u32)).mod_floor(secp_p);
+    let value = ec_double_new_x(&slope, &x, secp_p);
 
     //Assign variables to vm scope
     exec_scopes.insert_value("slope", slope);
@@ -334,7 +337,7 @@ pub fn ec_double_assign_new_y(exec_scopes: &mut ExecutionScopes) -> Result<(), H
         exec_scopes.get::<BigInt>("SECP_P")?,
     );
 
-    let value = (slope * (x - new_x) - y).mod_floor(&secp_p);
+    let value = ec_new_y(&slope, &(x, y), &new_x, &secp_p);
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("new_y", value);
     Ok(())
@@ -375,7 +378,7 @@ pub fn fast_ec_add_assign_new_x(
     let x1 = point1.x.pack86().mod_floor(secp_p);
     let y0 = point0.y.pack86().mod_floor(secp_p);
 
-    let value = (&slope * &slope - &x0 - &x1).mod_floor(secp_p);
+    let value = ec_add_new_x(&slope, &x0, &x1, secp_p);
     //Assign variables to vm scope
     exec_scopes.insert_value("slope", slope);
     exec_scopes.insert_value("x0", x0);
@@ -399,7 +402,7 @@ pub fn fast_ec_add_assign_new_y(exec_scopes: &mut ExecutionScopes) -> Result<(),
         exec_scopes.get::<BigInt>("y0")?,
         exec_scopes.get::<BigInt>("SECP_P")?,
     );
-    let value = (slope * (x0 - new_x) - y0).mod_floor(&secp_p);
+    let value = ec_new_y(&slope, &(x0, y0), &new_x, &secp_p);
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("new_y", value);
 
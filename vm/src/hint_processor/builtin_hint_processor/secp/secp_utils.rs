@@ -2,11 +2,12 @@ use core::str::FromStr;
 
 use crate::stdlib::{boxed::Box, prelude::*};
 
+use crate::math_utils::curve::{SECP256K1, SECP256R1};
 use crate::vm::errors::hint_errors::HintError;
 
 use lazy_static::lazy_static;
 use num_bigint::{BigInt, BigUint};
-use num_traits::{Num, Zero};
+use num_traits::Zero;
 
 // Constants in package "starkware.cairo.common.cairo_secp.constants".
 pub const BASE_86: &str = "starkware.cairo.common.cairo_secp.constants.BASE";
@@ -20,18 +21,14 @@ pub const P2: &str = "starkware.cairo.common.cairo_secp.constants.P2";
 pub const SECP_REM: &str = "starkware.cairo.common.cairo_secp.constants.SECP_REM";
 // Constants in package "starkware.cairo.common.cairo_secp.secp_utils"
 lazy_static! {
-    //SECP_P = 2**256 - 2**32 - 2**9 - 2**8 - 2**7 - 2**6 - 2**4 - 1
-    pub(crate) static ref SECP_P: BigInt = BigInt::from_str(
-        "115792089237316195423570985008687907853269984665640564039457584007908834671663"
-    )
-    .unwrap();
+    pub(crate) static ref SECP_P: BigInt = SECP256K1.p.clone();
     //SECP_P_V2 = 2**255-19
     pub(crate) static ref SECP_P_V2: BigInt = BigInt::from_str(
         "57896044618658097711785492504343953926634992332820282019728792003956564819949"
     )
     .unwrap();
 
-    pub(crate) static ref ALPHA: BigInt = BigInt::zero();
+    pub(crate) static ref ALPHA: BigInt = SECP256K1.a.clone();
 
     pub(crate) static ref ALPHA_V2: BigInt = BigInt::from_str(
         "42204101795669822316448953119945047945709099015225996174933988943478124189485"
@@ -47,30 +44,14 @@ lazy_static! {
     pub(crate) static ref BASE_MINUS_ONE: BigUint = BigUint::from_str(
         "77371252455336267181195263"
     ).unwrap();
-    // N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
-    pub(crate) static ref N: BigInt = BigInt::from_str(
-        "115792089237316195423570985008687907852837564279074904382605163141518161494337"
-    ).unwrap();
+    pub(crate) static ref N: BigInt = SECP256K1.n.clone();
 }
 // Constants in package "starkware.cairo.common.cairo_secp.secp256r1_utils"
 lazy_static! {
-    //SECP256R1_P = 2**256 - 2**224 + 2**192 + 2**96 - 1
-    pub(crate) static ref SECP256R1_P: BigInt = BigInt::from_str(
-        "115792089210356248762697446949407573530086143415290314195533631308867097853951"
-    ).unwrap();
-    //SECP256R1_N = 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
-    pub(crate) static ref SECP256R1_N: BigInt = BigInt::from_str(
-        "115792089210356248762697446949407573529996955224135760342422259061068512044369"
-    ).unwrap();
-    //SECP256R1_ALPHA = 0xFFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC
-    pub(crate) static ref SECP256R1_ALPHA: BigInt = BigInt::from_str(
-        "115792089210356248762697446949407573530086143415290314195533631308867097853948"
-    ).unwrap();
-    pub(crate) static ref SECP256R1_B: BigInt = BigInt::from_str_radix(
-        "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
-        16,
-    )
-    .unwrap();
+    pub(crate) static ref SECP256R1_P: BigInt = SECP256R1.p.clone();
+    pub(crate) static ref SECP256R1_N: BigInt = SECP256R1.n.clone();
+    pub(crate) static ref SECP256R1_ALPHA: BigInt = SECP256R1.a.clone();
+    pub(crate) static ref SECP256R1_B: BigInt = SECP256R1.b.clone();
 }
 
 /*
@@ -75,9 +75,8 @@ fn sha256_main(
 
     let mut message: Vec<u8> = Vec::with_capacity(4 * input_chunk_size_felts);
 
-    for i in 0..input_chunk_size_felts {
-        let input_element = vm.get_integer((input_ptr + i)?)?;
-        let bytes = felt_to_u32(input_element.as_ref())?.to_be_bytes();
+    for input_element in vm.get_felt_range(input_ptr, input_chunk_size_felts)? {
+        let bytes = felt_to_u32(&input_element)?.to_be_bytes();
         message.extend(bytes);
     }
 
@@ -164,9 +163,8 @@ pub fn sha256_main_arbitrary_input_length(
     };
 
     let mut iv = vm
-        .get_integer_range(iv_ptr, state_size)?
-        .into_iter()
-        .map(|x| felt_to_u32(x.as_ref()))
+        .get_felt_range(iv_ptr, state_size)?
+        .map(|x| felt_to_u32(&x))
         .collect::<Result<Vec<u32>, _>>()?
         .try_into()
         .expect("size is constant");
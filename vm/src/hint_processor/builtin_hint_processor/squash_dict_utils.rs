@@ -837,6 +837,66 @@ mod tests {
         check_memory![vm.segments.memory, ((1, 1), 0), ((1, 2), 1)];
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn squash_dict_try_clone_scopes_mid_run() {
+        //Dict = {1: (1,1), 1: (1,2)}
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 5;
+        vm.segments = segments![
+            ((1, 0), (2, 0)),
+            ((1, 3), 6),
+            ((1, 4), 2),
+            ((2, 0), 1),
+            ((2, 1), 1),
+            ((2, 2), 1),
+            ((2, 3), 1),
+            ((2, 4), 1),
+            ((2, 5), 2)
+        ];
+        let ids_data = ids_data![
+            "dict_accesses",
+            "big_keys",
+            "first_key",
+            "ptr_diff",
+            "n_accesses"
+        ];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            run_hint!(vm, ids_data, SQUASH_DICT, &mut exec_scopes),
+            Ok(())
+        );
+        //Clone the scopes between the squash_dict hints
+        let mut copy = exec_scopes.try_clone().unwrap();
+        let access_indices_scope_value: HashMap<Felt252, Vec<Felt252>> =
+            HashMap::from([(Felt252::ONE, vec![Felt252::ZERO, Felt252::ONE])]);
+        check_scope!(
+            &copy,
+            [
+                ("access_indices", access_indices_scope_value.clone()),
+                ("keys", Vec::<Felt252>::new()),
+                ("key", Felt252::ONE)
+            ]
+        );
+        //Modifying the copy doesn't affect the original scopes
+        copy.get_mut_ref::<HashMap<Felt252, Vec<Felt252>>>("access_indices")
+            .unwrap()
+            .get_mut(&Felt252::ONE)
+            .unwrap()
+            .pop();
+        check_scope!(
+            &exec_scopes,
+            [("access_indices", access_indices_scope_value)]
+        );
+        check_scope!(
+            &copy,
+            [(
+                "access_indices",
+                HashMap::from([(Felt252::ONE, vec![Felt252::ZERO])])
+            )]
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn squash_dict_valid_two_key_dict_no_max_size() {
@@ -14,12 +14,14 @@ use crate::{
         ops::{Shl, Shr},
         prelude::*,
     },
-    types::{errors::math_errors::MathError, relocatable::Relocatable},
+    types::relocatable::Relocatable,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use num_bigint::BigUint;
-use num_integer::{div_rem, Integer};
-use num_traits::{One, Zero};
+use num_integer::Integer;
+use num_traits::One;
+
+use super::biguint_limbs::{add_carries, div_rem, pack};
 
 // TODO: use this type in all uint256 functions
 pub(crate) struct Uint256<'a> {
@@ -75,7 +77,7 @@ impl<'a> Uint256<'a> {
     }
 
     pub(crate) fn pack(self) -> BigUint {
-        (self.high.to_biguint() << 128) + self.low.to_biguint()
+        pack([self.low, self.high], 128)
     }
 
     pub(crate) fn split(num: &BigUint) -> Self {
@@ -118,31 +120,35 @@ pub fn uint256_add(
     ap_tracking: &ApTracking,
     low_only: bool,
 ) -> Result<(), HintError> {
-    let shift = pow2_const(128);
+    let shift = BigUint::one() << 128_u32;
 
     let a = Uint256::from_var_name("a", vm, ids_data, ap_tracking)?;
     let b = Uint256::from_var_name("b", vm, ids_data, ap_tracking)?;
-    let a_low = a.low.as_ref();
-    let b_low = b.low.as_ref();
 
     // Main logic
     // sum_low = ids.a.low + ids.b.low
     // ids.carry_low = 1 if sum_low >= ids.SHIFT else 0
-    let carry_low = Felt252::from((a_low + b_low >= shift) as u8);
+    // sum_high = ids.a.high + ids.b.high + ids.carry_low
+    // ids.carry_high = 1 if sum_high >= ids.SHIFT else 0
+    let [carry_low, carry_high] = add_carries(&[a.low, a.high], &[b.low, b.high], &shift);
 
     if !low_only {
-        let a_high = a.high.as_ref();
-        let b_high = b.high.as_ref();
-
-        // Main logic
-        // sum_high = ids.a.high + ids.b.high + ids.carry_low
-        // ids.carry_high = 1 if sum_high >= ids.SHIFT else 0
-        let carry_high = Felt252::from((a_high + b_high + carry_low >= shift) as u8);
-
-        insert_value_from_var_name("carry_high", carry_high, vm, ids_data, ap_tracking)?;
+        insert_value_from_var_name(
+            "carry_high",
+            Felt252::from(carry_high),
+            vm,
+            ids_data,
+            ap_tracking,
+        )?;
     }
 
-    insert_value_from_var_name("carry_low", carry_low, vm, ids_data, ap_tracking)
+    insert_value_from_var_name(
+        "carry_low",
+        Felt252::from(carry_low),
+        vm,
+        ids_data,
+        ap_tracking,
+    )
 }
 
 /*
@@ -397,7 +403,7 @@ pub fn uint256_offseted_unsigned_div_rem(
     let div = (div_high.to_biguint() << 128_u32) + div_low.to_biguint();
     //a and div will always be positive numbers
     //Then, Rust div_rem equals Python divmod
-    let (quotient, remainder) = div_rem(a, div);
+    let (quotient, remainder) = div_rem(&a, &div)?;
 
     let quotient = Uint256::from(&quotient);
     let remainder = Uint256::from(&remainder);
@@ -455,10 +461,7 @@ pub fn uint256_mul_div_mod(
     let a = a_high.to_biguint().shl(128_usize) + a_low.to_biguint();
     let b = b_high.to_biguint().shl(128_usize) + b_low.to_biguint();
     let div = div_high.to_biguint().shl(128_usize) + div_low.to_biguint();
-    if div.is_zero() {
-        return Err(MathError::DividedByZero.into());
-    }
-    let (quotient, remainder) = (a * b).div_mod_floor(&div);
+    let (quotient, remainder) = div_rem(&(a * b), &div)?;
 
     // ids.quotient_low.low
     vm.insert_value(
@@ -507,7 +510,10 @@ mod tests {
             },
             hint_processor_definition::HintProcessorLogic,
         },
-        types::relocatable::{MaybeRelocatable, Relocatable},
+        types::{
+            errors::math_errors::MathError,
+            relocatable::{MaybeRelocatable, Relocatable},
+        },
         utils::test_utils::*,
         vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
     };
@@ -964,6 +970,25 @@ mod tests {
         ];
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_unsigned_div_rem_divided_by_zero() {
+        let hint_code = hint_code::UINT256_UNSIGNED_DIV_REM;
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 10;
+        //Create hint_data
+        let ids_data =
+            non_continuous_ids_data![("a", -6), ("div", -4), ("quotient", 0), ("remainder", 2)];
+        //Insert ids into memory
+        vm.segments = segments![((1, 4), 89), ((1, 5), 72), ((1, 6), 0), ((1, 7), 0)];
+        //Execute the hint
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::Math(MathError::DividedByZero))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_unsigned_div_rem_expanded_ok() {
@@ -1,11 +1,9 @@
 use crate::Felt252;
-use num_bigint::BigUint;
 use num_integer::Integer;
 use num_traits::Zero;
 
 use crate::math_utils::isqrt;
 use crate::stdlib::{boxed::Box, collections::HashMap, prelude::*};
-use crate::types::errors::math_errors::MathError;
 use crate::{
     hint_processor::hint_processor_definition::HintReference,
     math_utils::pow2_const_nz,
@@ -13,6 +11,7 @@ use crate::{
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 
+use super::biguint_limbs::{add_carries, div_rem};
 use super::hint_utils::{
     get_constant_from_var_name, get_integer_from_var_name, get_relocatable_from_var_name,
     insert_value_from_var_name, insert_value_into_ap,
@@ -58,10 +57,7 @@ pub fn uint384_unsigned_div_rem(
     let a = Uint384::from_var_name("a", vm, ids_data, ap_tracking)?.pack();
     let div = Uint384::from_var_name("div", vm, ids_data, ap_tracking)?.pack();
 
-    if div.is_zero() {
-        return Err(MathError::DividedByZero.into());
-    }
-    let (quotient, remainder) = a.div_mod_floor(&div);
+    let (quotient, remainder) = div_rem(&a, &div)?;
 
     let quotient_split = Uint384::split(&quotient);
     quotient_split.insert_from_var_name("quotient", vm, ids_data, ap_tracking)?;
@@ -109,30 +105,29 @@ pub fn add_no_uint384_check(
     // This hint is not from the cairo commonlib, and its lib can be found under different paths, so we cant rely on a full path name
     let shift = get_constant_from_var_name("SHIFT", constants)?.to_biguint();
 
-    let sum_d0 = (a.limbs[0].as_ref().to_biguint()) + (b.limbs[0].as_ref().to_biguint());
-    let carry_d0 = BigUint::from((sum_d0 >= shift) as usize);
-    let sum_d1 =
-        (a.limbs[1].as_ref().to_biguint()) + (b.limbs[1].as_ref().to_biguint()) + &carry_d0;
-    let carry_d1 = BigUint::from((sum_d1 >= shift) as usize);
-    let sum_d2 =
-        (a.limbs[2].as_ref().to_biguint()) + (b.limbs[2].as_ref().to_biguint()) + &carry_d1;
-    let carry_d2 = Felt252::from((sum_d2 >= shift) as usize);
+    let [carry_d0, carry_d1, carry_d2] = add_carries(&a.limbs, &b.limbs, &shift);
 
     insert_value_from_var_name(
         "carry_d0",
-        Felt252::from(&carry_d0),
+        Felt252::from(carry_d0),
         vm,
         ids_data,
         ap_tracking,
     )?;
     insert_value_from_var_name(
         "carry_d1",
-        Felt252::from(&carry_d1),
+        Felt252::from(carry_d1),
         vm,
         ids_data,
         ap_tracking,
     )?;
-    insert_value_from_var_name("carry_d2", carry_d2, vm, ids_data, ap_tracking)
+    insert_value_from_var_name(
+        "carry_d2",
+        Felt252::from(carry_d2),
+        vm,
+        ids_data,
+        ap_tracking,
+    )
 }
 
 /* Implements Hint
@@ -242,6 +237,7 @@ pub fn sub_reduced_a_and_reduced_b(
 mod tests {
     use super::*;
     use crate::hint_processor::builtin_hint_processor::hint_code;
+    use crate::types::errors::math_errors::MathError;
 
     use crate::felt_str;
     use crate::{
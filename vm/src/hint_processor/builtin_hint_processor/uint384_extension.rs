@@ -1,13 +1,11 @@
+use super::biguint_limbs::div_rem;
 use super::secp::bigint_utils::{Uint384, Uint768};
 use crate::stdlib::{collections::HashMap, prelude::*};
-use crate::types::errors::math_errors::MathError;
 use crate::{
     hint_processor::hint_processor_definition::HintReference,
     serde::deserialize_program::ApTracking,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
-use num_integer::Integer;
-use num_traits::Zero;
 
 /* Implements Hint:
        %{
@@ -54,10 +52,7 @@ pub fn unsigned_div_rem_uint768_by_uint384(
     let a = Uint768::from_var_name("a", vm, ids_data, ap_tracking)?.pack();
     let div = Uint384::from_var_name("div", vm, ids_data, ap_tracking)?.pack();
 
-    if div.is_zero() {
-        return Err(MathError::DividedByZero.into());
-    }
-    let (quotient, remainder) = a.div_mod_floor(&div);
+    let (quotient, remainder) = div_rem(&a, &div)?;
     let quotient_split = Uint768::split(&quotient);
     quotient_split.insert_from_var_name("quotient", vm, ids_data, ap_tracking)?;
     let remainder_split = Uint384::split(&remainder);
@@ -73,6 +68,7 @@ mod tests {
     use crate::hint_processor::builtin_hint_processor::hint_code;
     use crate::hint_processor::builtin_hint_processor::secp::bigint_utils::Uint768;
     use crate::hint_processor::hint_processor_definition::HintProcessorLogic;
+    use crate::types::errors::math_errors::MathError;
     use crate::utils::test_utils::*;
 
     use assert_matches::assert_matches;
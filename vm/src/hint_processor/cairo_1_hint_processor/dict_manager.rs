@@ -12,6 +12,7 @@ use crate::Felt252;
 use crate::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine};
 
 /// Stores the data of a specific dictionary.
+#[derive(Clone)]
 pub struct DictTrackerExecScope {
     /// The data of the dictionary.
     data: HashMap<Felt252, MaybeRelocatable>,
@@ -22,7 +23,7 @@ pub struct DictTrackerExecScope {
 }
 
 /// Helper object to allocate, track and destruct all dictionaries in the run.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct DictManagerExecScope {
     /// Maps between a segment index and the DictTrackerExecScope associated with it.
     segment_to_tracker: HashMap<isize, usize>,
@@ -159,7 +160,7 @@ impl DictManagerExecScope {
 }
 
 /// Helper object for the management of dict_squash hints.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct DictSquashExecScope {
     /// A map from key to the list of indices accessing it, each list in reverse order.
     pub(crate) access_indices: HashMap<Felt252, Vec<Felt252>>,
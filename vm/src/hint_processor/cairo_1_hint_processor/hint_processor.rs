@@ -11,6 +11,7 @@ use crate::vm::runners::cairo_runner::RunResources;
 use crate::Felt252;
 use crate::{
     hint_processor::hint_processor_definition::HintProcessorLogic,
+    math_utils::safe_div_rem,
     types::{errors::math_errors::MathError, exec_scope::ExecutionScopes},
     vm::errors::vm_errors::VirtualMachineError,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
@@ -51,6 +52,20 @@ fn get_beta() -> Felt252 {
     .unwrap()
 }
 
+// Returns the amount of elements looped over by the hints whose work grows with their inputs.
+// Operands that can't be read count as no iterations, as the hint itself reports the error.
+fn hint_iterations(vm: &VirtualMachine, hint: &Hint) -> usize {
+    match hint {
+        Hint::Core(CoreHintBase::Core(CoreHint::InitSquashData { n_accesses, .. })) => {
+            res_operand_get_val(vm, n_accesses)
+                .ok()
+                .and_then(|n_accesses| n_accesses.to_usize())
+                .unwrap_or_default()
+        }
+        _ => 0,
+    }
+}
+
 /// HintProcessor for Cairo 1 compiler hints.
 pub struct Cairo1HintProcessor {
     hints: HashMap<usize, Vec<Hint>>,
@@ -427,10 +442,9 @@ impl Cairo1HintProcessor {
         quotient: &CellRef,
         remainder: &CellRef,
     ) -> Result<(), HintError> {
-        let lhs_value = res_operand_get_val(vm, lhs)?.to_biguint();
-        let rhs_value = res_operand_get_val(vm, rhs)?.to_biguint();
-        let quotient_value = Felt252::from(&(&lhs_value / &rhs_value));
-        let remainder_value = Felt252::from(&(lhs_value % rhs_value));
+        let lhs_value = res_operand_get_val(vm, lhs)?;
+        let rhs_value = res_operand_get_val(vm, rhs)?;
+        let (quotient_value, remainder_value) = safe_div_rem(&lhs_value, &rhs_value)?;
         vm.insert_value(cell_ref_to_relocatable(quotient, vm)?, quotient_value)?;
         vm.insert_value(cell_ref_to_relocatable(remainder, vm)?, remainder_value)
             .map_err(HintError::from)
@@ -813,7 +827,7 @@ impl Cairo1HintProcessor {
             let end = as_relocatable(vm, end)?;
             while curr != end {
                 let value = vm.get_integer(curr)?;
-                if let Some(shortstring) = as_cairo_short_string(&value) {
+                if let Some(shortstring) = crate::utils::as_printable_short_string(&value) {
                     println!("[DEBUG]\t{shortstring: <31}\t(raw: {value: <31})");
                 } else {
                     println!("[DEBUG]\t{0: <31}\t(raw: {value: <31}) ", ' ');
@@ -1282,6 +1296,9 @@ impl HintProcessorLogic for Cairo1HintProcessor {
     ) -> Result<(), HintError> {
         let hints: &Vec<Hint> = hint_data.downcast_ref().ok_or(HintError::WrongHintData)?;
         for hint in hints {
+            if self.run_resources.charges_hint_iterations() {
+                self.run_resources.consume_steps(hint_iterations(vm, hint));
+            }
             self.execute(vm, exec_scopes, hint)?;
         }
         Ok(())
@@ -1297,6 +1314,10 @@ impl ResourceTracker for Cairo1HintProcessor {
         self.run_resources.consume_step()
     }
 
+    fn consume_steps(&mut self, n_steps: usize) {
+        self.run_resources.consume_steps(n_steps)
+    }
+
     fn get_n_steps(&self) -> Option<usize> {
         self.run_resources.get_n_steps()
     }
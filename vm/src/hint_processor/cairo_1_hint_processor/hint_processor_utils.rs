@@ -132,37 +132,3 @@ pub(crate) fn res_operand_get_val(
         }
     }
 }
-
-#[cfg(feature = "std")]
-pub(crate) fn as_cairo_short_string(value: &Felt252) -> Option<String> {
-    let mut as_string = String::default();
-    let mut is_end = false;
-    for byte in value
-        .to_bytes_be()
-        .into_iter()
-        .skip_while(num_traits::Zero::is_zero)
-    {
-        if byte == 0 {
-            is_end = true;
-        } else if is_end || !byte.is_ascii() {
-            return None;
-        } else {
-            as_string.push(byte as char);
-        }
-    }
-    Some(as_string)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn simple_as_cairo_short_string() {
-        // Values extracted from cairo book example
-        let s = "Hello, Scarb!";
-        let x = Felt252::from(5735816763073854913753904210465_u128);
-        assert!(s.is_ascii());
-        let cairo_string = as_cairo_short_string(&x).expect("call to as_cairo_short_string failed");
-        assert_eq!(cairo_string, s);
-    }
-}
@@ -1,9 +1,11 @@
-use crate::stdlib::{any::Any, boxed::Box, collections::HashMap, prelude::*};
+use crate::stdlib::{any::Any, boxed::Box, collections::HashMap, prelude::*, sync::Arc};
 
 use crate::any_box;
 use crate::serde::deserialize_program::ApTracking;
+use crate::serde::deserialize_program::Identifier;
 use crate::serde::deserialize_program::OffsetValue;
 use crate::serde::deserialize_program::Reference;
+use crate::serde::reference_expression::ReferenceExpression;
 use crate::types::exec_scope::ExecutionScopes;
 use crate::types::instruction::Register;
 use crate::types::relocatable::Relocatable;
@@ -48,9 +50,37 @@ pub trait HintProcessorLogic {
             code: hint_code.to_string(),
             ap_tracking: ap_tracking_data.clone(),
             ids_data: get_ids_data(reference_ids, references)?,
+            identifiers: Arc::default(),
         }))
     }
 
+    // Transforms hint data like compile_hint, also receiving the identifiers of the program.
+    // This is the method used by the vm to compile hints, its default implementation calls
+    // compile_hint and hands the identifiers to the resulting HintProcessorData, if any, so that
+    // hints can resolve the members of ids variables through them
+    fn compile_hint_with_identifiers(
+        &self,
+        hint_code: &str,
+        ap_tracking_data: &ApTracking,
+        reference_ids: &HashMap<String, usize>,
+        references: &[HintReference],
+        //Identifiers of the program, shared by all of its hints
+        identifiers: &Arc<HashMap<String, Identifier>>,
+    ) -> Result<Box<dyn Any>, VirtualMachineError> {
+        let mut hint_data =
+            self.compile_hint(hint_code, ap_tracking_data, reference_ids, references)?;
+        if let Some(hint_data) = hint_data.downcast_mut::<HintProcessorData>() {
+            hint_data.identifiers = identifiers.clone();
+        }
+        Ok(hint_data)
+    }
+
+    // Returns false if the program was compiled with a version newer than the ones whose hints are
+    // implemented by this processor, so that unknown hints can point at the compiler version
+    fn supports_compiler_version(&self, _compiler_version: &str) -> bool {
+        true
+    }
+
     #[cfg(feature = "extensive_hints")]
     // Executes the hint which's data is provided by a dynamic structure previously created by compile_hint
     // Also returns a map of hints to be loaded after the current hint is executed
@@ -107,6 +137,9 @@ pub struct HintReference {
     pub outer_dereference: bool,
     pub ap_tracking_data: Option<ApTracking>,
     pub cairo_type: Option<String>,
+    /// The parsed reference expression, only present when the offsets above can't represent it,
+    /// in which case it is used instead of them
+    pub expression: Option<Arc<ReferenceExpression>>,
 }
 
 impl HintReference {
@@ -118,6 +151,7 @@ impl HintReference {
             outer_dereference: true,
             inner_dereference: false,
             cairo_type: None,
+            expression: None,
         }
     }
 
@@ -135,6 +169,7 @@ impl HintReference {
             outer_dereference: dereference,
             inner_dereference: false,
             cairo_type: None,
+            expression: None,
         }
     }
 }
@@ -150,14 +185,19 @@ impl From<Reference> for HintReference {
             ap_tracking_data: match (
                 &reference.value_address.offset1,
                 &reference.value_address.offset2,
+                &reference.value_address.expression,
             ) {
-                (OffsetValue::Reference(Register::AP, _, _, _), _)
-                | (_, OffsetValue::Reference(Register::AP, _, _, _)) => {
+                (OffsetValue::Reference(Register::AP, _, _, _), _, _)
+                | (_, OffsetValue::Reference(Register::AP, _, _, _), _) => {
+                    Some(reference.ap_tracking_data.clone())
+                }
+                (_, _, Some(expression)) if expression.uses_ap() => {
                     Some(reference.ap_tracking_data.clone())
                 }
                 _ => None,
             },
             cairo_type: Some(reference.value_address.value_type.clone()),
+            expression: reference.value_address.expression.clone(),
         }
     }
 }
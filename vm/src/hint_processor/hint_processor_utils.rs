@@ -1,7 +1,10 @@
 use crate::stdlib::boxed::Box;
 
 use crate::{
-    serde::deserialize_program::{ApTracking, OffsetValue},
+    serde::{
+        deserialize_program::{ApTracking, OffsetValue},
+        reference_expression::ReferenceExpression,
+    },
     types::{
         errors::math_errors::MathError,
         instruction::Register,
@@ -58,6 +61,14 @@ pub fn get_maybe_relocatable_from_reference(
     hint_reference: &HintReference,
     ap_tracking: &ApTracking,
 ) -> Option<MaybeRelocatable> {
+    if let Some(expression) = &hint_reference.expression {
+        return evaluate_expression(
+            vm,
+            expression,
+            &hint_reference.ap_tracking_data,
+            ap_tracking,
+        );
+    }
     let offset1 = get_offset_value(
         vm,
         &hint_reference.offset1,
@@ -91,6 +102,15 @@ pub fn compute_addr_from_reference(
     vm: &VirtualMachine,
     ap_tracking: &ApTracking,
 ) -> Option<Relocatable> {
+    if let Some(expression) = &hint_reference.expression {
+        // The address of `[A]` is `A`, other expressions are their own address
+        let address = match expression {
+            ReferenceExpression::Deref(inner) => inner,
+            expression => expression,
+        };
+        return evaluate_expression(vm, address, &hint_reference.ap_tracking_data, ap_tracking)?
+            .get_relocatable();
+    }
     let offset1 = get_offset_value(
         vm,
         &hint_reference.offset1,
@@ -110,6 +130,35 @@ pub fn compute_addr_from_reference(
     val.get_relocatable()
 }
 
+/// Evaluates a reference expression with the current values of the registers and memory
+fn evaluate_expression(
+    vm: &VirtualMachine,
+    expression: &ReferenceExpression,
+    reference_ap_tracking: &Option<ApTracking>,
+    hint_ap_tracking: &ApTracking,
+) -> Option<MaybeRelocatable> {
+    let evaluate = |expression: &ReferenceExpression| {
+        evaluate_expression(vm, expression, reference_ap_tracking, hint_ap_tracking)
+    };
+    Some(match expression {
+        ReferenceExpression::Register(Register::FP) => vm.get_fp().into(),
+        ReferenceExpression::Register(Register::AP) => apply_ap_tracking_correction(
+            vm.get_ap(),
+            reference_ap_tracking.as_ref()?,
+            hint_ap_tracking,
+        )?
+        .into(),
+        ReferenceExpression::Immediate(value) => value.into(),
+        ReferenceExpression::Deref(inner) => vm.get_maybe(&evaluate(inner)?)?,
+        ReferenceExpression::Add(lhs, rhs) => evaluate(lhs)?.add(&evaluate(rhs)?).ok()?,
+        ReferenceExpression::Sub(lhs, rhs) => evaluate(lhs)?.sub(&evaluate(rhs)?).ok()?,
+        ReferenceExpression::Mul(lhs, rhs) => {
+            (evaluate(lhs)?.get_int()? * evaluate(rhs)?.get_int()?).into()
+        }
+        ReferenceExpression::Cast(inner, _) => evaluate(inner)?,
+    })
+}
+
 fn apply_ap_tracking_correction(
     ap: Relocatable,
     ref_ap_tracking: &ApTracking,
@@ -119,8 +168,13 @@ fn apply_ap_tracking_correction(
     if ref_ap_tracking.group != hint_ap_tracking.group {
         return None;
     }
-    let ap_diff = hint_ap_tracking.offset - ref_ap_tracking.offset;
-    (ap - ap_diff).ok()
+    // the reference may have been defined after the hint's position within the group (e.g. in
+    // inlined code), in which case ap hasn't yet reached the reference's offset
+    if hint_ap_tracking.offset >= ref_ap_tracking.offset {
+        (ap - (hint_ap_tracking.offset - ref_ap_tracking.offset)).ok()
+    } else {
+        (ap + (ref_ap_tracking.offset - hint_ap_tracking.offset)).ok()
+    }
 }
 
 //Tries to convert a Felt252 value to usize
@@ -169,7 +223,9 @@ fn get_offset_value(
 mod tests {
     use super::*;
 
-    use crate::{relocatable, utils::test_utils::*, vm::vm_memory::memory::Memory};
+    use crate::{
+        relocatable, stdlib::sync::Arc, utils::test_utils::*, vm::vm_memory::memory::Memory,
+    };
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
@@ -310,6 +366,42 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tracking_correction_ap_moved() {
+        let ref_ap_tracking = ApTracking {
+            group: 1,
+            offset: 2,
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 5,
+        };
+
+        assert_matches!(
+            apply_ap_tracking_correction(relocatable!(1, 7), &ref_ap_tracking, &hint_ap_tracking),
+            Some(relocatable!(1, 4))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tracking_correction_reference_ahead_of_hint() {
+        let ref_ap_tracking = ApTracking {
+            group: 1,
+            offset: 5,
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 2,
+        };
+
+        assert_matches!(
+            apply_ap_tracking_correction(relocatable!(1, 4), &ref_ap_tracking, &hint_ap_tracking),
+            Some(relocatable!(1, 7))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn tracking_correction_invalid_group() {
@@ -367,6 +459,7 @@ mod tests {
             inner_dereference: true,
             ap_tracking_data: Default::default(),
             cairo_type: None,
+            expression: None,
         };
 
         assert_eq!(
@@ -392,6 +485,24 @@ mod tests {
             inner_dereference: false,
             ap_tracking_data: Default::default(),
             cairo_type: None,
+            expression: None,
+        };
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &hint_ref, &ApTracking::new())
+                .expect("Unexpected get integer fail"),
+            Felt252::THREE
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_reference_expression_with_triple_deref() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 2), (0, 0)), ((0, 2), (0, 5)), ((0, 5), 3)];
+        let hint_ref = HintReference {
+            expression: ReferenceExpression::parse("[cast([[fp + 2] + 2], felt*)]").map(Arc::new),
+            ..HintReference::new_simple(0)
         };
 
         assert_eq!(
@@ -399,5 +510,69 @@ mod tests {
                 .expect("Unexpected get integer fail"),
             Felt252::THREE
         );
+        assert_eq!(
+            compute_addr_from_reference(&hint_ref, &vm, &ApTracking::new()),
+            Some(relocatable!(0, 5))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_reference_expression_with_operations() {
+        // [fp] * [fp + 1] - [ap + (-1)], with ap moved by 2 since the reference was defined
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 3), ((1, 1), 5), ((1, 2), 7)];
+        vm.set_ap(5);
+        let hint_ref = HintReference {
+            expression: ReferenceExpression::parse("cast([fp] * [fp + 1] - [ap + (-1)], felt)")
+                .map(Arc::new),
+            ap_tracking_data: Some(ApTracking {
+                group: 1,
+                offset: 1,
+            }),
+            ..HintReference::new_simple(0)
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 3,
+        };
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &hint_ref, &hint_ap_tracking)
+                .expect("Unexpected get integer fail"),
+            Felt252::from(8)
+        );
+        assert_matches!(
+            get_integer_from_reference(&vm, &hint_ref, &ApTracking::new()),
+            Err(HintError::UnknownIdentifierInternal)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_reference_defined_ahead_of_hint() {
+        // Inlined code may attach to a hint a reference whose ap offset is greater than the
+        // hint's own, the reference then points past the current ap
+        let mut vm = vm!();
+        vm.segments = segments![((1, 4), 9)];
+        vm.set_ap(2);
+        let hint_ref = HintReference {
+            expression: ReferenceExpression::parse("[cast(ap + 1, felt*)]").map(Arc::new),
+            ap_tracking_data: Some(ApTracking {
+                group: 2,
+                offset: 4,
+            }),
+            ..HintReference::new_simple(0)
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 2,
+            offset: 3,
+        };
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &hint_ref, &hint_ap_tracking)
+                .expect("Unexpected get integer fail"),
+            Felt252::from(9)
+        );
     }
 }
@@ -3,13 +3,19 @@
 //! ## Feature Flags
 //! - `std`: Enables usage of the [`std`] standard library. Enabled by default.
 //! - `test_utils`: Enables the following to help with tests (not enabled by default):
-//!    - [`Hooks`](crate::vm::hooks::Hooks) support for the [VirtualMachine](vm::vm_core::VirtualMachine);
+//!    - the `hooks` feature;
 //!    - the `print_*` family of hints;
 //!    - the `skip_next_instruction()` hints;
+//!    - [`crypto::sign`] to generate valid ECDSA signatures for the signature builtin;
+//!    - the [`testing`] helpers ([`MemoryBuilder`](testing::MemoryBuilder), [`vm_with_memory!`]) to set up a VM in unit tests, and the [`differential`](testing::differential) harness to compare hint processors;
 //!    - implementations of [`arbitrary::Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/) for some structs.
 //! - `cairo-1-hints`: Enable hints that were introduced in Cairo 1. Not enabled by default.
 //! - `cairo-0-secp-hints`: Enable secp hints that were introduced in Cairo 0. Not enabled by default.
 //! - `cairo-0-data-availability-hints`: Enable data availability hints that were introduced in Cairo 0. Not enabled by default.
+//! - `cairo-0-bootloader-hints`: Enable the simple bootloader hints that load, hash and call the program tasks of [bootloader] inputs and configure the output pages from their fact topologies. Tasks with hints need `extensive_hints`. Not enabled by default.
+//! - `hooks`: Enables [`Hooks`](crate::vm::hooks::Hooks) support for the [VirtualMachine](vm::vm_core::VirtualMachine), including the instruction-level step hooks used by profiling and coverage tools. Not enabled by default.
+//! - `keccak-asm`: Use the ARMv8 SHA3 instructions in [`crypto::keccak_f1600`] when available. Not enabled by default.
+//! - `blake2s-vectorized`: Use the row-wise implementation of [`crypto::blake2s_compress`], which the compiler lowers to SIMD instructions. Not enabled by default.
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![deny(warnings)]
@@ -56,11 +62,19 @@ pub mod stdlib {
 
 pub mod air_private_input;
 pub mod air_public_input;
+#[cfg(feature = "std")]
+pub mod batch_run;
+pub mod bootloader;
 pub mod cairo_run;
+pub mod coverage;
+pub mod crypto;
 pub mod hint_processor;
 pub mod math_utils;
+pub mod prelude;
 pub mod program_hash;
 pub mod serde;
+#[cfg(feature = "test_utils")]
+pub mod testing;
 pub mod types;
 pub mod utils;
 pub mod vm;
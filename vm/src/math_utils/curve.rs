@@ -0,0 +1,202 @@
+//! Arithmetic over elliptic curves in short Weierstrass form, y^2 = x^3 + a*x + b (mod p).
+//!
+//! Points are given in affine form (x, y), as in the rest of [`crate::math_utils`].
+
+use core::str::FromStr;
+
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Num, Zero};
+
+use super::{div_mod, ec_double_slope, line_slope};
+use crate::types::errors::math_errors::MathError;
+
+/// An elliptic curve y^2 = x^3 + a*x + b over GF(p), whose group has order `n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeierstrassCurve {
+    pub p: BigInt,
+    pub a: BigInt,
+    pub b: BigInt,
+    pub n: BigInt,
+}
+
+lazy_static! {
+    /// The curve used by the `cairo_secp` library, see `starkware.cairo.common.cairo_secp.secp_utils`.
+    pub static ref SECP256K1: WeierstrassCurve = WeierstrassCurve {
+        // 2**256 - 2**32 - 2**9 - 2**8 - 2**7 - 2**6 - 2**4 - 1
+        p: BigInt::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007908834671663"
+        )
+        .unwrap(),
+        a: BigInt::zero(),
+        b: BigInt::from(7),
+        // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+        n: BigInt::from_str(
+            "115792089237316195423570985008687907852837564279074904382605163141518161494337"
+        )
+        .unwrap(),
+    };
+
+    /// See `starkware.cairo.common.cairo_secp.secp256r1_utils`.
+    pub static ref SECP256R1: WeierstrassCurve = WeierstrassCurve {
+        // 2**256 - 2**224 + 2**192 + 2**96 - 1
+        p: BigInt::from_str(
+            "115792089210356248762697446949407573530086143415290314195533631308867097853951"
+        )
+        .unwrap(),
+        // 0xFFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC
+        a: BigInt::from_str(
+            "115792089210356248762697446949407573530086143415290314195533631308867097853948"
+        )
+        .unwrap(),
+        b: BigInt::from_str_radix(
+            "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+            16,
+        )
+        .unwrap(),
+        // 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
+        n: BigInt::from_str(
+            "115792089210356248762697446949407573529996955224135760342422259061068512044369"
+        )
+        .unwrap(),
+    };
+}
+
+impl WeierstrassCurve {
+    pub fn new(p: BigInt, a: BigInt, b: BigInt, n: BigInt) -> Self {
+        Self { p, a, b, n }
+    }
+
+    /// Computes x^3 + a*x + b (mod p).
+    pub fn y_squared(&self, x: &BigInt) -> BigInt {
+        (x.modpow(&BigInt::from(3), &self.p) + &self.a * x + &self.b).mod_floor(&self.p)
+    }
+
+    /// Returns whether the point satisfies the curve equation.
+    pub fn contains(&self, point: &(BigInt, BigInt)) -> bool {
+        (&point.1 * &point.1).mod_floor(&self.p) == self.y_squared(&point.0)
+    }
+
+    /// See [line_slope].
+    pub fn line_slope(
+        &self,
+        point_a: &(BigInt, BigInt),
+        point_b: &(BigInt, BigInt),
+    ) -> Result<BigInt, MathError> {
+        line_slope(point_a, point_b, &self.p)
+    }
+
+    /// See [ec_double_slope].
+    pub fn double_slope(&self, point: &(BigInt, BigInt)) -> Result<BigInt, MathError> {
+        ec_double_slope(point, &self.a, &self.p)
+    }
+
+    /// Adds two points with different x coordinates.
+    pub fn add(
+        &self,
+        point_a: &(BigInt, BigInt),
+        point_b: &(BigInt, BigInt),
+    ) -> Result<(BigInt, BigInt), MathError> {
+        let slope = self.line_slope(point_a, point_b)?;
+        let x = ec_add_new_x(&slope, &point_a.0, &point_b.0, &self.p);
+        let y = ec_new_y(&slope, point_a, &x, &self.p);
+        Ok((x, y))
+    }
+
+    /// Doubles a point with y != 0.
+    pub fn double(&self, point: &(BigInt, BigInt)) -> Result<(BigInt, BigInt), MathError> {
+        let slope = self.double_slope(point)?;
+        let x = ec_double_new_x(&slope, &point.0, &self.p);
+        let y = ec_new_y(&slope, point, &x, &self.p);
+        Ok((x, y))
+    }
+
+    /// Computes a / b (mod n), as done when verifying signatures.
+    pub fn div_mod_n(&self, a: &BigInt, b: &BigInt) -> Result<BigInt, MathError> {
+        div_mod(a, b, &self.n)
+    }
+}
+
+/// Computes the x coordinate of the sum of two points given the slope of the line connecting
+/// them: slope^2 - x0 - x1 (mod p).
+pub fn ec_add_new_x(slope: &BigInt, x0: &BigInt, x1: &BigInt, prime: &BigInt) -> BigInt {
+    (slope * slope - x0 - x1).mod_floor(prime)
+}
+
+/// Computes the x coordinate of the double of a point given the slope of the curve at it:
+/// slope^2 - 2*x (mod p).
+pub fn ec_double_new_x(slope: &BigInt, x: &BigInt, prime: &BigInt) -> BigInt {
+    (slope * slope - (x << 1u32)).mod_floor(prime)
+}
+
+/// Computes the y coordinate of the result of adding or doubling, given the slope used,
+/// the first operand and the new x coordinate: slope * (x0 - new_x) - y0 (mod p).
+pub fn ec_new_y(
+    slope: &BigInt,
+    point: &(BigInt, BigInt),
+    new_x: &BigInt,
+    prime: &BigInt,
+) -> BigInt {
+    (slope * (&point.0 - new_x) - &point.1).mod_floor(prime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math_utils::{ec_add, ec_double};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn secp256k1_generator() -> (BigInt, BigInt) {
+        (
+            BigInt::from_str_radix(
+                "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                16,
+            )
+            .unwrap(),
+            BigInt::from_str_radix(
+                "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+                16,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp256k1_double_and_add_stay_on_curve() {
+        let g = secp256k1_generator();
+        assert!(SECP256K1.contains(&g));
+        let g2 = SECP256K1.double(&g).unwrap();
+        assert!(SECP256K1.contains(&g2));
+        assert_eq!(
+            g2,
+            ec_double(g.clone(), &SECP256K1.a, &SECP256K1.p).unwrap()
+        );
+        let g3 = SECP256K1.add(&g2, &g).unwrap();
+        assert!(SECP256K1.contains(&g3));
+        assert_eq!(g3, ec_add(g2, g, &SECP256K1.p).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp256r1_generator_on_curve() {
+        let g = (
+            BigInt::from_str_radix(
+                "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+                16,
+            )
+            .unwrap(),
+            BigInt::from_str_radix(
+                "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+                16,
+            )
+            .unwrap(),
+        );
+        assert!(SECP256R1.contains(&g));
+        assert!(!SECP256K1.contains(&g));
+        assert!(SECP256R1.contains(&SECP256R1.double(&g).unwrap()));
+    }
+}
@@ -1,5 +1,13 @@
+//! Math utilities used across the VM, hints and builtins.
+//!
+//! Most functions come in two flavours: a [`BigInt`] version that works over any field GF(p),
+//! and a [`Felt252`] version that works over the Cairo field (avoiding conversions when the
+//! operands already are felts).
+
+pub mod curve;
 mod is_prime;
 
+/// Probabilistic primality test (Miller-Rabin).
 pub use is_prime::is_prime;
 
 use core::cmp::min;
@@ -96,8 +104,20 @@ pub fn isqrt(n: &BigUint) -> Result<BigUint, MathError> {
 }
 
 /// Performs integer division between x and y; fails if x is not divisible by y.
-pub fn safe_div(x: &Felt252, y: &Felt252) -> Result<Felt252, MathError> {
-    let (q, r) = x.div_rem(&y.try_into().map_err(|_| MathError::DividedByZero)?);
+///
+/// # Errors
+/// - [MathError::DividedByZeroFelt] if `y` is zero.
+/// - [MathError::SafeDivFail] if `x` is not divisible by `y`.
+///
+/// # Examples
+///
+/// ```
+/// # use cairo_vm::{Felt252, math_utils::safe_div_felt};
+/// assert_eq!(safe_div_felt(&Felt252::from(10), &Felt252::from(5)), Ok(Felt252::from(2)));
+/// assert!(safe_div_felt(&Felt252::from(10), &Felt252::from(3)).is_err());
+/// ```
+pub fn safe_div_felt(x: &Felt252, y: &Felt252) -> Result<Felt252, MathError> {
+    let (q, r) = safe_div_rem(x, y)?;
 
     if !r.is_zero() {
         Err(MathError::SafeDivFail(Box::new((*x, *y))))
@@ -106,6 +126,31 @@ pub fn safe_div(x: &Felt252, y: &Felt252) -> Result<Felt252, MathError> {
     }
 }
 
+/// Same as [safe_div_felt].
+#[deprecated(note = "use `safe_div_felt` instead")]
+pub fn safe_div(x: &Felt252, y: &Felt252) -> Result<Felt252, MathError> {
+    safe_div_felt(x, y)
+}
+
+/// Computes the quotient and remainder of the integer division between x and y.
+///
+/// # Errors
+/// - [MathError::DividedByZeroFelt] if `y` is zero.
+pub fn safe_div_rem(x: &Felt252, y: &Felt252) -> Result<(Felt252, Felt252), MathError> {
+    let y = y
+        .try_into()
+        .map_err(|_| MathError::DividedByZeroFelt(Box::new(*x)))?;
+    Ok(x.div_rem(&y))
+}
+
+/// Computes the remainder of the integer division between x and y.
+///
+/// # Errors
+/// - [MathError::DividedByZeroFelt] if `y` is zero.
+pub fn safe_mod(x: &Felt252, y: &Felt252) -> Result<Felt252, MathError> {
+    Ok(safe_div_rem(x, y)?.1)
+}
+
 /// Performs integer division between x and y; fails if x is not divisible by y.
 pub fn safe_div_bigint(x: &BigInt, y: &BigInt) -> Result<BigInt, MathError> {
     if y.is_zero() {
@@ -125,9 +170,13 @@ pub fn safe_div_bigint(x: &BigInt, y: &BigInt) -> Result<BigInt, MathError> {
 }
 
 /// Performs integer division between x and y; fails if x is not divisible by y.
+///
+/// # Errors
+/// - [MathError::DividedByZeroUsize] if `y` is zero.
+/// - [MathError::SafeDivFailUsize] if `x` is not divisible by `y`.
 pub fn safe_div_usize(x: usize, y: usize) -> Result<usize, MathError> {
     if y.is_zero() {
-        return Err(MathError::DividedByZero);
+        return Err(MathError::DividedByZeroUsize(x));
     }
 
     let (q, r) = x.div_mod_floor(&y);
@@ -183,7 +232,19 @@ fn igcdex(num_a: &BigInt, num_b: &BigInt) -> (BigInt, BigInt, BigInt) {
     }
 }
 
-///Finds a nonnegative integer x < p such that (m * x) % p == n.
+/// Finds a nonnegative integer x < p such that (m * x) % p == n.
+///
+/// # Errors
+/// - [MathError::DivModIgcdexNotZero] if `m` is not invertible modulo `p`.
+///
+/// # Examples
+///
+/// ```
+/// # use cairo_vm::math_utils::div_mod;
+/// # use num_bigint::BigInt;
+/// let x = div_mod(&BigInt::from(3), &BigInt::from(2), &BigInt::from(7)).unwrap();
+/// assert_eq!(x, BigInt::from(5));
+/// ```
 pub fn div_mod(n: &BigInt, m: &BigInt, p: &BigInt) -> Result<BigInt, MathError> {
     let (a, _, c) = igcdex(m, p);
     if !c.is_one() {
@@ -196,6 +257,24 @@ pub fn div_mod(n: &BigInt, m: &BigInt, p: &BigInt) -> Result<BigInt, MathError>
     Ok((n * a).mod_floor(p))
 }
 
+/// Finds x such that m * x == n over the Cairo field.
+///
+/// This is the [`Felt252`] version of [div_mod], with `p` being the Cairo prime.
+///
+/// # Errors
+/// - [MathError::DividedByZero] if `m` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use cairo_vm::{Felt252, math_utils::div_mod_felt};
+/// let x = div_mod_felt(&Felt252::from(3), &Felt252::from(2)).unwrap();
+/// assert_eq!(x * Felt252::from(2), Felt252::from(3));
+/// ```
+pub fn div_mod_felt(n: &Felt252, m: &Felt252) -> Result<Felt252, MathError> {
+    Ok(n.field_div(&m.try_into().map_err(|_| MathError::DividedByZero)?))
+}
+
 pub(crate) fn div_mod_unsigned(
     n: &BigUint,
     m: &BigUint,
@@ -210,6 +289,11 @@ pub(crate) fn div_mod_unsigned(
     .map(|i| i.to_biguint().unwrap())
 }
 
+/// Adds two points on an elliptic curve over the field GF(p).
+/// Assumes the points are given in affine form (x, y) and have different x coordinates.
+///
+/// # Errors
+/// - [MathError::DivModIgcdexNotZero] if the slope between the points can't be computed.
 pub fn ec_add(
     point_a: (BigInt, BigInt),
     point_b: (BigInt, BigInt),
@@ -236,8 +320,11 @@ pub fn line_slope(
     )
 }
 
-///  Doubles a point on an elliptic curve with the equation y^2 = x^3 + alpha*x + beta mod p.
+/// Doubles a point on an elliptic curve with the equation y^2 = x^3 + alpha*x + beta mod p.
 /// Assumes the point is given in affine form (x, y) and has y != 0.
+///
+/// # Errors
+/// - [MathError::DivModIgcdexNotZero] if the slope at the point can't be computed.
 pub fn ec_double(
     point: (BigInt, BigInt),
     alpha: &BigInt,
@@ -248,6 +335,7 @@ pub fn ec_double(
     let y = (m * (point.0 - &x) - point.1).mod_floor(prime);
     Ok((x, y))
 }
+
 /// Computes the slope of an elliptic curve with the equation y^2 = x^3 + alpha*x + beta mod p, at
 /// the given point.
 /// Assumes the point is given in affine form (x, y) and has y != 0.
@@ -264,8 +352,61 @@ pub fn ec_double_slope(
     )
 }
 
+/// Adds two points on an elliptic curve over the Cairo field.
+///
+/// This is the [`Felt252`] version of [ec_add]. The points are given in affine form (x, y).
+///
+/// # Errors
+/// - [MathError::EcAddSameXCoordinate] if both points have the same x coordinate.
+pub fn ec_add_felt(
+    point_a: (Felt252, Felt252),
+    point_b: (Felt252, Felt252),
+) -> Result<(Felt252, Felt252), MathError> {
+    let m = div_mod_felt(&(point_a.1 - point_b.1), &(point_a.0 - point_b.0))
+        .map_err(|_| MathError::EcAddSameXCoordinate(Box::new((point_a, point_b))))?;
+    let x = m * m - point_a.0 - point_b.0;
+    let y = m * (point_a.0 - x) - point_a.1;
+    Ok((x, y))
+}
+
+/// Doubles a point on an elliptic curve with the equation y^2 = x^3 + alpha*x + beta over the
+/// Cairo field.
+///
+/// This is the [`Felt252`] version of [ec_double]. The point is given in affine form (x, y).
+///
+/// # Errors
+/// - [MathError::EcDoubleZeroY] if the point's y coordinate is zero.
+pub fn ec_double_felt(
+    point: (Felt252, Felt252),
+    alpha: &Felt252,
+) -> Result<(Felt252, Felt252), MathError> {
+    let m = div_mod_felt(
+        &(Felt252::THREE * point.0 * point.0 + alpha),
+        &(Felt252::TWO * point.1),
+    )
+    .map_err(|_| MathError::EcDoubleZeroY(Box::new(point)))?;
+    let x = m * m - Felt252::TWO * point.0;
+    let y = m * (point.0 - x) - point.1;
+    Ok((x, y))
+}
+
+/// Computes a square root of `a` modulo the prime `p`.
+///
+/// Returns `None` if `p` is not prime or if `a` is not a quadratic residue modulo `p`.
+/// Uses the closed formulas for `p % 4 == 3` and `p % 8 == 5`, falling back to Tonelli-Shanks.
+/// The closed formulas return the smaller of the two roots, but the other paths may return
+/// either of them.
+///
+/// # Examples
+///
+/// ```
+/// # use cairo_vm::math_utils::sqrt_mod_p;
+/// # use num_bigint::BigUint;
+/// assert_eq!(sqrt_mod_p(&BigUint::from(4_u32), &BigUint::from(7_u32)), Some(BigUint::from(2_u32)));
+/// assert_eq!(sqrt_mod_p(&BigUint::from(3_u32), &BigUint::from(7_u32)), None);
+/// ```
 // Adapted from sympy _sqrt_prime_power with k == 1
-pub fn sqrt_prime_power(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+pub fn sqrt_mod_p(a: &BigUint, p: &BigUint) -> Option<BigUint> {
     if p.is_zero() || !is_prime(p) {
         return None;
     }
@@ -300,6 +441,12 @@ pub fn sqrt_prime_power(a: &BigUint, p: &BigUint) -> Option<BigUint> {
     Some(sqrt_tonelli_shanks(&a, p))
 }
 
+/// Same as [sqrt_mod_p], kept for backwards compatibility.
+#[deprecated(note = "use `sqrt_mod_p` instead")]
+pub fn sqrt_prime_power(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    sqrt_mod_p(a, p)
+}
+
 fn sqrt_tonelli_shanks(n: &BigUint, prime: &BigUint) -> BigUint {
     // Based on Tonelli-Shanks' algorithm for finding square roots
     // and sympy's library implementation of said algorithm.
@@ -368,10 +515,15 @@ fn legendre_symbol(a: &BigUint, p: &BigUint) -> i8 {
     }
 }
 
+/// Returns whether `a` is a quadratic residue modulo the prime `p` (Euler's criterion).
+///
+/// # Errors
+///
+/// Returns [`MathError::IsQuadResidueZeroPrime`] if `p` is zero.
 // Ported from sympy implementation
 // Simplified as a & p are nonnegative
 // Asumes p is a prime number
-pub(crate) fn is_quad_residue(a: &BigUint, p: &BigUint) -> Result<bool, MathError> {
+pub fn is_quad_residue(a: &BigUint, p: &BigUint) -> Result<bool, MathError> {
     if p.is_zero() {
         return Err(MathError::IsQuadResidueZeroPrime);
     }
@@ -385,9 +537,22 @@ pub(crate) fn is_quad_residue(a: &BigUint, p: &BigUint) -> Result<bool, MathErro
     )
 }
 
+/// Returns whether `a` is a quadratic residue in the Cairo field.
+pub fn is_quad_residue_felt(a: &Felt252) -> bool {
+    a == &Felt252::ZERO
+        || a == &Felt252::ONE
+        || a.pow_felt(&Felt252::MAX.div_rem(pow2_const_nz(1)).0) == Felt252::ONE
+}
+
+/// Returns whether `n`, interpreted as an unsigned integer, is prime.
+pub fn is_prime_felt(n: &Felt252) -> bool {
+    is_prime(&n.to_biguint())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::felt_str;
     use crate::utils::test_utils::*;
     use crate::utils::CAIRO_PRIME;
     use assert_matches::assert_matches;
@@ -481,7 +646,7 @@ mod tests {
     fn compute_safe_div() {
         let x = Felt252::from(26);
         let y = Felt252::from(13);
-        assert_matches!(safe_div(&x, &y), Ok(i) if i == Felt252::from(2));
+        assert_matches!(safe_div_felt(&x, &y), Ok(i) if i == Felt252::from(2));
     }
 
     #[test]
@@ -489,7 +654,7 @@ mod tests {
     fn compute_safe_div_non_divisor() {
         let x = Felt252::from(25);
         let y = Felt252::from(4);
-        let result = safe_div(&x, &y);
+        let result = safe_div_felt(&x, &y);
         assert_matches!(
             result,
             Err(MathError::SafeDivFail(bx)) if *bx == (Felt252::from(25), Felt252::from(4)));
@@ -500,8 +665,24 @@ mod tests {
     fn compute_safe_div_by_zero() {
         let x = Felt252::from(25);
         let y = Felt252::ZERO;
-        let result = safe_div(&x, &y);
-        assert_matches!(result, Err(MathError::DividedByZero));
+        let result = safe_div_felt(&x, &y);
+        assert_matches!(result, Err(MathError::DividedByZeroFelt(bx)) if *bx == x);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_safe_div_rem_and_mod() {
+        let x = Felt252::from(25);
+        let y = Felt252::from(7);
+        assert_eq!(
+            safe_div_rem(&x, &y),
+            Ok((Felt252::from(3), Felt252::from(4)))
+        );
+        assert_eq!(safe_mod(&x, &y), Ok(Felt252::from(4)));
+        assert_matches!(
+            safe_mod(&x, &Felt252::ZERO),
+            Err(MathError::DividedByZeroFelt(bx)) if *bx == x
+        );
     }
 
     #[test]
@@ -522,7 +703,10 @@ mod tests {
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn compute_safe_div_usize_by_zero() {
-        assert_matches!(safe_div_usize(25, 0), Err(MathError::DividedByZero));
+        assert_matches!(
+            safe_div_usize(25, 0),
+            Err(MathError::DividedByZeroUsize(25))
+        );
     }
 
     #[test]
@@ -706,6 +890,94 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_double_felt_for_valid_point() {
+        let point = (
+            felt_str!(
+                "1937407885261715145522756206040455121546447384489085099828343908348117672673"
+            ),
+            felt_str!(
+                "2010355627224183802477187221870580930152258042445852905639855522404179702985"
+            ),
+        );
+        assert_eq!(
+            ec_double_felt(point, &Felt252::ONE),
+            Ok((
+                felt_str!(
+                    "58460926014232092148191979591712815229424797874927791614218178721848875644"
+                ),
+                felt_str!(
+                    "1065613861227134732854284722490492186040898336012372352512913425790457998694"
+                )
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_double_felt_zero_y() {
+        let point = (Felt252::from(5), Felt252::ZERO);
+        assert_eq!(
+            ec_double_felt(point, &Felt252::ONE),
+            Err(MathError::EcDoubleZeroY(Box::new(point)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_add_felt_for_valid_points() {
+        let point_a = (
+            felt_str!(
+                "3139037544796708144595053687182055617920475701120786241351436619796497072089"
+            ),
+            felt_str!(
+                "2119589567875935397690285099786081818522144748339117565577200220779667999801"
+            ),
+        );
+        let point_b = (
+            felt_str!(
+                "3324833730090626974525872402899302150520188025637965566623476530814354734325"
+            ),
+            felt_str!(
+                "3147007486456030910661996439995670279305852583596209647900952752170983517249"
+            ),
+        );
+        assert_eq!(
+            ec_add_felt(point_a, point_b),
+            Ok((
+                felt_str!(
+                    "1183418161532233795704555250127335895546712857142554564893196731153957537489"
+                ),
+                felt_str!(
+                    "1938007580204102038458825306058547644691739966277761828724036384003180924526"
+                )
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn calculate_ec_add_felt_same_x_coordinate() {
+        let point_a = (Felt252::from(5), Felt252::from(3));
+        let point_b = (Felt252::from(5), Felt252::from(7));
+        assert_eq!(
+            ec_add_felt(point_a, point_b),
+            Err(MathError::EcAddSameXCoordinate(Box::new((
+                point_a, point_b
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn div_mod_felt_by_zero() {
+        assert_eq!(
+            div_mod_felt(&Felt252::ONE, &Felt252::ZERO),
+            Err(MathError::DividedByZero)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn calculate_ec_add_for_valid_points_b() {
@@ -812,6 +1084,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power() {
         let n: BigUint = 25_u32.into();
         let p: BigUint = 18446744069414584321_u128.into();
@@ -820,6 +1093,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power_p_is_zero() {
         let n = BigUint::one();
         let p: BigUint = BigUint::zero();
@@ -828,6 +1102,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power_non_prime() {
         let p: BigUint = BigUint::from_bytes_be(&[
             69, 15, 232, 82, 215, 167, 38, 143, 173, 94, 133, 111, 1, 2, 182, 229, 110, 113, 76, 0,
@@ -843,6 +1118,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power_none() {
         let n: BigUint = 10_u32.into();
         let p: BigUint = 602_u32.into();
@@ -851,6 +1127,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power_prime_two() {
         let n: BigUint = 25_u32.into();
         let p: BigUint = 2_u32.into();
@@ -859,6 +1136,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power_prime_mod_8_is_5_sign_not_one() {
         let n: BigUint = 676_u32.into();
         let p: BigUint = 9956234341095173_u64.into();
@@ -870,6 +1148,7 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[allow(deprecated)]
     fn test_sqrt_prime_power_prime_mod_8_is_5_sign_is_one() {
         let n: BigUint = 130283432663_u64.into();
         let p: BigUint = 743900351477_u64.into();
@@ -879,6 +1158,31 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_sqrt_mod_p_p_mod_4_is_3() {
+        let p = BigUint::from(19_u32);
+        assert_eq!(sqrt_mod_p(&BigUint::from(17_u32), &p), Some(6_u32.into()));
+        assert_eq!(sqrt_mod_p(&BigUint::from(2_u32), &p), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_sqrt_mod_p_tonelli_shanks() {
+        // 17 % 8 == 1, so neither closed formula applies
+        let p = BigUint::from(17_u32);
+        assert_eq!(sqrt_mod_p(&BigUint::from(8_u32), &p), Some(5_u32.into()));
+        assert_eq!(sqrt_mod_p(&BigUint::from(3_u32), &p), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_is_prime_felt() {
+        assert!(is_prime_felt(&Felt252::from(7919)));
+        assert!(!is_prime_felt(&Felt252::from(7917)));
+        assert!(is_prime_felt(&Felt252::from(2147483647)));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_legendre_symbol_zero() {
@@ -976,7 +1280,9 @@ mod tests {
             // Generate a prime here instead of relying on y, otherwise y may never be a prime number
             let p : &BigUint = &RandPrime::gen_prime(&mut rng, 384,  None);
             let x_sq = x * x;
-            if let Some(sqrt) = sqrt_prime_power(&x_sq, p) {
+            #[allow(deprecated)]
+            let sqrt = sqrt_prime_power(&x_sq, p);
+            if let Some(sqrt) = sqrt {
                 if &sqrt != x {
                     prop_assert_eq!(&(p - sqrt), x);
                 } else {
@@ -0,0 +1,42 @@
+//! Re-exports of the types most commonly needed to run programs with the VM.
+//!
+//! ```
+//! use cairo_vm::prelude::*;
+//! ```
+//!
+//! These paths are kept stable across internal reorganizations of the crate.
+
+pub use crate::{
+    cairo_run::{cairo_run, cairo_run_program, CairoRunConfig},
+    hint_processor::{
+        builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+        hint_processor_definition::{HintProcessor, HintProcessorLogic, HintReference},
+    },
+    types::{
+        builtin_name::BuiltinName,
+        errors::{math_errors::MathError, program_errors::ProgramError},
+        exec_scope::ExecutionScopes,
+        layout_name::LayoutName,
+        program::Program,
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        context::run_context::RegisterState,
+        errors::{
+            cairo_run_errors::CairoRunError,
+            hint_errors::{HintError, HintResult},
+            memory_errors::MemoryError,
+            runner_errors::{RunnerError, RunnerResult},
+            vm_errors::{VirtualMachineError, VmResult},
+            vm_exception::VmException,
+        },
+        runners::cairo_runner::{
+            CairoArg, CairoRunner, ExecutionResources, RunResources, RunnerConfig, RunnerMode,
+        },
+        vm_core::{VirtualMachine, VmConfig},
+    },
+    Felt252,
+};
+
+#[cfg(feature = "cairo-1-hints")]
+pub use crate::hint_processor::cairo_1_hint_processor::hint_processor::Cairo1HintProcessor;
@@ -1,9 +1,10 @@
-use starknet_crypto::pedersen_hash;
+use crate::crypto::{pedersen_hash, poseidon_hash_many};
 
 use crate::Felt252;
 
 use crate::stdlib::vec::Vec;
 use crate::types::builtin_name::BuiltinName;
+use crate::types::errors::program_errors::ProgramError;
 use crate::types::relocatable::MaybeRelocatable;
 use crate::vm::runners::cairo_pie::StrippedProgram;
 
@@ -27,6 +28,9 @@ pub enum ProgramHashError {
 
     #[error("Invalid program data: data contains relocatable(s)")]
     InvalidProgramData,
+
+    #[error(transparent)]
+    Program(#[from] ProgramError),
 }
 
 /// Computes a hash chain over the data, in the following order:
@@ -66,51 +70,55 @@ fn maybe_relocatable_to_field_element(
         .ok_or(ProgramHashError::InvalidProgramData)
 }
 
-/// Computes the Pedersen hash of a program.
-/// [(cairo_lang reference)](https://github.com/starkware-libs/cairo-lang/blob/efa9648f57568aad8f8a13fbf027d2de7c63c2c0/src/starkware/cairo/bootloaders/hash_program.py#L11)
-pub fn compute_program_hash_chain(
+/// Builds the data hashed into a program's hash: the program header (bootloader version, main
+/// and number of builtins), followed by the builtin names and the program's code.
+fn program_hash_data(
     program: &StrippedProgram,
     bootloader_version: usize,
-) -> Result<Felt252, ProgramHashError> {
-    let program_main = program.main;
-    let program_main = Felt252::from(program_main);
-
-    // Convert builtin names to field elements
-    let builtin_list: Result<Vec<Felt252>, _> = program
-        .builtins
-        .iter()
-        .map(builtin_name_to_field_element)
-        .collect();
-    let builtin_list = builtin_list?;
-
-    let program_header = vec![
+) -> Result<Vec<Felt252>, ProgramHashError> {
+    let program_header = [
         Felt252::from(bootloader_version),
-        program_main,
+        Felt252::from(program.main),
         Felt252::from(program.builtins.len()),
     ];
 
-    let program_data: Result<Vec<_>, _> = program
-        .data
-        .iter()
-        .map(maybe_relocatable_to_field_element)
-        .collect();
-    let program_data = program_data?;
-
-    let data_chain_len = program_header.len() + builtin_list.len() + program_data.len();
-    let data_chain_len_vec = vec![Felt252::from(data_chain_len)];
-
-    // Prepare a chain of iterators to feed to the hash function
-    let data_chain = [
-        &data_chain_len_vec,
-        &program_header,
-        &builtin_list,
-        &program_data,
-    ];
+    let mut data = program_header.to_vec();
+    for builtin in program.builtins.iter() {
+        data.push(builtin_name_to_field_element(builtin)?);
+    }
+    for value in program.data.iter() {
+        data.push(maybe_relocatable_to_field_element(value)?);
+    }
+    Ok(data)
+}
 
-    let hash = compute_hash_chain(data_chain.iter().flat_map(|&v| v.iter()), pedersen_hash)?;
+/// Computes the Pedersen hash of a program.
+/// [(cairo_lang reference)](https://github.com/starkware-libs/cairo-lang/blob/efa9648f57568aad8f8a13fbf027d2de7c63c2c0/src/starkware/cairo/bootloaders/hash_program.py#L11)
+pub fn compute_program_hash_chain(
+    program: &StrippedProgram,
+    bootloader_version: usize,
+) -> Result<Felt252, ProgramHashError> {
+    let program_data = program_hash_data(program, bootloader_version)?;
+    let data_chain_len = [Felt252::from(program_data.len())];
+
+    let hash = compute_hash_chain(
+        data_chain_len.iter().chain(program_data.iter()),
+        pedersen_hash,
+    )?;
     Ok(hash)
 }
 
+/// Computes the Poseidon hash of a program.
+/// Unlike the Pedersen hash chain, the hashed data is not prefixed by its length.
+/// [(cairo_lang reference)](https://github.com/starkware-libs/cairo-lang/blob/efa9648f57568aad8f8a13fbf027d2de7c63c2c0/src/starkware/cairo/bootloaders/hash_program.py#L11)
+pub fn compute_program_hash_poseidon(
+    program: &StrippedProgram,
+    bootloader_version: usize,
+) -> Result<Felt252, ProgramHashError> {
+    let program_data = program_hash_data(program, bootloader_version)?;
+    Ok(poseidon_hash_many(&program_data))
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "std")]
@@ -119,6 +127,21 @@ mod tests {
     use starknet_crypto::pedersen_hash;
 
     use super::*;
+    use crate::types::relocatable::Relocatable;
+    use assert_matches::assert_matches;
+
+    fn stripped_program() -> StrippedProgram {
+        StrippedProgram {
+            data: vec![
+                MaybeRelocatable::from(Felt252::from(0x40780017fff7fffu64)),
+                MaybeRelocatable::from(Felt252::from(2)),
+                MaybeRelocatable::from(Felt252::from(0x208b7fff7fff7ffeu64)),
+            ],
+            builtins: vec![BuiltinName::output, BuiltinName::pedersen],
+            main: 1,
+            prime: (),
+        }
+    }
 
     #[test]
     fn test_compute_hash_chain() {
@@ -137,32 +160,104 @@ mod tests {
         assert_eq!(computed_hash, expected_hash);
     }
 
+    #[test]
+    fn test_compute_program_hash_chain_hashes_length_prefixed_data() {
+        let expected_data = [
+            Felt252::from(8),
+            Felt252::from(1),
+            Felt252::from(1),
+            Felt252::from(2),
+            Felt252::from_bytes_be_slice(b"output"),
+            Felt252::from_bytes_be_slice(b"pedersen"),
+            Felt252::from(0x40780017fff7fffu64),
+            Felt252::from(2),
+            Felt252::from(0x208b7fff7fff7ffeu64),
+        ];
+        assert_eq!(
+            compute_program_hash_chain(&stripped_program(), 1).unwrap(),
+            compute_hash_chain(expected_data.iter(), pedersen_hash).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compute_program_hash_poseidon() {
+        let expected_data = [
+            Felt252::from(1),
+            Felt252::from(1),
+            Felt252::from(2),
+            Felt252::from_bytes_be_slice(b"output"),
+            Felt252::from_bytes_be_slice(b"pedersen"),
+            Felt252::from(0x40780017fff7fffu64),
+            Felt252::from(2),
+            Felt252::from(0x208b7fff7fff7ffeu64),
+        ];
+        let program_hash = compute_program_hash_poseidon(&stripped_program(), 1).unwrap();
+        assert_eq!(program_hash, poseidon_hash_many(&expected_data));
+        assert_ne!(
+            program_hash,
+            compute_program_hash_chain(&stripped_program(), 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compute_program_hash_rejects_relocatable_data() {
+        let mut program = stripped_program();
+        program
+            .data
+            .push(MaybeRelocatable::from(Relocatable::from((1, 0))));
+        assert_matches!(
+            compute_program_hash_poseidon(&program, 0),
+            Err(ProgramHashError::InvalidProgramData)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    fn stripped_program_hash_chain(
+        program: &Program,
+        bootloader_version: usize,
+    ) -> Result<Felt252, ProgramHashError> {
+        compute_program_hash_chain(&program.get_stripped_program()?, bootloader_version)
+    }
+
     #[cfg(feature = "std")]
     #[rstest]
     // Expected hashes generated with `cairo-hash-program`
     #[case::fibonacci(
         "../cairo_programs/fibonacci.json",
-        "0x43b17e9592f33142246af4c06cd2b574b460dd1f718d76b51341175a62b220f"
+        "0x43b17e9592f33142246af4c06cd2b574b460dd1f718d76b51341175a62b220f",
+        stripped_program_hash_chain
     )]
     #[case::field_arithmetic(
         "../cairo_programs/field_arithmetic.json",
-        "0x1031772ca86e618b058101af9c9a3277bac90712b750bcea1cc69d6c7cad8a7"
+        "0x1031772ca86e618b058101af9c9a3277bac90712b750bcea1cc69d6c7cad8a7",
+        stripped_program_hash_chain
     )]
     #[case::keccak_copy_inputs(
         "../cairo_programs/keccak_copy_inputs.json",
-        "0x49484fdc8e7a85061f9f21b7e21fe276d8a88c8e96681101a2518809e686c6c"
+        "0x49484fdc8e7a85061f9f21b7e21fe276d8a88c8e96681101a2518809e686c6c",
+        stripped_program_hash_chain
+    )]
+    #[case::fibonacci_program_hash(
+        "../cairo_programs/fibonacci.json",
+        "0x43b17e9592f33142246af4c06cd2b574b460dd1f718d76b51341175a62b220f",
+        Program::compute_program_hash
+    )]
+    #[case::keccak_copy_inputs_program_hash(
+        "../cairo_programs/keccak_copy_inputs.json",
+        "0x49484fdc8e7a85061f9f21b7e21fe276d8a88c8e96681101a2518809e686c6c",
+        Program::compute_program_hash
     )]
     fn test_compute_program_hash_chain(
         #[case] program_path: PathBuf,
         #[case] expected_program_hash: String,
+        #[case] compute_program_hash: fn(&Program, usize) -> Result<Felt252, ProgramHashError>,
     ) {
         let program =
             Program::from_file(program_path.as_path(), Some("main"))
                 .expect("Could not load program. Did you compile the sample programs? Run `make test` in the root directory.");
-        let stripped_program = program.get_stripped_program().unwrap();
         let bootloader_version = 0;
 
-        let program_hash = compute_program_hash_chain(&stripped_program, bootloader_version)
+        let program_hash = compute_program_hash(&program, bootloader_version)
             .expect("Failed to compute program hash.");
 
         let program_hash_hex = format!("{:#x}", program_hash);
@@ -20,7 +20,10 @@ use crate::{
 use crate::utils::PRIME_STR;
 use crate::Felt252;
 use crate::{
-    serde::deserialize_utils,
+    serde::{
+        deserialize_utils,
+        reference_expression::{self, ReferenceExpression},
+    },
     types::{
         errors::program_errors::ProgramError,
         instruction::Register,
@@ -49,6 +52,8 @@ pub struct ProgramJson {
     #[serde(default)]
     pub attributes: Vec<Attribute>,
     pub debug_info: Option<DebugInfo>,
+    #[serde(default)]
+    pub compiler_version: Option<String>,
 }
 
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
@@ -121,6 +126,9 @@ pub struct Attribute {
     pub value: String,
     #[cfg_attr(feature = "test_utils", serde(skip_serializing_if = "Option::is_none"))]
     pub flow_tracking_data: Option<FlowTrackingData>,
+    /// Not present in programs compiled before cairo-lang 0.10
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accessible_scopes: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -164,17 +172,24 @@ fn arbitrary_parent_location(u: &mut Unstructured, depth: u8) -> arbitrary::Resu
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct DebugInfo {
     pub(crate) instruction_locations: HashMap<usize, InstructionLocation>,
+    /// The source code of the compiled files, by filename
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub(crate) file_contents: HashMap<String, String>,
 }
 
 impl DebugInfo {
     pub fn new(instruction_locations: HashMap<usize, InstructionLocation>) -> Self {
         Self {
             instruction_locations,
+            file_contents: HashMap::new(),
         }
     }
     pub fn get_instruction_locations(&self) -> HashMap<usize, InstructionLocation> {
         self.instruction_locations.clone()
     }
+    pub fn get_file_contents(&self) -> &HashMap<String, String> {
+        &self.file_contents
+    }
 }
 
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
@@ -182,6 +197,13 @@ impl DebugInfo {
 pub struct InstructionLocation {
     pub inst: Location,
     pub hints: Vec<HintLocation>,
+    // Only present in programs compiled with cairo-lang 0.10 onwards.
+    // The VM doesn't use them, so they are only kept in the ProgramJson and are dropped when
+    // building the Program
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accessible_scopes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow_tracking_data: Option<FlowTrackingData>,
 }
 
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
@@ -286,6 +308,14 @@ pub struct ValueAddress {
     pub outer_dereference: bool, // [] in [cast(A + B, type)]
     pub inner_dereference: bool, // [] in cast([A + B], type)
     pub value_type: String,      // type in cast(A + B, type)
+    /// The whole parsed expression, only kept when it doesn't fit the `A + B` shape of the
+    /// fields above
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "reference_expression::optional_expression"
+    )]
+    pub expression: Option<Arc<ReferenceExpression>>,
 }
 
 impl ValueAddress {
@@ -303,6 +333,7 @@ impl ValueAddress {
             outer_dereference: false,
             inner_dereference: false,
             value_type: String::from("felt"),
+            expression: None,
         }
     }
 }
@@ -390,11 +421,15 @@ impl<'de> de::Visitor<'de> for ValueAddressVisitor {
     {
         let parse_res = deserialize_utils::parse_value(value);
 
-        if let Ok((_, res)) = parse_res {
-            return Ok(res);
-        }
-
-        Ok(ValueAddress::no_hint_reference_default())
+        let mut value_address = match parse_res {
+            Ok((_, res)) => res,
+            Err(_) => ValueAddress::no_hint_reference_default(),
+        };
+        // References the fields can represent are evaluated through them, which is faster
+        value_address.expression = ReferenceExpression::parse(value)
+            .filter(|expression| !expression.fits_value_address(&value_address))
+            .map(Arc::new);
+        Ok(value_address)
     }
 }
 
@@ -424,6 +459,15 @@ pub fn deserialize_program_json(reader: &[u8]) -> Result<ProgramJson, ProgramErr
     let program_json = serde_json::from_slice(reader)?;
     Ok(program_json)
 }
+/// Deserializes a program without holding the whole JSON in memory, see
+/// [Program::from_reader](crate::types::program::Program::from_reader).
+#[cfg(feature = "std")]
+pub fn deserialize_program_json_from_reader(
+    reader: impl std::io::Read,
+) -> Result<ProgramJson, ProgramError> {
+    let program_json = serde_json::from_reader(std::io::BufReader::new(reader))?;
+    Ok(program_json)
+}
 pub fn deserialize_and_parse_program(
     reader: &[u8],
     entrypoint: Option<&str>,
@@ -472,6 +516,18 @@ pub fn parse_program_json(
 
     let hints_collection = HintsCollection::new(&program_json.hints, program_json.data.len())?;
 
+    let (instruction_locations, file_contents) = match program_json.debug_info {
+        Some(debug_info) => {
+            let mut instruction_locations = debug_info.instruction_locations;
+            for location in instruction_locations.values_mut() {
+                location.accessible_scopes = Vec::new();
+                location.flow_tracking_data = None;
+            }
+            (Some(instruction_locations), debug_info.file_contents)
+        }
+        None => (None, HashMap::new()),
+    };
+
     let shared_program_data = SharedProgramData {
         data: program_json.data,
         hints_collection,
@@ -483,11 +539,11 @@ pub fn parse_program_json(
             .into_iter()
             .filter(|attr| attr.name == "error_message")
             .collect(),
-        instruction_locations: program_json
-            .debug_info
-            .map(|debug_info| debug_info.instruction_locations),
-        identifiers: program_json.identifiers,
+        instruction_locations,
+        identifiers: Arc::new(program_json.identifiers),
         reference_manager: Program::get_reference_list(&program_json.reference_manager),
+        compiler_version: program_json.compiler_version,
+        file_contents,
     };
     Ok(Program {
         shared_program_data: Arc::new(shared_program_data),
@@ -500,6 +556,7 @@ pub fn parse_program_json(
 mod tests {
     use super::*;
     use crate::felt_str;
+    use crate::types::builtin_name::CustomBuiltinName;
     use assert_matches::assert_matches;
     use core::num::NonZeroUsize;
 
@@ -558,6 +615,21 @@ mod tests {
         assert!(no_prefix_error.is_err());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deserialize_custom_builtins() {
+        let sha256 = BuiltinName::custom(CustomBuiltinName::new("sha256").unwrap());
+        let builtins: Vec<BuiltinName> = serde_json::from_str(r#"["output", "sha256"]"#).unwrap();
+        assert_eq!(builtins, vec![BuiltinName::output, sha256]);
+        assert_eq!(
+            serde_json::to_string(&builtins).unwrap(),
+            r#"["output","sha256"]"#
+        );
+
+        let unregistered_name = r#"["output", "sha265"]"#;
+        assert!(serde_json::from_str::<Vec<BuiltinName>>(unregistered_name).is_err());
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn deserialize_from_string_json() {
@@ -719,6 +791,7 @@ mod tests {
                         outer_dereference: true,
                         inner_dereference: false,
                         value_type: "felt".to_string(),
+                        expression: None,
                     },
                 },
                 Reference {
@@ -733,6 +806,7 @@ mod tests {
                         outer_dereference: true,
                         inner_dereference: false,
                         value_type: "felt".to_string(),
+                        expression: None,
                     },
                 },
                 Reference {
@@ -747,6 +821,7 @@ mod tests {
                         outer_dereference: false,
                         inner_dereference: false,
                         value_type: "felt".to_string(),
+                        expression: None,
                     },
                 },
                 Reference {
@@ -761,6 +836,7 @@ mod tests {
                         outer_dereference: true,
                         inner_dereference: false,
                         value_type: "felt*".to_string(),
+                        expression: None,
                     },
                 },
             ],
@@ -1218,6 +1294,11 @@ mod tests {
                     },
                     reference_ids: HashMap::new(),
                 }),
+                accessible_scopes: vec![
+                    String::from("openzeppelin.security.safemath.library"),
+                    String::from("openzeppelin.security.safemath.library.SafeUint256"),
+                    String::from("openzeppelin.security.safemath.library.SafeUint256.add"),
+                ],
             },
             Attribute {
                 name: String::from("error_message"),
@@ -1231,6 +1312,11 @@ mod tests {
                     },
                     reference_ids: HashMap::new(),
                 }),
+                accessible_scopes: vec![
+                    String::from("openzeppelin.security.safemath.library"),
+                    String::from("openzeppelin.security.safemath.library.SafeUint256"),
+                    String::from("openzeppelin.security.safemath.library.SafeUint256.sub_le"),
+                ],
             },
         ];
 
@@ -1311,6 +1397,7 @@ mod tests {
         let program_json: ProgramJson = serde_json::from_str(valid_json).unwrap();
 
         let debug_info: DebugInfo = DebugInfo {
+            file_contents: HashMap::new(),
             instruction_locations: HashMap::from([
                 (
                     0,
@@ -1324,6 +1411,14 @@ mod tests {
                             start_col: 5,
                         },
                         hints: vec![],
+                        accessible_scopes: vec![
+                            String::from("starkware.cairo.lang.compiler.lib.registers"),
+                            String::from("starkware.cairo.lang.compiler.lib.registers.get_fp_and_pc"),
+                        ],
+                        flow_tracking_data: Some(FlowTrackingData {
+                            ap_tracking: ApTracking { group: 0, offset: 0 },
+                            reference_ids: HashMap::new(),
+                        }),
                     },
                 ),
                 (
@@ -1338,6 +1433,14 @@ mod tests {
                             start_col: 5,
                         },
                         hints: vec![],
+                        accessible_scopes: vec![
+                            String::from("starkware.cairo.common.alloc"),
+                            String::from("starkware.cairo.common.alloc.alloc"),
+                        ],
+                        flow_tracking_data: Some(FlowTrackingData {
+                            ap_tracking: ApTracking { group: 1, offset: 1 },
+                            reference_ids: HashMap::new(),
+                        }),
                     },
                 ),
             ]),
@@ -1440,9 +1543,15 @@ mod tests {
                         }), String::from( "While expanding the reference 'syscall_ptr' in:"))
                     ), start_line: 9, start_col: 18 },
                     hints: vec![],
+                    accessible_scopes: vec![
+                        String::from("__main__"),
+                        String::from("__main__"),
+                        String::from("__main__.constructor"),
+                    ],
+                    flow_tracking_data: None,
                 }),
             ]
-        ) };
+        ), file_contents: HashMap::new() };
 
         assert_eq!(program_json.debug_info, Some(debug_info));
     }
@@ -1630,4 +1739,84 @@ mod tests {
         ));
         _ = deserialize_and_parse_program(program, None).expect("should be able to read file");
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deserialize_program_legacy_format() {
+        let program_json: ProgramJson = serde_json::from_slice(include_bytes!(
+            "../../../cairo_programs/manually_compiled/program_legacy_format.json"
+        ))
+        .unwrap();
+
+        assert_eq!(program_json.compiler_version, None);
+        let attribute = &program_json.attributes[0];
+        assert_eq!(attribute.value, "Main failed");
+        assert_eq!(attribute.flow_tracking_data, None);
+        assert!(attribute.accessible_scopes.is_empty());
+        let debug_info = program_json.debug_info.as_ref().unwrap();
+        assert!(debug_info.get_file_contents().is_empty());
+        let instruction_location = &debug_info.instruction_locations[&0];
+        assert_eq!(instruction_location.inst.start_line, 4);
+        assert_eq!(instruction_location.hints[0].location.start_line, 3);
+        assert!(instruction_location.accessible_scopes.is_empty());
+        assert_eq!(instruction_location.flow_tracking_data, None);
+
+        let program = parse_program_json(program_json, Some("main")).unwrap();
+        assert_eq!(program.shared_program_data.main, Some(0));
+        assert_eq!(
+            program
+                .shared_program_data
+                .hints_collection
+                .get_hints_for_pc(0)[0]
+                .code,
+            "memory[ap] = 5"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deserialize_program_new_format() {
+        let program_json: ProgramJson = serde_json::from_slice(include_bytes!(
+            "../../../cairo_programs/manually_compiled/program_new_format.json"
+        ))
+        .unwrap();
+        let scopes = vec![String::from("__main__"), String::from("__main__.main")];
+
+        assert_eq!(program_json.compiler_version.as_deref(), Some("0.13.1"));
+        let attribute = &program_json.attributes[0];
+        assert_eq!(attribute.value, "Main failed");
+        assert_eq!(attribute.accessible_scopes, scopes);
+        assert_eq!(
+            attribute.flow_tracking_data,
+            Some(FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids: HashMap::new(),
+            })
+        );
+        let debug_info = program_json.debug_info.as_ref().unwrap();
+        assert!(debug_info.get_file_contents()["format_test.cairo"].starts_with("func main()"));
+        let instruction_location = &debug_info.instruction_locations[&2];
+        assert_eq!(instruction_location.inst.start_line, 6);
+        assert_eq!(instruction_location.accessible_scopes, scopes);
+        assert_eq!(
+            instruction_location.flow_tracking_data,
+            Some(FlowTrackingData {
+                ap_tracking: ApTracking {
+                    group: 0,
+                    offset: 1
+                },
+                reference_ids: HashMap::new(),
+            })
+        );
+
+        let program = parse_program_json(program_json, Some("main")).unwrap();
+        assert_eq!(program.shared_program_data.main, Some(0));
+        assert_eq!(program.compiler_version(), Some("0.13.1"));
+        assert!(program.get_file_contents()["format_test.cairo"].starts_with("func main()"));
+        // The scopes and flow tracking data of the instructions are not kept in the Program
+        let instruction_location = program.get_instruction_location(2).unwrap();
+        assert_eq!(instruction_location.inst.start_line, 6);
+        assert!(instruction_location.accessible_scopes.is_empty());
+        assert_eq!(instruction_location.flow_tracking_data, None);
+    }
 }
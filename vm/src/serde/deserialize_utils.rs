@@ -211,6 +211,7 @@ pub(crate) fn parse_value(input: &str) -> IResult<&str, ValueAddress> {
         outer_dereference,
         inner_dereference,
         value_type: type_,
+        expression: None,
     };
 
     Ok((rem_input, value_address))
@@ -239,7 +240,7 @@ pub(crate) fn parse_value(input: &str) -> IResult<&str, ValueAddress> {
 /// very similar to `nom::bytes::complete::take_until(">")`, except it also takes nested brackets.
 /// NOTE: trimmed down from https://docs.rs/parse-hyperlinks to fix bugs. The project itself seems
 /// abandonned.
-fn take_until_unbalanced(
+pub(super) fn take_until_unbalanced(
     opening_bracket: char,
     closing_bracket: char,
 ) -> impl Fn(&str) -> IResult<&str, &str> {
@@ -417,6 +418,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -437,7 +439,8 @@ mod tests {
                     offset2: OffsetValue::Reference(Register::FP, 0_i32, true, false),
                     outer_dereference: false,
                     inner_dereference: false,
-                    value_type: "felt".to_string()
+                    value_type: "felt".to_string(),
+                    expression: None,
                 }
             )
         )
@@ -459,6 +462,7 @@ mod tests {
                     outer_dereference: false,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -479,6 +483,7 @@ mod tests {
                     outer_dereference: false,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -500,6 +505,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -521,6 +527,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "__main__.felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -542,6 +549,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -563,6 +571,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -584,6 +593,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "__main__.felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -605,6 +615,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "__main__.felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -626,6 +637,7 @@ mod tests {
                     outer_dereference: false,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -647,6 +659,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "starkware.cairo.common.cairo_secp.ec.EcPoint".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -668,6 +681,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "starkware.cairo.common.cairo_secp.ec.EcPoint*".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -689,6 +703,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -710,6 +725,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: true,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -731,6 +747,7 @@ mod tests {
                     outer_dereference: true,
                     inner_dereference: false,
                     value_type: "felt".to_string(),
+                    expression: None,
                 }
             ))
         );
@@ -1,3 +1,4 @@
 pub mod deserialize_program;
 mod deserialize_utils;
+pub mod reference_expression;
 pub(crate) mod serialize_program;
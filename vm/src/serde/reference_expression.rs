@@ -0,0 +1,468 @@
+//! A parser for the expressions of the references in compiled programs, such as
+//! `[cast([fp + (-4)] + 2, felt*)]`.
+//!
+//! Unlike [ValueAddress](super::deserialize_program::ValueAddress), which only covers the
+//! `A + B` shapes most hints use, the parsed [ReferenceExpression] keeps the whole expression:
+//! nested casts and dereferences, and sums, differences and products of any of them.
+//! Only the references the [ValueAddress] fields can't represent keep their parsed expression.
+
+use crate::stdlib::{boxed::Box, fmt, prelude::*};
+use crate::types::instruction::Register;
+use crate::Felt252;
+
+use super::deserialize_program::{OffsetValue, ValueAddress};
+use super::deserialize_utils::take_until_unbalanced;
+#[cfg(feature = "test_utils")]
+use arbitrary::Arbitrary;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, multispace0, one_of},
+    combinator::{all_consuming, map, map_opt, value},
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+#[cfg_attr(feature = "test_utils", derive(Arbitrary))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ReferenceExpression {
+    Register(Register),
+    Immediate(Felt252),
+    /// `[A]`
+    Deref(Box<ReferenceExpression>),
+    Add(Box<ReferenceExpression>, Box<ReferenceExpression>),
+    Sub(Box<ReferenceExpression>, Box<ReferenceExpression>),
+    Mul(Box<ReferenceExpression>, Box<ReferenceExpression>),
+    /// `cast(A, type)`
+    Cast(Box<ReferenceExpression>, String),
+}
+
+impl ReferenceExpression {
+    /// Parses a reference expression as found in the `value` of the references of a compiled
+    /// program. Returns `None` if the input isn't a valid expression.
+    pub fn parse(input: &str) -> Option<Self> {
+        all_consuming(delimited(multispace0, expression, multispace0))(input)
+            .ok()
+            .map(|(_, expression)| expression)
+    }
+
+    /// Returns true if the expression depends on the ap register, in which case it can only be
+    /// evaluated with the ap tracking data of its reference.
+    pub fn uses_ap(&self) -> bool {
+        match self {
+            ReferenceExpression::Register(register) => *register == Register::AP,
+            ReferenceExpression::Immediate(_) => false,
+            ReferenceExpression::Deref(inner) | ReferenceExpression::Cast(inner, _) => {
+                inner.uses_ap()
+            }
+            ReferenceExpression::Add(lhs, rhs)
+            | ReferenceExpression::Sub(lhs, rhs)
+            | ReferenceExpression::Mul(lhs, rhs) => lhs.uses_ap() || rhs.uses_ap(),
+        }
+    }
+
+    /// Returns true if the fields of `value_address` evaluate to the same value as the
+    /// expression, in which case hints can use them instead of evaluating the expression.
+    pub(crate) fn fits_value_address(&self, value_address: &ValueAddress) -> bool {
+        let offset1 = Self::from_offset_value(&value_address.offset1);
+        let mut expression = match &value_address.offset2 {
+            OffsetValue::Immediate(value) if *value == Felt252::ZERO => offset1,
+            OffsetValue::Value(0) => offset1,
+            offset2 @ OffsetValue::Reference(_, _, _, false) => ReferenceExpression::Sub(
+                Box::new(offset1),
+                Box::new(Self::from_offset_value(offset2)),
+            ),
+            offset2 => ReferenceExpression::Add(
+                Box::new(offset1),
+                Box::new(Self::from_offset_value(offset2)),
+            ),
+        };
+        if value_address.inner_dereference {
+            expression = ReferenceExpression::Deref(Box::new(expression));
+        }
+        if value_address.outer_dereference {
+            expression = ReferenceExpression::Deref(Box::new(expression));
+        }
+        // Casts don't change the value, and the type of the fields is the pointee's
+        self.without_casts() == expression
+    }
+
+    fn from_offset_value(offset_value: &OffsetValue) -> Self {
+        match offset_value {
+            OffsetValue::Immediate(value) => ReferenceExpression::Immediate(*value),
+            OffsetValue::Value(value) => ReferenceExpression::Immediate(Felt252::from(*value)),
+            OffsetValue::Reference(register, offset, deref, _) => {
+                let mut expression = ReferenceExpression::Register(*register);
+                if *offset != 0 {
+                    expression = ReferenceExpression::Add(
+                        Box::new(expression),
+                        Box::new(ReferenceExpression::Immediate(Felt252::from(*offset))),
+                    );
+                }
+                if *deref {
+                    expression = ReferenceExpression::Deref(Box::new(expression));
+                }
+                expression
+            }
+        }
+    }
+
+    fn without_casts(&self) -> Self {
+        let without_casts = |expression: &ReferenceExpression| Box::new(expression.without_casts());
+        match self {
+            ReferenceExpression::Register(_) | ReferenceExpression::Immediate(_) => self.clone(),
+            ReferenceExpression::Deref(inner) => ReferenceExpression::Deref(without_casts(inner)),
+            ReferenceExpression::Add(lhs, rhs) => {
+                ReferenceExpression::Add(without_casts(lhs), without_casts(rhs))
+            }
+            ReferenceExpression::Sub(lhs, rhs) => {
+                ReferenceExpression::Sub(without_casts(lhs), without_casts(rhs))
+            }
+            ReferenceExpression::Mul(lhs, rhs) => {
+                ReferenceExpression::Mul(without_casts(lhs), without_casts(rhs))
+            }
+            ReferenceExpression::Cast(inner, _) => inner.without_casts(),
+        }
+    }
+}
+
+/// Writes the expression in the same syntax it is parsed from
+impl fmt::Display for ReferenceExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Operands which bind looser than their operator are wrapped in parentheses
+        let is_sum = |expression: &ReferenceExpression| {
+            matches!(
+                expression,
+                ReferenceExpression::Add(..) | ReferenceExpression::Sub(..)
+            )
+        };
+        match self {
+            ReferenceExpression::Register(Register::AP) => write!(f, "ap"),
+            ReferenceExpression::Register(Register::FP) => write!(f, "fp"),
+            ReferenceExpression::Immediate(value) if -*value < *value => {
+                write!(f, "(-{})", -*value)
+            }
+            ReferenceExpression::Immediate(value) => write!(f, "{value}"),
+            ReferenceExpression::Deref(inner) => write!(f, "[{inner}]"),
+            ReferenceExpression::Add(lhs, rhs) | ReferenceExpression::Sub(lhs, rhs) => {
+                let op = if matches!(self, ReferenceExpression::Add(..)) {
+                    '+'
+                } else {
+                    '-'
+                };
+                if is_sum(rhs) {
+                    write!(f, "{lhs} {op} ({rhs})")
+                } else {
+                    write!(f, "{lhs} {op} {rhs}")
+                }
+            }
+            ReferenceExpression::Mul(lhs, rhs) => {
+                if is_sum(lhs) {
+                    write!(f, "({lhs})")?;
+                } else {
+                    write!(f, "{lhs}")?;
+                }
+                if is_sum(rhs) || matches!(**rhs, ReferenceExpression::Mul(..)) {
+                    write!(f, " * ({rhs})")
+                } else {
+                    write!(f, " * {rhs}")
+                }
+            }
+            ReferenceExpression::Cast(inner, cairo_type) => {
+                write!(f, "cast({inner}, {cairo_type})")
+            }
+        }
+    }
+}
+
+/// (De)serializes the optional expression of a reference as a string in the syntax of the
+/// compiled programs, omitted by the callers when there is no expression.
+pub(crate) mod optional_expression {
+    use super::ReferenceExpression;
+    use crate::stdlib::{prelude::*, sync::Arc};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        expression: &Option<Arc<ReferenceExpression>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match expression {
+            Some(expression) => serializer.collect_str(expression),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Arc<ReferenceExpression>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|expression| {
+                ReferenceExpression::parse(&expression)
+                    .map(Arc::new)
+                    .ok_or_else(|| {
+                        de::Error::custom(format!("invalid reference expression: {expression}"))
+                    })
+            })
+            .transpose()
+    }
+}
+
+fn operator<'a>(operators: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, char> {
+    delimited(multispace0, one_of(operators), multispace0)
+}
+
+// expression := term (("+" | "-") term)*
+fn expression(input: &str) -> IResult<&str, ReferenceExpression> {
+    let (mut input, mut expression) = term(input)?;
+    while let Ok((rest, (op, rhs))) = tuple((operator("+-"), term))(input) {
+        expression = match op {
+            '+' => ReferenceExpression::Add(Box::new(expression), Box::new(rhs)),
+            _ => ReferenceExpression::Sub(Box::new(expression), Box::new(rhs)),
+        };
+        input = rest;
+    }
+    Ok((input, expression))
+}
+
+// term := factor ("*" factor)*
+fn term(input: &str) -> IResult<&str, ReferenceExpression> {
+    let (mut input, mut term) = factor(input)?;
+    while let Ok((rest, (_, rhs))) = tuple((operator("*"), factor))(input) {
+        term = ReferenceExpression::Mul(Box::new(term), Box::new(rhs));
+        input = rest;
+    }
+    Ok((input, term))
+}
+
+// factor := "[" expression "]" | cast | "(" expression ")" | "-" factor | register | number
+fn factor(input: &str) -> IResult<&str, ReferenceExpression> {
+    alt((
+        map(
+            delimited(
+                terminated(char('['), multispace0),
+                expression,
+                preceded(multispace0, char(']')),
+            ),
+            |inner| ReferenceExpression::Deref(Box::new(inner)),
+        ),
+        cast,
+        delimited(
+            terminated(char('('), multispace0),
+            expression,
+            preceded(multispace0, char(')')),
+        ),
+        map(preceded(char('-'), factor), |inner| match inner {
+            ReferenceExpression::Immediate(num) => ReferenceExpression::Immediate(-num),
+            inner => ReferenceExpression::Sub(
+                Box::new(ReferenceExpression::Immediate(Felt252::ZERO)),
+                Box::new(inner),
+            ),
+        }),
+        map(
+            alt((
+                value(Register::AP, tag("ap")),
+                value(Register::FP, tag("fp")),
+            )),
+            ReferenceExpression::Register,
+        ),
+        map_opt(digit1, |num| {
+            Felt252::from_dec_str(num)
+                .ok()
+                .map(ReferenceExpression::Immediate)
+        }),
+    ))(input)
+}
+
+// cast := "cast(" expression "," type ")"
+fn cast(input: &str) -> IResult<&str, ReferenceExpression> {
+    let (input, (_, inner, _, cairo_type, _)) = tuple((
+        terminated(tag("cast("), multispace0),
+        expression,
+        operator(","),
+        take_until_unbalanced('(', ')'),
+        char(')'),
+    ))(input)?;
+    Ok((
+        input,
+        ReferenceExpression::Cast(Box::new(inner), cairo_type.trim().to_string()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::deserialize_program::deserialize_value_address;
+    use ReferenceExpression as Expr;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn boxed(expression: ReferenceExpression) -> Box<ReferenceExpression> {
+        Box::new(expression)
+    }
+
+    fn cast(expression: ReferenceExpression, cairo_type: &str) -> ReferenceExpression {
+        Expr::Cast(boxed(expression), cairo_type.to_string())
+    }
+
+    fn fp_plus(offset: i32) -> ReferenceExpression {
+        Expr::Add(
+            boxed(Expr::Register(Register::FP)),
+            boxed(Expr::Immediate(Felt252::from(offset))),
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_simple_reference() {
+        assert_eq!(
+            Expr::parse("[cast(fp + (-4), felt*)]"),
+            Some(Expr::Deref(boxed(cast(fp_plus(-4), "felt*"))))
+        );
+        assert_eq!(
+            Expr::parse("cast(ap, felt)"),
+            Some(cast(Expr::Register(Register::AP), "felt"))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_double_dereference() {
+        assert_eq!(
+            Expr::parse("[cast([fp + (-3)] + 2, felt**)]"),
+            Some(Expr::Deref(boxed(cast(
+                Expr::Add(
+                    boxed(Expr::Deref(boxed(fp_plus(-3)))),
+                    boxed(Expr::Immediate(Felt252::from(2)))
+                ),
+                "felt**"
+            ))))
+        );
+        assert_eq!(
+            Expr::parse("[[fp + 1]]"),
+            Some(Expr::Deref(boxed(Expr::Deref(boxed(fp_plus(1))))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_operations_on_references() {
+        assert_eq!(
+            Expr::parse("cast([fp + 1] + [fp + 2] * 3 - [fp], felt)"),
+            Some(cast(
+                Expr::Sub(
+                    boxed(Expr::Add(
+                        boxed(Expr::Deref(boxed(fp_plus(1)))),
+                        boxed(Expr::Mul(
+                            boxed(Expr::Deref(boxed(fp_plus(2)))),
+                            boxed(Expr::Immediate(Felt252::from(3)))
+                        ))
+                    )),
+                    boxed(Expr::Deref(boxed(Expr::Register(Register::FP))))
+                ),
+                "felt"
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_tuple_and_struct_types() {
+        assert_eq!(
+            Expr::parse("[cast(ap + (-1), (a: felt, b: starkware.cairo.common.uint256.Uint256)*)]"),
+            Some(Expr::Deref(boxed(cast(
+                Expr::Add(
+                    boxed(Expr::Register(Register::AP)),
+                    boxed(Expr::Immediate(Felt252::from(-1)))
+                ),
+                "(a: felt, b: starkware.cairo.common.uint256.Uint256)*"
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_invalid_expressions() {
+        assert_eq!(Expr::parse("[cast(fp + (-4), felt*)"), None);
+        assert_eq!(Expr::parse("cast(sp, felt)"), None);
+        assert_eq!(Expr::parse("fp +"), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn uses_ap() {
+        assert!(Expr::parse("cast([ap + (-1)] + 2, felt)")
+            .unwrap()
+            .uses_ap());
+        assert!(!Expr::parse("[cast(fp, felt*)]").unwrap().uses_ap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn display_round_trip() {
+        for input in [
+            "[cast(fp + (-4), felt*)]",
+            "cast([ap + (-1)] + 2, felt)",
+            "[cast([[fp + 2] + 2], felt*)]",
+            "cast([fp + 1] + [fp + 2] * 3 - [fp], felt)",
+            "cast(([fp] + 1) * ([fp + 1] - [fp + 2]), felt)",
+            "cast(fp - (ap - 2), felt)",
+            "cast(-[fp], felt)",
+            "[cast(ap + (-1), (a: felt, b: starkware.cairo.common.uint256.Uint256)*)]",
+        ] {
+            let expression = Expr::parse(input).unwrap();
+            assert_eq!(Expr::parse(&expression.to_string()), Some(expression));
+        }
+        assert_eq!(
+            Expr::parse("[cast(fp + (-4), felt*)]").unwrap().to_string(),
+            "[cast(fp + (-4), felt*)]"
+        );
+    }
+
+    fn value_address(value: &str) -> ValueAddress {
+        deserialize_value_address(serde_json::Value::String(value.to_string())).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn expression_only_kept_when_not_fitting_value_address() {
+        for simple in [
+            "[cast(fp + (-4), felt*)]",
+            "cast([fp + (-3)] + 2, felt)",
+            "[cast(fp, felt**)]",
+            "[cast([ap] + [fp + 1], __main__.felt*)]",
+            "cast(825323, felt)",
+        ] {
+            assert_eq!(value_address(simple).expression, None, "{simple}");
+        }
+        for complex in [
+            "[cast([[fp + 2] + 2], felt*)]",
+            "cast([fp] * [fp + 1] - [ap + (-1)], felt)",
+        ] {
+            assert_eq!(
+                value_address(complex).expression.as_deref(),
+                Expr::parse(complex).as_ref(),
+                "{complex}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn serialize_value_address_expression() {
+        // Simple references serialize as they did before expressions were parsed
+        let simple = serde_json::to_value(value_address("[cast(fp + (-4), felt*)]")).unwrap();
+        assert!(simple.get("expression").is_none());
+
+        let complex = value_address("[cast([[fp + 2] + 2], felt*)]");
+        let serialized = serde_json::to_value(&complex).unwrap();
+        assert_eq!(
+            serialized["expression"],
+            serde_json::json!("[cast([[fp + 2] + 2], felt*)]")
+        );
+        assert_eq!(
+            serde_json::from_value::<ValueAddress>(serialized).unwrap(),
+            complex
+        );
+    }
+}
@@ -29,6 +29,8 @@ pub(crate) struct ProgramSerializer {
     pub reference_manager: ReferenceManagerSerializer,
     pub attributes: Vec<Attribute>,
     pub debug_info: Option<DebugInfo>,
+    #[serde(default)]
+    pub compiler_version: Option<String>,
 }
 
 impl From<ProgramSerializer> for ProgramJson {
@@ -63,6 +65,7 @@ impl From<ProgramSerializer> for ProgramJson {
             reference_manager,
             attributes: program_json.attributes,
             debug_info: program_json.debug_info,
+            compiler_version: program_json.compiler_version,
         }
     }
 }
@@ -204,6 +207,7 @@ impl From<&Program> for ProgramSerializer {
                     outer_dereference: r.outer_dereference,
                     inner_dereference: r.inner_dereference,
                     value_type: r.cairo_type.unwrap_or_default(),
+                    expression: r.expression,
                 },
                 ap_tracking_data: r.ap_tracking_data.unwrap_or_default(),
                 pc: None,
@@ -211,8 +215,8 @@ impl From<&Program> for ProgramSerializer {
             .collect::<Vec<_>>();
 
         let mut identifiers = HashMap::new();
-        for (key, identifier) in program.shared_program_data.identifiers.clone() {
-            identifiers.insert(key, identifier.into());
+        for (key, identifier) in program.shared_program_data.identifiers.iter() {
+            identifiers.insert(key.clone(), identifier.clone().into());
         }
 
         let mut hints: BTreeMap<usize, Vec<HintParamsSerializer>> = BTreeMap::new();
@@ -238,8 +242,10 @@ impl From<&Program> for ProgramSerializer {
                 .clone()
                 .map(|instruction_locations| DebugInfo {
                     instruction_locations,
+                    file_contents: program.shared_program_data.file_contents.clone(),
                 }),
             reference_manager: ReferenceManagerSerializer { references },
+            compiler_version: program.shared_program_data.compiler_version.clone(),
         }
     }
 }
@@ -331,4 +337,24 @@ mod tests {
             assert_eq!(original_program, new_program);
         }
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn serialize_and_deserialize_program_keeps_file_contents() {
+        let original_program = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/manually_compiled/program_new_format.json"),
+            Some("main"),
+        )
+        .unwrap();
+        assert!(!original_program.get_file_contents().is_empty());
+
+        let program_serialized = original_program.serialize().unwrap();
+        let new_program = Program::deserialize(&program_serialized, Some("main")).unwrap();
+
+        assert_eq!(
+            new_program.get_file_contents(),
+            original_program.get_file_contents()
+        );
+        assert_eq!(original_program, new_program);
+    }
 }
@@ -0,0 +1,133 @@
+//! Helpers to set up a [VirtualMachine] in unit tests, e.g. to run a hint against a given memory.
+
+pub mod differential;
+
+use crate::stdlib::prelude::*;
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::utils::from_relocatable_to_indexes;
+use crate::vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine};
+
+/// Collects memory cells and writes them into a [VirtualMachine], adding the segments (both
+/// regular and temporary) needed to hold them.
+///
+/// ```
+/// use cairo_vm::testing::MemoryBuilder;
+/// use cairo_vm::Felt252;
+///
+/// let vm = MemoryBuilder::new()
+///     .cell((1, 0), Felt252::from(5))
+///     .cell((1, 1), (2, 0))
+///     .build()
+///     .unwrap();
+/// assert_eq!(vm.segments.num_segments(), 3);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MemoryBuilder {
+    cells: Vec<(Relocatable, MaybeRelocatable)>,
+}
+
+impl MemoryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cell(
+        mut self,
+        addr: impl Into<Relocatable>,
+        value: impl Into<MaybeRelocatable>,
+    ) -> Self {
+        self.cells.push((addr.into(), value.into()));
+        self
+    }
+
+    /// Writes the cells into the VM's memory.
+    /// Fails if a cell is already set to a different value.
+    pub fn load_into(self, vm: &mut VirtualMachine) -> Result<(), MemoryError> {
+        for (addr, value) in self.cells {
+            let (index, _) = from_relocatable_to_indexes(addr);
+            if addr.segment_index < 0 {
+                while vm.segments.num_temp_segments() <= index {
+                    vm.segments.add_temporary_segment();
+                }
+            } else {
+                while vm.segments.num_segments() <= index {
+                    vm.segments.add();
+                }
+            }
+            vm.segments.memory.insert(addr, value)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a VM (with the trace disabled) holding the cells.
+    pub fn build(self) -> Result<VirtualMachine, MemoryError> {
+        let mut vm = VirtualMachine::new(false);
+        self.load_into(&mut vm)?;
+        Ok(vm)
+    }
+}
+
+/// Creates a [VirtualMachine](crate::vm::vm_core::VirtualMachine) with the given memory cells,
+/// see [MemoryBuilder].
+///
+/// ```
+/// use cairo_vm::{vm_with_memory, Felt252};
+///
+/// let vm = vm_with_memory![((1, 0), 5), ((1, 1), (2, 0))];
+/// assert_eq!(*vm.segments.memory.get_integer((1, 0).into()).unwrap(), Felt252::from(5));
+/// ```
+#[macro_export]
+macro_rules! vm_with_memory {
+    ( $( ($addr:expr, $val:expr) ),* $(,)? ) => {
+        $crate::testing::MemoryBuilder::new()
+            $(
+                .cell(
+                    $crate::types::relocatable::Relocatable::from($addr),
+                    $crate::types::relocatable::MaybeRelocatable::from($val),
+                )
+            )*
+            .build()
+            .unwrap()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Felt252;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn build_adds_segments() {
+        let vm = crate::vm_with_memory![((2, 1), 7), ((-2, 0), (0, 3))];
+        assert_eq!(vm.segments.num_segments(), 3);
+        assert_eq!(vm.segments.num_temp_segments(), 2);
+        assert_eq!(
+            *vm.segments.memory.get_integer((2, 1).into()).unwrap(),
+            Felt252::from(7)
+        );
+        assert_eq!(
+            vm.segments.memory.get_relocatable((-2, 0).into()),
+            Ok((0, 3).into())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_into_inconsistent_memory() {
+        let mut vm = MemoryBuilder::new()
+            .cell((0, 0), Felt252::from(1))
+            .build()
+            .unwrap();
+        assert_matches!(
+            MemoryBuilder::new()
+                .cell((0, 0), Felt252::from(2))
+                .load_into(&mut vm),
+            Err(MemoryError::InconsistentMemory(_))
+        );
+    }
+}
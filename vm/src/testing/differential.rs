@@ -0,0 +1,419 @@
+//! Differential testing of hint processors.
+//!
+//! Runs a program under a hint processor and compares the execution against a reference: either
+//! another hint processor (e.g. a previous release of the builtin one) or the trace and memory
+//! recorded by another VM (e.g. `cairo-run --trace_file --memory_file` from cairo-lang).
+//! The first divergence is reported, which catches hint regressions such as wrong modular
+//! reductions without having to write an assertion for each hint.
+
+use crate::stdlib::prelude::*;
+use crate::types::{
+    layout_name::LayoutName,
+    program::Program,
+    relocatable::{MaybeRelocatable, Relocatable},
+};
+use crate::vm::{
+    errors::{runner_errors::RunnerError, trace_errors::TraceError},
+    runners::cairo_runner::CairoRunner,
+    trace::trace_entry::{RelocatedTraceEntry, TraceEntry},
+    vm_memory::memory::{Memory, MemoryCell},
+};
+use crate::{hint_processor::hint_processor_definition::HintProcessor, Felt252};
+use thiserror_no_std::Error;
+
+/// The first difference found between a run and its reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// The registers differ before executing the given step
+    Registers {
+        step: usize,
+        expected: TraceEntry,
+        actual: TraceEntry,
+    },
+    /// The relocated registers differ before executing the given step
+    RelocatedRegisters {
+        step: usize,
+        expected: RelocatedTraceEntry,
+        actual: RelocatedTraceEntry,
+    },
+    /// A memory cell holds a different value, or is only set in one of the runs
+    Memory {
+        addr: Relocatable,
+        expected: Option<MaybeRelocatable>,
+        actual: Option<MaybeRelocatable>,
+    },
+    /// A relocated memory cell holds a different value, or is only set in one of the runs
+    RelocatedMemory {
+        addr: usize,
+        expected: Option<Felt252>,
+        actual: Option<Felt252>,
+    },
+    /// The runs stopped after a different number of steps or with a different error
+    Outcome {
+        expected_steps: usize,
+        actual_steps: usize,
+        expected_error: Option<String>,
+        actual_error: Option<String>,
+    },
+}
+
+struct TracedRun {
+    runner: CairoRunner,
+    error: Option<String>,
+}
+
+impl TracedRun {
+    fn new(
+        program: &Program,
+        layout: LayoutName,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<Self, RunnerError> {
+        let mut runner = CairoRunner::new(program, layout, None, false, true)?;
+        let end = runner.initialize(false)?;
+        let error = runner
+            .run_until_pc(end, hint_processor)
+            .err()
+            .map(|error| error.to_string());
+        Ok(TracedRun { runner, error })
+    }
+
+    fn trace(&self) -> &[TraceEntry] {
+        self.runner.vm.trace.as_deref().unwrap_or_default()
+    }
+}
+
+/// Runs the program under both hint processors and returns the first divergence of the tested
+/// one from the reference, if any.
+///
+/// Registers are compared step by step. Memory is only compared once both runs stopped, so a
+/// wrong value is reported at its address rather than at the step that wrote it.
+pub fn diff_hint_processors(
+    program: &Program,
+    layout: LayoutName,
+    reference: &mut dyn HintProcessor,
+    tested: &mut dyn HintProcessor,
+) -> Result<Option<Divergence>, RunnerError> {
+    let expected = TracedRun::new(program, layout, reference)?;
+    let actual = TracedRun::new(program, layout, tested)?;
+
+    let registers = expected
+        .trace()
+        .iter()
+        .zip(actual.trace())
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+        .map(|(step, (expected, actual))| Divergence::Registers {
+            step,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        });
+    let memory = memory_divergences(
+        &expected.runner.vm.segments.memory,
+        &actual.runner.vm.segments.memory,
+    );
+    let outcome = (expected.trace().len() != actual.trace().len()
+        || expected.error != actual.error)
+        .then(|| Divergence::Outcome {
+            expected_steps: expected.trace().len(),
+            actual_steps: actual.trace().len(),
+            expected_error: expected.error.clone(),
+            actual_error: actual.error.clone(),
+        });
+
+    // Cells set in only one of the runs are usually a consequence of the outcome divergence.
+    let (mismatched_cells, missing_cells): (Vec<_>, Vec<_>) =
+        memory.into_iter().partition(|divergence| {
+            matches!(
+                divergence,
+                Divergence::Memory {
+                    expected: Some(_),
+                    actual: Some(_),
+                    ..
+                }
+            )
+        });
+    Ok(registers
+        .or_else(|| mismatched_cells.into_iter().next())
+        .or(outcome)
+        .or_else(|| missing_cells.into_iter().next()))
+}
+
+fn memory_divergences(expected: &Memory, actual: &Memory) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+    let mut compare = |expected: &[Vec<MemoryCell>],
+                       actual: &[Vec<MemoryCell>],
+                       segment_index: fn(usize) -> isize| {
+        for index in 0..expected.len().max(actual.len()) {
+            let expected = expected.get(index).map_or(&[][..], |segment| &segment[..]);
+            let actual = actual.get(index).map_or(&[][..], |segment| &segment[..]);
+            for offset in 0..expected.len().max(actual.len()) {
+                let expected = expected.get(offset).and_then(MemoryCell::get_value);
+                let actual = actual.get(offset).and_then(MemoryCell::get_value);
+                if expected != actual {
+                    divergences.push(Divergence::Memory {
+                        addr: Relocatable::from((segment_index(index), offset)),
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+    };
+    compare(&expected.data, &actual.data, |index| index as isize);
+    compare(&expected.temp_data, &actual.temp_data, |index| {
+        -(index as isize) - 1
+    });
+    divergences
+}
+
+/// Compares a relocated run against a recorded trace and memory, and returns the first
+/// divergence, if any.
+///
+/// The runner must have been run with the trace enabled and relocated (see
+/// [CairoRunner::relocate]).
+pub fn diff_with_recording(
+    runner: &CairoRunner,
+    expected_trace: &[RelocatedTraceEntry],
+    expected_memory: &[Option<Felt252>],
+) -> Result<Option<Divergence>, TraceError> {
+    let actual_trace = runner
+        .relocated_trace
+        .as_ref()
+        .ok_or(TraceError::TraceNotRelocated)?;
+    if runner.relocated_memory.is_empty() {
+        return Err(TraceError::MemoryNotRelocated);
+    }
+
+    let registers = expected_trace
+        .iter()
+        .zip(actual_trace)
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+        .map(
+            |(step, (expected, actual))| Divergence::RelocatedRegisters {
+                step,
+                expected: expected.clone(),
+                actual: actual.clone(),
+            },
+        );
+    let outcome = (expected_trace.len() != actual_trace.len()).then(|| Divergence::Outcome {
+        expected_steps: expected_trace.len(),
+        actual_steps: actual_trace.len(),
+        expected_error: None,
+        actual_error: None,
+    });
+    let memory = (0..expected_memory.len().max(runner.relocated_memory.len()))
+        .map(|addr| {
+            (
+                addr,
+                expected_memory.get(addr).copied().flatten(),
+                runner.relocated_memory.get(addr).copied().flatten(),
+            )
+        })
+        .find(|(_, expected, actual)| expected != actual)
+        .map(|(addr, expected, actual)| Divergence::RelocatedMemory {
+            addr,
+            expected,
+            actual,
+        });
+    Ok(registers.or(outcome).or(memory))
+}
+
+/// Decodes a trace written by [write_encoded_trace](crate::cairo_run::write_encoded_trace) or
+/// by cairo-lang's `--trace_file`. Trailing bytes that don't make a whole entry are ignored.
+pub fn decode_trace(bytes: &[u8]) -> Vec<RelocatedTraceEntry> {
+    bytes
+        .chunks_exact(24)
+        .map(|entry| {
+            let word = |index: usize| {
+                let mut word = [0; 8];
+                word.copy_from_slice(&entry[index * 8..(index + 1) * 8]);
+                u64::from_le_bytes(word) as usize
+            };
+            RelocatedTraceEntry {
+                ap: word(0),
+                fp: word(1),
+                pc: word(2),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DecodeMemoryError {
+    #[error("Memory address {0} is out of the maximum memory size {1}")]
+    AddressOutOfBounds(u64, usize),
+}
+
+/// Decodes a relocated memory written by
+/// [write_encoded_memory](crate::cairo_run::write_encoded_memory) or by cairo-lang's
+/// `--memory_file`. Trailing bytes that don't make a whole cell are ignored.
+///
+/// As the addresses are read from the input, the decoded memory is capped to `max_size` cells
+/// (holes included) so that a corrupted file can't make it allocate without bound.
+pub fn decode_memory(
+    bytes: &[u8],
+    max_size: usize,
+) -> Result<Vec<Option<Felt252>>, DecodeMemoryError> {
+    let mut memory = Vec::new();
+    for cell in bytes.chunks_exact(40) {
+        let mut addr = [0; 8];
+        addr.copy_from_slice(&cell[..8]);
+        let addr = u64::from_le_bytes(addr);
+        let size = usize::try_from(addr)
+            .ok()
+            .and_then(|addr| addr.checked_add(1))
+            .filter(|size| *size <= max_size)
+            .ok_or(DecodeMemoryError::AddressOutOfBounds(addr, max_size))?;
+        let mut value = [0; 32];
+        value.copy_from_slice(&cell[8..]);
+        if memory.len() < size {
+            memory.resize(size, None);
+        }
+        memory[size - 1] = Some(Felt252::from_bytes_le(&value));
+    }
+    Ok(memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cairo_run::{cairo_run, write_encoded_memory, write_encoded_trace, CairoRunConfig};
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::{
+        BuiltinHintProcessor, HintFunc,
+    };
+    use crate::stdlib::{collections::HashMap, rc::Rc};
+    use crate::vm::errors::hint_errors::HintError;
+    use crate::vm::vm_core::VirtualMachine;
+    use crate::{
+        hint_processor::builtin_hint_processor::hint_utils::get_integer_from_var_name,
+        hint_processor::hint_processor_definition::HintReference,
+        serde::deserialize_program::ApTracking, types::exec_scope::ExecutionScopes,
+    };
+    use bincode::enc::write::SliceWriter;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn program(bytes: &[u8]) -> Program {
+        Program::from_bytes(bytes, Some("main")).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn same_hint_processor_does_not_diverge() {
+        let program = program(include_bytes!("../../../cairo_programs/assert_nn.json"));
+        assert_eq!(
+            diff_hint_processors(
+                &program,
+                LayoutName::all_cairo,
+                &mut BuiltinHintProcessor::new_empty(),
+                &mut BuiltinHintProcessor::new_empty(),
+            )
+            .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn overridden_hint_diverges() {
+        // Makes `assert_nn` fail for every value
+        fn failing_assert_nn(
+            vm: &mut VirtualMachine,
+            _exec_scopes: &mut ExecutionScopes,
+            ids_data: &HashMap<String, HintReference>,
+            ap_tracking: &ApTracking,
+            _constants: &HashMap<String, Felt252>,
+        ) -> Result<(), HintError> {
+            let a = get_integer_from_var_name("a", vm, ids_data, ap_tracking)?;
+            Err(HintError::AssertNNValueOutOfRange(Box::new(a)))
+        }
+
+        let program = program(include_bytes!("../../../cairo_programs/assert_nn.json"));
+        let mut tested = BuiltinHintProcessor::new_empty();
+        tested.add_hint(
+            crate::hint_processor::builtin_hint_processor::hint_code::ASSERT_NN.to_string(),
+            Rc::new(HintFunc(Box::new(failing_assert_nn))),
+        );
+        let divergence = diff_hint_processors(
+            &program,
+            LayoutName::all_cairo,
+            &mut BuiltinHintProcessor::new_empty(),
+            &mut tested,
+        )
+        .unwrap();
+        assert!(matches!(
+            divergence,
+            Some(Divergence::Outcome {
+                expected_error: None,
+                actual_error: Some(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn diff_with_recorded_run() {
+        let config = CairoRunConfig {
+            trace_enabled: true,
+            relocate_mem: true,
+            ..Default::default()
+        };
+        let runner = cairo_run(
+            include_bytes!("../../../cairo_programs/fibonacci.json"),
+            &config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+        let mut trace = vec![0; 24 * runner.relocated_trace.as_ref().unwrap().len()];
+        write_encoded_trace(
+            runner.relocated_trace.as_ref().unwrap(),
+            &mut SliceWriter::new(&mut trace),
+        )
+        .unwrap();
+        let mut memory = vec![0; 40 * runner.relocated_memory.iter().flatten().count()];
+        write_encoded_memory(&runner.relocated_memory, &mut SliceWriter::new(&mut memory)).unwrap();
+        let (trace, mut memory) = (
+            decode_trace(&trace),
+            decode_memory(&memory, runner.relocated_memory.len()).unwrap(),
+        );
+
+        assert_eq!(trace, *runner.relocated_trace.as_ref().unwrap());
+        assert_eq!(diff_with_recording(&runner, &trace, &memory), Ok(None));
+
+        let last = memory.len() - 1;
+        memory[last] = Some(Felt252::from(3));
+        assert_eq!(
+            diff_with_recording(&runner, &trace, &memory),
+            Ok(Some(Divergence::RelocatedMemory {
+                addr: last,
+                expected: Some(Felt252::from(3)),
+                actual: runner.relocated_memory[last],
+            }))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn decode_memory_rejects_addresses_out_of_bounds() {
+        let cell = |addr: u64| {
+            let mut cell = addr.to_le_bytes().to_vec();
+            cell.extend_from_slice(&Felt252::from(7).to_bytes_le());
+            cell
+        };
+        assert_eq!(
+            decode_memory(&cell(3), 4),
+            Ok(vec![None, None, None, Some(Felt252::from(7))])
+        );
+        assert_eq!(
+            decode_memory(&cell(4), 4),
+            Err(DecodeMemoryError::AddressOutOfBounds(4, 4))
+        );
+        assert_eq!(
+            decode_memory(&cell(u64::MAX), usize::MAX),
+            Err(DecodeMemoryError::AddressOutOfBounds(u64::MAX, usize::MAX))
+        );
+    }
+}
@@ -658,3 +658,30 @@ fn fibonacci_with_run_resources_error() {
     .is_err());
     assert_eq!(hint_processor.run_resources(), &RunResources::new(0));
 }
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn init_squash_data_with_run_resources_charging_hint_iterations() {
+    let program_data =
+        include_bytes!("../../../cairo_programs/cairo-1-contracts/init_squash_data.casm");
+    let contract_class: CasmContractClass = serde_json::from_slice(program_data).unwrap();
+    let remaining_steps = |run_resources: RunResources| {
+        let mut hint_processor =
+            Cairo1HintProcessor::new(&contract_class.hints, run_resources, false);
+        assert_matches!(
+            run_cairo_1_entrypoint_with_run_resources(
+                contract_class.clone(),
+                0,
+                &mut hint_processor,
+                &[10_usize.into()],
+            ),
+            Ok(x) if x == [10_usize.into()]
+        );
+        hint_processor.run_resources().get_n_steps().unwrap()
+    };
+    // The dictionary accesses looped over by InitSquashData are charged on top of the VM steps
+    assert!(
+        remaining_steps(RunResources::new(10_000).with_hint_iterations())
+            < remaining_steps(RunResources::new(10_000))
+    );
+}
@@ -0,0 +1,176 @@
+use crate::stdlib::{cell::RefCell, rc::Rc};
+
+use crate::{
+    hint_processor::builtin_hint_processor::{
+        builtin_hint_processor_definition::BuiltinHintProcessor, dict_manager::DictManager,
+    },
+    types::{
+        program::Program,
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    utils::test_utils::*,
+    vm::errors::{
+        cairo_run_errors::CairoRunError, hint_errors::HintError, vm_errors::VirtualMachineError,
+    },
+    Felt252,
+};
+use assert_matches::assert_matches;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+fn run_main(
+    program: &Program,
+    hint_processor: &mut BuiltinHintProcessor,
+) -> Result<(), CairoRunError> {
+    let mut runner = cairo_runner!(program);
+    runner.initialize_function_runner().unwrap();
+    let main = program.get_identifier("__main__.main").unwrap().pc.unwrap();
+    runner.run_from_entrypoint(main, &[], true, None, hint_processor)
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn dict_manager_shared_across_entrypoint_runs_on_the_same_runner() {
+    let program = Program::from_bytes(
+        include_bytes!("../../../cairo_programs/dict_reentry.json"),
+        Some("main"),
+    )
+    .unwrap();
+    let dict_manager = Rc::new(RefCell::new(DictManager::new()));
+    let mut hint_processor =
+        BuiltinHintProcessor::new_empty().with_dict_manager(Rc::clone(&dict_manager));
+    let mut runner = cairo_runner!(program);
+    runner.initialize_function_runner().unwrap();
+    let create_and_write = program
+        .get_identifier("__main__.create_and_write")
+        .unwrap()
+        .pc
+        .unwrap();
+    let read_and_write = program
+        .get_identifier("__main__.read_and_write")
+        .unwrap()
+        .pc
+        .unwrap();
+
+    // create_and_write(key=12, value=34) -> (dict_ptr)
+    runner
+        .run_from_entrypoint(
+            create_and_write,
+            &[
+                &MaybeRelocatable::from(12).into(),
+                &MaybeRelocatable::from(34).into(),
+            ],
+            true,
+            None,
+            &mut hint_processor,
+        )
+        .unwrap();
+    let dict_ptr = runner.vm.get_return_values(1).unwrap()[0].clone();
+    let dict_segment = Relocatable::try_from(&dict_ptr).unwrap().segment_index;
+
+    // read_and_write{dict_ptr}(key=12, new_value=35) -> (dict_ptr, value)
+    runner
+        .run_from_entrypoint(
+            read_and_write,
+            &[
+                &dict_ptr.into(),
+                &MaybeRelocatable::from(12).into(),
+                &MaybeRelocatable::from(35).into(),
+            ],
+            true,
+            None,
+            &mut hint_processor,
+        )
+        .unwrap();
+    // The second call read the value written by the first one
+    let return_values = runner.vm.get_return_values(2).unwrap();
+    assert_eq!(return_values[1], MaybeRelocatable::from(34));
+
+    // Both calls updated the same tracker
+    let dict_manager = dict_manager.borrow();
+    assert_eq!(dict_manager.trackers.len(), 1);
+    let tracker = &dict_manager.trackers[&dict_segment];
+    assert_eq!(
+        MaybeRelocatable::from(tracker.current_ptr),
+        return_values[0]
+    );
+    let dict = tracker.get_dictionary_copy();
+    assert_eq!(
+        dict.get(&MaybeRelocatable::from(12)),
+        Some(&MaybeRelocatable::from(35))
+    );
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn dict_manager_shared_across_entrypoint_runs() {
+    let program = Program::from_bytes(
+        include_bytes!("../../../cairo_programs/dict.json"),
+        Some("main"),
+    )
+    .unwrap();
+    let dict_manager = Rc::new(RefCell::new(DictManager::new()));
+    let mut hint_processor =
+        BuiltinHintProcessor::new_empty().with_dict_manager(Rc::clone(&dict_manager));
+
+    run_main(&program, &mut hint_processor).unwrap();
+
+    // The dictionary created during the first run outlives it
+    let first_run_trackers = dict_manager.borrow_mut().take_trackers();
+    assert_eq!(first_run_trackers.len(), 1);
+    let first_run_dict = first_run_trackers
+        .values()
+        .next()
+        .unwrap()
+        .get_dictionary_copy();
+    assert_eq!(
+        first_run_dict.get(&MaybeRelocatable::from(12)),
+        Some(&MaybeRelocatable::from(35))
+    );
+    assert_eq!(
+        first_run_dict.get(&MaybeRelocatable::from(20)),
+        Some(&MaybeRelocatable::from(Felt252::from(-5)))
+    );
+
+    // Re-entering with the same hint processor keeps using the shared manager
+    run_main(&program, &mut hint_processor).unwrap();
+
+    let dict_manager = dict_manager.borrow();
+    assert_eq!(dict_manager.trackers.len(), 1);
+    assert_eq!(
+        dict_manager
+            .trackers
+            .values()
+            .next()
+            .unwrap()
+            .get_dictionary_copy(),
+        first_run_dict
+    );
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn dict_manager_shared_across_entrypoint_runs_rejects_tracked_segments() {
+    let program = Program::from_bytes(
+        include_bytes!("../../../cairo_programs/dict.json"),
+        Some("main"),
+    )
+    .unwrap();
+    let dict_manager = Rc::new(RefCell::new(DictManager::new()));
+    let mut hint_processor =
+        BuiltinHintProcessor::new_empty().with_dict_manager(Rc::clone(&dict_manager));
+
+    run_main(&program, &mut hint_processor).unwrap();
+
+    // The first run's trackers weren't taken out, so the new dictionary collides with them
+    assert_matches!(
+        run_main(&program, &mut hint_processor),
+        Err(CairoRunError::VmException(exception))
+            if matches!(
+                &exception.inner_exc,
+                VirtualMachineError::Hint(bx)
+                    if matches!(bx.1, HintError::CantCreateDictionaryOnTakenSegment(_))
+            )
+    );
+}
@@ -35,6 +35,7 @@ mod run_deprecated_contract_class_simplified;
 #[cfg(feature = "cairo-1-hints")]
 mod cairo_1_run_from_entrypoint_tests;
 mod cairo_run_test;
+mod dict_manager_test;
 mod pedersen_test;
 mod struct_test;
 
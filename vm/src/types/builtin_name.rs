@@ -1,7 +1,9 @@
-use serde::{Deserialize, Serialize};
+use crate::stdlib::{boxed::Box, string::String, vec::Vec};
+use lazy_static::lazy_static;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 #[cfg(feature = "test_utils")]
-use arbitrary::{self, Arbitrary};
+use arbitrary::{self, Arbitrary, Unstructured};
 
 // Internal constants
 const OUTPUT_BUILTIN_NAME: &str = "output";
@@ -30,9 +32,21 @@ const SEGMENT_ARENA_BUILTIN_NAME_WITH_SUFFIX: &str = "segment_arena_builtin";
 const ADD_MOD_BUILTIN_NAME_WITH_SUFFIX: &str = "add_mod_builtin";
 const MUL_MOD_BUILTIN_NAME_WITH_SUFFIX: &str = "mul_mod_builtin";
 
+const BUILTIN_NAME_SUFFIX: &str = "_builtin";
+// Builtin names are encoded as Cairo short strings (e.g. in the program hash)
+const MAX_BUILTIN_NAME_LEN: usize = 31;
+// Custom builtin names are never freed, so the amount of them that can be registered is capped
+const MAX_CUSTOM_BUILTIN_NAMES: usize = 64;
+
+lazy_static! {
+    // Names with the "_builtin" suffix of the custom builtins registered with `CustomBuiltinName::new`
+    static ref CUSTOM_BUILTIN_NAMES: spin::RwLock<Vec<&'static String>> =
+        spin::RwLock::new(Vec::new());
+}
+
 /// Enum representing the name of a cairo builtin
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
-#[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
+#[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
 #[allow(non_camel_case_types)]
 pub enum BuiltinName {
     output,
@@ -47,6 +61,102 @@ pub enum BuiltinName {
     range_check96,
     add_mod,
     mul_mod,
+    /// A builtin that isn't part of Cairo, run by a user-provided
+    /// [CustomBuiltin](crate::vm::runners::builtin_runner::CustomBuiltin)
+    custom(CustomBuiltinName),
+}
+
+/// Name of a builtin that isn't part of Cairo, see [BuiltinName::custom]
+// Holds the name with the "_builtin" suffix behind a thin pointer, to keep [BuiltinName] small
+#[derive(PartialEq, Copy, Clone, Eq, Hash)]
+pub struct CustomBuiltinName(&'static String);
+
+impl CustomBuiltinName {
+    /// Registers the name of a custom builtin from its string representation, without the
+    /// "_builtin" suffix, returning the already registered name if there is one.
+    /// Returns None if the name is the one of a Cairo builtin, if it isn't a non-empty ascii
+    /// string of at most 31 characters, as builtin names are encoded as Cairo short strings, or if
+    /// 64 custom builtin names were already registered.
+    ///
+    /// As [BuiltinName] is `Copy`, registered names are kept for the lifetime of the process.
+    /// Custom builtin names must be registered before deserializing the programs that use them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use cairo_vm::types::builtin_name::{BuiltinName, CustomBuiltinName};
+    ///
+    /// let name = CustomBuiltinName::new("sha256").unwrap();
+    /// assert_eq!(BuiltinName::custom(name).to_str_with_suffix(), "sha256_builtin");
+    /// assert_eq!(CustomBuiltinName::get("sha256"), Some(name));
+    ///
+    /// assert_eq!(CustomBuiltinName::new("poseidon"), None);
+    ///
+    /// ```
+    pub fn new(name: &str) -> Option<Self> {
+        if name.is_empty()
+            || name.len() > MAX_BUILTIN_NAME_LEN
+            || !name.is_ascii()
+            || BuiltinName::from_str(name).is_some()
+        {
+            return None;
+        }
+        let mut names = CUSTOM_BUILTIN_NAMES.write();
+        if let Some(registered) = Self::find(&names, name) {
+            return Some(registered);
+        }
+        if names.len() >= MAX_CUSTOM_BUILTIN_NAMES {
+            return None;
+        }
+        let name_with_suffix: &'static String =
+            Box::leak(Box::new(format!("{name}{BUILTIN_NAME_SUFFIX}")));
+        names.push(name_with_suffix);
+        Some(CustomBuiltinName(name_with_suffix))
+    }
+
+    /// Returns the custom builtin name registered with [CustomBuiltinName::new] from its string
+    /// representation, without the "_builtin" suffix.
+    /// Returns None if no such name was registered.
+    pub fn get(name: &str) -> Option<Self> {
+        Self::find(&CUSTOM_BUILTIN_NAMES.read(), name)
+    }
+
+    fn find(names: &[&'static String], name: &str) -> Option<Self> {
+        names
+            .iter()
+            .find(|registered| {
+                registered
+                    .strip_suffix(BUILTIN_NAME_SUFFIX)
+                    .is_some_and(|registered| registered == name)
+            })
+            .copied()
+            .map(CustomBuiltinName)
+    }
+
+    /// Returns the string representation of the custom builtin name, without the "_builtin" suffix
+    pub fn to_str(self) -> &'static str {
+        let name_with_suffix: &'static str = self.0;
+        &name_with_suffix[..name_with_suffix.len() - BUILTIN_NAME_SUFFIX.len()]
+    }
+
+    fn to_str_with_suffix(self) -> &'static str {
+        self.0
+    }
+}
+
+impl core::fmt::Debug for CustomBuiltinName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("CustomBuiltinName")
+            .field(&self.to_str())
+            .finish()
+    }
+}
+
+#[cfg(feature = "test_utils")]
+impl<'a> Arbitrary<'a> for CustomBuiltinName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        CustomBuiltinName::new(u.arbitrary::<&str>()?).ok_or(arbitrary::Error::IncorrectFormat)
+    }
 }
 
 impl BuiltinName {
@@ -75,6 +185,7 @@ impl BuiltinName {
             BuiltinName::range_check96 => RANGE_CHECK_96_BUILTIN_NAME_WITH_SUFFIX,
             BuiltinName::add_mod => ADD_MOD_BUILTIN_NAME_WITH_SUFFIX,
             BuiltinName::mul_mod => MUL_MOD_BUILTIN_NAME_WITH_SUFFIX,
+            BuiltinName::custom(name) => name.to_str_with_suffix(),
         }
     }
 
@@ -103,10 +214,12 @@ impl BuiltinName {
             BuiltinName::range_check96 => RANGE_CHECK_96_BUILTIN_NAME,
             BuiltinName::add_mod => ADD_MOD_BUILTIN_NAME,
             BuiltinName::mul_mod => MUL_MOD_BUILTIN_NAME,
+            BuiltinName::custom(name) => name.to_str(),
         }
     }
 
-    /// Converts a [`BuiltinName`] from its string representation removing the "_builtin" suffix
+    /// Converts a [`BuiltinName`] from its string representation removing the "_builtin" suffix.
+    /// Only Cairo builtins are converted, see [`CustomBuiltinName::new`] for custom builtins
     ///
     /// ## Example
     ///
@@ -138,7 +251,8 @@ impl BuiltinName {
 
     // Implementing this as a trait would generate confusion as `Display` impl uses suffixed version
     #[allow(clippy::should_implement_trait)]
-    /// Converts a [`BuiltinName`] from its string representation
+    /// Converts a [`BuiltinName`] from its string representation.
+    /// Only Cairo builtins are converted, see [`CustomBuiltinName::new`] for custom builtins
     ///
     /// ## Example
     ///
@@ -176,9 +290,26 @@ impl core::fmt::Display for BuiltinName {
     }
 }
 
+impl Serialize for BuiltinName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_str())
+    }
+}
+
+// Names that aren't the ones of Cairo builtins are only accepted if they were registered as custom
+// builtin names with `CustomBuiltinName::new`
+impl<'de> Deserialize<'de> for BuiltinName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        BuiltinName::from_str(&name)
+            .or_else(|| CustomBuiltinName::get(&name).map(BuiltinName::custom))
+            .ok_or_else(|| D::Error::custom(format!("Invalid builtin name {name}")))
+    }
+}
+
 // Implementation of custom serialization & deserialization for maps using builtin names with suffixes as keys
 pub(crate) mod serde_generic_map_impl {
-    use super::BuiltinName;
+    use super::{BuiltinName, CustomBuiltinName, BUILTIN_NAME_SUFFIX};
     use crate::stdlib::{collections::HashMap, string::String};
     use serde::{de::Error, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -204,7 +335,15 @@ pub(crate) mod serde_generic_map_impl {
         let map = HashMap::<String, V>::deserialize(d)?;
         // Then match keys to BuiltinName and handle invalid names
         map.into_iter()
-            .map(|(k, v)| BuiltinName::from_str_with_suffix(&k).map(|k| (k, v)))
+            .map(|(k, v)| {
+                BuiltinName::from_str_with_suffix(&k)
+                    .or_else(|| {
+                        k.strip_suffix(BUILTIN_NAME_SUFFIX)
+                            .and_then(CustomBuiltinName::get)
+                            .map(BuiltinName::custom)
+                    })
+                    .map(|k| (k, v))
+            })
             .collect::<Option<HashMap<_, _>>>()
             .ok_or(D::Error::custom("Invalid builtin name"))
     }
@@ -0,0 +1,47 @@
+use crate::{stdlib::prelude::*, types::relocatable::MaybeRelocatable};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CairoArg {
+    Single(MaybeRelocatable),
+    Array(Vec<MaybeRelocatable>),
+    Composed(Vec<CairoArg>),
+}
+
+impl From<MaybeRelocatable> for CairoArg {
+    fn from(other: MaybeRelocatable) -> Self {
+        CairoArg::Single(other)
+    }
+}
+
+impl From<Vec<MaybeRelocatable>> for CairoArg {
+    fn from(other: Vec<MaybeRelocatable>) -> Self {
+        CairoArg::Array(other)
+    }
+}
+
+/// Memory layout of a value returned by a Cairo function, used to read it back as a [CairoArg].
+/// See [VirtualMachine::get_typed_return_values](crate::vm::vm_core::VirtualMachine::get_typed_return_values).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CairoReturnType {
+    /// A single cell, read as [CairoArg::Single].
+    Single,
+    /// An array returned as its length followed by a pointer to its first element, read as
+    /// [CairoArg::Array].
+    Array,
+    /// A span returned as pointers to its first and past-the-end elements, read as
+    /// [CairoArg::Array].
+    Span,
+    /// A struct with the given members, read as [CairoArg::Composed].
+    Composed(Vec<CairoReturnType>),
+}
+
+impl CairoReturnType {
+    /// Returns the number of cells the value takes on the stack
+    pub fn size(&self) -> usize {
+        match self {
+            CairoReturnType::Single => 1,
+            CairoReturnType::Array | CairoReturnType::Span => 2,
+            CairoReturnType::Composed(members) => members.iter().map(CairoReturnType::size).sum(),
+        }
+    }
+}
@@ -9,6 +9,7 @@ use thiserror_no_std::Error;
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 
 #[derive(Debug, Error, PartialEq)]
+#[non_exhaustive]
 pub enum MathError {
     // Math functions
     #[error("Can't calculate the square root of negative number: {0})")]
@@ -25,13 +26,23 @@ pub enum MathError {
     SafeDivFailUsize(Box<(usize, usize)>),
     #[error("Attempted to divide by zero")]
     DividedByZero,
+    #[error("Attempted to divide {0} by zero")]
+    DividedByZeroFelt(Box<Felt252>),
+    #[error("Attempted to divide {0} by zero")]
+    DividedByZeroUsize(usize),
     #[error("Failed to calculate the square root of: {0})")]
     FailedToGetSqrt(Box<BigUint>),
     #[error("is_quad_residue: p must be > 0")]
     IsQuadResidueZeroPrime,
+    #[error("Can't add EC points {:?} and {:?}: they have the same x coordinate", (*.0).0, (*.0).1)]
+    EcAddSameXCoordinate(Box<((Felt252, Felt252), (Felt252, Felt252))>),
+    #[error("Can't double EC point {0:?}: its y coordinate is zero")]
+    EcDoubleZeroY(Box<(Felt252, Felt252)>),
     // Relocatable Operations
     #[error("Cant convert felt: {0} to Relocatable")]
     Felt252ToRelocatable(Box<Felt252>),
+    #[error("Cant convert relocatable: {0} to Felt252")]
+    RelocatableToFelt252(Box<Relocatable>),
     #[error("Operation failed: {} - {}, offsets cant be negative", (*.0).0, (*.0).1)]
     RelocatableSubFelt252NegOffset(Box<(Relocatable, Felt252)>),
     #[error("Operation failed: {} - {}, offsets cant be negative", (*.0).0, (*.0).1)]
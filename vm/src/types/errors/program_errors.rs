@@ -3,6 +3,7 @@ use crate::utils::PRIME_STR;
 use thiserror_no_std::Error;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ProgramError {
     #[cfg(feature = "std")]
     #[error(transparent)]
@@ -21,6 +22,10 @@ pub enum ProgramError {
     StrippedProgramNoMain,
     #[error("Hint PC ({0}) is greater or equal to program length ({1})")]
     InvalidHintPc(usize, usize),
+    #[error("Reference PC ({0}) is greater or equal to program length ({1})")]
+    InvalidReferencePc(usize, usize),
+    #[error("Hint at PC {0} uses reference id {1}, but there are only {2} references")]
+    InvalidHintReferenceId(usize, usize, usize),
 }
 
 #[cfg(test)]
@@ -1,22 +1,184 @@
-use crate::stdlib::{any::Any, cell::RefCell, collections::HashMap, prelude::*, rc::Rc};
+#[cfg(feature = "cairo-1-hints")]
+use crate::hint_processor::cairo_1_hint_processor::dict_manager::{
+    DictManagerExecScope, DictSquashExecScope,
+};
+use crate::stdlib::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    prelude::*,
+    rc::Rc,
+};
 use crate::{
     any_box,
     hint_processor::builtin_hint_processor::dict_manager::DictManager,
     vm::errors::{exec_scope_errors::ExecScopeError, hint_errors::HintError},
+    Felt252,
 };
+use lazy_static::lazy_static;
+use num_bigint::{BigInt, BigUint};
+use serde::Serialize;
+
+/// A value that can be deep-copied from behind a `dyn Any`.
+///
+/// Implemented for every `Clone` type. The types of the values that [`ExecutionScopes::try_clone`]
+/// can copy are registered with [`ExecutionScopes::register_cloneable_type`].
+pub trait CloneAny: Any {
+    fn clone_any(&self) -> Box<dyn Any>;
+}
+
+impl<T: Any + Clone> CloneAny for T {
+    fn clone_any(&self) -> Box<dyn Any> {
+        Box::new(self.clone())
+    }
+}
+
+// Copies a scope value known to be of type `T`
+type CloneFn = fn(&dyn Any) -> Option<Box<dyn Any>>;
+
+// How to copy the values of a type, along with the type's name
+type CloneableType = (CloneFn, &'static str);
+
+fn cloneable_type<T: CloneAny>() -> (TypeId, CloneableType) {
+    (
+        TypeId::of::<T>(),
+        (clone_as::<T>, core::any::type_name::<T>()),
+    )
+}
+
+fn clone_as<T: CloneAny>(value: &dyn Any) -> Option<Box<dyn Any>> {
+    value.downcast_ref::<T>().map(CloneAny::clone_any)
+}
+
+// The dict manager is shared through an `Rc`, so a copy needs its own dictionaries
+fn clone_dict_manager(value: &dyn Any) -> Option<Box<dyn Any>> {
+    value
+        .downcast_ref::<Rc<RefCell<DictManager>>>()
+        .map(|dict_manager| any_box!(Rc::new(RefCell::new(dict_manager.borrow().clone()))))
+}
+
+lazy_static! {
+    // The types of the variables created by the builtin and Cairo 1 hints, shared by every
+    // `ExecutionScopes` so that creating one doesn't allocate a registry
+    static ref DEFAULT_CLONEABLE_TYPES: HashMap<TypeId, CloneableType> = {
+        let types = [
+            cloneable_type::<Felt252>(),
+            cloneable_type::<BigInt>(),
+            cloneable_type::<BigUint>(),
+            cloneable_type::<u64>(),
+            cloneable_type::<usize>(),
+            cloneable_type::<bool>(),
+            cloneable_type::<Vec<Felt252>>(),
+            cloneable_type::<Vec<BigInt>>(),
+            cloneable_type::<Vec<u64>>(),
+            cloneable_type::<Vec<usize>>(),
+            cloneable_type::<HashMap<Felt252, Vec<Felt252>>>(),
+            cloneable_type::<HashMap<Felt252, Vec<u64>>>(),
+            (
+                TypeId::of::<Rc<RefCell<DictManager>>>(),
+                (
+                    clone_dict_manager as CloneFn,
+                    core::any::type_name::<Rc<RefCell<DictManager>>>(),
+                ),
+            ),
+        ];
+        #[cfg(feature = "cairo-1-hints")]
+        let types = types.into_iter().chain([
+            cloneable_type::<DictManagerExecScope>(),
+            cloneable_type::<DictSquashExecScope>(),
+        ]);
+        types.into_iter().collect()
+    };
+}
+
+/// A variable of an execution scope, see [`ExecutionScopes::get_scope_variables`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ScopeVariable {
+    pub name: String,
+    /// Only known for the types registered with [`ExecutionScopes::register_cloneable_type`]
+    pub type_name: Option<&'static str>,
+}
 
 #[derive(Debug)]
 pub struct ExecutionScopes {
     pub data: Vec<HashMap<String, Box<dyn Any>>>,
+    // Only the types registered on top of `DEFAULT_CLONEABLE_TYPES`
+    cloneable_types: HashMap<TypeId, CloneableType>,
 }
 
 impl ExecutionScopes {
     pub fn new() -> ExecutionScopes {
+        Self::from_scopes(vec![HashMap::new()])
+    }
+
+    /// Creates the execution scopes from the variables of each scope, the last one being the
+    /// current scope.
+    pub fn from_scopes(data: Vec<HashMap<String, Box<dyn Any>>>) -> ExecutionScopes {
         ExecutionScopes {
-            data: vec![HashMap::new()],
+            data,
+            cloneable_types: HashMap::new(),
         }
     }
 
+    /// Allows [`try_clone`](Self::try_clone) to copy the variables of type `T`.
+    pub fn register_cloneable_type<T: CloneAny>(&mut self) {
+        if !DEFAULT_CLONEABLE_TYPES.contains_key(&TypeId::of::<T>()) {
+            self.cloneable_types.extend([cloneable_type::<T>()]);
+        }
+    }
+
+    fn get_cloneable_type(&self, type_id: &TypeId) -> Option<&CloneableType> {
+        DEFAULT_CLONEABLE_TYPES
+            .get(type_id)
+            .or_else(|| self.cloneable_types.get(type_id))
+    }
+
+    /// Deep-copies all the scopes, so that the copy can be modified (e.g. while executing
+    /// speculatively) without affecting the original, and later be restored.
+    ///
+    /// Fails if a variable's type wasn't registered with
+    /// [`register_cloneable_type`](Self::register_cloneable_type) nor inserted with
+    /// [`insert_cloneable_value`](Self::insert_cloneable_value).
+    pub fn try_clone(&self) -> Result<ExecutionScopes, ExecScopeError> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for scope in &self.data {
+            let mut scope_copy = HashMap::with_capacity(scope.len());
+            for (name, value) in scope {
+                let value_copy = self
+                    .get_cloneable_type(&value.as_ref().type_id())
+                    .and_then(|(clone_fn, _)| clone_fn(value.as_ref()))
+                    .ok_or_else(|| ExecScopeError::NonCloneableVariable(name.as_str().into()))?;
+                scope_copy.insert(name.clone(), value_copy);
+            }
+            data.push(scope_copy);
+        }
+        Ok(ExecutionScopes {
+            data,
+            cloneable_types: self.cloneable_types.clone(),
+        })
+    }
+
+    /// Lists the variables of every scope, sorted by name, from the main scope to the current one.
+    /// Meant for inspecting the scopes after a failure, as the values themselves are opaque.
+    pub fn get_scope_variables(&self) -> Vec<Vec<ScopeVariable>> {
+        self.data
+            .iter()
+            .map(|scope| {
+                let mut variables: Vec<_> = scope
+                    .iter()
+                    .map(|(name, value)| ScopeVariable {
+                        name: name.clone(),
+                        type_name: self
+                            .get_cloneable_type(&value.as_ref().type_id())
+                            .map(|(_, type_name)| *type_name),
+                    })
+                    .collect();
+                variables.sort_by(|a, b| a.name.cmp(&b.name));
+                variables
+            })
+            .collect()
+    }
+
     pub fn enter_scope(&mut self, new_scope_locals: HashMap<String, Box<dyn Any>>) {
         self.data.push(new_scope_locals);
     }
@@ -182,6 +344,12 @@ impl ExecutionScopes {
     pub fn insert_value<T: 'static>(&mut self, name: &str, value: T) {
         self.assign_or_update_variable(name, any_box!(value));
     }
+
+    ///Inserts the value into the current scope, registering its type as cloneable
+    pub fn insert_cloneable_value<T: CloneAny>(&mut self, name: &str, value: T) {
+        self.register_cloneable_type::<T>();
+        self.insert_value(name, value);
+    }
 }
 
 impl Default for ExecutionScopes {
@@ -193,7 +361,7 @@ impl Default for ExecutionScopes {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Felt252;
+    use crate::hint_processor::builtin_hint_processor::dict_manager::DictTracker;
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
@@ -214,7 +382,7 @@ mod tests {
 
         let scope = HashMap::from([(var_name, var_value)]);
 
-        let scopes = ExecutionScopes { data: vec![scope] };
+        let scopes = ExecutionScopes::from_scopes(vec![scope]);
         assert_eq!(scopes.get_local_variables().unwrap().len(), 1);
         assert_eq!(
             scopes
@@ -235,12 +403,10 @@ mod tests {
 
         let new_scope = HashMap::from([(var_name, var_value)]);
 
-        let mut scopes = ExecutionScopes {
-            data: vec![HashMap::from([(
-                String::from("b"),
-                (Box::new(Felt252::ONE) as Box<dyn Any>),
-            )])],
-        };
+        let mut scopes = ExecutionScopes::from_scopes(vec![HashMap::from([(
+            String::from("b"),
+            (Box::new(Felt252::ONE) as Box<dyn Any>),
+        )])]);
 
         assert_eq!(scopes.get_local_variables().unwrap().len(), 1);
         assert_eq!(
@@ -336,7 +502,7 @@ mod tests {
 
         let scope = HashMap::from([(var_name, var_value)]);
 
-        let mut scopes = ExecutionScopes { data: vec![scope] };
+        let mut scopes = ExecutionScopes::from_scopes(vec![scope]);
 
         let var_value_new: Box<dyn Any> = Box::new(Felt252::from(3));
 
@@ -362,7 +528,7 @@ mod tests {
 
         let scope = HashMap::from([(var_name, var_value)]);
 
-        let mut scopes = ExecutionScopes { data: vec![scope] };
+        let mut scopes = ExecutionScopes::from_scopes(vec![scope]);
 
         assert!(scopes
             .get_local_variables()
@@ -475,4 +641,148 @@ mod tests {
         assert!(scopes.get_any_boxed_mut("no_variable").is_err());
         assert!(scopes.get_any_boxed_ref("no_variable").is_err());
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_clone_deep_copies_scopes() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Custom(Vec<u8>);
+
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("a", Felt252::from(2));
+        scopes.insert_value("dict_manager", Rc::new(RefCell::new(DictManager::new())));
+        scopes.enter_scope(HashMap::new());
+        scopes.insert_cloneable_value("custom", Custom(vec![1, 2]));
+
+        let mut copy = scopes.try_clone().unwrap();
+        assert_eq!(copy.data.len(), 2);
+        copy.get_mut_ref::<Custom>("custom").unwrap().0.push(3);
+        copy.exit_scope().unwrap();
+        copy.insert_value("a", Felt252::from(3));
+        copy.get_dict_manager()
+            .unwrap()
+            .borrow_mut()
+            .trackers
+            .insert(
+                1,
+                DictTracker::new_empty(crate::types::relocatable::Relocatable::from((1, 0))),
+            );
+
+        assert_eq!(scopes.get_ref::<Custom>("custom"), Ok(&Custom(vec![1, 2])));
+        scopes.exit_scope().unwrap();
+        assert_eq!(scopes.get::<Felt252>("a"), Ok(Felt252::from(2)));
+        assert!(scopes
+            .get_dict_manager()
+            .unwrap()
+            .borrow()
+            .trackers
+            .is_empty());
+        assert_eq!(copy.get_dict_manager().unwrap().borrow().trackers.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "cairo-1-hints")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_clone_cairo_1_dict_scopes() {
+        let mut vm = crate::vm::vm_core::VirtualMachine::new(false);
+        let mut dict_manager = DictManagerExecScope::new(false);
+        let dict_ptr = dict_manager.new_default_dict(&mut vm).unwrap();
+
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_box("dict_manager_exec_scope", Box::new(dict_manager));
+        scopes.insert_value(
+            "dict_squash_exec_scope",
+            DictSquashExecScope {
+                access_indices: HashMap::from([(Felt252::ONE, vec![Felt252::ZERO])]),
+                keys: vec![Felt252::ONE],
+            },
+        );
+
+        let mut copy = scopes.try_clone().unwrap();
+        copy.get_mut_ref::<DictManagerExecScope>("dict_manager_exec_scope")
+            .unwrap()
+            .insert_to_tracker(dict_ptr, Felt252::ONE, Felt252::from(7).into());
+        copy.get_mut_ref::<DictSquashExecScope>("dict_squash_exec_scope")
+            .unwrap()
+            .pop_current_key()
+            .unwrap();
+
+        assert_eq!(
+            scopes
+                .get_ref::<DictManagerExecScope>("dict_manager_exec_scope")
+                .unwrap()
+                .get_from_tracker(dict_ptr, &Felt252::ONE),
+            None
+        );
+        assert_eq!(
+            scopes
+                .get_ref::<DictSquashExecScope>("dict_squash_exec_scope")
+                .unwrap()
+                .current_key(),
+            Some(Felt252::ONE)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn register_cloneable_type_only_keeps_non_default_types() {
+        #[derive(Clone)]
+        struct Custom;
+
+        let mut scopes = ExecutionScopes::new();
+        assert!(scopes.cloneable_types.is_empty());
+        scopes.register_cloneable_type::<Felt252>();
+        assert!(scopes.cloneable_types.is_empty());
+        scopes.register_cloneable_type::<Custom>();
+        assert_eq!(scopes.cloneable_types.len(), 1);
+
+        scopes.insert_value("custom", Custom);
+        scopes.insert_value("n", Felt252::ONE);
+        assert_eq!(scopes.try_clone().unwrap().cloneable_types.len(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_clone_non_cloneable_variable() {
+        struct NonCloneable;
+
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("a", NonCloneable);
+        assert_eq!(
+            scopes.try_clone().err(),
+            Some(ExecScopeError::NonCloneableVariable("a".into()))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_scope_variables() {
+        struct Unregistered;
+
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("n", Felt252::from(3));
+        scopes.enter_scope(HashMap::new());
+        scopes.insert_value("z", Unregistered);
+        scopes.insert_value("dict_manager", Rc::new(RefCell::new(DictManager::new())));
+
+        assert_eq!(
+            scopes.get_scope_variables(),
+            vec![
+                vec![ScopeVariable {
+                    name: String::from("n"),
+                    type_name: Some(core::any::type_name::<Felt252>()),
+                }],
+                vec![
+                    ScopeVariable {
+                        name: String::from("dict_manager"),
+                        type_name: Some(core::any::type_name::<Rc<RefCell<DictManager>>>()),
+                    },
+                    ScopeVariable {
+                        name: String::from("z"),
+                        type_name: None,
+                    },
+                ],
+            ]
+        );
+    }
 }
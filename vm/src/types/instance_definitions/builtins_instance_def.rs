@@ -1,4 +1,5 @@
-use crate::types::layout::CairoLayoutParams;
+use crate::stdlib::prelude::*;
+use crate::types::{builtin_name::BuiltinName, layout::CairoLayoutParams, layout_name::LayoutName};
 
 use super::mod_instance_def::ModInstanceDef;
 use super::LowRatio;
@@ -29,6 +30,60 @@ pub(crate) struct BuiltinsInstanceDef {
 }
 
 impl BuiltinsInstanceDef {
+    /// Returns true if the layout includes the given builtin
+    pub(crate) fn includes(&self, name: BuiltinName) -> bool {
+        match name {
+            BuiltinName::output => self.output,
+            BuiltinName::pedersen => self.pedersen.is_some(),
+            BuiltinName::range_check => self.range_check.is_some(),
+            BuiltinName::ecdsa => self.ecdsa.is_some(),
+            BuiltinName::bitwise => self.bitwise.is_some(),
+            BuiltinName::ec_op => self.ec_op.is_some(),
+            BuiltinName::keccak => self.keccak.is_some(),
+            BuiltinName::poseidon => self.poseidon.is_some(),
+            BuiltinName::range_check96 => self.range_check96.is_some(),
+            BuiltinName::add_mod => self.add_mod.is_some(),
+            BuiltinName::mul_mod => self.mul_mod.is_some(),
+            BuiltinName::segment_arena | BuiltinName::custom(_) => false,
+        }
+    }
+
+    /// Returns the layouts, other than the dynamic one, that include all of the given builtins.
+    /// The segment arena and the custom builtins aren't part of any layout, as they run without a
+    /// builtin instance, so they are ignored
+    pub(crate) fn layouts_including(builtins: &[BuiltinName]) -> Vec<LayoutName> {
+        [
+            (LayoutName::plain, Self::plain()),
+            (LayoutName::small, Self::small()),
+            (LayoutName::dex, Self::dex()),
+            (LayoutName::recursive, Self::recursive()),
+            (LayoutName::starknet, Self::starknet()),
+            (
+                LayoutName::starknet_with_keccak,
+                Self::starknet_with_keccak(),
+            ),
+            (
+                LayoutName::recursive_large_output,
+                Self::recursive_large_output(),
+            ),
+            (
+                LayoutName::recursive_with_poseidon,
+                Self::recursive_with_poseidon(),
+            ),
+            (LayoutName::all_solidity, Self::all_solidity()),
+            (LayoutName::all_cairo, Self::all_cairo()),
+        ]
+        .into_iter()
+        .filter(|(_, layout_builtins)| {
+            builtins
+                .iter()
+                .filter(|name| !matches!(name, BuiltinName::segment_arena | BuiltinName::custom(_)))
+                .all(|name| layout_builtins.includes(*name))
+        })
+        .map(|(layout, _)| layout)
+        .collect()
+    }
+
     pub(crate) fn plain() -> BuiltinsInstanceDef {
         BuiltinsInstanceDef {
             output: false,
@@ -208,9 +263,7 @@ impl BuiltinsInstanceDef {
         let bitwise = Some(BitwiseInstanceDef {
             ratio: Some(params.bitwise_ratio),
         });
-        let ec_op = Some(EcOpInstanceDef {
-            ratio: Some(params.ec_op_ratio),
-        });
+        let ec_op = Some(EcOpInstanceDef::new(Some(params.ec_op_ratio)));
         let keccak = Some(KeccakInstanceDef {
             ratio: Some(params.keccak_ratio),
         });
@@ -389,4 +442,31 @@ mod tests {
         assert!(builtins.keccak.is_none());
         assert!(builtins.poseidon.is_none());
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn layouts_including_builtins() {
+        assert_eq!(
+            BuiltinsInstanceDef::layouts_including(&[BuiltinName::output, BuiltinName::keccak]),
+            vec![LayoutName::starknet_with_keccak, LayoutName::all_cairo]
+        );
+        assert_eq!(BuiltinsInstanceDef::layouts_including(&[]).len(), 10);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn layouts_including_builtins_ignores_segment_arena() {
+        assert_eq!(
+            BuiltinsInstanceDef::layouts_including(&[BuiltinName::segment_arena]).len(),
+            10
+        );
+        assert_eq!(
+            BuiltinsInstanceDef::layouts_including(&[
+                BuiltinName::output,
+                BuiltinName::keccak,
+                BuiltinName::segment_arena
+            ]),
+            vec![LayoutName::starknet_with_keccak, LayoutName::all_cairo]
+        );
+    }
 }
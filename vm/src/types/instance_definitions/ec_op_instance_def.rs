@@ -1,3 +1,4 @@
+use crate::Felt252;
 use serde::Serialize;
 
 pub(crate) const CELLS_PER_EC_OP: u32 = 7;
@@ -7,20 +8,39 @@ pub(crate) const SCALAR_HEIGHT: u32 = 256;
 #[derive(Serialize, Clone, Debug, PartialEq)]
 pub(crate) struct EcOpInstanceDef {
     pub(crate) ratio: Option<u32>,
+    // Coefficients of the curve y^2 = x^3 + alpha * x + beta (mod prime)
+    pub(crate) alpha: Felt252,
+    pub(crate) beta: Felt252,
 }
 
 impl Default for EcOpInstanceDef {
     fn default() -> Self {
-        EcOpInstanceDef { ratio: Some(256) }
+        EcOpInstanceDef::new(Some(256))
     }
 }
 
 impl EcOpInstanceDef {
     pub(crate) fn new(ratio: Option<u32>) -> Self {
-        EcOpInstanceDef { ratio }
+        EcOpInstanceDef {
+            ratio,
+            alpha: stark_curve_alpha(),
+            beta: stark_curve_beta(),
+        }
     }
 }
 
+/// Alpha coefficient of the STARK curve, used by the ec_op builtin unless configured otherwise
+pub(crate) fn stark_curve_alpha() -> Felt252 {
+    Felt252::ONE
+}
+
+/// Beta coefficient of the STARK curve, used by the ec_op builtin unless configured otherwise
+pub(crate) fn stark_curve_beta() -> Felt252 {
+    let beta_low = Felt252::from(0x609ad26c15c915c1f4cdfcb99cee9e89_u128);
+    let beta_high = Felt252::from(0x6f21413efbe40de150e596d72f7a8c5_u128);
+    (beta_high * (Felt252::ONE + Felt252::from(u128::MAX))) + beta_low
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,14 +51,32 @@ mod tests {
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_new() {
-        let builtin_instance = EcOpInstanceDef { ratio: Some(8) };
+        let builtin_instance = EcOpInstanceDef {
+            ratio: Some(8),
+            alpha: stark_curve_alpha(),
+            beta: stark_curve_beta(),
+        };
         assert_eq!(EcOpInstanceDef::new(Some(8)), builtin_instance);
     }
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_default() {
-        let builtin_instance = EcOpInstanceDef { ratio: Some(256) };
+        let builtin_instance = EcOpInstanceDef {
+            ratio: Some(256),
+            alpha: stark_curve_alpha(),
+            beta: stark_curve_beta(),
+        };
         assert_eq!(EcOpInstanceDef::default(), builtin_instance);
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_default_curve_is_stark_curve() {
+        assert_eq!(stark_curve_alpha(), Felt252::ONE);
+        assert_eq!(
+            stark_curve_beta(),
+            crate::felt_hex!("0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89")
+        );
+    }
 }
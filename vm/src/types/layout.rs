@@ -527,10 +527,7 @@ mod tests {
             layout.builtins.bitwise,
             Some(BitwiseInstanceDef { ratio: Some(32) })
         );
-        assert_eq!(
-            layout.builtins.ec_op,
-            Some(EcOpInstanceDef { ratio: Some(32) })
-        );
+        assert_eq!(layout.builtins.ec_op, Some(EcOpInstanceDef::new(Some(32))));
         assert_eq!(
             layout.builtins.keccak,
             Some(KeccakInstanceDef { ratio: Some(32) })
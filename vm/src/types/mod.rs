@@ -1,4 +1,5 @@
 pub mod builtin_name;
+pub mod cairo_arg;
 pub mod errors;
 pub mod exec_scope;
 pub mod instance_definitions;
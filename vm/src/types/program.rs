@@ -11,6 +11,7 @@ use crate::{
     vm::runners::cairo_pie::StrippedProgram,
 };
 
+use crate::program_hash::{compute_program_hash_chain, ProgramHashError};
 #[cfg(feature = "cairo-1-hints")]
 use crate::serde::deserialize_program::{ApTracking, FlowTrackingData};
 use crate::utils::PRIME_STR;
@@ -19,7 +20,7 @@ use crate::{
     hint_processor::hint_processor_definition::HintReference,
     serde::deserialize_program::{
         deserialize_and_parse_program, Attribute, HintParams, Identifier, InstructionLocation,
-        OffsetValue, ReferenceManager,
+        Member, OffsetValue, Reference, ReferenceManager,
     },
     types::{
         errors::program_errors::ProgramError, instruction::Register, relocatable::MaybeRelocatable,
@@ -29,6 +30,8 @@ use crate::{
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use core::num::NonZeroUsize;
 
+#[cfg(feature = "std")]
+use crate::serde::deserialize_program::deserialize_program_json_from_reader;
 #[cfg(feature = "std")]
 use std::path::Path;
 
@@ -69,8 +72,10 @@ pub struct SharedProgramData {
     pub(crate) end: Option<usize>,
     pub(crate) error_message_attributes: Vec<Attribute>,
     pub(crate) instruction_locations: Option<HashMap<usize, InstructionLocation>>,
-    pub(crate) identifiers: HashMap<String, Identifier>,
+    pub(crate) identifiers: Arc<HashMap<String, Identifier>>,
     pub reference_manager: Vec<HintReference>,
+    pub(crate) compiler_version: Option<String>,
+    pub(crate) file_contents: HashMap<String, String>,
 }
 
 #[cfg(feature = "test_utils")]
@@ -100,8 +105,10 @@ impl<'a> Arbitrary<'a> for SharedProgramData {
             end: Option::<usize>::arbitrary(u)?,
             error_message_attributes: Vec::<Attribute>::arbitrary(u)?,
             instruction_locations: Option::<HashMap<usize, InstructionLocation>>::arbitrary(u)?,
-            identifiers: HashMap::<String, Identifier>::arbitrary(u)?,
+            identifiers: Arc::new(HashMap::<String, Identifier>::arbitrary(u)?),
             reference_manager: Vec::<HintReference>::arbitrary(u)?,
+            compiler_version: Option::<String>::arbitrary(u)?,
+            file_contents: HashMap::<String, String>::arbitrary(u)?,
         })
     }
 }
@@ -170,6 +177,20 @@ impl HintsCollection {
     pub fn get_hint_range_for_pc(&self, pc: usize) -> Option<HintRange> {
         self.hints_ranges.get(pc).cloned()
     }
+
+    /// Returns the hints placed at the given pc offset of the program segment.
+    pub fn get_hints_for_pc(&self, pc: usize) -> &[HintParams] {
+        #[cfg(not(feature = "extensive_hints"))]
+        let range = self.get_hint_range_for_pc(pc).flatten();
+        #[cfg(feature = "extensive_hints")]
+        let range = self
+            .hints_ranges
+            .get(&Relocatable::from((0_isize, pc)))
+            .copied();
+        range
+            .and_then(|(start, length)| self.hints.get(start..start + length.get()))
+            .unwrap_or(&[])
+    }
 }
 
 impl From<&HintsCollection> for BTreeMap<usize, Vec<HintParams>> {
@@ -197,6 +218,15 @@ type HintRange = Option<(usize, NonZeroUsize)>;
 #[cfg(feature = "extensive_hints")]
 pub type HintRange = (usize, NonZeroUsize);
 
+/// A struct declared by the program, see [Program::iter_structs].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructDefinition<'a> {
+    pub name: &'a str,
+    pub size: usize,
+    /// The struct's members, sorted by offset
+    pub members: Vec<(&'a str, &'a Member)>,
+}
+
 #[cfg_attr(feature = "test_utils", derive(Arbitrary))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Program {
@@ -230,7 +260,9 @@ impl Program {
             hints_collection,
             error_message_attributes,
             instruction_locations,
-            identifiers,
+            identifiers: Arc::new(identifiers),
+            compiler_version: None,
+            file_contents: HashMap::new(),
             reference_manager: Self::get_reference_list(&reference_manager),
         };
         Ok(Self {
@@ -264,7 +296,9 @@ impl Program {
             hints_collection,
             error_message_attributes,
             instruction_locations,
-            identifiers,
+            identifiers: Arc::new(identifiers),
+            compiler_version: None,
+            file_contents: HashMap::new(),
             reference_manager: Self::get_reference_list(&reference_manager),
         };
         Ok(Self {
@@ -277,7 +311,19 @@ impl Program {
     #[cfg(feature = "std")]
     pub fn from_file(path: &Path, entrypoint: Option<&str>) -> Result<Program, ProgramError> {
         let file_content = std::fs::read(path)?;
-        deserialize_and_parse_program(&file_content, entrypoint)
+        Self::from_bytes(&file_content, entrypoint)
+    }
+
+    /// Deserializes a program as it's read, instead of loading the whole JSON in memory first.
+    /// This lowers the peak memory needed to load big programs (e.g. the Starknet OS) at the
+    /// cost of a slower deserialization than [Program::from_bytes].
+    #[cfg(feature = "std")]
+    pub fn from_reader(
+        reader: impl std::io::Read,
+        entrypoint: Option<&str>,
+    ) -> Result<Program, ProgramError> {
+        let program_json = deserialize_program_json_from_reader(reader)?;
+        parse_program_json(program_json, entrypoint)
     }
 
     pub fn from_bytes(bytes: &[u8], entrypoint: Option<&str>) -> Result<Program, ProgramError> {
@@ -305,6 +351,18 @@ impl Program {
         self.builtins.len()
     }
 
+    /// Returns the version of the compiler the program was compiled with, if the compiled
+    /// artifact recorded it.
+    pub fn compiler_version(&self) -> Option<&str> {
+        self.shared_program_data.compiler_version.as_deref()
+    }
+
+    /// Returns the source code of the compiled files by filename, if the program's debug info
+    /// included it.
+    pub fn get_file_contents(&self) -> &HashMap<String, String> {
+        &self.shared_program_data.file_contents
+    }
+
     pub fn get_identifier(&self, id: &str) -> Option<&Identifier> {
         self.shared_program_data.identifiers.get(id)
     }
@@ -325,6 +383,15 @@ impl Program {
         Some(relocated_instructions)
     }
 
+    /// Returns the source location of the instruction at the given pc offset, if the program
+    /// was compiled with debug info.
+    pub fn get_instruction_location(&self, pc: usize) -> Option<&InstructionLocation> {
+        self.shared_program_data
+            .instruction_locations
+            .as_ref()?
+            .get(&pc)
+    }
+
     pub fn iter_identifiers(&self) -> impl Iterator<Item = (&str, &Identifier)> {
         self.shared_program_data
             .identifiers
@@ -332,6 +399,86 @@ impl Program {
             .map(|(cairo_type, identifier)| (cairo_type.as_str(), identifier))
     }
 
+    /// Returns the value of a constant, given its full name or its name within `__main__`.
+    pub fn get_constant(&self, name: &str) -> Option<&Felt252> {
+        self.constants
+            .get(name)
+            .or_else(|| self.constants.get(&format!("__main__.{name}")))
+    }
+
+    /// Iterates over the functions of the program, along with the pc of their first instruction.
+    pub fn iter_functions(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.iter_identifiers().filter_map(|(name, identifier)| {
+            (identifier.type_.as_deref() == Some("function"))
+                .then_some(identifier.pc)
+                .flatten()
+                .map(|pc| (name, pc))
+        })
+    }
+
+    /// Iterates over the structs declared by the program, including the `Args`, `ImplicitArgs`
+    /// and `Return` structs of its functions.
+    pub fn iter_structs(&self) -> impl Iterator<Item = StructDefinition<'_>> {
+        self.iter_identifiers()
+            .filter_map(|(name, identifier)| struct_definition(name, identifier))
+    }
+
+    /// Returns the struct with the given full name.
+    pub fn get_struct(&self, name: &str) -> Option<StructDefinition<'_>> {
+        let (name, identifier) = self.shared_program_data.identifiers.get_key_value(name)?;
+        struct_definition(name, identifier)
+    }
+
+    /// Returns the amount of cells taken by the return values of the function with the given
+    /// full name, if its `Return` type can be resolved.
+    pub(crate) fn get_return_size(&self, function: &str) -> Option<usize> {
+        let identifier = self.get_identifier(&format!("{function}.Return"))?;
+        match identifier.type_.as_deref() {
+            Some("struct") => identifier.size,
+            Some("type_definition") => self.get_type_size(identifier.cairo_type.as_deref()?),
+            _ => None,
+        }
+    }
+
+    /// Returns the amount of cells taken by a value of the given cairo type.
+    /// Supports felts, pointers, structs declared by the program and (possibly named) tuples of those.
+    fn get_type_size(&self, cairo_type: &str) -> Option<usize> {
+        let cairo_type = cairo_type.trim();
+        if cairo_type.ends_with('*') || cairo_type == "felt" || cairo_type == "codeoffset" {
+            return Some(1);
+        }
+        let Some(members) = cairo_type
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+        else {
+            return self.get_struct(cairo_type).map(|s| s.size);
+        };
+        let mut size = 0;
+        let mut depth = 0;
+        let mut start = 0;
+        for (i, c) in members.char_indices().chain([(members.len(), ',')]) {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    let member = members[start..i].trim();
+                    start = i + 1;
+                    if member.is_empty() {
+                        continue;
+                    }
+                    // Named tuple members are declared as `name: type`
+                    let member_type = match member.split_once(':') {
+                        Some((_, member_type)) if !member.starts_with('(') => member_type,
+                        _ => member,
+                    };
+                    size += self.get_type_size(member_type)?;
+                }
+                _ => {}
+            }
+        }
+        Some(size)
+    }
+
     pub(crate) fn get_reference_list(reference_manager: &ReferenceManager) -> Vec<HintReference> {
         reference_manager
             .references
@@ -343,14 +490,22 @@ impl Program {
                     outer_dereference: r.value_address.outer_dereference,
                     inner_dereference: r.value_address.inner_dereference,
                     // only store `ap` tracking data if the reference is referred to it
-                    ap_tracking_data: match (&r.value_address.offset1, &r.value_address.offset2) {
-                        (OffsetValue::Reference(Register::AP, _, _, _), _)
-                        | (_, OffsetValue::Reference(Register::AP, _, _, _)) => {
+                    ap_tracking_data: match (
+                        &r.value_address.offset1,
+                        &r.value_address.offset2,
+                        &r.value_address.expression,
+                    ) {
+                        (OffsetValue::Reference(Register::AP, _, _, _), _, _)
+                        | (_, OffsetValue::Reference(Register::AP, _, _, _), _) => {
+                            Some(r.ap_tracking_data.clone())
+                        }
+                        (_, _, Some(expression)) if expression.uses_ap() => {
                             Some(r.ap_tracking_data.clone())
                         }
                         _ => None,
                     },
                     cairo_type: Some(r.value_address.value_type.clone()),
+                    expression: r.value_address.expression.clone(),
                 }
             })
             .collect()
@@ -386,6 +541,15 @@ impl Program {
         })
     }
 
+    /// Computes the hash of the program as done by the bootloader, see
+    /// [compute_program_hash_chain](crate::program_hash::compute_program_hash_chain).
+    pub fn compute_program_hash(
+        &self,
+        bootloader_version: usize,
+    ) -> Result<Felt252, ProgramHashError> {
+        compute_program_hash_chain(&self.get_stripped_program()?, bootloader_version)
+    }
+
     pub fn from_stripped_program(stripped: &StrippedProgram) -> Program {
         Program {
             shared_program_data: Arc::new(SharedProgramData {
@@ -416,6 +580,27 @@ impl Program {
     }
 }
 
+fn struct_definition<'a>(
+    name: &'a str,
+    identifier: &'a Identifier,
+) -> Option<StructDefinition<'a>> {
+    if identifier.type_.as_deref() != Some("struct") {
+        return None;
+    }
+    let mut members: Vec<(&str, &Member)> = identifier
+        .members
+        .iter()
+        .flatten()
+        .map(|(name, member)| (name.as_str(), member))
+        .collect();
+    members.sort_by_key(|(_, member)| member.offset);
+    Some(StructDefinition {
+        name,
+        size: identifier.size.unwrap_or_default(),
+        members,
+    })
+}
+
 impl Default for Program {
     fn default() -> Self {
         Self {
@@ -426,6 +611,180 @@ impl Default for Program {
     }
 }
 
+/// Assembles a [Program] from its parts, without going through the compiled JSON
+/// representation.
+///
+/// Entrypoint, `__start__` and `__end__` pcs are resolved from the identifiers on
+/// [ProgramBuilder::build], the same way [Program::from_bytes] does.
+///
+/// ```
+/// use cairo_vm::types::program::ProgramBuilder;
+/// use cairo_vm::types::builtin_name::BuiltinName;
+/// use cairo_vm::Felt252;
+///
+/// // [ap] = 5, ap++; ret
+/// let program = ProgramBuilder::new()
+///     .builtin(BuiltinName::output)
+///     .data([
+///         Felt252::from(0x480680017fff8000_u64),
+///         Felt252::from(5),
+///         Felt252::from(0x208b7fff7fff7ffe_u64),
+///     ])
+///     .build()
+///     .unwrap();
+/// assert_eq!(program.data_len(), 3);
+/// assert_eq!(program.builtins_len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProgramBuilder {
+    builtins: Vec<BuiltinName>,
+    data: Vec<MaybeRelocatable>,
+    main: Option<usize>,
+    entrypoint: Option<String>,
+    hints: BTreeMap<usize, Vec<HintParams>>,
+    reference_manager: ReferenceManager,
+    identifiers: HashMap<String, Identifier>,
+    error_message_attributes: Vec<Attribute>,
+    instruction_locations: Option<HashMap<usize, InstructionLocation>>,
+    compiler_version: Option<String>,
+}
+
+impl ProgramBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn builtin(mut self, builtin: BuiltinName) -> Self {
+        self.builtins.push(builtin);
+        self
+    }
+
+    pub fn builtins(mut self, builtins: impl IntoIterator<Item = BuiltinName>) -> Self {
+        self.builtins.extend(builtins);
+        self
+    }
+
+    /// Appends the given values to the program's bytecode.
+    pub fn data(mut self, data: impl IntoIterator<Item = impl Into<MaybeRelocatable>>) -> Self {
+        self.data.extend(data.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the pc of the main function.
+    pub fn main(mut self, pc: usize) -> Self {
+        self.main = Some(pc);
+        self
+    }
+
+    /// Sets the main function by name, looking up the `__main__.{entrypoint}` identifier.
+    /// Takes precedence over [ProgramBuilder::main].
+    pub fn entrypoint(mut self, entrypoint: &str) -> Self {
+        self.entrypoint = Some(entrypoint.to_string());
+        self
+    }
+
+    /// Adds a hint to be executed before the instruction at `pc`.
+    pub fn hint(mut self, pc: usize, hint: HintParams) -> Self {
+        self.hints.entry(pc).or_default().push(hint);
+        self
+    }
+
+    /// Adds a reference, which hints refer to by its position.
+    pub fn reference(mut self, reference: Reference) -> Self {
+        self.reference_manager.references.push(reference);
+        self
+    }
+
+    pub fn identifier(mut self, name: &str, identifier: Identifier) -> Self {
+        self.identifiers.insert(name.to_string(), identifier);
+        self
+    }
+
+    pub fn error_message_attribute(mut self, attribute: Attribute) -> Self {
+        self.error_message_attributes.push(attribute);
+        self
+    }
+
+    pub fn instruction_location(mut self, pc: usize, location: InstructionLocation) -> Self {
+        self.instruction_locations
+            .get_or_insert_with(HashMap::new)
+            .insert(pc, location);
+        self
+    }
+
+    pub fn compiler_version(mut self, compiler_version: &str) -> Self {
+        self.compiler_version = Some(compiler_version.to_string());
+        self
+    }
+
+    /// Builds the program.
+    /// Fails if a hint or reference is placed outside of the program, if a hint uses a
+    /// reference id that is not in the reference manager, if the entrypoint can't be found,
+    /// or if a constant has no value.
+    pub fn build(self) -> Result<Program, ProgramError> {
+        let program_length = self.data.len();
+        let references_len = self.reference_manager.references.len();
+        if let Some(pc) = self
+            .reference_manager
+            .references
+            .iter()
+            .filter_map(|reference| reference.pc)
+            .find(|pc| *pc >= program_length)
+        {
+            return Err(ProgramError::InvalidReferencePc(pc, program_length));
+        }
+        for (pc, hints) in self.hints.iter() {
+            for hint in hints {
+                if let Some(id) = hint
+                    .flow_tracking_data
+                    .reference_ids
+                    .values()
+                    .find(|id| **id >= references_len)
+                {
+                    return Err(ProgramError::InvalidHintReferenceId(
+                        *pc,
+                        *id,
+                        references_len,
+                    ));
+                }
+            }
+        }
+        let hints_collection = HintsCollection::new(&self.hints, program_length)?;
+
+        let main = match self.entrypoint {
+            Some(entrypoint) => Some(
+                self.identifiers
+                    .get(&format!("__main__.{entrypoint}"))
+                    .and_then(|identifier| identifier.pc)
+                    .ok_or(ProgramError::EntrypointNotFound(entrypoint))?,
+            ),
+            None => self.main,
+        };
+        let pc_of = |name: &str| self.identifiers.get(name).and_then(|id| id.pc);
+        let (start, end) = (pc_of("__main__.__start__"), pc_of("__main__.__end__"));
+        let constants = Program::extract_constants(&self.identifiers)?;
+
+        let shared_program_data = SharedProgramData {
+            data: self.data,
+            hints_collection,
+            main,
+            start,
+            end,
+            error_message_attributes: self.error_message_attributes,
+            instruction_locations: self.instruction_locations,
+            identifiers: Arc::new(self.identifiers),
+            reference_manager: Program::get_reference_list(&self.reference_manager),
+            compiler_version: self.compiler_version,
+            file_contents: HashMap::new(),
+        };
+        Ok(Program {
+            shared_program_data: Arc::new(shared_program_data),
+            constants,
+            builtins: self.builtins,
+        })
+    }
+}
+
 #[cfg(feature = "cairo-1-hints")]
 // Note: This Program will only work when using run_from_entrypoint, and the Cairo1Hintprocesso
 impl TryFrom<CasmContractClass> for Program {
@@ -549,7 +908,7 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.shared_program_data.data, data);
         assert_eq!(program.shared_program_data.main, None);
-        assert_eq!(program.shared_program_data.identifiers, HashMap::new());
+        assert!(program.shared_program_data.identifiers.is_empty());
         assert_eq!(
             program.shared_program_data.hints_collection.hints,
             Vec::new()
@@ -596,7 +955,7 @@ mod tests {
         assert_eq!(program.shared_program_data.main, None);
         assert_eq!(program.shared_program_data.start, Some(0));
         assert_eq!(program.shared_program_data.end, Some(1));
-        assert_eq!(program.shared_program_data.identifiers, HashMap::new());
+        assert!(program.shared_program_data.identifiers.is_empty());
         assert_eq!(
             program.shared_program_data.hints_collection.hints,
             Vec::new()
@@ -658,7 +1017,7 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.shared_program_data.data, data);
         assert_eq!(program.shared_program_data.main, None);
-        assert_eq!(program.shared_program_data.identifiers, HashMap::new());
+        assert!(program.shared_program_data.identifiers.is_empty());
 
         #[cfg(not(feature = "extensive_hints"))]
         let program_hints: HashMap<_, _> = program
@@ -689,6 +1048,18 @@ mod tests {
             })
             .collect();
         assert_eq!(program_hints, hints);
+        assert_eq!(
+            program
+                .shared_program_data
+                .hints_collection
+                .get_hints_for_pc(5),
+            &hints[&5][..]
+        );
+        assert!(program
+            .shared_program_data
+            .hints_collection
+            .get_hints_for_pc(2)
+            .is_empty());
     }
 
     #[test]
@@ -752,7 +1123,7 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.shared_program_data.data, data);
         assert_eq!(program.shared_program_data.main, None);
-        assert_eq!(program.shared_program_data.identifiers, identifiers);
+        assert_eq!(*program.shared_program_data.identifiers, identifiers);
         assert_eq!(
             program.constants,
             [("__main__.main.SIZEOF_LOCALS", Felt252::ZERO)]
@@ -997,6 +1368,8 @@ mod tests {
                     start_col: 0,
                 },
                 hints: vec![],
+                accessible_scopes: vec![],
+                flow_tracking_data: None,
             }
         }
 
@@ -1034,6 +1407,8 @@ mod tests {
         assert_eq!(relocated_instructions.get(&7), Some(&il_1));
         assert_eq!(relocated_instructions.get(&12), Some(&il_2));
         assert_eq!(relocated_instructions.get(&14), Some(&il_3));
+        assert_eq!(program.get_instruction_location(10), Some(&il_2));
+        assert_eq!(program.get_instruction_location(7), None);
     }
 
     #[test]
@@ -1102,6 +1477,61 @@ mod tests {
         assert_eq!(collected_identifiers, identifiers);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_constant() {
+        let program = Program::from_bytes(
+            include_bytes!(
+                "../../../cairo_programs/manually_compiled/deserialize_constant_test.json"
+            ),
+            Some("main"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            program.get_constant("starkware.cairo.common.cairo_keccak.packed_keccak.BLOCK_SIZE"),
+            Some(&Felt252::from(3))
+        );
+        assert_eq!(
+            program.get_constant("compare_abs_arrays.SIZEOF_LOCALS"),
+            Some(&Felt252::ZERO)
+        );
+        assert_eq!(program.get_constant("BLOCK_SIZE"), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_functions_and_structs() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/manually_compiled/valid_program_b.json"),
+            Some("main"),
+        )
+        .unwrap();
+
+        let functions: BTreeMap<&str, usize> = program.iter_functions().collect();
+        assert_eq!(
+            functions,
+            BTreeMap::from([
+                ("__main__.check_range", 4),
+                ("__main__.main", 13),
+                ("starkware.cairo.common.serialize.serialize_word", 0),
+            ])
+        );
+
+        assert_eq!(program.iter_structs().count(), 9);
+        let implicit_args = program.get_struct("__main__.main.ImplicitArgs").unwrap();
+        assert_eq!(implicit_args.size, 2);
+        assert_eq!(
+            implicit_args
+                .members
+                .iter()
+                .map(|(name, member)| (*name, member.cairo_type.as_str(), member.offset))
+                .collect::<Vec<_>>(),
+            vec![("output_ptr", "felt*", 0), ("range_check_ptr", "felt", 1)]
+        );
+        assert_eq!(program.get_struct("__main__.main"), None);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn new_program_with_invalid_identifiers() {
@@ -1247,7 +1677,7 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.shared_program_data.data, data);
         assert_eq!(program.shared_program_data.main, Some(0));
-        assert_eq!(program.shared_program_data.identifiers, identifiers);
+        assert_eq!(*program.shared_program_data.identifiers, identifiers);
     }
 
     /// Deserialize a program without an entrypoint.
@@ -1274,6 +1704,11 @@ mod tests {
                 },
                 reference_ids: HashMap::new(),
             }),
+            accessible_scopes: vec![
+                String::from("openzeppelin.security.safemath.library"),
+                String::from("openzeppelin.security.safemath.library.SafeUint256"),
+                String::from("openzeppelin.security.safemath.library.SafeUint256.add"),
+            ],
         }];
 
         let data: Vec<MaybeRelocatable> = vec![
@@ -1351,13 +1786,27 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.shared_program_data.data, data);
         assert_eq!(program.shared_program_data.main, None);
-        assert_eq!(program.shared_program_data.identifiers, identifiers);
+        assert_eq!(*program.shared_program_data.identifiers, identifiers);
         assert_eq!(
             program.shared_program_data.error_message_attributes,
             error_message_attributes
         )
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn deserialize_program_from_reader() {
+        let bytes = include_bytes!("../../../cairo_programs/fibonacci.json");
+        assert_eq!(
+            Program::from_reader(&bytes[..], Some("main")).unwrap(),
+            Program::from_bytes(bytes, Some("main")).unwrap()
+        );
+        assert_matches!(
+            Program::from_reader(&bytes[..bytes.len() / 2], Some("main")),
+            Err(ProgramError::Parse(_))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn deserialize_program_constants_test() {
@@ -1412,7 +1861,9 @@ mod tests {
             end: None,
             error_message_attributes: Vec::new(),
             instruction_locations: None,
-            identifiers: HashMap::new(),
+            identifiers: Arc::default(),
+            compiler_version: None,
+            file_contents: HashMap::new(),
             reference_manager: Program::get_reference_list(&ReferenceManager {
                 references: Vec::new(),
             }),
@@ -1449,4 +1900,166 @@ mod tests {
             Err(ProgramError::StrippedProgramNoMain)
         );
     }
+
+    fn builder_identifier(type_: &str, pc: Option<usize>, value: Option<Felt252>) -> Identifier {
+        Identifier {
+            pc,
+            type_: Some(type_.to_string()),
+            value,
+            full_name: None,
+            members: None,
+            cairo_type: None,
+            size: None,
+        }
+    }
+
+    fn builder_hint(code: &str, reference_ids: HashMap<String, usize>) -> HintParams {
+        HintParams {
+            code: code.to_string(),
+            accessible_scopes: vec![String::from("__main__")],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::default(),
+                reference_ids,
+            },
+        }
+    }
+
+    fn builder_reference(pc: usize) -> Reference {
+        Reference {
+            ap_tracking_data: ApTracking::default(),
+            pc: Some(pc),
+            value_address:
+                crate::serde::deserialize_program::ValueAddress::no_hint_reference_default(),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder_builds_program() {
+        let data = vec![
+            mayberelocatable!(5189976364521848832),
+            mayberelocatable!(1000),
+            mayberelocatable!(2345108766317314046),
+        ];
+        let program = ProgramBuilder::new()
+            .builtins([BuiltinName::output, BuiltinName::range_check])
+            .data(data.clone())
+            .hint(
+                0,
+                builder_hint("a", HashMap::from([(String::from("__main__.main.x"), 0)])),
+            )
+            .hint(0, builder_hint("b", HashMap::new()))
+            .hint(2, builder_hint("c", HashMap::new()))
+            .reference(builder_reference(0))
+            .identifier(
+                "__main__.main",
+                builder_identifier("function", Some(0), None),
+            )
+            .identifier(
+                "__main__.SIZE",
+                builder_identifier("const", None, Some(Felt252::from(3))),
+            )
+            .entrypoint("main")
+            .compiler_version("0.13.1")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            program.iter_builtins().collect::<Vec<_>>(),
+            vec![&BuiltinName::output, &BuiltinName::range_check]
+        );
+        assert_eq!(program.shared_program_data.data, data);
+        assert_eq!(program.shared_program_data.main, Some(0));
+        assert_eq!(program.shared_program_data.start, None);
+        assert_eq!(program.compiler_version(), Some("0.13.1"));
+        assert_eq!(
+            program.get_constant("__main__.SIZE"),
+            Some(&Felt252::from(3))
+        );
+        assert_eq!(program.shared_program_data.reference_manager.len(), 1);
+        let hints = &program.shared_program_data.hints_collection;
+        assert_eq!(
+            hints
+                .get_hints_for_pc(0)
+                .iter()
+                .map(|h| h.code.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert!(hints.get_hints_for_pc(1).is_empty());
+        assert_eq!(hints.get_hints_for_pc(2)[0].code, "c");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder_resolves_start_and_end() {
+        let program = ProgramBuilder::new()
+            .data([Felt252::ZERO; 4])
+            .main(1)
+            .identifier(
+                "__main__.__start__",
+                builder_identifier("label", Some(0), None),
+            )
+            .identifier(
+                "__main__.__end__",
+                builder_identifier("label", Some(3), None),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(program.shared_program_data.main, Some(1));
+        assert_eq!(program.shared_program_data.start, Some(0));
+        assert_eq!(program.shared_program_data.end, Some(3));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder_entrypoint_not_found() {
+        assert_matches!(
+            ProgramBuilder::new()
+                .data([Felt252::ZERO])
+                .entrypoint("main")
+                .build(),
+            Err(ProgramError::EntrypointNotFound(entrypoint)) if entrypoint == "main"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder_invalid_hint_pc() {
+        assert_matches!(
+            ProgramBuilder::new()
+                .data([Felt252::ZERO; 2])
+                .hint(2, builder_hint("a", HashMap::new()))
+                .build(),
+            Err(ProgramError::InvalidHintPc(2, 2))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder_invalid_hint_reference_id() {
+        assert_matches!(
+            ProgramBuilder::new()
+                .data([Felt252::ZERO; 2])
+                .reference(builder_reference(0))
+                .hint(
+                    1,
+                    builder_hint("a", HashMap::from([(String::from("x"), 1)]))
+                )
+                .build(),
+            Err(ProgramError::InvalidHintReferenceId(1, 1, 1))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder_invalid_reference_pc() {
+        assert_matches!(
+            ProgramBuilder::new()
+                .data([Felt252::ZERO; 2])
+                .reference(builder_reference(5))
+                .build(),
+            Err(ProgramError::InvalidReferencePc(5, 2))
+        );
+    }
 }
@@ -201,10 +201,10 @@ impl Sub<Relocatable> for Relocatable {
     }
 }
 
-impl TryInto<Relocatable> for MaybeRelocatable {
+impl TryFrom<MaybeRelocatable> for Relocatable {
     type Error = MemoryError;
-    fn try_into(self) -> Result<Relocatable, MemoryError> {
-        match self {
+    fn try_from(value: MaybeRelocatable) -> Result<Self, MemoryError> {
+        match value {
             MaybeRelocatable::RelocatableValue(rel) => Ok(rel),
             _ => Err(MemoryError::AddressNotRelocatable),
         }
@@ -227,6 +227,57 @@ impl TryFrom<&MaybeRelocatable> for Relocatable {
     }
 }
 
+impl TryFrom<&MaybeRelocatable> for Felt252 {
+    type Error = MathError;
+    fn try_from(value: &MaybeRelocatable) -> Result<Self, MathError> {
+        match value {
+            MaybeRelocatable::Int(num) => Ok(*num),
+            MaybeRelocatable::RelocatableValue(rel) => {
+                Err(MathError::RelocatableToFelt252(Box::new(*rel)))
+            }
+        }
+    }
+}
+
+impl TryFrom<MaybeRelocatable> for Felt252 {
+    type Error = MathError;
+    fn try_from(value: MaybeRelocatable) -> Result<Self, MathError> {
+        Felt252::try_from(&value)
+    }
+}
+
+/// See [MaybeRelocatable::add]
+impl Add<&MaybeRelocatable> for &MaybeRelocatable {
+    type Output = Result<MaybeRelocatable, MathError>;
+    fn add(self, other: &MaybeRelocatable) -> Self::Output {
+        MaybeRelocatable::add(self, other)
+    }
+}
+
+/// See [MaybeRelocatable::add_usize]
+impl Add<usize> for &MaybeRelocatable {
+    type Output = Result<MaybeRelocatable, MathError>;
+    fn add(self, other: usize) -> Self::Output {
+        self.add_usize(other)
+    }
+}
+
+/// See [MaybeRelocatable::sub]
+impl Sub<&MaybeRelocatable> for &MaybeRelocatable {
+    type Output = Result<MaybeRelocatable, MathError>;
+    fn sub(self, other: &MaybeRelocatable) -> Self::Output {
+        MaybeRelocatable::sub(self, other)
+    }
+}
+
+/// See [MaybeRelocatable::sub_usize]
+impl Sub<usize> for &MaybeRelocatable {
+    type Output = Result<MaybeRelocatable, MathError>;
+    fn sub(self, other: usize) -> Self::Output {
+        self.sub_usize(other)
+    }
+}
+
 impl MaybeRelocatable {
     /// Adds a Felt252 to self
     pub fn add_int(&self, other: &Felt252) -> Result<MaybeRelocatable, MathError> {
@@ -828,6 +879,49 @@ mod tests {
         assert_eq!(Err(MemoryError::AddressNotRelocatable), err)
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn mayberelocatable_try_into_felt() {
+        let value = mayberelocatable!(7);
+        assert_eq!(Ok(Felt252::from(7)), Felt252::try_from(&value));
+        assert_eq!(Ok(Felt252::from(7)), value.try_into());
+
+        let address = mayberelocatable!(1, 2);
+        assert_eq!(
+            Err(MathError::RelocatableToFelt252(Box::new(relocatable!(
+                1, 2
+            )))),
+            Felt252::try_from(address)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn mayberelocatable_operators() {
+        let addr = mayberelocatable!(1, 2);
+        let num = mayberelocatable!(3);
+        assert_eq!(&addr + &num, Ok(mayberelocatable!(1, 5)));
+        assert_eq!(&num + &num, Ok(mayberelocatable!(6)));
+        assert_eq!(&addr + 4, Ok(mayberelocatable!(1, 6)));
+        assert_eq!(&addr - &num, Ok(mayberelocatable!(1, 0)));
+        assert_eq!(&mayberelocatable!(1, 5) - &addr, Ok(mayberelocatable!(3)));
+        assert_eq!(&addr - 2, Ok(mayberelocatable!(1, 0)));
+        assert_eq!(
+            &addr + &addr,
+            Err(MathError::RelocatableAdd(Box::new((
+                relocatable!(1, 2),
+                relocatable!(1, 2)
+            ))))
+        );
+        assert_eq!(
+            &addr - 3,
+            Err(MathError::RelocatableSubUsizeNegOffset(Box::new((
+                relocatable!(1, 2),
+                3
+            ))))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn relocatable_sub_rel_test() {
@@ -1,5 +1,6 @@
-use crate::stdlib::prelude::*;
+use crate::stdlib::{fmt, prelude::*};
 use crate::types::relocatable::Relocatable;
+use crate::Felt252;
 use lazy_static::lazy_static;
 use num_bigint::BigUint;
 use num_traits::Num;
@@ -49,6 +50,38 @@ pub fn from_relocatable_to_indexes(relocatable: Relocatable) -> (usize, usize) {
     }
 }
 
+/// Returns the felt interpreted as a Cairo short string, if all of its bytes (skipping the
+/// leading zeroes) are printable ascii characters.
+/// Values shorter than two characters are ignored, as most of them are just small integers.
+pub fn as_printable_short_string(value: &Felt252) -> Option<String> {
+    let bytes: Vec<u8> = value
+        .to_bytes_be()
+        .into_iter()
+        .skip_while(|byte| *byte == 0)
+        .collect();
+    if bytes.len() < 2
+        || !bytes
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+    {
+        return None;
+    }
+    Some(bytes.into_iter().map(char::from).collect())
+}
+
+/// Displays a felt followed by its Cairo short string when it has a printable one (see
+/// [as_printable_short_string]), e.g. `448378203247 ('hello')`.
+pub struct FeltWithShortString<'a>(pub &'a Felt252);
+
+impl fmt::Display for FeltWithShortString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match as_printable_short_string(self.0) {
+            Some(short_string) => write!(f, "{} ('{short_string}')", self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
 #[cfg(test)]
 #[macro_use]
 pub mod test_utils {
@@ -306,7 +339,9 @@ pub mod test_utils {
                 end: None,
                 error_message_attributes: crate::stdlib::vec::Vec::new(),
                 instruction_locations: None,
-                identifiers: crate::stdlib::collections::HashMap::new(),
+                identifiers: Arc::default(),
+                compiler_version: None,
+                file_contents: crate::stdlib::collections::HashMap::new(),
                 reference_manager: Program::get_reference_list(&ReferenceManager {
                     references: crate::stdlib::vec::Vec::new(),
                 }),
@@ -394,7 +429,9 @@ pub mod test_utils {
                     end: val.end,
                     error_message_attributes: val.error_message_attributes,
                     instruction_locations: val.instruction_locations,
-                    identifiers: val.identifiers,
+                    identifiers: Arc::new(val.identifiers),
+                    compiler_version: None,
+                    file_contents: crate::stdlib::collections::HashMap::new(),
                     reference_manager: Program::get_reference_list(&val.reference_manager),
                 }),
                 constants: val.constants,
@@ -976,7 +1013,9 @@ mod test {
             end: None,
             error_message_attributes: Vec::new(),
             instruction_locations: None,
-            identifiers: HashMap::new(),
+            identifiers: Arc::default(),
+            compiler_version: None,
+            file_contents: HashMap::new(),
             reference_manager: Program::get_reference_list(&ReferenceManager {
                 references: Vec::new(),
             }),
@@ -1000,7 +1039,9 @@ mod test {
             end: None,
             error_message_attributes: Vec::new(),
             instruction_locations: None,
-            identifiers: HashMap::new(),
+            identifiers: Arc::default(),
+            compiler_version: None,
+            file_contents: HashMap::new(),
             reference_manager: Program::get_reference_list(&ReferenceManager {
                 references: Vec::new(),
             }),
@@ -1025,7 +1066,9 @@ mod test {
             end: None,
             error_message_attributes: Vec::new(),
             instruction_locations: None,
-            identifiers: HashMap::new(),
+            identifiers: Arc::default(),
+            compiler_version: None,
+            file_contents: HashMap::new(),
             reference_manager: Program::get_reference_list(&ReferenceManager {
                 references: Vec::new(),
             }),
@@ -1041,4 +1084,27 @@ mod test {
             program!(builtins = vec![BuiltinName::range_check], main = Some(2),)
         )
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn printable_short_string() {
+        // Values extracted from cairo book example
+        let value = Felt252::from(5735816763073854913753904210465_u128);
+        assert_eq!(
+            as_printable_short_string(&value),
+            Some("Hello, Scarb!".to_string())
+        );
+        assert_eq!(as_printable_short_string(&Felt252::from(42)), None);
+        assert_eq!(as_printable_short_string(&Felt252::from(0x0a0b)), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_with_short_string_display() {
+        assert_eq!(
+            FeltWithShortString(&Felt252::from(448378203247_u64)).to_string(),
+            "448378203247 ('hello')"
+        );
+        assert_eq!(FeltWithShortString(&Felt252::from(42)).to_string(), "42");
+    }
 }
@@ -0,0 +1,118 @@
+use crate::stdlib::{ops::Range, prelude::*};
+use crate::types::{builtin_name::BuiltinName, relocatable::Relocatable};
+
+use super::{context::run_context::RegisterState, vm_core::VirtualMachine};
+
+/// A snapshot of the minimal VM state at a given step, used to split the proving of long
+/// executions into step windows.
+///
+/// Checkpoints can be taken manually with [VirtualMachine::checkpoint] or every N steps by
+/// setting [VmConfig::checkpoint_interval](super::vm_core::VmConfig::checkpoint_interval).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Number of steps executed when the checkpoint was taken
+    pub step: usize,
+    pub registers: RegisterState,
+    /// Size of each (non-temporary) segment, including memory holes
+    pub segment_sizes: Vec<usize>,
+    /// Next free cell of each builtin segment, in the order of the VM's builtin runners
+    pub builtin_pointers: Vec<(BuiltinName, Relocatable)>,
+}
+
+impl Checkpoint {
+    pub(crate) fn new(vm: &VirtualMachine) -> Self {
+        let segment_sizes: Vec<usize> = vm
+            .segments
+            .memory
+            .data
+            .iter()
+            .map(|segment| segment.len())
+            .collect();
+        let builtin_pointers = vm
+            .builtin_runners
+            .iter()
+            .map(|builtin| {
+                let base = builtin.base();
+                let size = segment_sizes.get(base).copied().unwrap_or_default();
+                (builtin.name(), Relocatable::from((base as isize, size)))
+            })
+            .collect();
+        Checkpoint {
+            step: vm.current_step,
+            registers: vm.get_registers(),
+            segment_sizes,
+            builtin_pointers,
+        }
+    }
+
+    /// Returns the cells added to each segment between this checkpoint and a later one.
+    /// Segments that didn't grow are skipped.
+    ///
+    /// Note that cells written below a segment's size at this checkpoint (e.g. memory holes filled
+    /// afterwards) aren't included.
+    pub fn segment_growth(&self, later: &Checkpoint) -> Vec<Range<Relocatable>> {
+        later
+            .segment_sizes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, end)| {
+                let start = self.segment_sizes.get(index).copied().unwrap_or_default();
+                (start < *end).then(|| {
+                    Relocatable::from((index as isize, start))
+                        ..Relocatable::from((index as isize, *end))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use crate::vm::runners::builtin_runner::OutputBuiltinRunner;
+    use crate::Felt252;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn checkpoint_and_segment_growth() {
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((0, 1), 2), ((1, 0), 3)];
+        let mut output = OutputBuiltinRunner::new(true);
+        output.initialize_segments(&mut vm.segments);
+        vm.builtin_runners.push(output.into());
+        run_context!(vm, 1, 1, 0);
+
+        let first = vm.checkpoint();
+        assert_eq!(first.step, 0);
+        assert_eq!(first.segment_sizes, vec![2, 1, 0]);
+        assert_eq!(
+            first.builtin_pointers,
+            vec![(BuiltinName::output, Relocatable::from((2, 0)))]
+        );
+
+        vm.segments
+            .memory
+            .insert((1, 2).into(), Felt252::from(4))
+            .unwrap();
+        vm.segments
+            .memory
+            .insert((2, 0).into(), Felt252::from(5))
+            .unwrap();
+        let second = vm.checkpoint();
+        assert_eq!(
+            second.builtin_pointers,
+            vec![(BuiltinName::output, Relocatable::from((2, 1)))]
+        );
+        assert_eq!(
+            first.segment_growth(&second),
+            vec![
+                Relocatable::from((1, 1))..Relocatable::from((1, 3)),
+                Relocatable::from((2, 0))..Relocatable::from((2, 1)),
+            ]
+        );
+    }
+}
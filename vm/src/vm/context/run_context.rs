@@ -9,6 +9,17 @@ use crate::{
 };
 use num_traits::abs;
 
+/// The values of the pc, ap and fp registers at a given point of the execution.
+///
+/// As in [TraceEntry](crate::vm::trace::trace_entry::TraceEntry), ap and fp are offsets into the
+/// execution segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterState {
+    pub pc: Relocatable,
+    pub ap: usize,
+    pub fp: usize,
+}
+
 pub struct RunContext {
     pub(crate) pc: Relocatable,
     pub(crate) ap: usize,
@@ -3,7 +3,32 @@ use crate::{
         ApUpdate, FpUpdate, Instruction, Op1Addr, Opcode, PcUpdate, Register, Res,
     },
     vm::errors::vm_errors::VirtualMachineError,
+    Felt252,
 };
+use num_traits::ToPrimitive;
+
+/// Decodes the instructions run by the [VirtualMachine](crate::vm::vm_core::VirtualMachine).
+///
+/// The VM uses [CairoInstructionDecoder] unless another decoder is set with
+/// [VmConfig::instruction_decoder](crate::vm::vm_core::VmConfig::instruction_decoder), which lets
+/// embedders run alternative encodings (e.g. experimental opcodes) without forking the VM.
+/// Decoded instructions from the program segment are cached, so decoding must be deterministic.
+pub trait InstructionDecoder: Send + Sync {
+    fn decode(&self, encoded_instr: &Felt252) -> Result<Instruction, VirtualMachineError>;
+}
+
+/// The instruction encoding of Cairo, see [decode_instruction].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CairoInstructionDecoder;
+
+impl InstructionDecoder for CairoInstructionDecoder {
+    fn decode(&self, encoded_instr: &Felt252) -> Result<Instruction, VirtualMachineError> {
+        let encoded_instr = encoded_instr
+            .to_u64()
+            .ok_or(VirtualMachineError::InvalidInstructionEncoding)?;
+        decode_instruction(encoded_instr)
+    }
+}
 
 //  0|  opcode|ap_update|pc_update|res_logic|op1_src|op0_reg|dst_reg
 // 15|14 13 12|    11 10|  9  8  7|     6  5|4  3  2|      1|      0
@@ -144,6 +169,21 @@ mod decoder_test {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_instruction_decoder() {
+        assert_eq!(
+            CairoInstructionDecoder
+                .decode(&Felt252::from(0x14A7800080008000_u64))
+                .unwrap(),
+            decode_instruction(0x14A7800080008000).unwrap()
+        );
+        assert_matches!(
+            CairoInstructionDecoder.decode(&Felt252::from(u128::MAX)),
+            Err(VirtualMachineError::InvalidInstructionEncoding)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn non_zero_high_bit() {
@@ -0,0 +1,222 @@
+//! Renders decoded [Instruction]s back as Cairo assembly, e.g. `[ap + 1] = [fp + (-3)] + 5, ap++`.
+//!
+//! Meant for debuggers, error messages and external tooling inspecting the program segment.
+
+use crate::{
+    math_utils::signed_felt,
+    stdlib::{fmt, prelude::*},
+    types::{
+        instruction::{ApUpdate, Instruction, Op1Addr, Opcode, PcUpdate, Register, Res},
+        relocatable::MaybeRelocatable,
+    },
+    vm::decoding::decoder::decode_instruction,
+    Felt252,
+};
+use num_traits::ToPrimitive;
+
+/// Renders `instruction` as Cairo assembly.
+///
+/// `imm` is the immediate following the instruction, used when its op1 is [Op1Addr::Imm]. It is
+/// rendered as a signed value, so `jmp rel -2` is shown instead of `jmp rel <p - 2>`.
+pub fn disassemble_instruction(instruction: &Instruction, imm: Option<&Felt252>) -> String {
+    DisassembledInstruction { instruction, imm }.to_string()
+}
+
+/// Renders the instructions in `data`, usually the program segment, as Cairo assembly.
+///
+/// Returns the offset of each instruction along with its text. Immediates are rendered as part
+/// of their instruction, while values which aren't instructions are rendered as `dw <value>`.
+pub fn disassemble_program(data: &[MaybeRelocatable]) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut pc = 0;
+    while pc < data.len() {
+        let instruction = match &data[pc] {
+            MaybeRelocatable::Int(value) => value
+                .to_u64()
+                .and_then(|encoding| decode_instruction(encoding).ok()),
+            MaybeRelocatable::RelocatableValue(_) => None,
+        };
+        match instruction {
+            Some(instruction) => {
+                let imm = data.get(pc + 1).and_then(MaybeRelocatable::get_int_ref);
+                lines.push((pc, disassemble_instruction(&instruction, imm)));
+                pc += instruction.size();
+            }
+            None => {
+                lines.push((pc, format!("dw {}", data[pc])));
+                pc += 1;
+            }
+        }
+    }
+    lines
+}
+
+struct DisassembledInstruction<'a> {
+    instruction: &'a Instruction,
+    imm: Option<&'a Felt252>,
+}
+
+struct Deref(Register, isize);
+
+impl fmt::Display for Deref {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let register = match self.0 {
+            Register::AP => "ap",
+            Register::FP => "fp",
+        };
+        match self.1 {
+            0 => write!(f, "[{register}]"),
+            off if off < 0 => write!(f, "[{register} + ({off})]"),
+            off => write!(f, "[{register} + {off}]"),
+        }
+    }
+}
+
+impl DisassembledInstruction<'_> {
+    fn dst(&self) -> Deref {
+        Deref(self.instruction.dst_register, self.instruction.off0)
+    }
+
+    fn op0(&self) -> Deref {
+        Deref(self.instruction.op0_register, self.instruction.off1)
+    }
+
+    fn op1(&self) -> String {
+        let off2 = self.instruction.off2;
+        match self.instruction.op1_addr {
+            Op1Addr::Imm => match self.imm {
+                Some(imm) => signed_felt(*imm).to_string(),
+                None => "<missing immediate>".to_string(),
+            },
+            Op1Addr::AP => Deref(Register::AP, off2).to_string(),
+            Op1Addr::FP => Deref(Register::FP, off2).to_string(),
+            Op1Addr::Op0 => match off2 {
+                0 => format!("[{}]", self.op0()),
+                off if off < 0 => format!("[{} + ({off})]", self.op0()),
+                off => format!("[{} + {off}]", self.op0()),
+            },
+        }
+    }
+
+    /// Like [op1](Self::op1), but with negative immediates wrapped in parentheses.
+    fn op1_operand(&self) -> String {
+        let op1 = self.op1();
+        match op1.starts_with('-') {
+            true => format!("({op1})"),
+            false => op1,
+        }
+    }
+
+    fn res(&self) -> String {
+        match self.instruction.res {
+            Res::Add => format!("{} + {}", self.op0(), self.op1_operand()),
+            Res::Mul => format!("{} * {}", self.op0(), self.op1_operand()),
+            Res::Op1 | Res::Unconstrained => self.op1(),
+        }
+    }
+}
+
+impl fmt::Display for DisassembledInstruction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let instruction = self.instruction;
+        match (instruction.opcode, instruction.pc_update) {
+            (Opcode::Ret, _) => return write!(f, "ret"),
+            (Opcode::Call, PcUpdate::Jump) => return write!(f, "call abs {}", self.op1()),
+            (Opcode::Call, _) => return write!(f, "call rel {}", self.op1()),
+            (Opcode::AssertEq, _) => write!(f, "{} = {}", self.dst(), self.res())?,
+            (Opcode::NOp, PcUpdate::Jump) => write!(f, "jmp abs {}", self.res())?,
+            (Opcode::NOp, PcUpdate::JumpRel) => write!(f, "jmp rel {}", self.res())?,
+            (Opcode::NOp, PcUpdate::Jnz) => {
+                write!(f, "jmp rel {} if {} != 0", self.op1(), self.dst())?
+            }
+            (Opcode::NOp, PcUpdate::Regular) => {
+                return match instruction.ap_update {
+                    ApUpdate::Add => write!(f, "ap += {}", self.res()),
+                    ApUpdate::Add1 => write!(f, "ap += 1"),
+                    _ => write!(f, "nop"),
+                };
+            }
+        }
+        match instruction.ap_update {
+            ApUpdate::Add1 => write!(f, ", ap++"),
+            ApUpdate::Add => write!(f, ", ap += {}", self.res()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn disassemble(encoding: u64, imm: Option<Felt252>) -> String {
+        disassemble_instruction(&decode_instruction(encoding).unwrap(), imm.as_ref())
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn disassemble_assert_eq() {
+        assert_eq!(
+            disassemble(0x482680017ffd8000, Some(Felt252::from(5))),
+            "[ap] = [fp + (-3)] + 5, ap++"
+        );
+        assert_eq!(
+            disassemble(0x482680017ffd8000, Some(Felt252::from(-5))),
+            "[ap] = [fp + (-3)] + (-5), ap++"
+        );
+        assert_eq!(
+            disassemble(0x480a7ffc7fff8000, None),
+            "[ap] = [fp + (-4)], ap++"
+        );
+        assert_eq!(
+            disassemble(0x400380027ffc7fff, None),
+            "[fp + (-1)] = [[fp + (-4)] + 2]"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn disassemble_control_flow() {
+        assert_eq!(disassemble(0x208b7fff7fff7ffe, None), "ret");
+        assert_eq!(
+            disassemble(0x1104800180018000, Some(Felt252::from(4))),
+            "call rel 4"
+        );
+        assert_eq!(
+            disassemble(0x10780017fff7fff, Some(Felt252::from(-2))),
+            "jmp rel -2"
+        );
+        assert_eq!(
+            disassemble(0x20680017fff7fff, Some(Felt252::from(6))),
+            "jmp rel 6 if [ap + (-1)] != 0"
+        );
+        assert_eq!(
+            disassemble(0x40780017fff7fff, Some(Felt252::from(3))),
+            "ap += 3"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn disassemble_program_listing() {
+        let data = vec![
+            MaybeRelocatable::from(Felt252::from(0x480680017fff8000_u64)),
+            MaybeRelocatable::from(Felt252::from(7)),
+            MaybeRelocatable::from((2, 0)),
+            MaybeRelocatable::from(Felt252::from(0x208b7fff7fff7ffe_u64)),
+            MaybeRelocatable::from(Felt252::from(1_u64 << 63)),
+        ];
+        assert_eq!(
+            disassemble_program(&data),
+            vec![
+                (0, "[ap] = 7, ap++".to_string()),
+                (2, "dw 2:0".to_string()),
+                (3, "ret".to_string()),
+                (4, "dw 9223372036854775808".to_string()),
+            ]
+        );
+    }
+}
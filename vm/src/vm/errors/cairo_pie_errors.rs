@@ -3,6 +3,7 @@ use thiserror_no_std::Error;
 use crate::types::builtin_name::BuiltinName;
 
 #[derive(Eq, Hash, PartialEq, Debug, Error)]
+#[non_exhaustive]
 pub enum CairoPieValidationError {
     #[error("Invalid main() address.")]
     InvalidMainAddress,
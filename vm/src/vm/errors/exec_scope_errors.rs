@@ -1,9 +1,12 @@
 use thiserror_no_std::Error;
 
 #[derive(Eq, Hash, PartialEq, Debug, Error)]
+#[non_exhaustive]
 pub enum ExecScopeError {
     #[error("Cannot exit main scope.")]
     ExitMainScopeError,
     #[error("Every enter_scope() requires a corresponding exit_scope().")]
     NoScopeError,
+    #[error("Variable {0} can't be cloned, its type wasn't registered as cloneable.")]
+    NonCloneableVariable(Box<str>),
 }
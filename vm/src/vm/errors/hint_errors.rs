@@ -17,8 +17,11 @@ use super::{
     exec_scope_errors::ExecScopeError, memory_errors::MemoryError, vm_errors::VirtualMachineError,
 };
 
+pub type HintResult<T> = Result<T, HintError>;
+
 // For more info on #[error] syntax, see https://docs.rs/thiserror/latest/thiserror/#details
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum HintError {
     #[error(transparent)]
     FromScopeError(#[from] ExecScopeError),
@@ -170,6 +173,8 @@ pub enum HintError {
     NonLeFelt252(Box<(Felt252, Felt252)>),
     #[error("Unknown Hint: {0}")]
     UnknownHint(Box<str>),
+    #[error("Unknown Hint: {}\nThe program was compiled with cairo-lang {}, which is newer than the versions supported by the hint processor", (*.0).0, (*.0).1)]
+    UnknownHintNewerCompilerVersion(Box<(Box<str>, String)>),
     #[error("Signature hint must point to the signature builtin segment, not {0}.")]
     AddSignatureWrongEcdsaPtr(Box<Relocatable>),
     #[error("Signature hint must point to the public key cell, not {0}.")]
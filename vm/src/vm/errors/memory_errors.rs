@@ -14,6 +14,7 @@ use crate::types::{
 };
 
 #[derive(Debug, PartialEq, Error)]
+#[non_exhaustive]
 pub enum MemoryError {
     #[error(transparent)]
     Math(#[from] MathError),
@@ -45,8 +46,8 @@ pub enum MemoryError {
     DuplicatedRelocation(isize),
     #[error("Segment effective sizes haven't been calculated.")]
     MissingSegmentUsedSizes,
-    #[error("Found a memory gap when calling get_continuous_range with base:{} and size: {}", (*.0).0, (*.0).1)]
-    GetRangeMemoryGap(Box<(Relocatable, usize)>),
+    #[error("Found a memory gap at {} when calling get_continuous_range with base: {} and size: {}", (*.0).2, (*.0).0, (*.0).1)]
+    GetRangeMemoryGap(Box<(Relocatable, usize, Relocatable)>),
     #[error("Error calculating builtin memory units")]
     ErrorCalculatingMemoryUnits,
     #[error("Missing memory cells for {0}")]
@@ -57,9 +58,9 @@ pub enum MemoryError {
     InitializingVerifyingKey(Box<Vec<u8>>),
     #[error(
         "Signature {}, is invalid, with respect to the public key {}, 
-    and the message hash {}.", (*.0).0, (*.0).1, (*.0).2
+    and the message hash {}, for ECDSA builtin at address {}.", (*.0).1, (*.0).2, (*.0).3, (*.0).0
     )]
-    InvalidSignature(Box<(String, Felt252, Felt252)>),
+    InvalidSignature(Box<(Relocatable, String, Felt252, Felt252)>),
     #[error(
         "Signature hint is missing for ECDSA builtin at address {0}.
     Add it using 'ecdsa_builtin.add_signature'."
@@ -104,6 +105,7 @@ pub enum MemoryError {
 }
 
 #[derive(Debug, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum InsufficientAllocatedCellsError {
     #[error("Number of steps must be at least {} for the {}.", (*.0).0, (*.0).1)]
     MinStepNotReached(Box<(usize, BuiltinName)>),
@@ -1,3 +1,9 @@
+//! Error types returned by the VM, its runners and the hint processors.
+//!
+//! The public error enums are `#[non_exhaustive]`: new variants are added whenever hints or
+//! checks are implemented, and adding them is not considered a breaking change. Downstream code
+//! matching on them needs a wildcard arm.
+
 pub mod cairo_pie_errors;
 pub mod cairo_run_errors;
 pub mod exec_scope_errors;
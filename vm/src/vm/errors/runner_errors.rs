@@ -10,7 +10,10 @@ use super::{memory_errors::MemoryError, trace_errors::TraceError};
 use crate::types::{errors::math_errors::MathError, relocatable::Relocatable};
 use crate::Felt252;
 
+pub type RunnerResult<T> = Result<T, RunnerError>;
+
 #[derive(Debug, PartialEq, Error)]
+#[non_exhaustive]
 pub enum RunnerError {
     #[error("Initialization failure: No execution base")]
     NoExecBase,
@@ -40,8 +43,8 @@ pub enum RunnerError {
     EcOpSameXCoordinate(Box<str>),
     #[error("EcOpBuiltin: point {0:?} is not on the curve")]
     PointNotOnCurve(Box<(Felt252, Felt252)>),
-    #[error("Builtin(s) {:?} not present in layout {}", (*.0).0, (*.0).1)]
-    NoBuiltinForInstance(Box<(HashSet<BuiltinName>, LayoutName)>),
+    #[error("Builtin(s) {:?} not present in layout {}, the program's builtins are supported by the layouts {:?}", (*.0).0, (*.0).1, (*.0).2)]
+    NoBuiltinForInstance(Box<(HashSet<BuiltinName>, LayoutName, Vec<LayoutName>)>),
     #[error("end_run called twice.")]
     EndRunCalledTwice,
     #[error("end_run must be called before finalize_segments.")]
@@ -136,6 +139,16 @@ pub enum RunnerError {
     MissingDynamicLayoutParams,
     #[error("dynamic layout {0} ratio should be 0 when disabled")]
     BadDynamicLayoutBuiltinRatio(BuiltinName),
+    #[error("finalize_segments must be called before fill_builtin_holes.")]
+    FillBuiltinHolesNoFinalizeSegments,
+    #[error("Implicit argument {} of function {} is not a builtin pointer", (*.0).1, (*.0).0)]
+    NonBuiltinImplicitArgument(Box<(String, String)>),
+    #[error("Function {} expects {} argument cells, got {}", (*.0).0, (*.0).1, (*.0).2)]
+    FunctionArgsCountMismatch(Box<(String, usize, usize)>),
+    #[error("Could not compute the size of the return values of function {0}")]
+    UnknownReturnSize(Box<str>),
+    #[error("No factory was provided for the custom builtin {0}")]
+    MissingCustomBuiltinFactory(BuiltinName),
 }
 
 #[cfg(test)]
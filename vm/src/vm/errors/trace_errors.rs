@@ -3,6 +3,7 @@ use thiserror_no_std::Error;
 use crate::vm::errors::memory_errors::MemoryError;
 
 #[derive(Debug, PartialEq, Error)]
+#[non_exhaustive]
 pub enum TraceError {
     #[error("Trace is not enabled for this run")]
     TraceNotEnabled,
@@ -14,6 +15,10 @@ pub enum TraceError {
     MemoryError(#[from] MemoryError),
     #[error("Trace not relocated")]
     TraceNotRelocated,
+    #[error("Memory not relocated")]
+    MemoryNotRelocated,
+    #[error("The trace was filtered by pc and can't be exported as a full trace")]
+    FilteredTrace,
 }
 
 #[cfg(test)]
@@ -20,7 +20,10 @@ use crate::{
 
 pub const HINT_ERROR_STR: &str = "Got an exception while executing a hint: ";
 
+pub type VmResult<T> = Result<T, VirtualMachineError>;
+
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum VirtualMachineError {
     #[error(transparent)]
     RunnerError(#[from] RunnerError),
@@ -80,6 +83,8 @@ pub enum VirtualMachineError {
     NotImplemented,
     #[error("Inconsistent auto-deduction for {}, expected {}, got {:?}", (*.0).0, (*.0).1, (*.0).2)]
     InconsistentAutoDeduction(Box<(BuiltinName, MaybeRelocatable, Option<MaybeRelocatable>)>),
+    #[error("Auto deductions are disabled, the {} builtin output at {} must be present in memory", (*.0).0, (*.0).1)]
+    MissingBuiltinOutput(Box<(BuiltinName, Relocatable)>),
     #[error("Invalid hint encoding at pc: {0}")]
     InvalidHintEncoding(Box<MaybeRelocatable>),
     #[error("Expected output builtin to be present")]
@@ -7,12 +7,13 @@ use crate::{
     types::relocatable::Relocatable,
 };
 
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 use thiserror_no_std::Error;
 
 use crate::{
     hint_processor::hint_processor_utils::get_maybe_relocatable_from_reference,
     serde::deserialize_program::{ApTracking, Attribute, Location, OffsetValue},
-    types::{instruction::Register, relocatable::MaybeRelocatable},
+    types::{exec_scope::ScopeVariable, instruction::Register, relocatable::MaybeRelocatable},
     vm::runners::cairo_runner::CairoRunner,
 };
 
@@ -24,6 +25,18 @@ pub struct VmException {
     pub inner_exc: VirtualMachineError,
     pub error_attr_value: Option<String>,
     pub traceback: Option<String>,
+    pub traceback_entries: Vec<TracebackEntry>,
+    /// The variables of each execution scope when the error occurred, see
+    /// [ExecutionScopes::get_scope_variables](crate::types::exec_scope::ExecutionScopes::get_scope_variables)
+    pub scope_variables: Vec<Vec<ScopeVariable>>,
+}
+
+/// A call frame of the traceback of a [VmException].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TracebackEntry {
+    pub pc: Relocatable,
+    pub inst_location: Option<Location>,
+    pub error_attr_value: Option<String>,
 }
 
 impl VmException {
@@ -49,8 +62,31 @@ impl VmException {
             inner_exc: error,
             error_attr_value,
             traceback: get_traceback(runner),
+            traceback_entries: get_traceback_entries(runner),
+            scope_variables: runner.exec_scopes.get_scope_variables(),
         }
     }
+
+    /// Serializes the exception as a JSON object, with the same fields as its [Serialize]
+    /// implementation.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Serializes the pc, instruction location, error message attributes, traceback entries and scope
+/// variables, along with the message of the inner error as `error`.
+impl Serialize for VmException {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("VmException", 6)?;
+        state.serialize_field("pc", &self.pc)?;
+        state.serialize_field("inst_location", &self.inst_location)?;
+        state.serialize_field("error", &self.inner_exc.to_string())?;
+        state.serialize_field("error_attr_value", &self.error_attr_value)?;
+        state.serialize_field("traceback", &self.traceback_entries)?;
+        state.serialize_field("scope_variables", &self.scope_variables)?;
+        state.end()
+    }
 }
 
 pub fn get_error_attr_value(pc: usize, runner: &CairoRunner) -> Option<String> {
@@ -116,6 +152,30 @@ pub fn get_traceback(runner: &CairoRunner) -> Option<String> {
         .then(|| format!("Cairo traceback (most recent call last):\n{traceback}"))
 }
 
+// Returns the traceback entries at the current pc, in the same order as [get_traceback].
+pub fn get_traceback_entries(runner: &CairoRunner) -> Vec<TracebackEntry> {
+    runner
+        .vm
+        .get_traceback_entries()
+        .into_iter()
+        .map(|(_fp, pc)| {
+            let (inst_location, error_attr_value) = if pc.segment_index == 0 {
+                (
+                    get_location(pc.offset, runner, None),
+                    get_error_attr_value(pc.offset, runner),
+                )
+            } else {
+                (None, None)
+            };
+            TracebackEntry {
+                pc,
+                inst_location,
+                error_attr_value,
+            }
+        })
+        .collect()
+}
+
 // Substitutes references in the given error_message attribute with their actual value.
 // References are defined with '{}'. E.g., 'x must be positive. Got: {x}'.
 fn substitute_error_message_references(
@@ -178,6 +238,13 @@ fn get_value_from_simple_reference(
     // Filter ap-based references
     match reference.offset1 {
         OffsetValue::Reference(Register::AP, _, _, _) => None,
+        _ if reference
+            .expression
+            .as_ref()
+            .is_some_and(|expression| expression.uses_ap()) =>
+        {
+            None
+        }
         _ => {
             // Filer complex types (only felt/felt pointers)
             match reference.cairo_type {
@@ -328,6 +395,8 @@ mod test {
         let instruction_location = InstructionLocation {
             inst: location.clone(),
             hints: vec![],
+            accessible_scopes: vec![],
+            flow_tracking_data: None,
         };
         let program = program!(
             instruction_locations = Some(HashMap::from([(pc.offset, instruction_location)])),
@@ -341,10 +410,32 @@ mod test {
                 inner_exc: VirtualMachineError::NoImm,
                 error_attr_value: None,
                 traceback: None,
-            } if x == pc && y == location
+                traceback_entries,
+                scope_variables: _,
+            } if x == pc && y == location && traceback_entries.is_empty()
         )
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_vm_exception_from_vm_error_keeps_scope_variables() {
+        let program = program!();
+        let mut runner = cairo_runner!(program);
+        runner.exec_scopes.insert_value("n", 5_u64);
+        runner.exec_scopes.enter_scope(HashMap::new());
+        let vm_excep = VmException::from_vm_error(&runner, VirtualMachineError::NoImm);
+        assert_eq!(
+            vm_excep.scope_variables,
+            vec![
+                vec![ScopeVariable {
+                    name: String::from("n"),
+                    type_name: Some("u64"),
+                }],
+                vec![],
+            ]
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn location_to_string_no_message() {
@@ -397,6 +488,8 @@ mod test {
             ))),
             error_attr_value: None,
             traceback: None,
+            traceback_entries: Vec::new(),
+            scope_variables: Vec::new(),
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -422,6 +515,8 @@ mod test {
             ))),
             error_attr_value: Some(String::from("Error message: Block may fail\n")),
             traceback: None,
+            traceback_entries: Vec::new(),
+            scope_variables: Vec::new(),
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -457,6 +552,8 @@ mod test {
             ))),
             error_attr_value: None,
             traceback: None,
+            traceback_entries: Vec::new(),
+            scope_variables: Vec::new(),
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -504,6 +601,8 @@ mod test {
             ))),
             error_attr_value: None,
             traceback: None,
+            traceback_entries: Vec::new(),
+            scope_variables: Vec::new(),
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -523,6 +622,7 @@ mod test {
             end_pc: 5,
             value: String::from("Invalid hash"),
             flow_tracking_data: None,
+            accessible_scopes: vec![],
         }];
         let program = program!(error_message_attributes = attributes,);
         let runner = cairo_runner!(program);
@@ -541,6 +641,7 @@ mod test {
             end_pc: 5,
             value: String::from("Invalid hash"),
             flow_tracking_data: None,
+            accessible_scopes: vec![],
         }];
         let program = program!(error_message_attributes = attributes,);
         let runner = cairo_runner!(program);
@@ -563,6 +664,8 @@ mod test {
         let instruction_location = InstructionLocation {
             inst: location.clone(),
             hints: vec![],
+            accessible_scopes: vec![],
+            flow_tracking_data: None,
         };
         let program =
             program!(instruction_locations = Some(HashMap::from([(2, instruction_location)])),);
@@ -586,6 +689,8 @@ mod test {
         let instruction_location = InstructionLocation {
             inst: location,
             hints: vec![],
+            accessible_scopes: vec![],
+            flow_tracking_data: None,
         };
         let program =
             program!(instruction_locations = Some(HashMap::from([(2, instruction_location)])),);
@@ -623,6 +728,8 @@ mod test {
         let instruction_location = InstructionLocation {
             inst: location_a,
             hints: vec![hint_location],
+            accessible_scopes: vec![],
+            flow_tracking_data: None,
         };
         let program =
             program!(instruction_locations = Some(HashMap::from([(2, instruction_location)])),);
@@ -696,6 +803,73 @@ cairo_programs/bad_programs/bad_usort.cairo:64:5: (pc=0:60)
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_traceback_entries_bad_usort() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/bad_programs/bad_usort.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false);
+
+        let end = cairo_runner.initialize(false).unwrap();
+        assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_err());
+        let entries = get_traceback_entries(&cairo_runner);
+        assert_eq!(
+            entries.iter().map(|entry| entry.pc).collect::<Vec<_>>(),
+            vec![(0, 97).into(), (0, 30).into(), (0, 60).into()]
+        );
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.inst_location.as_ref().unwrap().start_line)
+                .collect::<Vec<_>>(),
+            vec![91, 36, 64]
+        );
+        assert!(entries.iter().all(|entry| entry.error_attr_value.is_none()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn vm_exception_to_json() {
+        let vm_excep = VmException {
+            pc: (0, 2).into(),
+            inst_location: None,
+            inner_exc: VirtualMachineError::NoImm,
+            error_attr_value: Some(String::from("Error message: Block may fail\n")),
+            traceback: Some(String::from("Cairo traceback (most recent call last):\n")),
+            traceback_entries: vec![TracebackEntry {
+                pc: (0, 7).into(),
+                inst_location: None,
+                error_attr_value: None,
+            }],
+            scope_variables: vec![vec![ScopeVariable {
+                name: String::from("n"),
+                type_name: Some("u64"),
+            }]],
+        };
+        let json: serde_json::Value = serde_json::from_str(&vm_excep.to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "pc": {"segment_index": 0, "offset": 2},
+                "inst_location": null,
+                "error": VirtualMachineError::NoImm.to_string(),
+                "error_attr_value": "Error message: Block may fail\n",
+                "traceback": [
+                    {
+                        "pc": {"segment_index": 0, "offset": 7},
+                        "inst_location": null,
+                        "error_attr_value": null
+                    }
+                ],
+                "scope_variables": [[{"name": "n", "type_name": "u64"}]]
+            })
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn location_to_string_with_contents_no_contents() {
@@ -1141,6 +1315,8 @@ cairo_programs/bad_programs/uint256_sub_b_gt_256.cairo:10:2: (pc=0:12)
         let instruction_location = InstructionLocation {
             inst: location,
             hints: vec![],
+            accessible_scopes: vec![],
+            flow_tracking_data: None,
         };
         let program =
             program!(instruction_locations = Some(HashMap::from([(5, instruction_location)])),);
@@ -1154,7 +1330,9 @@ cairo_programs/bad_programs/uint256_sub_b_gt_256.cairo:10:2: (pc=0:12)
                 inner_exc: VirtualMachineError::NoImm,
                 error_attr_value: None,
                 traceback: None,
-            } if x == pc
+                traceback_entries,
+                scope_variables: _,
+            } if x == pc && traceback_entries.is_empty()
         )
     }
 }
@@ -8,13 +8,16 @@
 //! - before_first_step, executed before entering the execution loop in [run_until_pc](CairoRunner::run_until_pc)
 //! - pre_step_instruction, executed before each instruction_step in [step](VirtualMachine::step)
 //! - post_step_instruction, executed after each instruction_step in [step](VirtualMachine::step)
+//! - pre_step, executed right before each instruction is run, see [set_pre_step_hook](VirtualMachine::set_pre_step_hook)
+//! - post_step, executed right after each instruction is run, see [set_post_step_hook](VirtualMachine::set_post_step_hook)
 
 use crate::stdlib::{any::Any, collections::HashMap, prelude::*, sync::Arc};
 
 use crate::Felt252;
 
 use crate::{
-    hint_processor::hint_processor_definition::HintProcessor, types::exec_scope::ExecutionScopes,
+    hint_processor::hint_processor_definition::HintProcessor,
+    types::{exec_scope::ExecutionScopes, instruction::Instruction},
 };
 
 use super::{errors::vm_errors::VirtualMachineError, vm_core::VirtualMachine};
@@ -35,6 +38,9 @@ type StepHookFunc = Arc<
         + Send,
 >;
 
+type InstructionHookFunc =
+    Arc<dyn Fn(&VirtualMachine, &Instruction) -> Result<(), VirtualMachineError> + Sync + Send>;
+
 /// The hooks to be executed during the VM run
 ///
 /// They can be individually ignored by setting them to [None]
@@ -43,6 +49,8 @@ pub struct Hooks {
     before_first_step: Option<BeforeFirstStepHookFunc>,
     pre_step_instruction: Option<StepHookFunc>,
     post_step_instruction: Option<StepHookFunc>,
+    pre_step: Option<InstructionHookFunc>,
+    post_step: Option<InstructionHookFunc>,
 }
 
 impl Hooks {
@@ -55,11 +63,62 @@ impl Hooks {
             before_first_step,
             pre_step_instruction,
             post_step_instruction,
+            pre_step: None,
+            post_step: None,
         }
     }
 }
 
 impl VirtualMachine {
+    /// Sets a hook called right before each instruction is run, with the decoded instruction.
+    /// The registers (see [get_pc](VirtualMachine::get_pc), [get_ap](VirtualMachine::get_ap)
+    /// and [get_fp](VirtualMachine::get_fp)) still hold their values prior to the instruction.
+    /// Instructions skipped by a hint don't trigger the hook.
+    pub fn set_pre_step_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&VirtualMachine, &Instruction) -> Result<(), VirtualMachineError>
+            + Sync
+            + Send
+            + 'static,
+    {
+        self.hooks.pre_step = Some(Arc::new(hook));
+    }
+
+    /// Sets a hook called right after each instruction is run, with the decoded instruction.
+    /// The registers already hold their updated values.
+    /// Instructions skipped by a hint don't trigger the hook.
+    pub fn set_post_step_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&VirtualMachine, &Instruction) -> Result<(), VirtualMachineError>
+            + Sync
+            + Send
+            + 'static,
+    {
+        self.hooks.post_step = Some(Arc::new(hook));
+    }
+
+    pub(crate) fn execute_pre_step(
+        &self,
+        instruction: &Instruction,
+    ) -> Result<(), VirtualMachineError> {
+        if let Some(hook_func) = &self.hooks.pre_step {
+            (hook_func)(self, instruction)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn execute_post_step(
+        &self,
+        instruction: &Instruction,
+    ) -> Result<(), VirtualMachineError> {
+        if let Some(hook_func) = &self.hooks.post_step {
+            (hook_func)(self, instruction)?;
+        }
+
+        Ok(())
+    }
+
     pub fn execute_before_first_step(
         &mut self,
         hint_data: &[Box<dyn Any>],
@@ -229,4 +288,65 @@ mod tests {
         let end = cairo_runner.initialize(false).unwrap();
         assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_ok());
     }
+
+    #[test]
+    fn instruction_step_hooks() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let program = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/sqrt.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+
+        let pre_steps = Arc::new(AtomicUsize::new(0));
+        let post_steps = Arc::new(AtomicUsize::new(0));
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let steps = Arc::clone(&pre_steps);
+        cairo_runner.vm.set_pre_step_hook(move |vm, _instruction| {
+            // The instruction hasn't been run yet
+            assert_eq!(vm.current_step, steps.fetch_add(1, Ordering::Relaxed));
+            Ok(())
+        });
+        let steps = Arc::clone(&post_steps);
+        cairo_runner.vm.set_post_step_hook(move |vm, _instruction| {
+            // The instruction has already been run
+            assert_eq!(vm.current_step, steps.fetch_add(1, Ordering::Relaxed) + 1);
+            Ok(())
+        });
+
+        let end = cairo_runner.initialize(false).unwrap();
+        assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_ok());
+
+        assert_eq!(
+            pre_steps.load(Ordering::Relaxed),
+            cairo_runner.vm.current_step
+        );
+        assert_eq!(
+            post_steps.load(Ordering::Relaxed),
+            cairo_runner.vm.current_step
+        );
+    }
+
+    #[test]
+    fn instruction_step_hook_failure() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/sqrt.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        cairo_runner
+            .vm
+            .set_post_step_hook(|_vm, _instruction| Err(VirtualMachineError::Unexpected));
+
+        let end = cairo_runner.initialize(false).unwrap();
+        assert!(cairo_runner.run_until_pc(end, &mut hint_processor).is_err());
+        // The failing hook runs after the first instruction
+        assert_eq!(cairo_runner.vm.current_step, 1);
+    }
 }
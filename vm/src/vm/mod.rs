@@ -1,3 +1,4 @@
+pub mod checkpoint;
 pub mod context;
 pub mod decoding;
 pub mod errors;
@@ -7,6 +8,6 @@ pub mod trace;
 pub mod vm_core;
 pub mod vm_memory;
 
-#[cfg(feature = "test_utils")]
-#[cfg_attr(docsrs, doc(cfg(feature = "test_utils")))]
+#[cfg(feature = "hooks")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hooks")))]
 pub mod hooks;
@@ -0,0 +1,339 @@
+use crate::stdlib::{boxed::Box, fmt, sync::Arc, vec::Vec};
+use crate::{
+    types::{
+        builtin_name::{BuiltinName, CustomBuiltinName},
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        errors::{memory_errors::MemoryError, runner_errors::RunnerError},
+        vm_memory::{
+            memory::{Memory, ValidationRule},
+            memory_segments::MemorySegmentManager,
+        },
+    },
+};
+use num_integer::div_ceil;
+
+/// A builtin that isn't part of Cairo, e.g. an appchain-specific one.
+///
+/// Custom builtins are listed after the Cairo builtins in the program's builtins and are run by a
+/// [CustomBuiltinRunner], whose builtin is created by the [CustomBuiltinFactory] registered under
+/// its name in [RunnerConfig::custom_builtins](crate::vm::runners::cairo_runner::RunnerConfig).
+/// As they aren't part of any layout, they have no allocated cells and their runs can't be proven.
+pub trait CustomBuiltin: Send + Sync {
+    /// Returns the number of memory cells of each instance of the builtin, must be positive.
+    fn cells_per_instance(&self) -> u32;
+
+    /// Returns the number of input cells of each instance, which come before its output cells.
+    /// Must be positive and not greater than [Self::cells_per_instance].
+    fn n_input_cells(&self) -> u32;
+
+    /// Returns the values pushed into the stack for the builtin when calling the program's main,
+    /// given the base of the builtin segment.
+    /// The builtin's final stack is a single stop pointer to its segment, checked against the
+    /// used cells of the segment.
+    fn initial_stack(&self, base: Relocatable) -> Vec<MaybeRelocatable> {
+        vec![base.into()]
+    }
+
+    /// Deduces the value of the output cell at `address` from the inputs of its instance.
+    /// Returns None if it can't be deduced (e.g. the inputs aren't set yet).
+    fn deduce_memory_cell(
+        &self,
+        _address: Relocatable,
+        _memory: &Memory,
+    ) -> Result<Option<MaybeRelocatable>, RunnerError> {
+        Ok(None)
+    }
+
+    /// Returns true if [Self::validate_memory_cell] must be run on the values written to the
+    /// builtin segment.
+    fn has_validation_rule(&self) -> bool {
+        false
+    }
+
+    /// Validates the value at `address` of the builtin segment, returning the validated addresses.
+    fn validate_memory_cell(
+        &self,
+        _memory: &Memory,
+        address: Relocatable,
+    ) -> Result<Vec<Relocatable>, MemoryError> {
+        Ok(vec![address])
+    }
+
+    /// Returns the number of used cells of the builtin segment, given its index.
+    fn get_used_cells(
+        &self,
+        segments: &MemorySegmentManager,
+        segment_index: usize,
+    ) -> Result<usize, MemoryError> {
+        segments
+            .get_segment_used_size(segment_index)
+            .ok_or(MemoryError::MissingSegmentUsedSizes)
+    }
+}
+
+/// Creates the [CustomBuiltin] of each run of a program using the custom builtin.
+pub trait CustomBuiltinFactory: Send + Sync {
+    fn new_builtin(&self) -> Box<dyn CustomBuiltin>;
+}
+
+impl<F> CustomBuiltinFactory for F
+where
+    F: Fn() -> Box<dyn CustomBuiltin> + Send + Sync,
+{
+    fn new_builtin(&self) -> Box<dyn CustomBuiltin> {
+        self()
+    }
+}
+
+#[derive(Clone)]
+pub struct CustomBuiltinRunner {
+    name: CustomBuiltinName,
+    builtin: Arc<dyn CustomBuiltin>,
+    pub base: usize,
+    pub(crate) stop_ptr: Option<usize>,
+    pub(crate) included: bool,
+}
+
+impl CustomBuiltinRunner {
+    pub(crate) fn new(
+        name: CustomBuiltinName,
+        builtin: Box<dyn CustomBuiltin>,
+        included: bool,
+    ) -> Self {
+        CustomBuiltinRunner {
+            name,
+            builtin: builtin.into(),
+            base: 0,
+            stop_ptr: None,
+            included,
+        }
+    }
+
+    pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
+        self.base = segments.add().segment_index as usize // segments.add() always returns a positive index
+    }
+
+    pub fn initial_stack(&self) -> Vec<MaybeRelocatable> {
+        if self.included {
+            self.builtin
+                .initial_stack(Relocatable::from((self.base as isize, 0)))
+        } else {
+            vec![]
+        }
+    }
+
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    pub fn name(&self) -> BuiltinName {
+        BuiltinName::custom(self.name)
+    }
+
+    /// Returns the user-provided builtin run by this runner
+    pub fn builtin(&self) -> &dyn CustomBuiltin {
+        self.builtin.as_ref()
+    }
+
+    pub fn cells_per_instance(&self) -> u32 {
+        self.builtin.cells_per_instance()
+    }
+
+    pub fn n_input_cells(&self) -> u32 {
+        self.builtin.n_input_cells()
+    }
+
+    pub fn add_validation_rule(&self, memory: &mut Memory) {
+        if !self.builtin.has_validation_rule() {
+            return;
+        }
+        let builtin = self.builtin.clone();
+        let rule = ValidationRule(Box::new(
+            move |memory: &Memory, address: Relocatable| -> Result<Vec<Relocatable>, MemoryError> {
+                builtin.validate_memory_cell(memory, address)
+            },
+        ));
+        memory.add_validation_rule(self.base, rule);
+    }
+
+    pub fn deduce_memory_cell(
+        &self,
+        address: Relocatable,
+        memory: &Memory,
+    ) -> Result<Option<MaybeRelocatable>, RunnerError> {
+        self.builtin.deduce_memory_cell(address, memory)
+    }
+
+    pub fn get_used_cells(&self, segments: &MemorySegmentManager) -> Result<usize, MemoryError> {
+        self.builtin.get_used_cells(segments, self.base)
+    }
+
+    pub fn get_used_instances(
+        &self,
+        segments: &MemorySegmentManager,
+    ) -> Result<usize, MemoryError> {
+        let used_cells = self.get_used_cells(segments)?;
+        Ok(div_ceil(used_cells, self.cells_per_instance() as usize))
+    }
+}
+
+impl fmt::Debug for CustomBuiltinRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomBuiltinRunner")
+            .field("name", &self.name)
+            .field("base", &self.base)
+            .field("stop_ptr", &self.stop_ptr)
+            .field("included", &self.included)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::relocatable;
+    use crate::utils::test_utils::*;
+    use crate::vm::runners::builtin_runner::BuiltinRunner;
+    use crate::Felt252;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// Doubles its input, which must be an integer
+    struct DoubleBuiltin;
+
+    impl CustomBuiltin for DoubleBuiltin {
+        fn cells_per_instance(&self) -> u32 {
+            2
+        }
+
+        fn n_input_cells(&self) -> u32 {
+            1
+        }
+
+        fn deduce_memory_cell(
+            &self,
+            address: Relocatable,
+            memory: &Memory,
+        ) -> Result<Option<MaybeRelocatable>, RunnerError> {
+            if address.offset % 2 == 0 {
+                return Ok(None);
+            }
+            Ok(memory
+                .get_integer((address - 1)?)
+                .ok()
+                .map(|input| MaybeRelocatable::from(input.double())))
+        }
+
+        fn has_validation_rule(&self) -> bool {
+            true
+        }
+
+        fn validate_memory_cell(
+            &self,
+            memory: &Memory,
+            address: Relocatable,
+        ) -> Result<Vec<Relocatable>, MemoryError> {
+            memory.get_integer(address)?;
+            Ok(vec![address])
+        }
+    }
+
+    fn double_builtin(included: bool) -> BuiltinRunner {
+        CustomBuiltinRunner::new(
+            CustomBuiltinName::new("double").unwrap(),
+            Box::new(DoubleBuiltin),
+            included,
+        )
+        .into()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn name() {
+        assert_eq!(
+            double_builtin(true).name(),
+            BuiltinName::custom(CustomBuiltinName::new("double").unwrap())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initial_stack() {
+        let mut vm = vm!();
+        let mut builtin = double_builtin(true);
+        vm.segments.add();
+        builtin.initialize_segments(&mut vm.segments);
+        assert_eq!(builtin.initial_stack(), vec![mayberelocatable!(1, 0)]);
+        assert_eq!(
+            double_builtin(false).initial_stack(),
+            Vec::<MaybeRelocatable>::new()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell() {
+        let builtin = double_builtin(true);
+        let memory = memory![((0, 0), 3), ((0, 2), (1, 0))];
+        assert_eq!(
+            builtin.deduce_memory_cell(relocatable!(0, 1), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt252::from(6))))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(relocatable!(0, 3), &memory),
+            Ok(None)
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(relocatable!(0, 0), &memory),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validation_rule() {
+        let mut vm = vm!();
+        let mut builtin = double_builtin(true);
+        builtin.initialize_segments(&mut vm.segments);
+        builtin.add_validation_rule(&mut vm.segments.memory);
+        assert_matches!(
+            vm.segments.memory.insert((0, 0).into(), Felt252::ONE),
+            Ok(())
+        );
+        assert_matches!(
+            vm.segments.memory.insert((0, 1).into(), relocatable!(0, 0)),
+            Err(MemoryError::ExpectedInteger(_))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn final_stack() {
+        let mut vm = vm!();
+        let mut builtin = double_builtin(true);
+        builtin.initialize_segments(&mut vm.segments);
+        vm.segments = segments![((0, 0), 1), ((0, 1), 2), ((0, 2), 3), ((1, 0), (0, 4))];
+        vm.segments.segment_used_sizes = Some(vec![3, 1]);
+        assert_eq!(builtin.get_used_instances(&vm.segments), Ok(2));
+        assert_eq!(
+            builtin.final_stack(&vm.segments, relocatable!(1, 1)),
+            Ok(relocatable!(1, 0))
+        );
+        assert_eq!(builtin.get_memory_segment_addresses(), (0, Some(4)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_used_cells_and_allocated_size() {
+        let mut vm = vm!();
+        vm.segments.segment_used_sizes = Some(vec![5]);
+        assert_eq!(
+            double_builtin(true).get_used_cells_and_allocated_size(&vm),
+            Ok((5, 5))
+        );
+    }
+}
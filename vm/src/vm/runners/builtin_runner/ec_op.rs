@@ -1,8 +1,10 @@
 use crate::air_private_input::{PrivateInput, PrivateInputEcOp};
+use crate::math_utils::{ec_add_felt, ec_double_felt};
 use crate::stdlib::prelude::*;
 use crate::stdlib::{cell::RefCell, collections::HashMap};
 use crate::types::instance_definitions::ec_op_instance_def::{
-    CELLS_PER_EC_OP, INPUT_CELLS_PER_EC_OP, SCALAR_HEIGHT,
+    stark_curve_alpha, stark_curve_beta, EcOpInstanceDef, CELLS_PER_EC_OP, INPUT_CELLS_PER_EC_OP,
+    SCALAR_HEIGHT,
 };
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::MemoryError;
@@ -19,19 +21,44 @@ pub struct EcOpBuiltinRunner {
     pub base: usize,
     pub(crate) stop_ptr: Option<usize>,
     pub(crate) included: bool,
+    alpha: Felt252,
+    beta: Felt252,
     cache: RefCell<HashMap<Relocatable, Felt252>>,
 }
 
 impl EcOpBuiltinRunner {
     pub(crate) fn new(ratio: Option<u32>, included: bool) -> Self {
+        Self::from_instance_def(&EcOpInstanceDef::new(ratio), included)
+    }
+
+    pub(crate) fn from_instance_def(instance_def: &EcOpInstanceDef, included: bool) -> Self {
         EcOpBuiltinRunner {
             base: 0,
-            ratio,
+            ratio: instance_def.ratio,
             stop_ptr: None,
             included,
+            alpha: instance_def.alpha,
+            beta: instance_def.beta,
             cache: RefCell::new(HashMap::new()),
         }
     }
+
+    /// Sets the coefficients of the curve y^2 = x^3 + alpha * x + beta the builtin operates on.
+    /// The field prime is always the one of Felt252.
+    pub fn set_curve(&mut self, alpha: Felt252, beta: Felt252) {
+        self.alpha = alpha;
+        self.beta = beta;
+        self.cache.borrow_mut().clear();
+    }
+
+    pub fn alpha(&self) -> Felt252 {
+        self.alpha
+    }
+
+    pub fn beta(&self) -> Felt252 {
+        self.beta
+    }
+
     ///Returns True if the point (x, y) is on the elliptic curve defined as
     ///y^2 = x^3 + alpha * x + beta (mod p)
     ///or False otherwise.
@@ -74,6 +101,31 @@ impl EcOpBuiltinRunner {
             .map_err(|_| RunnerError::InvalidPoint)
     }
 
+    ///Same as `ec_op_impl`, but for an arbitrary curve y^2 = x^3 + alpha * x + beta.
+    ///Works with affine coordinates, as the projective implementation is specific to the STARK curve.
+    fn ec_op_impl_affine(
+        mut partial_sum: (Felt252, Felt252),
+        mut doubled_point: (Felt252, Felt252),
+        m: &Felt252,
+        alpha: &Felt252,
+        height: u32,
+    ) -> Result<(Felt252, Felt252), RunnerError> {
+        let slope = m.to_biguint();
+        for i in 0..(height as u64).min(slope.bits()) {
+            if partial_sum.0 == doubled_point.0 {
+                return Err(RunnerError::EcOpSameXCoordinate(
+                    Self::format_affine_ec_op_error(partial_sum, slope, doubled_point)
+                        .into_boxed_str(),
+                ));
+            };
+            if slope.bit(i) {
+                partial_sum = ec_add_felt(partial_sum, doubled_point)?;
+            }
+            doubled_point = ec_double_felt(doubled_point, alpha)?;
+        }
+        Ok(partial_sum)
+    }
+
     pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
         self.base = segments.add().segment_index as usize // segments.add() always returns a positive index
     }
@@ -102,10 +154,7 @@ impl EcOpBuiltinRunner {
         //Constant values declared here
         const EC_POINT_INDICES: [(usize, usize); 3] = [(0, 1), (2, 3), (5, 6)];
         const OUTPUT_INDICES: (usize, usize) = EC_POINT_INDICES[2];
-        let alpha: Felt252 = Felt252::ONE;
-        let beta_low: Felt252 = Felt252::from(0x609ad26c15c915c1f4cdfcb99cee9e89_u128);
-        let beta_high: Felt252 = Felt252::from(0x6f21413efbe40de150e596d72f7a8c5_u128);
-        let beta: Felt252 = (beta_high * (Felt252::ONE + Felt252::from(u128::MAX))) + beta_low;
+        let (alpha, beta) = (self.alpha, self.beta);
 
         let index = address.offset.mod_floor(&(CELLS_PER_EC_OP as usize));
         //Index should be an output cell
@@ -159,12 +208,24 @@ impl EcOpBuiltinRunner {
                 ))));
             };
         }
-        let result = EcOpBuiltinRunner::ec_op_impl(
-            (input_cells[0].to_owned(), input_cells[1].to_owned()),
-            (input_cells[2].to_owned(), input_cells[3].to_owned()),
-            &input_cells[4],
-            SCALAR_HEIGHT,
-        )?;
+        let partial_sum = (input_cells[0].to_owned(), input_cells[1].to_owned());
+        let doubled_point = (input_cells[2].to_owned(), input_cells[3].to_owned());
+        let result = if alpha == stark_curve_alpha() && beta == stark_curve_beta() {
+            EcOpBuiltinRunner::ec_op_impl(
+                partial_sum,
+                doubled_point,
+                &input_cells[4],
+                SCALAR_HEIGHT,
+            )?
+        } else {
+            EcOpBuiltinRunner::ec_op_impl_affine(
+                partial_sum,
+                doubled_point,
+                &input_cells[4],
+                &alpha,
+                SCALAR_HEIGHT,
+            )?
+        };
         self.cache.borrow_mut().insert(x_addr, result.0);
         self.cache.borrow_mut().insert(
             (x_addr + 1usize)
@@ -199,6 +260,14 @@ impl EcOpBuiltinRunner {
     ) -> String {
         let p = p.to_affine().map(|p| (p.x(), p.y())).unwrap_or_default();
         let q = q.to_affine().map(|q| (q.x(), q.y())).unwrap_or_default();
+        Self::format_affine_ec_op_error(p, m, q)
+    }
+
+    fn format_affine_ec_op_error(
+        p: (Felt252, Felt252),
+        m: num_bigint::BigUint,
+        q: (Felt252, Felt252),
+    ) -> String {
         format!("Cannot apply EC operation: computation reached two points with the same x coordinate. \n
     Attempting to compute P + m * Q where:\n
     P = {p:?} \n
@@ -578,6 +647,92 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_ec_op_impl_affine_matches_stark_curve_impl() {
+        let partial_sum = (
+            felt_hex!("0x6f0a1ddaf19c44781c8946db396f494a10ffab183c2d8cf6c4cd321a8d87fd9"),
+            felt_hex!("0x4afa52a9ef8c023d3385fddb6e1d78d57b0693b9b02d45d0f939b526d474c39"),
+        );
+        let doubled_point = (
+            felt_hex!("0x1ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca"),
+            felt_hex!("0x5668060aa49730b7be4801df46ec62de53ecd11abe43a32873000c36e8dc1f"),
+        );
+        let m = Felt252::from(34);
+        let height = 256;
+        assert_eq!(
+            EcOpBuiltinRunner::ec_op_impl_affine(
+                partial_sum,
+                doubled_point,
+                &m,
+                &stark_curve_alpha(),
+                height
+            ),
+            EcOpBuiltinRunner::ec_op_impl(partial_sum, doubled_point, &m, height)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_ec_op_impl_affine_invalid_same_x_coordinate() {
+        let point = (Felt252::from(1), Felt252::from(2));
+        let m = Felt252::from(3);
+        let result = EcOpBuiltinRunner::ec_op_impl_affine(point, point, &m, &Felt252::from(5), 256);
+        assert_eq!(
+            result,
+            Err(RunnerError::EcOpSameXCoordinate(
+                EcOpBuiltinRunner::format_affine_ec_op_error(point, m.to_biguint(), point)
+                    .into_boxed_str()
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell_ec_op_custom_curve() {
+        // Curve y^2 = x^3 + 5x - 2, with P = (1, 2) and Q = (2, 4)
+        let (alpha, beta) = (Felt252::from(5), Felt252::from(-2));
+        let (p, q) = (
+            (Felt252::from(1), Felt252::from(2)),
+            (Felt252::from(2), Felt252::from(4)),
+        );
+        let memory = memory![
+            ((3, 0), 1),
+            ((3, 1), 2),
+            ((3, 2), 2),
+            ((3, 3), 4),
+            ((3, 4), 3)
+        ];
+        let mut builtin = EcOpBuiltinRunner::new(Some(256), true);
+        assert_eq!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 5)), &memory),
+            Err(RunnerError::PointNotOnCurve(Box::new(p)))
+        );
+
+        builtin.set_curve(alpha, beta);
+        assert_eq!((builtin.alpha(), builtin.beta()), (alpha, beta));
+        // P + 3Q = (P + Q) + 2Q
+        let expected = ec_add_felt(
+            ec_add_felt(p, q).unwrap(),
+            ec_double_felt(q, &alpha).unwrap(),
+        )
+        .unwrap();
+        assert!(EcOpBuiltinRunner::point_on_curve(
+            &expected.0,
+            &expected.1,
+            &alpha,
+            &beta
+        ));
+        assert_eq!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 5)), &memory),
+            Ok(Some(MaybeRelocatable::from(expected.0)))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(Relocatable::from((3, 6)), &memory),
+            Ok(Some(MaybeRelocatable::from(expected.1)))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /* Data taken from this program execution:
@@ -1,4 +1,5 @@
 use crate::air_private_input::{PrivateInput, PrivateInputPair};
+use crate::crypto::pedersen_hash;
 use crate::stdlib::{cell::RefCell, prelude::*};
 use crate::types::builtin_name::BuiltinName;
 use crate::types::instance_definitions::pedersen_instance_def::CELLS_PER_HASH;
@@ -9,7 +10,6 @@ use crate::vm::runners::cairo_pie::BuiltinAdditionalData;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
 use num_integer::{div_ceil, Integer};
-use starknet_types_core::hash::StarkHash;
 
 #[derive(Debug, Clone)]
 pub struct HashBuiltinRunner {
@@ -19,9 +19,9 @@ pub struct HashBuiltinRunner {
     pub(crate) included: bool,
     // This act as a cache to optimize calls to deduce_memory_cell
     // Therefore need interior mutability
-    // 1 at position 'n' means offset 'n' relative to base pointer
-    // has been verified
-    pub(self) verified_addresses: RefCell<Vec<bool>>,
+    // Bitmap over the builtin instances, bit 'n' set means the output cell of
+    // instance 'n' (offset 3n + 2 relative to base pointer) has been verified
+    pub(self) verified_addresses: RefCell<Vec<u64>>,
 }
 
 impl HashBuiltinRunner {
@@ -55,17 +55,41 @@ impl HashBuiltinRunner {
         self.ratio
     }
 
+    /// Returns the amount of output cells whose hash has been verified.
+    pub fn verified_addresses_count(&self) -> usize {
+        self.verified_addresses
+            .borrow()
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // Returns true if the output cell at `offset` relative to base pointer has been verified
+    fn is_verified(&self, offset: usize) -> bool {
+        let instance = offset / CELLS_PER_HASH as usize;
+        self.verified_addresses
+            .borrow()
+            .get(instance / 64)
+            .is_some_and(|word| word & (1 << (instance % 64)) != 0)
+    }
+
+    // Marks the output cell at `offset` relative to base pointer as verified
+    fn set_verified(&self, offset: usize) {
+        let instance = offset / CELLS_PER_HASH as usize;
+        let mut verified_addresses = self.verified_addresses.borrow_mut();
+        if verified_addresses.len() <= instance / 64 {
+            verified_addresses.resize(instance / 64 + 1, 0);
+        }
+        verified_addresses[instance / 64] |= 1 << (instance % 64);
+    }
+
     pub fn deduce_memory_cell(
         &self,
         address: Relocatable,
         memory: &Memory,
     ) -> Result<Option<MaybeRelocatable>, RunnerError> {
         if address.offset.mod_floor(&(CELLS_PER_HASH as usize)) != 2
-            || *self
-                .verified_addresses
-                .borrow()
-                .get(address.offset)
-                .unwrap_or(&false)
+            || self.is_verified(address.offset)
         {
             return Ok(None);
         };
@@ -82,14 +106,9 @@ impl HashBuiltinRunner {
             num_a.as_ref().map(|x| x.as_ref()),
             num_b.as_ref().map(|x| x.as_ref()),
         ) {
-            if self.verified_addresses.borrow().len() <= address.offset {
-                self.verified_addresses
-                    .borrow_mut()
-                    .resize(address.offset + 1, false);
-            }
-            self.verified_addresses.borrow_mut()[address.offset] = true;
+            self.set_verified(address.offset);
             //Compute pedersen Hash
-            let result = starknet_types_core::hash::Pedersen::hash(num_b, num_a);
+            let result = pedersen_hash(num_b, num_a);
             return Ok(Some(MaybeRelocatable::from(result)));
         }
         Ok(None)
@@ -111,8 +130,9 @@ impl HashBuiltinRunner {
 
     pub fn get_additional_data(&self) -> BuiltinAdditionalData {
         let mut verified_addresses = Vec::new();
-        for (offset, is_verified) in self.verified_addresses.borrow().iter().enumerate() {
-            if *is_verified {
+        for (word_index, word) in self.verified_addresses.borrow().iter().enumerate() {
+            for bit in (0..64).filter(|bit| word & (1 << bit) != 0) {
+                let offset = (word_index * 64 + bit) * CELLS_PER_HASH as usize + 2;
                 verified_addresses.push(Relocatable::from((self.base as isize, offset)));
             }
         }
@@ -128,16 +148,14 @@ impl HashBuiltinRunner {
             BuiltinAdditionalData::Empty(_) => return Ok(()),
             _ => return Err(RunnerError::InvalidAdditionalData(BuiltinName::pedersen)),
         };
-        let mut verified_addresses = self.verified_addresses.borrow_mut();
         for addr in additional_data {
-            if addr.segment_index != self.base as isize {
+            // Only output cells can be verified
+            if addr.segment_index != self.base as isize
+                || addr.offset % CELLS_PER_HASH as usize != 2
+            {
                 return Err(RunnerError::InvalidAdditionalData(BuiltinName::pedersen));
             }
-            // Mark offset as verified
-            if addr.offset > verified_addresses.len() {
-                verified_addresses.resize(addr.offset, false);
-            }
-            verified_addresses.insert(addr.offset, true)
+            self.set_verified(addr.offset);
         }
         Ok(())
     }
@@ -394,10 +412,8 @@ mod tests {
                 "0x73b3ec210cccbb970f80c6826fb1c40ae9f487617696234ff147451405c339f"
             ))))
         );
-        assert_eq!(
-            builtin.verified_addresses.into_inner(),
-            vec![false, false, false, false, false, true],
-        );
+        assert!(builtin.is_verified(5));
+        assert_eq!(builtin.verified_addresses_count(), 1);
     }
 
     #[test]
@@ -422,8 +438,8 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn deduce_memory_cell_pedersen_for_preset_memory_already_computed() {
         let memory = memory![((0, 3), 32), ((0, 4), 72), ((0, 5), 0)];
-        let mut builtin = HashBuiltinRunner::new(Some(8), true);
-        builtin.verified_addresses = RefCell::new(vec![false, false, false, false, false, true]);
+        let builtin = HashBuiltinRunner::new(Some(8), true);
+        builtin.set_verified(5);
         let result = builtin.deduce_memory_cell(Relocatable::from((0, 5)), &memory);
         assert_eq!(result, Ok(None));
     }
@@ -462,10 +478,10 @@ mod tests {
 
     #[test]
     fn get_additional_data() {
-        let mut builtin = HashBuiltinRunner::new(Some(1), true);
-        let verified_addresses = vec![Relocatable::from((0, 3)), Relocatable::from((0, 6))];
-        builtin.verified_addresses =
-            RefCell::new(vec![false, false, false, true, false, false, true]);
+        let builtin = HashBuiltinRunner::new(Some(1), true);
+        let verified_addresses = vec![Relocatable::from((0, 5)), Relocatable::from((0, 8))];
+        builtin.set_verified(5);
+        builtin.set_verified(8);
         assert_eq!(
             builtin.get_additional_data(),
             BuiltinAdditionalData::Hash(verified_addresses)
@@ -474,13 +490,26 @@ mod tests {
 
     #[test]
     fn get_and_extend_additional_data() {
-        let mut builtin_a = HashBuiltinRunner::new(Some(1), true);
-        builtin_a.verified_addresses =
-            RefCell::new(vec![false, false, false, true, false, false, true]);
+        let builtin_a = HashBuiltinRunner::new(Some(1), true);
+        builtin_a.set_verified(5);
+        builtin_a.set_verified(8);
+        builtin_a.set_verified(3 * 200 + 2);
         let additional_data = builtin_a.get_additional_data();
         let mut builtin_b = HashBuiltinRunner::new(Some(1), true);
         builtin_b.extend_additional_data(&additional_data).unwrap();
         assert_eq!(builtin_a.verified_addresses, builtin_b.verified_addresses);
+        assert_eq!(builtin_b.verified_addresses_count(), 3);
+    }
+
+    #[test]
+    fn extend_additional_data_input_cell() {
+        let mut builtin = HashBuiltinRunner::new(Some(1), true);
+        assert_eq!(
+            builtin.extend_additional_data(&BuiltinAdditionalData::Hash(vec![Relocatable::from(
+                (0, 3)
+            )])),
+            Err(RunnerError::InvalidAdditionalData(BuiltinName::pedersen))
+        );
     }
 
     #[test]
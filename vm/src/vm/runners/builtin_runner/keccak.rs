@@ -1,4 +1,5 @@
 use crate::air_private_input::{PrivateInput, PrivateInputKeccakState};
+use crate::crypto::keccak_builtin_permutation;
 use crate::math_utils::safe_div_usize;
 use crate::stdlib::{cell::RefCell, collections::HashMap, prelude::*};
 use crate::types::builtin_name::BuiltinName;
@@ -12,10 +13,8 @@ use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
 use crate::Felt252;
 use lazy_static::lazy_static;
-use num_bigint::BigUint;
 use num_integer::div_ceil;
 
-const KECCAK_FELT_BYTE_SIZE: usize = 25; // 200 / 8
 const BITS: u32 = 200;
 lazy_static! {
     static ref KECCAK_INPUT_MAX: Felt252 = Felt252::TWO.pow(BITS);
@@ -76,9 +75,9 @@ impl KeccakBuiltinRunner {
         let first_input_addr = (address - index)?;
         let first_output_addr = (first_input_addr + INPUT_CELLS_PER_KECCAK as usize)?;
 
-        let mut input_felts = vec![];
+        let mut input_felts = [Felt252::ZERO; INPUT_CELLS_PER_KECCAK as usize];
 
-        for i in 0..INPUT_CELLS_PER_KECCAK as usize {
+        for (i, input_felt) in input_felts.iter_mut().enumerate() {
             let m_index = (first_input_addr + i)?;
             let val = match memory.get(&m_index) {
                 Some(value) => {
@@ -99,27 +98,15 @@ impl KeccakBuiltinRunner {
                 }
                 _ => return Ok(None),
             };
-            input_felts.push(val)
+            *input_felt = val;
         }
-        let input_message: Vec<u8> = input_felts
-            .iter()
-            .flat_map(|x| {
-                let mut bytes = x.to_bytes_le().to_vec();
-                bytes.resize(KECCAK_FELT_BYTE_SIZE, 0);
-                bytes
-            })
-            .collect();
-        let keccak_result = Self::keccak_f(&input_message)?;
-
-        let mut start_index = 0_usize;
-        for i in 0..INPUT_CELLS_PER_KECCAK {
-            let end_index = start_index + BITS as usize / 8;
-            self.cache.borrow_mut().insert((first_output_addr + i)?, {
-                let mut bytes = keccak_result[start_index..end_index].to_vec();
-                bytes.resize(32, 0);
-                Felt252::from_bytes_le_slice(&bytes)
-            });
-            start_index = end_index;
+        // The cells are packed into the permutation's 64-bit lanes limb by limb
+        let output_felts = keccak_builtin_permutation(&input_felts);
+
+        for (i, output_felt) in output_felts.into_iter().enumerate() {
+            self.cache
+                .borrow_mut()
+                .insert((first_output_addr + i)?, output_felt);
         }
         Ok(self.cache.borrow().get(&address).map(|x| x.into()))
     }
@@ -152,16 +139,6 @@ impl KeccakBuiltinRunner {
         safe_div_usize(262144_usize, diluted_n_bits as usize).unwrap_or(0)
     }
 
-    fn keccak_f(input_message: &[u8]) -> Result<Vec<u8>, RunnerError> {
-        let bigint = BigUint::from_bytes_le(input_message);
-        let mut keccak_input = bigint.to_u64_digits();
-        keccak_input.resize(25, 0);
-        // This unwrap wont fail as keccak_input's size is always 25
-        let mut keccak_input: [u64; 25] = keccak_input.try_into().unwrap();
-        keccak::f1600(&mut keccak_input);
-        Ok(keccak_input.iter().flat_map(|x| x.to_le_bytes()).collect())
-    }
-
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
         if let Some(segment) = memory.data.get(self.base) {
@@ -576,11 +553,15 @@ mod tests {
     }
 
     #[test]
-    fn keccak_f() {
-        let input_bytes = b"\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x05\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x06\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x07\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+    fn keccak_builtin_permutation_output() {
+        let input_felts: [Felt252; 8] = core::array::from_fn(|i| Felt252::from(i + 1));
         let expected_output_bytes = b"\xf6\x98\x81\xe1\x00!\x1f.\xc4*\x8c\x0c\x7fF\xc8q8\xdf\xb9\xbe\x07H\xca7T1\xab\x16\x17\xa9\x11\xff-L\x87\xb2iY.\x96\x82x\xde\xbb\\up?uz:0\xee\x08\x1b\x15\xd6\n\xab\r\x0b\x87T:w\x0fH\xe7!f},\x08a\xe5\xbe8\x16\x13\x9a?\xad~<9\xf7\x03`\x8b\xd8\xa3F\x8aQ\xf9\n9\xcdD\xb7.X\xf7\x8e\x1f\x17\x9e \xe5i\x01rr\xdf\xaf\x99k\x9f\x8e\x84\\\xday`\xf1``\x02q+\x8e\xad\x96\xd8\xff\xff3<\xb6\x01o\xd7\xa6\x86\x9d\xea\xbc\xfb\x08\xe1\xa3\x1c\x06z\xab@\xa1\xc1\xb1xZ\x92\x96\xc0.\x01\x13g\x93\x87!\xa6\xa8z\x9c@\x0bY'\xe7\xa7Qr\xe5\xc1\xa3\xa6\x88H\xa5\xc0@9k:y\xd1Kw\xd5";
-        let output_bytes = KeccakBuiltinRunner::keccak_f(input_bytes);
-        assert_eq!(output_bytes, Ok(expected_output_bytes.to_vec()));
+        let expected_output_felts: Vec<Felt252> = expected_output_bytes
+            .chunks(25)
+            .map(Felt252::from_bytes_le_slice)
+            .collect();
+        let output_felts = keccak_builtin_permutation(&input_felts);
+        assert_eq!(output_felts.to_vec(), expected_output_felts);
     }
 
     #[test]
@@ -28,8 +28,10 @@ use crate::vm::errors::vm_errors::VirtualMachineError;
 use crate::vm::vm_core::VirtualMachine;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
+use crate::Felt252;
 
 mod bitwise;
+mod custom;
 mod ec_op;
 mod hash;
 mod keccak;
@@ -41,9 +43,10 @@ mod segment_arena;
 mod signature;
 
 pub use self::keccak::KeccakBuiltinRunner;
-pub(crate) use self::range_check::{RC_N_PARTS_96, RC_N_PARTS_STANDARD};
+pub use self::range_check::{RC_MAX_N_PARTS, RC_N_PARTS_96, RC_N_PARTS_STANDARD};
 use self::segment_arena::ARENA_BUILTIN_SIZE;
 pub use bitwise::BitwiseBuiltinRunner;
+pub use custom::{CustomBuiltin, CustomBuiltinFactory, CustomBuiltinRunner};
 pub use ec_op::EcOpBuiltinRunner;
 pub use hash::HashBuiltinRunner;
 pub use modulo::ModBuiltinRunner;
@@ -61,8 +64,9 @@ use super::cairo_pie::BuiltinAdditionalData;
  * are either storing a `dyn Trait` inside an `Arc<Mutex<&dyn Trait>>` or
  * making the type itself `Send`. We opted for not complicating the user nor
  * moving the guarantees to runtime by using an `enum` rather than a `Trait`.
- * This works under the assumption that we don't expect downstream users to
- * extend Cairo by adding new builtin runners.
+ * Downstream users extending Cairo with new builtins do so through the
+ * `Custom` variant, whose `CustomBuiltin` trait requires `Send + Sync` for
+ * the enum to remain `Send`.
  */
 #[derive(Debug, Clone)]
 pub enum BuiltinRunner {
@@ -77,8 +81,51 @@ pub enum BuiltinRunner {
     Poseidon(PoseidonBuiltinRunner),
     SegmentArena(SegmentArenaBuiltinRunner),
     Mod(ModBuiltinRunner),
+    Custom(CustomBuiltinRunner),
 }
 
+/// Implemented by the builtin runners wrapped in [BuiltinRunner], so they can be looked up by type.
+/// See [VirtualMachine::get_builtin].
+pub trait BuiltinRunnerVariant {
+    fn from_builtin_runner(builtin_runner: &BuiltinRunner) -> Option<&Self>;
+    fn from_builtin_runner_mut(builtin_runner: &mut BuiltinRunner) -> Option<&mut Self>;
+}
+
+macro_rules! impl_builtin_runner_variant {
+    ($runner:ty, $variant:ident) => {
+        impl BuiltinRunnerVariant for $runner {
+            fn from_builtin_runner(builtin_runner: &BuiltinRunner) -> Option<&Self> {
+                match builtin_runner {
+                    BuiltinRunner::$variant(runner) => Some(runner),
+                    _ => None,
+                }
+            }
+
+            fn from_builtin_runner_mut(builtin_runner: &mut BuiltinRunner) -> Option<&mut Self> {
+                match builtin_runner {
+                    BuiltinRunner::$variant(runner) => Some(runner),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_builtin_runner_variant!(BitwiseBuiltinRunner, Bitwise);
+impl_builtin_runner_variant!(EcOpBuiltinRunner, EcOp);
+impl_builtin_runner_variant!(HashBuiltinRunner, Hash);
+impl_builtin_runner_variant!(OutputBuiltinRunner, Output);
+impl_builtin_runner_variant!(RangeCheckBuiltinRunner<RC_N_PARTS_STANDARD>, RangeCheck);
+impl_builtin_runner_variant!(RangeCheckBuiltinRunner<RC_N_PARTS_96>, RangeCheck96);
+impl_builtin_runner_variant!(KeccakBuiltinRunner, Keccak);
+impl_builtin_runner_variant!(SignatureBuiltinRunner, Signature);
+impl_builtin_runner_variant!(PoseidonBuiltinRunner, Poseidon);
+impl_builtin_runner_variant!(SegmentArenaBuiltinRunner, SegmentArena);
+// Matches both the add_mod and mul_mod builtins, use [VirtualMachine::get_builtin_by_name] to
+// tell them apart.
+impl_builtin_runner_variant!(ModBuiltinRunner, Mod);
+impl_builtin_runner_variant!(CustomBuiltinRunner, Custom);
+
 impl BuiltinRunner {
     ///Creates the necessary segments for the builtin in the MemorySegmentManager and stores the first address on the builtin's base
     pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
@@ -100,6 +147,7 @@ impl BuiltinRunner {
                 segment_arena.initialize_segments(segments)
             }
             BuiltinRunner::Mod(ref mut modulo) => modulo.initialize_segments(segments),
+            BuiltinRunner::Custom(ref mut custom) => custom.initialize_segments(segments),
         }
     }
 
@@ -116,6 +164,7 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.initial_stack(),
             BuiltinRunner::SegmentArena(ref segment_arena) => segment_arena.initial_stack(),
             BuiltinRunner::Mod(ref modulo) => modulo.initial_stack(),
+            BuiltinRunner::Custom(ref custom) => custom.initial_stack(),
         }
     }
 
@@ -180,7 +229,10 @@ impl BuiltinRunner {
         vm: &VirtualMachine,
     ) -> Result<usize, memory_errors::MemoryError> {
         match *self {
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => Ok(0),
+            // Custom builtins aren't part of the layout, so no cells are allocated for them
+            BuiltinRunner::Output(_)
+            | BuiltinRunner::SegmentArena(_)
+            | BuiltinRunner::Custom(_) => Ok(0),
             _ => {
                 match self.ratio() {
                     None => {
@@ -241,6 +293,7 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.included,
             BuiltinRunner::SegmentArena(ref segment_arena) => segment_arena.included,
             BuiltinRunner::Mod(ref modulo) => modulo.included,
+            BuiltinRunner::Custom(ref custom) => custom.included,
         }
     }
 
@@ -259,6 +312,7 @@ impl BuiltinRunner {
             //Warning, returns only the segment index, base offset will be 3
             BuiltinRunner::SegmentArena(ref segment_arena) => segment_arena.base(),
             BuiltinRunner::Mod(ref modulo) => modulo.base(),
+            BuiltinRunner::Custom(ref custom) => custom.base(),
         }
     }
 
@@ -267,7 +321,9 @@ impl BuiltinRunner {
             BuiltinRunner::Bitwise(bitwise) => bitwise.ratio(),
             BuiltinRunner::EcOp(ec) => ec.ratio(),
             BuiltinRunner::Hash(hash) => hash.ratio(),
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => None,
+            BuiltinRunner::Output(_)
+            | BuiltinRunner::SegmentArena(_)
+            | BuiltinRunner::Custom(_) => None,
             BuiltinRunner::RangeCheck(range_check) => range_check.ratio(),
             BuiltinRunner::RangeCheck96(range_check) => range_check.ratio(),
             BuiltinRunner::Keccak(keccak) => keccak.ratio(),
@@ -292,6 +348,7 @@ impl BuiltinRunner {
             BuiltinRunner::RangeCheck96(ref range_check) => range_check.add_validation_rule(memory),
             BuiltinRunner::Signature(ref signature) => signature.add_validation_rule(memory),
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.add_validation_rule(memory),
+            BuiltinRunner::Custom(ref custom) => custom.add_validation_rule(memory),
             _ => {}
         }
     }
@@ -307,10 +364,61 @@ impl BuiltinRunner {
             BuiltinRunner::Hash(ref hash) => hash.deduce_memory_cell(address, memory),
             BuiltinRunner::Keccak(ref keccak) => keccak.deduce_memory_cell(address, memory),
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.deduce_memory_cell(address, memory),
+            BuiltinRunner::Custom(ref custom) => custom.deduce_memory_cell(address, memory),
             _ => Ok(None),
         }
     }
 
+    /// Fills the missing cells of the instances within the first `allocated_size` cells of the
+    /// builtin segment, so that each instance holds valid inputs and outputs: missing inputs are
+    /// set to zero (the ec_op builtin instead copies them from the first instance, as zero isn't
+    /// a valid curve point) and missing outputs are deduced from the inputs.
+    /// Builtins without deductions are left untouched.
+    pub fn fill_holes(
+        &self,
+        memory: &mut Memory,
+        allocated_size: usize,
+    ) -> Result<(), RunnerError> {
+        let copy_first_instance = match self {
+            BuiltinRunner::Bitwise(_)
+            | BuiltinRunner::Hash(_)
+            | BuiltinRunner::Keccak(_)
+            | BuiltinRunner::Poseidon(_)
+            | BuiltinRunner::RangeCheck(_)
+            | BuiltinRunner::RangeCheck96(_) => false,
+            BuiltinRunner::EcOp(_) => true,
+            _ => return Ok(()),
+        };
+        let cells_per_instance = self.cells_per_instance() as usize;
+        let n_input_cells = self.n_input_cells() as usize;
+        let cell = |offset| Relocatable::from((self.base() as isize, offset));
+        for first_cell in (0..allocated_size).step_by(cells_per_instance) {
+            for input_cell in first_cell..first_cell + n_input_cells {
+                if memory.get(&cell(input_cell)).is_some() {
+                    continue;
+                }
+                let value = if copy_first_instance {
+                    match memory.get(&cell(input_cell - first_cell)) {
+                        Some(value) => value.into_owned(),
+                        None => continue,
+                    }
+                } else {
+                    Felt252::ZERO.into()
+                };
+                memory.insert(cell(input_cell), value)?;
+            }
+            for output_cell in first_cell + n_input_cells..first_cell + cells_per_instance {
+                if memory.get(&cell(output_cell)).is_some() {
+                    continue;
+                }
+                if let Some(value) = self.deduce_memory_cell(cell(output_cell), memory)? {
+                    memory.insert(cell(output_cell), value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_memory_segment_addresses(&self) -> (usize, Option<usize>) {
         (self.base(), self.stop_ptr())
     }
@@ -330,6 +438,7 @@ impl BuiltinRunner {
                 segment_arena.get_used_cells(segments)
             }
             BuiltinRunner::Mod(ref modulo) => modulo.get_used_cells(segments),
+            BuiltinRunner::Custom(ref custom) => custom.get_used_cells(segments),
         }
     }
 
@@ -353,6 +462,7 @@ impl BuiltinRunner {
                 segment_arena.get_used_instances(segments)
             }
             BuiltinRunner::Mod(modulo) => modulo.get_used_instances(segments),
+            BuiltinRunner::Custom(custom) => custom.get_used_instances(segments),
         }
     }
 
@@ -408,6 +518,7 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(_) => CELLS_PER_POSEIDON,
             BuiltinRunner::SegmentArena(_) => ARENA_BUILTIN_SIZE,
             BuiltinRunner::Mod(_) => CELLS_PER_MOD,
+            BuiltinRunner::Custom(custom) => custom.cells_per_instance(),
         }
     }
 
@@ -423,9 +534,17 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(_) => INPUT_CELLS_PER_POSEIDON,
             BuiltinRunner::SegmentArena(_) => ARENA_BUILTIN_SIZE,
             BuiltinRunner::Mod(_) => CELLS_PER_MOD,
+            BuiltinRunner::Custom(custom) => custom.n_input_cells(),
         }
     }
 
+    /// Returns true if the cell at the given offset of the builtin segment holds an output of
+    /// its instance, which is deduced from the instance's inputs
+    pub(crate) fn is_output_cell(&self, offset: usize) -> bool {
+        let cells_per_instance = self.cells_per_instance() as usize;
+        cells_per_instance != 0 && offset % cells_per_instance >= self.n_input_cells() as usize
+    }
+
     fn instances_per_component(&self) -> u32 {
         match self {
             BuiltinRunner::Keccak(_) => KECCAK_INSTANCES_PER_COMPONENT,
@@ -446,6 +565,7 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(_) => BuiltinName::poseidon,
             BuiltinRunner::SegmentArena(_) => BuiltinName::segment_arena,
             BuiltinRunner::Mod(b) => b.name(),
+            BuiltinRunner::Custom(b) => b.name(),
         }
     }
 
@@ -456,6 +576,9 @@ impl BuiltinRunner {
         if let BuiltinRunner::Mod(modulo) = self {
             modulo.run_additional_security_checks(vm)?;
         }
+        if let BuiltinRunner::Signature(signature) = self {
+            signature.run_additional_security_checks(vm)?;
+        }
         let cells_per_instance = self.cells_per_instance() as usize;
         let n_input_cells = self.n_input_cells() as usize;
         let builtin_segment_index = self.base();
@@ -527,7 +650,9 @@ impl BuiltinRunner {
         vm: &VirtualMachine,
     ) -> Result<(usize, usize), MemoryError> {
         match self {
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => {
+            BuiltinRunner::Output(_)
+            | BuiltinRunner::SegmentArena(_)
+            | BuiltinRunner::Custom(_) => {
                 let used = self.get_used_cells(&vm.segments)?;
                 Ok((used, used))
             }
@@ -604,6 +729,7 @@ impl BuiltinRunner {
                 segment_arena.stop_ptr = Some(stop_ptr)
             }
             BuiltinRunner::Mod(modulo) => modulo.stop_ptr = Some(stop_ptr),
+            BuiltinRunner::Custom(custom) => custom.stop_ptr = Some(stop_ptr),
         }
     }
 
@@ -620,10 +746,17 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.stop_ptr,
             BuiltinRunner::SegmentArena(ref segment_arena) => segment_arena.stop_ptr,
             BuiltinRunner::Mod(ref modulo) => modulo.stop_ptr,
+            BuiltinRunner::Custom(ref custom) => custom.stop_ptr,
         }
     }
 }
 
+impl From<CustomBuiltinRunner> for BuiltinRunner {
+    fn from(runner: CustomBuiltinRunner) -> Self {
+        BuiltinRunner::Custom(runner)
+    }
+}
+
 impl From<KeccakBuiltinRunner> for BuiltinRunner {
     fn from(runner: KeccakBuiltinRunner) -> Self {
         BuiltinRunner::Keccak(runner)
@@ -1728,4 +1861,46 @@ mod tests {
         let builtin: BuiltinRunner = PoseidonBuiltinRunner::new(None, true).into();
         assert_eq!(builtin.get_additional_data(), BuiltinAdditionalData::None)
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn fill_holes_bitwise() {
+        let builtin: BuiltinRunner = BitwiseBuiltinRunner::new(Some(256), true).into();
+        let mut vm = vm!();
+        // The first instance computes 12 & 10, but its other outputs were never read
+        vm.segments = segments![((0, 0), 12), ((0, 1), 10), ((0, 2), 8)];
+
+        builtin.fill_holes(&mut vm.segments.memory, 10).unwrap();
+
+        check_memory![
+            vm.segments.memory,
+            ((0, 0), 12),
+            ((0, 1), 10),
+            ((0, 2), 8),
+            ((0, 3), 6),
+            ((0, 4), 14),
+            ((0, 5), 0),
+            ((0, 6), 0),
+            ((0, 7), 0),
+            ((0, 8), 0),
+            ((0, 9), 0)
+        ];
+        assert!(vm
+            .segments
+            .memory
+            .get(&Relocatable::from((0, 10)))
+            .is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn fill_holes_output_builtin_untouched() {
+        let builtin: BuiltinRunner = OutputBuiltinRunner::new(true).into();
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1)];
+
+        builtin.fill_holes(&mut vm.segments.memory, 4).unwrap();
+
+        assert!(vm.segments.memory.get(&Relocatable::from((0, 1))).is_none());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::stdlib::{collections::HashMap, prelude::*};
+use crate::stdlib::{borrow::Cow, collections::HashMap, prelude::*};
 use crate::types::builtin_name::BuiltinName;
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::MemoryError;
@@ -8,6 +8,7 @@ use crate::vm::runners::cairo_pie::{
 };
 use crate::vm::vm_core::VirtualMachine;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
+use crate::Felt252;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct OutputBuiltinState {
@@ -113,6 +114,27 @@ impl OutputBuiltinRunner {
         }
     }
 
+    /// Returns the values written to the output segment.
+    ///
+    /// The output segment is expected to be contiguous and to only contain integers, an error
+    /// will be returned otherwise.
+    pub fn get_output(&self, vm: &VirtualMachine) -> Result<Vec<Felt252>, MemoryError> {
+        let size = match self
+            .stop_ptr
+            .or_else(|| vm.segments.get_segment_used_size(self.base))
+        {
+            Some(size) => size,
+            None => vm.segments.memory.data.get(self.base).map_or(0, Vec::len),
+        };
+        Ok(vm
+            .segments
+            .memory
+            .get_integer_range(Relocatable::from((self.base as isize, 0)), size)?
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect())
+    }
+
     pub fn add_attribute(&mut self, name: String, value: Vec<usize>) {
         self.attributes.insert(name, value);
     }
@@ -324,6 +346,51 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_output() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        builtin.new_state(2, true);
+
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((2, 0), 3), ((2, 1), 5), ((2, 2), 8)];
+
+        assert_eq!(
+            builtin.get_output(&vm),
+            Ok(vec![Felt252::from(3), Felt252::from(5), Felt252::from(8)])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_output_non_contiguous_segment() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        builtin.new_state(2, true);
+
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((2, 0), 3), ((2, 2), 8)];
+
+        assert_eq!(
+            builtin.get_output(&vm),
+            Err(MemoryError::UnknownMemoryCell(Box::new((2, 1).into())))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_output_relocatable_value() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        builtin.new_state(2, true);
+
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((2, 0), (0, 0))];
+
+        assert_eq!(
+            builtin.get_output(&vm),
+            Err(MemoryError::ExpectedInteger(Box::new((2, 0).into())))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_used_cells_and_allocated_size_test() {
@@ -1,4 +1,5 @@
 use crate::air_private_input::{PrivateInput, PrivateInputPoseidonState};
+use crate::crypto::poseidon_permutation;
 use crate::stdlib::{cell::RefCell, collections::HashMap, prelude::*};
 use crate::types::builtin_name::BuiltinName;
 use crate::types::instance_definitions::poseidon_instance_def::{
@@ -11,7 +12,6 @@ use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
 use crate::Felt252;
 use num_integer::div_ceil;
-use starknet_types_core::hash::Poseidon;
 
 #[derive(Debug, Clone)]
 pub struct PoseidonBuiltinRunner {
@@ -87,7 +87,7 @@ impl PoseidonBuiltinRunner {
         }
         // n_input_cells is fixed to 3, so this try_into will never fail
         let mut poseidon_state: [Felt252; 3] = input_felts.try_into().unwrap();
-        Poseidon::hades_permutation(&mut poseidon_state);
+        poseidon_permutation(&mut poseidon_state);
         for (i, elem) in poseidon_state.iter().enumerate() {
             self.cache
                 .borrow_mut()
@@ -26,11 +26,17 @@ const INNER_RC_BOUND_MASK: u64 = u16::MAX as u64;
 
 pub const RC_N_PARTS_STANDARD: u64 = 8;
 pub const RC_N_PARTS_96: u64 = 6;
+/// Largest amount of 16-bit parts a range check builtin can be made of, so that its bound
+/// (2^(16 * n_parts)) still fits in a felt.
+pub const RC_MAX_N_PARTS: u64 = 15;
 
 lazy_static! {
     pub static ref BOUND_STANDARD: Felt252 =
         Felt252::TWO.pow(INNER_RC_BOUND_SHIFT * RC_N_PARTS_STANDARD);
     pub static ref BOUND_96: Felt252 = Felt252::TWO.pow(INNER_RC_BOUND_SHIFT * RC_N_PARTS_96);
+    static ref BOUNDS: Vec<Felt252> = (0..=RC_MAX_N_PARTS)
+        .map(|n_parts| Felt252::TWO.pow(INNER_RC_BOUND_SHIFT * n_parts))
+        .collect();
 }
 
 #[derive(Debug, Clone)]
@@ -42,7 +48,16 @@ pub struct RangeCheckBuiltinRunner<const N_PARTS: u64> {
 }
 
 impl<const N_PARTS: u64> RangeCheckBuiltinRunner<N_PARTS> {
+    // Evaluated when building a runner, rejects at compile time bounds that don't fit in a felt
+    const VALID_N_PARTS: () = assert!(N_PARTS <= RC_MAX_N_PARTS);
+
+    /// Creates a range check builtin runner made of `N_PARTS` 16-bit parts, which checks that
+    /// values are lower than 2^(16 * `N_PARTS`).
+    /// The layouts use [`RC_N_PARTS_STANDARD`] for `range_check` and [`RC_N_PARTS_96`] for
+    /// `range_check96`.
     pub fn new(ratio: Option<u32>, included: bool) -> RangeCheckBuiltinRunner<N_PARTS> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_N_PARTS;
         RangeCheckBuiltinRunner {
             ratio: ratio.map(LowRatio::new_int),
             base: 0,
@@ -55,6 +70,8 @@ impl<const N_PARTS: u64> RangeCheckBuiltinRunner<N_PARTS> {
         ratio: Option<LowRatio>,
         included: bool,
     ) -> RangeCheckBuiltinRunner<N_PARTS> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_N_PARTS;
         RangeCheckBuiltinRunner {
             ratio,
             base: 0,
@@ -94,20 +111,24 @@ impl<const N_PARTS: u64> RangeCheckBuiltinRunner<N_PARTS> {
         }
     }
 
+    /// Number of 16-bit parts each checked value is split into.
     pub fn n_parts(&self) -> u64 {
         N_PARTS
     }
 
+    /// Exclusive upper bound of the checked values, 2^(16 * `N_PARTS`).
     pub fn bound(&self) -> &'static Felt252 {
         match N_PARTS {
+            RC_N_PARTS_STANDARD => &BOUND_STANDARD,
             RC_N_PARTS_96 => &BOUND_96,
-            _ => &BOUND_STANDARD,
+            _ => &BOUNDS[N_PARTS as usize],
         }
     }
 
     pub fn add_validation_rule(&self, memory: &mut Memory) {
+        let bound = self.bound();
         let rule = ValidationRule(Box::new(
-            |memory: &Memory, address: Relocatable| -> Result<Vec<Relocatable>, MemoryError> {
+            move |memory: &Memory, address: Relocatable| -> Result<Vec<Relocatable>, MemoryError> {
                 let num = memory
                     .get_integer(address)
                     .map_err(|_| MemoryError::RangeCheckFoundNonInt(Box::new(address)))?;
@@ -116,7 +137,7 @@ impl<const N_PARTS: u64> RangeCheckBuiltinRunner<N_PARTS> {
                 } else {
                     Err(MemoryError::RangeCheckNumOutOfBounds(Box::new((
                         num.into_owned(),
-                        Felt252::TWO.pow((N_PARTS * INNER_RC_BOUND_SHIFT) as u128),
+                        *bound,
                     ))))
                 }
             },
@@ -135,17 +156,15 @@ impl<const N_PARTS: u64> RangeCheckBuiltinRunner<N_PARTS> {
         let mut rc_bounds =
             (!range_check_segment.is_empty()).then_some((usize::MAX, usize::MIN))?;
 
-        // Split value into n_parts parts of less than _INNER_RC_BOUND size.
+        // Split value into its n_parts lowest parts of less than _INNER_RC_BOUND size.
         for value in range_check_segment {
             rc_bounds = value
                 .get_value()?
                 .get_int_ref()?
                 .to_le_digits()
-                // TODO: maybe skip leading zeros
                 .into_iter()
                 .flat_map(|digit| {
-                    (0..=3)
-                        .rev()
+                    (0..4)
                         .map(move |i| ((digit >> (i * INNER_RC_BOUND_SHIFT)) & INNER_RC_BOUND_MASK))
                 })
                 .take(N_PARTS as usize)
@@ -517,6 +536,63 @@ mod tests {
         assert_eq!(builtin.get_range_check_usage(&memory), None);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_range_check_usage_range_check96() {
+        let builtin = RangeCheckBuiltinRunner::<RC_N_PARTS_96>::new(Some(8), true);
+        // 0x0005_0004_0003_0002_0001_0006 has parts 6, 1, 2, 3, 4, 5
+        let memory = memory![((0, 0), 0x0005_0004_0003_0002_0001_0006_i128)];
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((1, 6)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn custom_n_parts_bound() {
+        assert_eq!(
+            RangeCheckBuiltinRunner::<RC_N_PARTS_STANDARD>::new(None, true).bound(),
+            &Felt252::TWO.pow(128_u32)
+        );
+        assert_eq!(
+            RangeCheckBuiltinRunner::<RC_N_PARTS_96>::new(None, true).bound(),
+            &Felt252::TWO.pow(96_u32)
+        );
+        let builtin = RangeCheckBuiltinRunner::<4>::new(None, true);
+        assert_eq!(builtin.n_parts(), 4);
+        assert_eq!(builtin.bound(), &Felt252::TWO.pow(64_u32));
+        assert_eq!(
+            RangeCheckBuiltinRunner::<RC_MAX_N_PARTS>::new(None, true).bound(),
+            &Felt252::TWO.pow(240_u32)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn custom_n_parts_validation_rule() {
+        let mut builtin = RangeCheckBuiltinRunner::<4>::new(None, true);
+        let mut segments = MemorySegmentManager::new();
+        builtin.initialize_segments(&mut segments);
+        segments
+            .memory
+            .insert(Relocatable::from((0, 0)), Felt252::from(u64::MAX))
+            .unwrap();
+        segments
+            .memory
+            .insert(Relocatable::from((0, 1)), Felt252::TWO.pow(64_u32))
+            .unwrap();
+        builtin.add_validation_rule(&mut segments.memory);
+        assert_eq!(
+            segments.memory.validate_existing_memory(),
+            Err(MemoryError::RangeCheckNumOutOfBounds(Box::new((
+                Felt252::TWO.pow(64_u32),
+                Felt252::TWO.pow(64_u32)
+            ))))
+        );
+        assert!(segments
+            .memory
+            .validated_addresses
+            .contains(&Relocatable::from((0, 0))));
+    }
+
     /// Test that the method get_used_perm_range_check_units works as intended.
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
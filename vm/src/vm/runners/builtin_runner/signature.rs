@@ -5,12 +5,14 @@ use crate::stdlib::{cell::RefCell, collections::HashMap, prelude::*, rc::Rc};
 use crate::types::builtin_name::BuiltinName;
 use crate::types::instance_definitions::ecdsa_instance_def::CELLS_PER_SIGNATURE;
 use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::errors::vm_errors::VirtualMachineError;
 use crate::vm::runners::cairo_pie::BuiltinAdditionalData;
 use crate::Felt252;
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},
     vm::{
         errors::memory_errors::MemoryError,
+        vm_core::VirtualMachine,
         vm_memory::{
             memory::{Memory, ValidationRule},
             memory_segments::MemorySegmentManager,
@@ -121,27 +123,50 @@ impl SignatureBuiltinRunner {
                     _ => return Err(MemoryError::MsgNonInt(Box::new(message_addr))),
                 };
 
-                let signatures_map = signatures.borrow();
-                let signature = signatures_map
-                    .get(&pubkey_addr)
-                    .ok_or_else(|| MemoryError::SignatureNotFound(Box::new(pubkey_addr)))?;
-
-                let public_key = Felt252::from_bytes_be(&pubkey.to_bytes_be());
-                let (r, s) = (signature.r, signature.s);
-                let message = Felt252::from_bytes_be(&msg.to_bytes_be());
-                match verify(&public_key, &message, &r, &s) {
-                    Ok(true) => Ok(vec![]),
-                    _ => Err(MemoryError::InvalidSignature(Box::new((
-                        format!("({}, {})", signature.r, signature.s),
-                        pubkey.into_owned(),
-                        msg.into_owned(),
-                    )))),
-                }
+                verify_signature(&signatures.borrow(), pubkey_addr, &pubkey, &msg)?;
+                Ok(vec![pubkey_addr, message_addr])
             },
         ));
         memory.add_validation_rule(self.base, rule);
     }
 
+    /// Verifies the recorded signature of every instance whose public key and message
+    /// are both present in the builtin segment.
+    /// Instances already verified by the validation rule are skipped, and instances with
+    /// missing cells are left to the generic builtin security checks.
+    pub(crate) fn run_additional_security_checks(
+        &self,
+        vm: &VirtualMachine,
+    ) -> Result<(), VirtualMachineError> {
+        let segment_len = vm
+            .segments
+            .memory
+            .data
+            .get(self.base)
+            .map(|segment| segment.len())
+            .unwrap_or_default();
+        let signatures = self.signatures.borrow();
+        for offset in (0..segment_len).step_by(CELLS_PER_SIGNATURE as usize) {
+            let pubkey_addr = Relocatable::from((self.base as isize, offset));
+            if vm
+                .segments
+                .memory
+                .validated_addresses
+                .contains(&pubkey_addr)
+            {
+                continue;
+            }
+            let message_addr = (pubkey_addr + 1_usize)?;
+            if let (Ok(pubkey), Ok(msg)) = (
+                vm.segments.memory.get_integer(pubkey_addr),
+                vm.segments.memory.get_integer(message_addr),
+            ) {
+                verify_signature(&signatures, pubkey_addr, &pubkey, &msg)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn ratio(&self) -> Option<u32> {
         self.ratio
     }
@@ -247,6 +272,28 @@ impl SignatureBuiltinRunner {
     }
 }
 
+/// Checks the signature recorded for the instance starting at `pubkey_addr` against the
+/// public key and message found in memory.
+fn verify_signature(
+    signatures: &HashMap<Relocatable, Signature>,
+    pubkey_addr: Relocatable,
+    pubkey: &Felt252,
+    msg: &Felt252,
+) -> Result<(), MemoryError> {
+    let signature = signatures
+        .get(&pubkey_addr)
+        .ok_or_else(|| MemoryError::SignatureNotFound(Box::new(pubkey_addr)))?;
+    match verify(pubkey, msg, &signature.r, &signature.s) {
+        Ok(true) => Ok(()),
+        _ => Err(MemoryError::InvalidSignature(Box::new((
+            pubkey_addr,
+            format!("({}, {})", signature.r, signature.s),
+            *pubkey,
+            *msg,
+        )))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,7 +311,8 @@ mod tests {
         },
     };
 
-    use crate::felt_str;
+    use crate::{felt_hex, felt_str};
+    use assert_matches::assert_matches;
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
@@ -622,4 +670,105 @@ mod tests {
 
         assert_eq!(private_inputs, expected_private_inputs);
     }
+
+    fn signature_builtin_with_valid_signature() -> BuiltinRunner {
+        let mut builtin = SignatureBuiltinRunner::new(Some(512), true);
+        let signature_r =
+            felt_hex!("0x411494b501a98abd8262b0da1351e17899a0c4ef23dd2f96fec5ba847310b20");
+        let signature_s =
+            felt_hex!("0x405c3191ab3883ef2b763af35bc5f5d15b3b4e99461d70e84c654a351a7c81b");
+        builtin
+            .add_signature(Relocatable::from((0, 0)), &(signature_r, signature_s))
+            .unwrap();
+        builtin.into()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_security_checks_valid_signature() {
+        let builtin = signature_builtin_with_valid_signature();
+        let mut vm = vm!();
+        vm.segments.memory = memory![
+            (
+                (0, 0),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            ((0, 1), 2)
+        ];
+        assert_matches!(builtin.run_security_checks(&vm), Ok(()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_security_checks_invalid_signature() {
+        let builtin = signature_builtin_with_valid_signature();
+        let mut vm = vm!();
+        vm.segments.memory = memory![
+            (
+                (0, 0),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            ((0, 1), 3)
+        ];
+        assert_matches!(
+            builtin.run_security_checks(&vm),
+            Err(VirtualMachineError::Memory(MemoryError::InvalidSignature(bx)))
+                if bx.0 == relocatable!(0, 0) && bx.3 == Felt252::from(3)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_security_checks_skips_validated_signatures() {
+        let BuiltinRunner::Signature(validating_builtin) = signature_builtin_with_valid_signature()
+        else {
+            unreachable!()
+        };
+        let mut vm = vm!();
+        vm.segments.memory = memory![
+            (
+                (0, 0),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            ((0, 1), 2)
+        ];
+        validating_builtin.add_validation_rule(&mut vm.segments.memory);
+        assert_matches!(vm.segments.memory.validate_existing_memory(), Ok(()));
+        assert!(vm
+            .segments
+            .memory
+            .validated_addresses
+            .contains(&relocatable!(0, 0)));
+        assert!(vm
+            .segments
+            .memory
+            .validated_addresses
+            .contains(&relocatable!(0, 1)));
+
+        // The signature isn't looked up again, as the validation rule already verified it
+        let builtin: BuiltinRunner = SignatureBuiltinRunner::new(Some(512), true).into();
+        assert_matches!(builtin.run_security_checks(&vm), Ok(()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_security_checks_missing_signature() {
+        let builtin: BuiltinRunner = SignatureBuiltinRunner::new(Some(512), true).into();
+        let mut vm = vm!();
+        vm.segments.memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 3), ((0, 3), 4)];
+        assert_matches!(
+            builtin.run_security_checks(&vm),
+            Err(VirtualMachineError::Memory(MemoryError::SignatureNotFound(bx)))
+                if *bx == relocatable!(0, 0)
+        );
+    }
 }
@@ -1,26 +1,33 @@
 use crate::{
     air_private_input::AirPrivateInput,
     air_public_input::{PublicInput, PublicInputError},
+    cairo_run::{EncodeMemoryError, EncodeRelocatedTraceError, EncodeTraceError},
+    crypto::poseidon_hash_many,
     math_utils::safe_div_usize,
     stdlib::{
         any::Any,
         collections::{HashMap, HashSet},
-        ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
+        ops::{Add, AddAssign, Mul, MulAssign, Range, Sub, SubAssign},
         prelude::*,
+        sync::Arc,
     },
     types::{builtin_name::BuiltinName, layout::CairoLayoutParams, layout_name::LayoutName},
     vm::{
         runners::builtin_runner::SegmentArenaBuiltinRunner,
-        trace::trace_entry::{relocate_trace_register, RelocatedTraceEntry},
+        trace::trace_entry::{relocate_trace_register, RelocatedTraceEntry, TraceEntry},
     },
     Felt252,
 };
 
 use crate::{
-    hint_processor::hint_processor_definition::{HintProcessor, HintReference},
+    hint_processor::{
+        hint_processor_definition::{HintProcessor, HintReference},
+        hint_processor_utils::get_maybe_relocatable_from_reference,
+    },
     types::{
         errors::{math_errors::MathError, program_errors::ProgramError},
         exec_scope::ExecutionScopes,
+        instruction::Instruction,
         layout::CairoLayout,
         program::Program,
         relocatable::{relocate_address, relocate_value, MaybeRelocatable, Relocatable},
@@ -29,6 +36,7 @@ use crate::{
     vm::{
         errors::{
             cairo_run_errors::CairoRunError,
+            hint_errors::HintError,
             memory_errors::{InsufficientAllocatedCellsError, MemoryError},
             runner_errors::RunnerError,
             trace_errors::TraceError,
@@ -37,18 +45,23 @@ use crate::{
         },
         security::verify_secure_runner,
         {
+            checkpoint::Checkpoint,
+            context::run_context::RunContext,
             runners::builtin_runner::{
-                BitwiseBuiltinRunner, BuiltinRunner, EcOpBuiltinRunner, HashBuiltinRunner,
-                OutputBuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner,
+                BitwiseBuiltinRunner, BuiltinRunner, CustomBuiltinFactory, CustomBuiltinRunner,
+                EcOpBuiltinRunner, HashBuiltinRunner, OutputBuiltinRunner, RangeCheckBuiltinRunner,
+                SignatureBuiltinRunner,
             },
-            vm_core::VirtualMachine,
+            vm_core::{Operands, VirtualMachine, VmConfig},
         },
     },
 };
+use bincode::enc::write::Writer;
 use num_integer::div_rem;
 use num_traits::{ToPrimitive, Zero};
 use serde::{Deserialize, Serialize};
 
+use super::cost_model::{Cost, CostModel};
 use super::{builtin_runner::ModBuiltinRunner, cairo_pie::CairoPieAdditionalData};
 use super::{
     builtin_runner::{
@@ -56,26 +69,12 @@ use super::{
     },
     cairo_pie::{self, CairoPie, CairoPieMetadata, CairoPieVersion},
 };
+use crate::types::instance_definitions::builtins_instance_def::BuiltinsInstanceDef;
 use crate::types::instance_definitions::mod_instance_def::ModInstanceDef;
+#[cfg(feature = "extensive_hints")]
+use crate::types::program::HintRange;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum CairoArg {
-    Single(MaybeRelocatable),
-    Array(Vec<MaybeRelocatable>),
-    Composed(Vec<CairoArg>),
-}
-
-impl From<MaybeRelocatable> for CairoArg {
-    fn from(other: MaybeRelocatable) -> Self {
-        CairoArg::Single(other)
-    }
-}
-
-impl From<Vec<MaybeRelocatable>> for CairoArg {
-    fn from(other: Vec<MaybeRelocatable>) -> Self {
-        CairoArg::Array(other)
-    }
-}
+pub use crate::types::cairo_arg::{CairoArg, CairoReturnType};
 
 // ================
 //   RunResources
@@ -85,6 +84,7 @@ impl From<Vec<MaybeRelocatable>> for CairoArg {
 #[derive(Clone, Default, Debug, PartialEq)]
 pub struct RunResources {
     n_steps: Option<usize>,
+    charge_hint_iterations: bool,
 }
 
 /// This trait is in charge of overseeing the VM's step usage in contexts where a limited amount of steps are available
@@ -99,13 +99,23 @@ pub trait ResourceTracker {
     }
     /// Subtracts 1 step from the available steps
     fn consume_step(&mut self) {}
+    /// Subtracts `n_steps` steps from the available steps, e.g. to charge the iterations done
+    /// inside a hint against the same limit as the VM steps
+    fn consume_steps(&mut self, n_steps: usize) {
+        for _ in 0..n_steps {
+            self.consume_step()
+        }
+    }
     /// Returns the available steps for the run
     fn get_n_steps(&self) -> Option<usize> {
         None
     }
     /// Returns a reference to the available resources
     fn run_resources(&self) -> &RunResources {
-        &RunResources { n_steps: None }
+        &RunResources {
+            n_steps: None,
+            charge_hint_iterations: false,
+        }
     }
 }
 
@@ -113,8 +123,25 @@ impl RunResources {
     pub fn new(n_steps: usize) -> Self {
         Self {
             n_steps: Some(n_steps),
+            charge_hint_iterations: false,
         }
     }
+
+    /// Also charges the elements looped over by the hints whose work grows with their inputs
+    /// (e.g. `squash_dict`, `usort` or `find_element`), one step per element. The elements are
+    /// counted from the hint's inputs before it runs, so a hint that exceeds the remaining steps
+    /// still runs, and the run stops after the current step. By default only the VM steps are
+    /// charged, as expected when re-running a Cairo PIE.
+    pub fn with_hint_iterations(mut self) -> Self {
+        self.charge_hint_iterations = true;
+        self
+    }
+
+    /// Returns true if the hint iterations are charged along with the VM steps, see
+    /// [RunResources::with_hint_iterations].
+    pub fn charges_hint_iterations(&self) -> bool {
+        self.charge_hint_iterations
+    }
 }
 
 impl ResourceTracker for RunResources {
@@ -126,8 +153,12 @@ impl ResourceTracker for RunResources {
     }
 
     fn consume_step(&mut self) {
-        if let Some(n_steps) = self.n_steps {
-            self.n_steps = Some(n_steps.saturating_sub(1));
+        self.consume_steps(1)
+    }
+
+    fn consume_steps(&mut self, n_steps: usize) {
+        if let Some(available_steps) = self.n_steps {
+            self.n_steps = Some(available_steps.saturating_sub(n_steps));
         }
     }
 
@@ -144,6 +175,7 @@ pub struct CairoRunner {
     pub vm: VirtualMachine,
     pub(crate) program: Program,
     layout: CairoLayout,
+    custom_builtins: HashMap<String, Arc<dyn CustomBuiltinFactory>>,
     final_pc: Option<Relocatable>,
     pub program_base: Option<Relocatable>,
     execution_base: Option<Relocatable>,
@@ -153,6 +185,7 @@ pub struct CairoRunner {
     initial_pc: Option<Relocatable>,
     run_ended: bool,
     segments_finalized: bool,
+    pub(crate) builtin_holes_filled: bool,
     execution_public_memory: Option<Vec<usize>>,
     runner_mode: RunnerMode,
     pub relocated_memory: Vec<Option<Felt252>>,
@@ -167,16 +200,45 @@ pub enum RunnerMode {
     ProofModeCairo1,
 }
 
-impl CairoRunner {
-    /// The `dynamic_layout_params` argument should only be used with dynamic layout.
+/// Options used to create a [CairoRunner], see [CairoRunner::new_with_config].
+#[derive(Clone)]
+pub struct RunnerConfig {
+    pub layout: LayoutName,
+    /// The `dynamic_layout_params` field should only be used with dynamic layout.
     /// It is ignored otherwise.
-    pub fn new_v2(
+    pub dynamic_layout_params: Option<CairoLayoutParams>,
+    pub mode: RunnerMode,
+    pub vm_config: VmConfig,
+    /// The factories of the custom builtins that can be used by the program, keyed by the
+    /// builtin names, without the "_builtin" suffix.
+    /// See [CustomBuiltin](crate::vm::runners::builtin_runner::CustomBuiltin).
+    pub custom_builtins: HashMap<String, Arc<dyn CustomBuiltinFactory>>,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        RunnerConfig {
+            layout: LayoutName::plain,
+            dynamic_layout_params: None,
+            mode: RunnerMode::ExecutionMode,
+            vm_config: VmConfig::default(),
+            custom_builtins: HashMap::new(),
+        }
+    }
+}
+
+impl CairoRunner {
+    pub fn new_with_config(
         program: &Program,
-        layout: LayoutName,
-        dynamic_layout_params: Option<CairoLayoutParams>,
-        mode: RunnerMode,
-        trace_enabled: bool,
+        config: RunnerConfig,
     ) -> Result<CairoRunner, RunnerError> {
+        let RunnerConfig {
+            layout,
+            dynamic_layout_params,
+            mode,
+            vm_config,
+            custom_builtins,
+        } = config;
         let cairo_layout = match layout {
             LayoutName::plain => CairoLayout::plain_instance(),
             LayoutName::small => CairoLayout::small_instance(),
@@ -197,8 +259,9 @@ impl CairoRunner {
         };
         Ok(CairoRunner {
             program: program.clone(),
-            vm: VirtualMachine::new(trace_enabled),
+            vm: VirtualMachine::with_config(vm_config),
             layout: cairo_layout,
+            custom_builtins,
             final_pc: None,
             program_base: None,
             execution_base: None,
@@ -208,6 +271,7 @@ impl CairoRunner {
             initial_pc: None,
             run_ended: false,
             segments_finalized: false,
+            builtin_holes_filled: false,
             runner_mode: mode.clone(),
             relocated_memory: Vec::new(),
             exec_scopes: ExecutionScopes::new(),
@@ -220,6 +284,77 @@ impl CairoRunner {
         })
     }
 
+    /// Creates a runner to re-execute the [CairoPie] of a previous run.
+    ///
+    /// The program is taken from the PIE, the segments are finalized with the sizes recorded in
+    /// the PIE, and its memory and builtin additional data are loaded. The additional data of the
+    /// pedersen builtin is only loaded if `trust_pedersen_data` is true, as it isn't trusted in
+    /// secure runs.
+    /// Returns the runner along with the end pc, to be passed to [Self::run_until_pc].
+    ///
+    /// PIEs can't be run in proof mode, so `config.mode` must be [RunnerMode::ExecutionMode].
+    pub fn from_cairo_pie(
+        pie: &CairoPie,
+        config: RunnerConfig,
+        allow_missing_builtins: bool,
+        trust_pedersen_data: bool,
+    ) -> Result<(CairoRunner, Relocatable), CairoRunError> {
+        if config.mode != RunnerMode::ExecutionMode {
+            return Err(RunnerError::CairoPieProofMode.into());
+        }
+        pie.run_validity_checks()?;
+
+        let program = Program::from_stripped_program(&pie.metadata.program);
+        let mut cairo_runner = CairoRunner::new_with_config(&program, config)?;
+
+        let end = cairo_runner.initialize(allow_missing_builtins)?;
+        cairo_runner.vm.finalize_segments_by_cairo_pie(pie);
+        // Load builtin additional data
+        for (name, data) in pie.additional_data.0.iter() {
+            if matches!(name, BuiltinName::pedersen) && !trust_pedersen_data {
+                continue;
+            }
+            if let Some(builtin) = cairo_runner
+                .vm
+                .builtin_runners
+                .iter_mut()
+                .find(|b| b.name() == *name)
+            {
+                builtin.extend_additional_data(data)?;
+            }
+        }
+        // Load previous execution memory
+        let has_zero_segment = cairo_runner.vm.segments.has_zero_segment() as usize;
+        let n_extra_segments = pie.metadata.extra_segments.len() - has_zero_segment;
+        cairo_runner
+            .vm
+            .segments
+            .load_pie_memory(&pie.memory, n_extra_segments)?;
+
+        Ok((cairo_runner, end))
+    }
+
+    /// The `dynamic_layout_params` argument should only be used with dynamic layout.
+    /// It is ignored otherwise.
+    pub fn new_v2(
+        program: &Program,
+        layout: LayoutName,
+        dynamic_layout_params: Option<CairoLayoutParams>,
+        mode: RunnerMode,
+        trace_enabled: bool,
+    ) -> Result<CairoRunner, RunnerError> {
+        Self::new_with_config(
+            program,
+            RunnerConfig {
+                layout,
+                dynamic_layout_params,
+                mode,
+                vm_config: VmConfig::new().trace_enabled(trace_enabled),
+                custom_builtins: HashMap::new(),
+            },
+        )
+    }
+
     pub fn new(
         program: &Program,
         layout: LayoutName,
@@ -263,6 +398,7 @@ impl CairoRunner {
     /// When running in proof_mode, all builtins in the layout will be created, and only those in the program will be included
     /// When not running in proof_mode, only program builtins will be created and included
     /// Unless `allow_missing_builtins` is set to true, an error will be returned if a builtin is included in the program but not on the layout
+    /// Custom builtins, which come after the other builtins of the program, are created with the factories of [RunnerConfig::custom_builtins]
     pub fn initialize_builtins(&mut self, allow_missing_builtins: bool) -> Result<(), RunnerError> {
         let builtin_ordered_list = vec![
             BuiltinName::output,
@@ -277,7 +413,19 @@ impl CairoRunner {
             BuiltinName::add_mod,
             BuiltinName::mul_mod,
         ];
-        if !is_subsequence(&self.program.builtins, &builtin_ordered_list) {
+        let n_cairo_builtins = self
+            .program
+            .builtins
+            .iter()
+            .take_while(|name| !matches!(name, BuiltinName::custom(_)))
+            .count();
+        let (cairo_builtins, program_custom_builtins) =
+            self.program.builtins.split_at(n_cairo_builtins);
+        if !is_subsequence(cairo_builtins, &builtin_ordered_list)
+            || !program_custom_builtins
+                .iter()
+                .all(|name| matches!(name, BuiltinName::custom(_)))
+        {
             return Err(RunnerError::DisorderedBuiltins);
         };
         let mut program_builtins: HashSet<&BuiltinName> = self.program.builtins.iter().collect();
@@ -336,7 +484,7 @@ impl CairoRunner {
             if included || self.is_proof_mode() {
                 self.vm
                     .builtin_runners
-                    .push(EcOpBuiltinRunner::new(instance_def.ratio, included).into());
+                    .push(EcOpBuiltinRunner::from_instance_def(instance_def, included).into());
             }
         }
 
@@ -386,10 +534,24 @@ impl CairoRunner {
                     .push(ModBuiltinRunner::new_mul_mod(instance_def, included).into());
             }
         }
+        for name in program_custom_builtins {
+            let BuiltinName::custom(custom_name) = name else {
+                continue;
+            };
+            if let Some(factory) = self.custom_builtins.get(custom_name.to_str()) {
+                program_builtins.remove(name);
+                self.vm.builtin_runners.push(
+                    CustomBuiltinRunner::new(*custom_name, factory.new_builtin(), true).into(),
+                );
+            } else if !allow_missing_builtins {
+                return Err(RunnerError::MissingCustomBuiltinFactory(*name));
+            }
+        }
         if !program_builtins.is_empty() && !allow_missing_builtins {
             return Err(RunnerError::NoBuiltinForInstance(Box::new((
                 program_builtins.iter().map(|n| **n).collect(),
                 self.layout.name,
+                BuiltinsInstanceDef::layouts_including(&self.program.builtins),
             ))));
         }
 
@@ -404,7 +566,11 @@ impl CairoRunner {
     // Initialize all program builtins. Values used are the original one from the CairoFunctionRunner
     // Values extracted from here: https://github.com/starkware-libs/cairo-lang/blob/4fb83010ab77aa7ead0c9df4b0c05e030bc70b87/src/starkware/cairo/common/cairo_function_runner.py#L28
     pub fn initialize_program_builtins(&mut self) -> Result<(), RunnerError> {
-        fn initialize_builtin(name: BuiltinName, vm: &mut VirtualMachine) {
+        fn initialize_builtin(
+            name: BuiltinName,
+            vm: &mut VirtualMachine,
+            custom_builtins: &HashMap<String, Arc<dyn CustomBuiltinFactory>>,
+        ) -> Result<(), RunnerError> {
             match name {
                 BuiltinName::pedersen => vm
                     .builtin_runners
@@ -444,11 +610,20 @@ impl CairoRunner {
                     ModBuiltinRunner::new_mul_mod(&ModInstanceDef::new(Some(1), 1, 96), true)
                         .into(),
                 ),
+                BuiltinName::custom(custom_name) => {
+                    let factory = custom_builtins
+                        .get(custom_name.to_str())
+                        .ok_or(RunnerError::MissingCustomBuiltinFactory(name))?;
+                    vm.builtin_runners.push(
+                        CustomBuiltinRunner::new(custom_name, factory.new_builtin(), true).into(),
+                    )
+                }
             }
+            Ok(())
         }
 
         for builtin_name in &self.program.builtins {
-            initialize_builtin(*builtin_name, &mut self.vm);
+            initialize_builtin(*builtin_name, &mut self.vm, &self.custom_builtins)?;
         }
         Ok(())
     }
@@ -637,11 +812,12 @@ impl CairoRunner {
             .iter_hints()
             .map(|hint| {
                 hint_executor
-                    .compile_hint(
+                    .compile_hint_with_identifiers(
                         &hint.code,
                         &hint.flow_tracking_data.ap_tracking,
                         &hint.flow_tracking_data.reference_ids,
                         references,
+                        &self.program.shared_program_data.identifiers,
                     )
                     .map_err(|_| VirtualMachineError::CompileHintFail(hint.code.clone().into()))
             })
@@ -661,51 +837,209 @@ impl CairoRunner {
         address: Relocatable,
         hint_processor: &mut dyn HintProcessor,
     ) -> Result<(), VirtualMachineError> {
+        let mut session = self.start_debug_session(hint_processor)?;
+        self.run_until_pc_or_event(address, hint_processor, &mut session, |_| None)?;
+        Ok(())
+    }
+
+    /// Compiles the program hints for a [DebugSession], which keeps them across the calls to
+    /// [CairoRunner::run_until_debug_event], [CairoRunner::run_until_breakpoint] and
+    /// [CairoRunner::single_step] so that the run can be paused and resumed.
+    pub fn start_debug_session(
+        &mut self,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<DebugSession, VirtualMachineError> {
         let references = &self.program.shared_program_data.reference_manager;
-        #[cfg(not(feature = "extensive_hints"))]
         let hint_data = self.get_hint_data(references, hint_processor)?;
-        #[cfg(feature = "extensive_hints")]
-        let mut hint_data = self.get_hint_data(references, hint_processor)?;
-        #[cfg(feature = "extensive_hints")]
-        let mut hint_ranges = self
+        #[cfg(feature = "hooks")]
+        self.vm.execute_before_first_step(&hint_data)?;
+        Ok(DebugSession {
+            hint_data,
+            #[cfg(feature = "extensive_hints")]
+            hint_ranges: self
+                .program
+                .shared_program_data
+                .hints_collection
+                .hints_ranges
+                .clone(),
+        })
+    }
+
+    // Executes a single step with the hints compiled in `session`
+    fn step_with_session(
+        &mut self,
+        hint_processor: &mut dyn HintProcessor,
+        session: &mut DebugSession,
+    ) -> Result<(), VirtualMachineError> {
+        #[cfg(not(feature = "extensive_hints"))]
+        let hint_data = self
             .program
             .shared_program_data
             .hints_collection
-            .hints_ranges
-            .clone();
-        #[cfg(feature = "test_utils")]
-        self.vm.execute_before_first_step(&hint_data)?;
-        while self.vm.get_pc() != address && !hint_processor.consumed() {
-            self.vm.step(
+            .get_hint_range_for_pc(self.vm.get_pc().offset)
+            .and_then(|range| {
+                range.and_then(|(start, length)| session.hint_data.get(start..start + length.get()))
+            })
+            .unwrap_or(&[]);
+        self.vm
+            .step(
                 hint_processor,
                 &mut self.exec_scopes,
                 #[cfg(feature = "extensive_hints")]
-                &mut hint_data,
+                &mut session.hint_data,
                 #[cfg(not(feature = "extensive_hints"))]
-                self.program
-                    .shared_program_data
-                    .hints_collection
-                    .get_hint_range_for_pc(self.vm.get_pc().offset)
-                    .and_then(|range| {
-                        range.and_then(|(start, length)| hint_data.get(start..start + length.get()))
-                    })
-                    .unwrap_or(&[]),
+                hint_data,
                 #[cfg(feature = "extensive_hints")]
-                &mut hint_ranges,
+                &mut session.hint_ranges,
                 &self.program.constants,
-            )?;
+            )
+            .map_err(|err| self.unknown_hint_compiler_version_error(err, hint_processor))
+    }
 
+    // Steps until the pc reaches `address` or `pause` returns an event after a step
+    fn run_until_pc_or_event(
+        &mut self,
+        address: Relocatable,
+        hint_processor: &mut dyn HintProcessor,
+        session: &mut DebugSession,
+        mut pause: impl FnMut(&VirtualMachine) -> Option<DebugEvent>,
+    ) -> Result<Option<DebugEvent>, VirtualMachineError> {
+        while self.vm.get_pc() != address && !hint_processor.consumed() {
+            self.step_with_session(hint_processor, session)?;
             hint_processor.consume_step();
+
+            if let Some(event) = pause(&self.vm) {
+                return Ok(Some(event));
+            }
         }
 
         if self.vm.get_pc() != address {
             return Err(VirtualMachineError::UnfinishedExecution);
         }
 
-        Ok(())
+        Ok(None)
+    }
+
+    // Points at the compiler version when a hint is unknown to a hint processor which doesn't
+    // support the version the program was compiled with
+    fn unknown_hint_compiler_version_error(
+        &self,
+        err: VirtualMachineError,
+        hint_processor: &dyn HintProcessor,
+    ) -> VirtualMachineError {
+        match err {
+            VirtualMachineError::Hint(bx) => match (*bx, self.program.compiler_version()) {
+                ((hint_index, HintError::UnknownHint(code)), Some(compiler_version))
+                    if !hint_processor.supports_compiler_version(compiler_version) =>
+                {
+                    VirtualMachineError::Hint(Box::new((
+                        hint_index,
+                        HintError::UnknownHintNewerCompilerVersion(Box::new((
+                            code,
+                            compiler_version.to_string(),
+                        ))),
+                    )))
+                }
+                (hint_error, _) => VirtualMachineError::Hint(Box::new(hint_error)),
+            },
+            err => err,
+        }
+    }
+
+    /// Runs until the pc reaches `address` or one of the `breakpoints`, which is checked after
+    /// every step so that the run can be resumed from a breakpoint.
+    /// Returns the breakpoint that was hit, if any.
+    pub fn run_until_breakpoint(
+        &mut self,
+        address: Relocatable,
+        breakpoints: &HashSet<Relocatable>,
+        hint_processor: &mut dyn HintProcessor,
+        session: &mut DebugSession,
+    ) -> Result<Option<Relocatable>, VirtualMachineError> {
+        Ok(self
+            .run_until_debug_event(
+                address,
+                breakpoints,
+                &HashSet::new(),
+                hint_processor,
+                session,
+            )?
+            .map(|event| match event {
+                DebugEvent::Breakpoint(pc) | DebugEvent::Watchpoint(pc) => pc,
+            }))
+    }
+
+    /// Runs until the pc reaches `address`, the pc reaches one of the `breakpoints` or one of
+    /// the `watchpoints` addresses is written to.
+    /// As memory is write-once, a watchpoint is only hit by the step writing its first value.
+    /// Returns the event that paused the run, if any.
+    pub fn run_until_debug_event(
+        &mut self,
+        address: Relocatable,
+        breakpoints: &HashSet<Relocatable>,
+        watchpoints: &HashSet<Relocatable>,
+        hint_processor: &mut dyn HintProcessor,
+        session: &mut DebugSession,
+    ) -> Result<Option<DebugEvent>, VirtualMachineError> {
+        let mut unwritten_watchpoints: Vec<Relocatable> = watchpoints
+            .iter()
+            .filter(|addr| self.vm.segments.memory.get(*addr).is_none())
+            .copied()
+            .collect();
+        self.run_until_pc_or_event(address, hint_processor, session, |vm| {
+            if let Some(index) = unwritten_watchpoints
+                .iter()
+                .position(|addr| vm.segments.memory.get(addr).is_some())
+            {
+                return Some(DebugEvent::Watchpoint(
+                    unwritten_watchpoints.swap_remove(index),
+                ));
+            }
+            if breakpoints.contains(&vm.get_pc()) {
+                return Some(DebugEvent::Breakpoint(vm.get_pc()));
+            }
+            None
+        })
+    }
+
+    /// Returns the values of the ids visible to the hints at the current pc, sorted by name.
+    /// The value is `None` if the reference can't be computed from the current memory.
+    ///
+    /// References are only tracked at hints, so there are no ids at other pcs.
+    pub fn get_ids_at_pc(&self) -> Vec<(String, Option<MaybeRelocatable>)> {
+        let pc = self.vm.get_pc();
+        if pc.segment_index != 0 {
+            return Vec::new();
+        }
+        let shared_program_data = &self.program.shared_program_data;
+        let Some(hint) = shared_program_data
+            .hints_collection
+            .get_hints_for_pc(pc.offset)
+            .last()
+        else {
+            return Vec::new();
+        };
+        let ap_tracking = &hint.flow_tracking_data.ap_tracking;
+        let mut ids: Vec<_> = hint
+            .flow_tracking_data
+            .reference_ids
+            .iter()
+            .filter_map(|(path, ref_id)| {
+                let name = path.rsplit('.').next()?;
+                let reference = shared_program_data.reference_manager.get(*ref_id)?;
+                Some((
+                    name.to_string(),
+                    get_maybe_relocatable_from_reference(&self.vm, reference, ap_tracking),
+                ))
+            })
+            .collect();
+        ids.sort_by(|(a, _), (b, _)| a.cmp(b));
+        ids
     }
 
     /// Execute an exact number of steps on the program from the actual position.
+    /// Unlike [CairoRunner::run_until_pc], the steps aren't charged against the hint
+    /// processor's [RunResources].
     pub fn run_for_steps(
         &mut self,
         steps: usize,
@@ -739,7 +1073,7 @@ impl CairoRunner {
                 return Err(VirtualMachineError::EndOfProgram(remaining_steps));
             }
 
-            self.vm.step(
+            if let Err(err) = self.vm.step(
                 hint_processor,
                 &mut self.exec_scopes,
                 #[cfg(feature = "extensive_hints")]
@@ -749,12 +1083,43 @@ impl CairoRunner {
                 #[cfg(feature = "extensive_hints")]
                 &mut hint_ranges,
                 &self.program.constants,
-            )?;
+            ) {
+                return Err(self.unknown_hint_compiler_version_error(err, hint_processor));
+            }
         }
 
         Ok(())
     }
 
+    /// Executes a single step, including the hints at the current pc.
+    /// Returns the executed instruction along with its operands.
+    pub fn single_step(
+        &mut self,
+        hint_processor: &mut dyn HintProcessor,
+        session: &mut DebugSession,
+    ) -> Result<(Instruction, Operands), VirtualMachineError> {
+        if self.final_pc.as_ref() == Some(&self.vm.get_pc()) {
+            return Err(VirtualMachineError::EndOfProgram(1));
+        }
+        let run_context = RunContext {
+            pc: self.vm.run_context.pc,
+            ap: self.vm.run_context.ap,
+            fp: self.vm.run_context.fp,
+        };
+        self.step_with_session(hint_processor, session)?;
+        // Memory is write-once, so the operands can be recomputed with the previous registers
+        let run_context = core::mem::replace(&mut self.vm.run_context, run_context);
+        let result = self
+            .vm
+            .decode_current_instruction()
+            .and_then(|instruction| {
+                let (operands, _, _) = self.vm.compute_operands(&instruction)?;
+                Ok((instruction, operands))
+            });
+        self.vm.run_context = run_context;
+        result
+    }
+
     /// Execute steps until a number of steps since the start of the program is reached.
     pub fn run_until_steps(
         &mut self,
@@ -915,13 +1280,19 @@ impl CairoRunner {
         if self.relocated_trace.is_some() {
             return Err(TraceError::AlreadyRelocated);
         }
+        if self.vm.trace_filtered {
+            return Err(TraceError::FilteredTrace);
+        }
 
-        let trace = self
-            .vm
-            .trace
-            .as_ref()
-            .ok_or(TraceError::TraceNotEnabled)?
-            .iter();
+        let decoded_trace;
+        let trace = match (&self.vm.trace, &self.vm.compact_trace) {
+            (Some(trace), _) => trace.iter(),
+            (None, Some(compact_trace)) => {
+                decoded_trace = compact_trace.to_trace_entries();
+                decoded_trace.iter()
+            }
+            (None, None) => return Err(TraceError::TraceNotEnabled),
+        };
         let mut relocated_trace = Vec::<RelocatedTraceEntry>::with_capacity(trace.len());
         let segment_1_base = relocation_table
             .get(1)
@@ -968,9 +1339,77 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Relocates the VM's trace and writes it straight into `dest`, without building the
+    /// relocated trace vector.
+    /// Uses the same encoding as [write_encoded_trace](crate::cairo_run::write_encoded_trace).
+    pub fn relocate_trace_into(
+        &mut self,
+        dest: &mut impl Writer,
+    ) -> Result<(), EncodeRelocatedTraceError> {
+        if self.vm.trace_filtered {
+            return Err(TraceError::FilteredTrace.into());
+        }
+        self.vm.segments.compute_effective_sizes();
+        let relocation_table = self
+            .vm
+            .segments
+            .relocate_segments()
+            .map_err(TraceError::MemoryError)?;
+        let segment_1_base = *relocation_table
+            .get(1)
+            .ok_or(TraceError::NoRelocationFound)?;
+        let trace: Box<dyn Iterator<Item = TraceEntry>> =
+            match (&self.vm.trace, &self.vm.compact_trace) {
+                (Some(trace), _) => Box::new(trace.iter().cloned()),
+                (None, Some(compact_trace)) => Box::new(compact_trace.iter()),
+                (None, None) => return Err(TraceError::TraceNotEnabled.into()),
+            };
+        for (i, entry) in trace.enumerate() {
+            let pc = relocate_trace_register(entry.pc, &relocation_table)?;
+            for register in [entry.ap + segment_1_base, entry.fp + segment_1_base, pc] {
+                dest.write(&(register as u64).to_le_bytes())
+                    .map_err(|e| EncodeTraceError::new(i, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Relocates the VM's memory and writes it straight into `dest`, without building the
+    /// relocated memory vector.
+    /// Uses the same encoding as [write_encoded_memory](crate::cairo_run::write_encoded_memory):
+    /// * address -> 8-byte encoded
+    /// * value -> 32-byte encoded
+    pub fn relocate_memory_into(
+        &mut self,
+        dest: &mut impl Writer,
+    ) -> Result<(), EncodeMemoryError> {
+        self.vm.segments.compute_effective_sizes();
+        let relocation_table = self.vm.segments.relocate_segments()?;
+        for (index, segment) in self.vm.segments.memory.data.iter().enumerate() {
+            for (seg_offset, cell) in segment.iter().enumerate() {
+                if let Some(cell) = cell.get_value() {
+                    let relocated_addr = relocate_address(
+                        Relocatable::from((index as isize, seg_offset)),
+                        &relocation_table,
+                    )?;
+                    let value = relocate_value(cell, &relocation_table)?;
+                    dest.write(&(relocated_addr as u64).to_le_bytes())
+                        .map_err(|e| EncodeMemoryError::Encode(relocated_addr, e))?;
+                    dest.write(&value.to_bytes_le())
+                        .map_err(|e| EncodeMemoryError::Encode(relocated_addr, e))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Relocates the VM's trace (if enabled) and memory (if `relocate_mem` is set).
+    /// The computed relocation table is stored in the VM, see [VirtualMachine::get_relocation_table].
+    /// Does nothing if the trace is disabled and `relocate_mem` is not set.
     pub fn relocate(&mut self, relocate_mem: bool) -> Result<(), TraceError> {
         self.vm.segments.compute_effective_sizes();
-        if !relocate_mem && self.vm.trace.is_none() {
+        let trace_enabled = self.vm.trace.is_some() || self.vm.compact_trace.is_some();
+        if !relocate_mem && !trace_enabled {
             return Ok(());
         }
         // relocate_segments can fail if compute_effective_sizes is not called before.
@@ -986,13 +1425,88 @@ impl CairoRunner {
                 return Err(TraceError::MemoryError(memory_error));
             }
         }
-        if self.vm.trace.is_some() {
+        if trace_enabled {
             self.relocate_trace(&relocation_table)?;
         }
         self.vm.relocation_table = Some(relocation_table);
         Ok(())
     }
 
+    /// Returns the entries of the relocated trace for the steps within the given range.
+    /// The range is truncated to the number of steps executed.
+    pub fn get_relocated_trace_window(
+        &self,
+        steps: Range<usize>,
+    ) -> Result<&[RelocatedTraceEntry], TraceError> {
+        let trace = self
+            .relocated_trace
+            .as_ref()
+            .ok_or(TraceError::TraceNotRelocated)?;
+        let end = steps.end.min(trace.len());
+        Ok(&trace[steps.start.min(end)..end])
+    }
+
+    /// Returns the relocated (address, value) pairs of the memory cells added between two
+    /// checkpoints, see [Checkpoint::segment_growth].
+    /// Requires the memory to be relocated, see [CairoRunner::relocate].
+    pub fn get_relocated_memory_window(
+        &self,
+        from: &Checkpoint,
+        to: &Checkpoint,
+    ) -> Result<Vec<(usize, Felt252)>, TraceError> {
+        let relocation_table = match self.vm.relocation_table.as_ref() {
+            Some(table) if !self.relocated_memory.is_empty() => table,
+            _ => return Err(TraceError::MemoryNotRelocated),
+        };
+        let mut cells = Vec::new();
+        for range in from.segment_growth(to) {
+            let base = relocation_table
+                .get(range.start.segment_index as usize)
+                .ok_or(MemoryError::Relocation)?;
+            for addr in base + range.start.offset..base + range.end.offset {
+                if let Some(Some(value)) = self.relocated_memory.get(addr) {
+                    cells.push((addr, *value));
+                }
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Computes a commitment to the result of the run, so that two parties running the same
+    /// program can compare their executions without exchanging the full trace and memory.
+    ///
+    /// The commitment is `poseidon_hash_many([n_steps, first_pc, first_ap, first_fp, last_pc,
+    /// last_ap, last_fp, memory_hash])`, where the registers are taken from the relocated trace
+    /// (zero if empty) and `memory_hash` is `poseidon_hash_many` of the relocated (address, value)
+    /// pairs, in increasing address order.
+    /// Requires the trace and memory to be relocated, see [CairoRunner::relocate].
+    pub fn get_execution_commitment(&self) -> Result<Felt252, TraceError> {
+        let trace = self
+            .relocated_trace
+            .as_ref()
+            .ok_or(TraceError::TraceNotRelocated)?;
+        if self.relocated_memory.is_empty() {
+            return Err(TraceError::MemoryNotRelocated);
+        }
+        let memory: Vec<Felt252> = self
+            .relocated_memory
+            .iter()
+            .enumerate()
+            .filter_map(|(addr, value)| Some([Felt252::from(addr), (*value)?]))
+            .flatten()
+            .collect();
+        let registers = |entry: Option<&RelocatedTraceEntry>| {
+            entry.map_or([Felt252::ZERO; 3], |entry| {
+                [entry.pc, entry.ap, entry.fp].map(Felt252::from)
+            })
+        };
+        let mut commitment = vec![Felt252::from(trace.len())];
+        commitment.extend(registers(trace.first()));
+        commitment.extend(registers(trace.last()));
+        commitment.push(poseidon_hash_many(&memory));
+        Ok(poseidon_hash_many(&commitment))
+    }
+
     // Returns a map from builtin base's segment index to stop_ptr offset
     // Aka the builtin's segment number and its maximum offset
     pub fn get_builtin_segments_info(&self) -> Result<Vec<(usize, usize)>, RunnerError> {
@@ -1033,13 +1547,21 @@ impl CairoRunner {
         Ok(builtin_segment_info)
     }
 
+    /// Returns the values written by the program to the output builtin's segment.
+    /// Returns an empty vector if the output builtin is not present.
+    pub fn get_program_output(&self) -> Result<Vec<Felt252>, RunnerError> {
+        match self.vm.get_builtin::<OutputBuiltinRunner>() {
+            Some(output_builtin) => Ok(output_builtin.get_output(&self.vm)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
     pub fn get_execution_resources(&self) -> Result<ExecutionResources, RunnerError> {
-        let n_steps = self
-            .vm
-            .trace
-            .as_ref()
-            .map(|x| x.len())
-            .unwrap_or(self.vm.current_step);
+        let n_steps = match &self.vm.trace {
+            // A filtered trace doesn't contain every step
+            Some(trace) if !self.vm.trace_filtered => trace.len(),
+            _ => self.vm.current_step,
+        };
         let n_memory_holes = self.get_memory_holes()?;
 
         let mut builtin_instance_counter = HashMap::new();
@@ -1057,6 +1579,11 @@ impl CairoRunner {
         })
     }
 
+    /// Computes the cost of the run's [ExecutionResources] according to the given [CostModel].
+    pub fn get_cost(&self, cost_model: &CostModel) -> Result<Cost, RunnerError> {
+        Ok(cost_model.cost(&self.get_execution_resources()?))
+    }
+
     // Finalizes the segments.
     //     Note:
     //     1.  end_run() must precede a call to this method.
@@ -1118,9 +1645,31 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Fills the unused instances of the builtins, up to the size allocated for them in proof
+    /// mode, with valid inputs and outputs (see [BuiltinRunner::fill_holes]), so that the
+    /// relocated memory satisfies the builtin constraints of the AIR even when the number of
+    /// used instances isn't a multiple of the instances per component.
+    /// finalize_segments() must precede a call to this method, and [verify_secure_runner] should
+    /// follow it so that the filled cells are checked too.
+    pub fn fill_builtin_holes(&mut self) -> Result<(), RunnerError> {
+        if !self.segments_finalized {
+            return Err(RunnerError::FillBuiltinHolesNoFinalizeSegments);
+        }
+        for builtin_runner in self.vm.builtin_runners.iter() {
+            let (_, size) = builtin_runner
+                .get_used_cells_and_allocated_size(&self.vm)
+                .map_err(RunnerError::FinalizeSegements)?;
+            builtin_runner.fill_holes(&mut self.vm.segments.memory, size)?;
+        }
+        self.builtin_holes_filled = true;
+        Ok(())
+    }
+
     /// Runs a cairo program from a give entrypoint, indicated by its pc offset, with the given arguments.
     /// If `verify_secure` is set to true, [verify_secure_runner] will be called to run extra verifications.
     /// `program_segment_size` is only used by the [verify_secure_runner] function and will be ignored if `verify_secure` is set to false.
+    /// It can be called several times on the same runner: each call after the first one runs on a new
+    /// execution segment, and the memory written by the previous calls stays available.
     pub fn run_from_entrypoint(
         &mut self,
         entrypoint: usize,
@@ -1129,6 +1678,11 @@ impl CairoRunner {
         program_segment_size: Option<usize>,
         hint_processor: &mut dyn HintProcessor,
     ) -> Result<(), CairoRunError> {
+        if self.run_ended {
+            self.execution_base = Some(self.vm.add_memory_segment());
+            self.vm.segments.segment_used_sizes = None;
+            self.run_ended = false;
+        }
         let stack = args
             .iter()
             .map(|arg| self.vm.segments.gen_cairo_arg(arg))
@@ -1149,6 +1703,116 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Runs the program function with the given name (either its full name or its name within
+    /// `__main__`) with the given explicit arguments, and returns its return values.
+    /// The builtin pointers taken by the function as implicit arguments are resolved by name and
+    /// placed before `args`, and their final values are validated once the run ends.
+    /// Each builtin pointer starts where the previous run on this runner left it, so the function
+    /// can be run several times on the same runner.
+    /// The runner's builtins and segments must be initialized beforehand, for example with
+    /// [CairoRunner::initialize_function_runner].
+    /// `verify_secure` and `program_segment_size` behave as in [CairoRunner::run_from_entrypoint].
+    pub fn run_function_by_name(
+        &mut self,
+        name: &str,
+        args: &[&CairoArg],
+        verify_secure: bool,
+        program_segment_size: Option<usize>,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<Vec<MaybeRelocatable>, CairoRunError> {
+        let program = &self.program;
+        let (full_name, entrypoint) = [name.to_string(), format!("__main__.{name}")]
+            .into_iter()
+            .find_map(|full_name| {
+                let identifier = program.get_identifier(&full_name)?;
+                if identifier.type_.as_deref() != Some("function") {
+                    return None;
+                }
+                let pc = identifier.pc?;
+                Some((full_name, pc))
+            })
+            .ok_or_else(|| ProgramError::EntrypointNotFound(name.to_string()))?;
+
+        let implicit_builtins = match program.get_struct(&format!("{full_name}.ImplicitArgs")) {
+            Some(implicit_args) => implicit_args
+                .members
+                .iter()
+                .map(|(arg_name, _)| {
+                    arg_name
+                        .strip_suffix("_ptr")
+                        .and_then(BuiltinName::from_str)
+                        .ok_or_else(|| {
+                            RunnerError::NonBuiltinImplicitArgument(Box::new((
+                                full_name.clone(),
+                                arg_name.to_string(),
+                            )))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+        if let Some(explicit_args) = program.get_struct(&format!("{full_name}.Args")) {
+            if explicit_args.size != args.len() {
+                return Err(RunnerError::FunctionArgsCountMismatch(Box::new((
+                    full_name,
+                    explicit_args.size,
+                    args.len(),
+                )))
+                .into());
+            }
+        }
+        let return_size = program
+            .get_return_size(&full_name)
+            .ok_or_else(|| RunnerError::UnknownReturnSize(full_name.clone().into_boxed_str()))?;
+
+        let builtin_args = implicit_builtins
+            .iter()
+            .map(|builtin_name| {
+                self.vm
+                    .builtin_runners
+                    .iter()
+                    .find(|b| b.name() == *builtin_name)
+                    .map(|b| {
+                        // Continue after the cells used by the previous runs, if any
+                        let offset = b.stop_ptr().unwrap_or_else(|| {
+                            self.vm
+                                .segments
+                                .memory
+                                .segment_len(b.base() as isize)
+                                .unwrap_or(0)
+                        });
+                        CairoArg::from(MaybeRelocatable::from((b.base() as isize, offset)))
+                    })
+                    .ok_or(RunnerError::MissingBuiltin(*builtin_name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let all_args: Vec<&CairoArg> = builtin_args.iter().chain(args.iter().copied()).collect();
+        self.run_from_entrypoint(
+            entrypoint,
+            &all_args,
+            verify_secure,
+            program_segment_size,
+            hint_processor,
+        )?;
+
+        // The function leaves its implicit arguments on the stack, followed by its return values
+        let return_values_ptr =
+            (self.vm.get_ap() - return_size).map_err(VirtualMachineError::Math)?;
+        let mut pointer = return_values_ptr;
+        for builtin_name in implicit_builtins.iter().rev() {
+            let builtin_runner = self
+                .vm
+                .builtin_runners
+                .iter_mut()
+                .find(|b| b.name() == *builtin_name)
+                .ok_or(RunnerError::MissingBuiltin(*builtin_name))?;
+            pointer = builtin_runner.final_stack(&self.vm.segments, pointer)?;
+        }
+        Ok(self
+            .vm
+            .get_continuous_range(return_values_ptr, return_size)?)
+    }
+
     // Returns Ok(()) if there are enough allocated cells for the builtins.
     // If not, the number of steps should be increased or a different layout should be used.
     pub fn check_used_cells(&self) -> Result<(), VirtualMachineError> {
@@ -1485,6 +2149,23 @@ pub struct SegmentInfo {
 //*   ExecutionResources
 //* ----------------------
 
+/// The program hints compiled for a run that is paused and resumed, see
+/// [CairoRunner::start_debug_session]. It must only be used with the runner that started it.
+pub struct DebugSession {
+    hint_data: Vec<Box<dyn Any>>,
+    #[cfg(feature = "extensive_hints")]
+    hint_ranges: HashMap<Relocatable, HintRange>,
+}
+
+/// Reason why [CairoRunner::run_until_debug_event] paused the run.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DebugEvent {
+    /// The pc reached this breakpoint.
+    Breakpoint(Relocatable),
+    /// The last step wrote to this watched address.
+    Watchpoint(Relocatable),
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Eq, PartialEq)]
 pub struct ExecutionResources {
     pub n_steps: usize,
@@ -1579,6 +2260,9 @@ mod tests {
     use crate::air_private_input::{PrivateInput, PrivateInputSignature, SignatureInput};
     use crate::cairo_run::{cairo_run, CairoRunConfig};
     use crate::stdlib::collections::{HashMap, HashSet};
+    use crate::types::builtin_name::CustomBuiltinName;
+    use crate::types::instruction::Opcode;
+    use crate::vm::runners::builtin_runner::CustomBuiltin;
     use crate::vm::vm_memory::memory::MemoryCell;
 
     use crate::felt_hex;
@@ -1594,6 +2278,31 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn new_with_config() {
+        let program = program!();
+        let config = RunnerConfig {
+            layout: LayoutName::all_cairo,
+            mode: RunnerMode::ProofModeCanonical,
+            vm_config: VmConfig::new().trace_enabled(true).compact_trace(true),
+            ..Default::default()
+        };
+        let cairo_runner = CairoRunner::new_with_config(&program, config).unwrap();
+        assert_eq!(cairo_runner.layout.name, LayoutName::all_cairo);
+        assert!(cairo_runner.is_proof_mode());
+        assert!(cairo_runner.vm.get_compact_trace().is_some());
+
+        let config = RunnerConfig {
+            layout: LayoutName::dynamic,
+            ..Default::default()
+        };
+        assert_matches!(
+            CairoRunner::new_with_config(&program, config).err(),
+            Some(RunnerError::MissingDynamicLayoutParams)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn check_memory_usage_ok_case() {
@@ -1658,6 +2367,99 @@ mod tests {
         assert!(cairo_runner.initialize_builtins(true).is_ok())
     }
 
+    struct NoOpBuiltin;
+
+    impl CustomBuiltin for NoOpBuiltin {
+        fn cells_per_instance(&self) -> u32 {
+            1
+        }
+
+        fn n_input_cells(&self) -> u32 {
+            1
+        }
+    }
+
+    fn no_op_builtin_config() -> RunnerConfig {
+        let factory: Arc<dyn CustomBuiltinFactory> =
+            Arc::new(|| Box::new(NoOpBuiltin) as Box<dyn CustomBuiltin>);
+        RunnerConfig {
+            layout: LayoutName::small,
+            custom_builtins: HashMap::from([(String::from("no_op"), factory)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_builtins_with_custom_builtin() {
+        let no_op = BuiltinName::custom(CustomBuiltinName::new("no_op").unwrap());
+        let program = program![BuiltinName::output, no_op];
+        let mut cairo_runner =
+            CairoRunner::new_with_config(&program, no_op_builtin_config()).unwrap();
+        assert_matches!(cairo_runner.initialize_builtins(false), Ok(()));
+        let names: Vec<_> = cairo_runner
+            .vm
+            .builtin_runners
+            .iter()
+            .map(|builtin| builtin.name())
+            .collect();
+        assert_eq!(names, vec![BuiltinName::output, no_op]);
+
+        cairo_runner.initialize_segments(None);
+        assert_eq!(
+            cairo_runner.vm.builtin_runners[1].initial_stack(),
+            vec![mayberelocatable!(3, 0)]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_builtins_with_custom_builtin_missing_factory() {
+        let sha256 = BuiltinName::custom(CustomBuiltinName::new("sha256").unwrap());
+        let program = program![BuiltinName::output, sha256];
+        let mut cairo_runner =
+            CairoRunner::new_with_config(&program, no_op_builtin_config()).unwrap();
+        assert_matches!(
+            cairo_runner.initialize_builtins(false),
+            Err(RunnerError::MissingCustomBuiltinFactory(name)) if name == sha256
+        );
+
+        let mut cairo_runner =
+            CairoRunner::new_with_config(&program, no_op_builtin_config()).unwrap();
+        assert_matches!(cairo_runner.initialize_builtins(true), Ok(()));
+        assert_eq!(cairo_runner.vm.builtin_runners.len(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_builtins_with_custom_builtin_before_cairo_builtin() {
+        let no_op = BuiltinName::custom(CustomBuiltinName::new("no_op").unwrap());
+        let program = program![no_op, BuiltinName::output];
+        let mut cairo_runner =
+            CairoRunner::new_with_config(&program, no_op_builtin_config()).unwrap();
+        assert_matches!(
+            cairo_runner.initialize_builtins(false),
+            Err(RunnerError::DisorderedBuiltins)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_program_builtins_with_custom_builtin() {
+        let no_op = BuiltinName::custom(CustomBuiltinName::new("no_op").unwrap());
+        let program = program![BuiltinName::pedersen, no_op];
+        let mut cairo_runner =
+            CairoRunner::new_with_config(&program, no_op_builtin_config()).unwrap();
+        assert_matches!(cairo_runner.initialize_program_builtins(), Ok(()));
+        assert_eq!(cairo_runner.vm.builtin_runners[1].name(), no_op);
+
+        let mut cairo_runner = cairo_runner!(program);
+        assert_matches!(
+            cairo_runner.initialize_program_builtins(),
+            Err(RunnerError::MissingCustomBuiltinFactory(name)) if name == no_op
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn initialize_segments_with_base() {
@@ -1918,6 +2720,261 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_unknown_hint_newer_compiler_version() {
+        let program = br#"{
+            "attributes": [],
+            "builtins": [],
+            "compiler_version": "0.99.0",
+            "data": [
+                "0x208b7fff7fff7ffe"
+            ],
+            "hints": {
+                "0": [
+                    {
+                        "accessible_scopes": [],
+                        "code": "memory[ap] = some_future_hint()",
+                        "flow_tracking_data": {
+                            "ap_tracking": {
+                                "group": 0,
+                                "offset": 0
+                            },
+                            "reference_ids": {}
+                        }
+                    }
+                ]
+            },
+            "identifiers": {
+                "__main__.main": {
+                    "decorators": [],
+                    "pc": 0,
+                    "type": "function"
+                }
+            },
+            "main_scope": "__main__",
+            "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+            "reference_manager": {
+                "references": []
+            }
+        }"#;
+        let program = Program::from_bytes(program, Some("main")).unwrap();
+        assert_eq!(program.compiler_version(), Some("0.99.0"));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        assert_matches!(
+            cairo_runner.run_until_pc(end, &mut hint_processor),
+            Err(VirtualMachineError::Hint(bx))
+                if bx.0 == 0
+                    && matches!(
+                        &bx.1,
+                        HintError::UnknownHintNewerCompilerVersion(data)
+                            if data.0.as_ref() == "memory[ap] = some_future_hint()"
+                                && data.1 == "0.99.0"
+                    )
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn from_cairo_pie_continues_execution() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        cairo_runner.read_return_values(false).unwrap();
+        let pie = cairo_runner.get_cairo_pie().unwrap();
+
+        let (mut pie_runner, end) =
+            CairoRunner::from_cairo_pie(&pie, RunnerConfig::default(), false, false).unwrap();
+        assert!(pie_runner.segments_finalized);
+        pie_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        assert_eq!(pie_runner.vm.current_step, pie.execution_resources.n_steps);
+        pie_runner
+            .end_run(false, false, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        pie_runner.read_return_values(false).unwrap();
+        pie_runner
+            .get_cairo_pie()
+            .unwrap()
+            .check_pie_compatibility(&pie)
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn from_cairo_pie_proof_mode() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut BuiltinHintProcessor::new_empty())
+            .unwrap();
+        let pie = cairo_runner.get_cairo_pie().unwrap();
+
+        let config = RunnerConfig {
+            mode: RunnerMode::ProofModeCanonical,
+            ..Default::default()
+        };
+        assert_matches!(
+            CairoRunner::from_cairo_pie(&pie, config, false, false),
+            Err(CairoRunError::Runner(RunnerError::CairoPieProofMode))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_until_breakpoint() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+
+        let mut cairo_runner = cairo_runner!(program.clone());
+        cairo_runner.initialize(false).unwrap();
+        cairo_runner.run_for_steps(3, &mut hint_processor).unwrap();
+        let breakpoint = cairo_runner.vm.get_pc();
+
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        let mut session = cairo_runner
+            .start_debug_session(&mut hint_processor)
+            .unwrap();
+        assert_eq!(
+            cairo_runner
+                .run_until_breakpoint(
+                    end,
+                    &HashSet::from([breakpoint]),
+                    &mut hint_processor,
+                    &mut session
+                )
+                .unwrap(),
+            Some(breakpoint)
+        );
+        // The three `[ap] = ..; ap++` before `call fib`, whose pc is first reached by the third step
+        assert_eq!(cairo_runner.vm.current_step, 3);
+        assert_eq!(
+            cairo_runner
+                .run_until_breakpoint(end, &HashSet::new(), &mut hint_processor, &mut session)
+                .unwrap(),
+            None
+        );
+        assert_eq!(cairo_runner.vm.get_pc(), end);
+        assert_eq!(cairo_runner.vm.current_step, 80);
+        assert!(cairo_runner.get_ids_at_pc().is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_until_debug_event_watchpoint() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        let watchpoint = cairo_runner.vm.get_ap();
+        let mut session = cairo_runner
+            .start_debug_session(&mut hint_processor)
+            .unwrap();
+
+        assert_eq!(
+            cairo_runner
+                .run_until_debug_event(
+                    end,
+                    &HashSet::new(),
+                    &HashSet::from([watchpoint]),
+                    &mut hint_processor,
+                    &mut session
+                )
+                .unwrap(),
+            Some(DebugEvent::Watchpoint(watchpoint))
+        );
+        assert_eq!(cairo_runner.vm.current_step, 1);
+        assert_eq!(
+            cairo_runner.vm.get_maybe(&watchpoint),
+            Some(MaybeRelocatable::from(1))
+        );
+        // Written addresses don't pause the run again
+        assert_eq!(
+            cairo_runner
+                .run_until_debug_event(
+                    end,
+                    &HashSet::new(),
+                    &HashSet::from([watchpoint]),
+                    &mut hint_processor,
+                    &mut session
+                )
+                .unwrap(),
+            None
+        );
+        assert_eq!(cairo_runner.vm.get_pc(), end);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn single_step() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        let (pc, ap) = (cairo_runner.vm.get_pc(), cairo_runner.vm.get_ap());
+        let mut session = cairo_runner
+            .start_debug_session(&mut hint_processor)
+            .unwrap();
+
+        // [ap] = 1; ap++
+        let (instruction, operands) = cairo_runner
+            .single_step(&mut hint_processor, &mut session)
+            .unwrap();
+        assert_eq!(instruction.opcode, Opcode::AssertEq);
+        assert_eq!(operands.dst(), &MaybeRelocatable::from(1));
+        assert_eq!(operands.op1(), &MaybeRelocatable::from(1));
+        assert_eq!(operands.res(), Some(&MaybeRelocatable::from(1)));
+        assert_eq!(cairo_runner.vm.current_step, 1);
+        assert_eq!(cairo_runner.vm.get_pc(), (pc + 2).unwrap());
+        assert_eq!(cairo_runner.vm.get_ap(), (ap + 1).unwrap());
+        // The run is resumed with the same session
+        assert_eq!(
+            cairo_runner
+                .run_until_breakpoint(end, &HashSet::new(), &mut hint_processor, &mut session)
+                .unwrap(),
+            None
+        );
+        assert_eq!(cairo_runner.vm.current_step, 80);
+        assert_matches!(
+            cairo_runner.single_step(&mut hint_processor, &mut session),
+            Err(VirtualMachineError::EndOfProgram(1))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn initialize_vm_no_builtins() {
@@ -3099,6 +4156,43 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_relocation_table_after_relocate() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let end = cairo_runner.initialize(false).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        assert_matches!(cairo_runner.run_until_pc(end, &mut hint_processor), Ok(()));
+        assert_eq!(cairo_runner.vm.get_relocation_table(), None);
+        assert_eq!(
+            cairo_runner.vm.get_relocated_address((1, 3).into()),
+            Err(MemoryError::UnrelocatedMemory)
+        );
+
+        // Nothing is relocated if neither the trace nor the memory are requested
+        assert_matches!(cairo_runner.relocate(false), Ok(()));
+        assert_eq!(cairo_runner.vm.get_relocation_table(), None);
+
+        assert_matches!(cairo_runner.relocate(true), Ok(()));
+        let relocation_table = cairo_runner.vm.get_relocation_table().unwrap().to_vec();
+        let program_size = cairo_runner.vm.get_segment_used_size(0).unwrap();
+        assert_eq!(relocation_table[0], 1);
+        assert_eq!(relocation_table[1], 1 + program_size);
+        assert_eq!(
+            cairo_runner.vm.get_relocated_address((1, 3).into()),
+            Ok(relocation_table[1] + 3)
+        );
+        assert_eq!(
+            cairo_runner.vm.get_relocated_address((-1, 0).into()),
+            Err(MemoryError::TemporarySegmentInRelocation(-1))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn write_output_from_preset_memory() {
@@ -3172,6 +4266,10 @@ mod tests {
         let mut output_buffer = String::new();
         cairo_runner.vm.write_output(&mut output_buffer).unwrap();
         assert_eq!(&output_buffer, "1\n17\n");
+        assert_eq!(
+            cairo_runner.get_program_output(),
+            Ok(vec![Felt252::from(1), Felt252::from(17)])
+        );
     }
 
     #[test]
@@ -3855,6 +4953,143 @@ mod tests {
         assert_eq!(runner.get_execution_resources().unwrap().n_steps, 80);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_execution_resources_run_program_filtered_trace_cleared_filter() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let config = RunnerConfig {
+            layout: LayoutName::all_cairo,
+            vm_config: VmConfig::new()
+                .trace_enabled(true)
+                .trace_pc_filter(vec![(0, 0).into()..(0, 1).into()]),
+            ..Default::default()
+        };
+        let mut runner = CairoRunner::new_with_config(&program, config).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let end = runner.initialize(false).unwrap();
+        runner.run_until_pc(end, &mut hint_processor).unwrap();
+        runner.end_run(false, false, &mut hint_processor).unwrap();
+        // The steps recorded while the filter was set are still missing from the trace
+        runner.vm.clear_trace_pc_filter();
+        assert!(runner.vm.trace.as_ref().unwrap().len() < 80);
+        assert_eq!(runner.get_execution_resources().unwrap().n_steps, 80);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_cost_run_program() {
+        let program_data = include_bytes!("../../../../cairo_programs/fibonacci.json");
+        let cairo_run_config = CairoRunConfig {
+            entrypoint: "main",
+            trace_enabled: true,
+            relocate_mem: false,
+            layout: LayoutName::all_cairo,
+            proof_mode: false,
+            secure_run: Some(false),
+            ..Default::default()
+        };
+        let mut hint_executor = BuiltinHintProcessor::new_empty();
+        let runner = cairo_run(program_data, &cairo_run_config, &mut hint_executor).unwrap();
+        let cost_model = CostModel {
+            step_weight: 3,
+            ..Default::default()
+        };
+        let cost = runner.get_cost(&cost_model).unwrap();
+        // 80 steps, no memory holes nor builtins
+        assert_eq!(cost.steps, 240);
+        assert_eq!(cost.total, 240);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_with_checkpoints_and_export_windows() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let config = RunnerConfig {
+            layout: LayoutName::all_cairo,
+            vm_config: VmConfig::new()
+                .trace_enabled(true)
+                .checkpoint_interval(core::num::NonZeroUsize::new(20).unwrap()),
+            ..Default::default()
+        };
+        let mut runner = CairoRunner::new_with_config(&program, config).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let end = runner.initialize(false).unwrap();
+        assert_matches!(
+            runner.get_relocated_trace_window(0..20),
+            Err(TraceError::TraceNotRelocated)
+        );
+        runner.run_until_pc(end, &mut hint_processor).unwrap();
+        runner.relocate(true).unwrap();
+
+        let checkpoints = runner.vm.get_checkpoints();
+        assert_eq!(
+            checkpoints.iter().map(|c| c.step).collect::<Vec<_>>(),
+            vec![20, 40, 60, 80]
+        );
+
+        let relocation_table = runner.vm.get_relocation_table().unwrap();
+        let window = runner.get_relocated_trace_window(20..40).unwrap();
+        assert_eq!(window, &runner.relocated_trace.as_ref().unwrap()[20..40]);
+        // The registers at the checkpoint are those of the first entry of the next window
+        assert_eq!(
+            window[0].ap,
+            relocation_table[1] + checkpoints[0].registers.ap
+        );
+        assert_eq!(
+            window[0].pc,
+            relocation_table[0] + checkpoints[0].registers.pc.offset
+        );
+        assert_eq!(runner.get_relocated_trace_window(75..100).unwrap().len(), 5);
+
+        let memory_window = runner
+            .get_relocated_memory_window(&checkpoints[0], &checkpoints[1])
+            .unwrap();
+        assert!(!memory_window.is_empty());
+        for (addr, value) in memory_window {
+            assert_eq!(runner.relocated_memory[addr], Some(value));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_execution_commitment() {
+        let program_data = include_bytes!("../../../../cairo_programs/fibonacci.json");
+        let cairo_run_config = CairoRunConfig {
+            trace_enabled: true,
+            relocate_mem: true,
+            ..Default::default()
+        };
+        let run = || {
+            cairo_run(
+                program_data,
+                &cairo_run_config,
+                &mut BuiltinHintProcessor::new_empty(),
+            )
+            .unwrap()
+        };
+        let mut runner = run();
+        let commitment = runner.get_execution_commitment().unwrap();
+        assert_eq!(run().get_execution_commitment(), Ok(commitment));
+
+        let last = runner.relocated_memory.len() - 1;
+        runner.relocated_memory[last] = Some(Felt252::from(3));
+        assert_ne!(runner.get_execution_commitment(), Ok(commitment));
+
+        runner.relocated_trace = None;
+        assert_eq!(
+            runner.get_execution_commitment(),
+            Err(TraceError::TraceNotRelocated)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_execution_resources_run_program_no_trace() {
@@ -3969,6 +5204,49 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn fill_builtin_holes_no_finalize_segments() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        cairo_runner.run_ended = true;
+        assert_eq!(
+            cairo_runner.fill_builtin_holes(),
+            Err(RunnerError::FillBuiltinHolesNoFinalizeSegments)
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn fill_builtin_holes_proof_mode() {
+        let cairo_run_config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            proof_mode: true,
+            fill_builtin_holes: true,
+            ..Default::default()
+        };
+        let cairo_runner = cairo_run(
+            include_bytes!("../../../../cairo_programs/proof_programs/bitwise_output.json"),
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+        assert_matches!(verify_secure_runner(&cairo_runner, true, None), Ok(()));
+
+        let bitwise = cairo_runner
+            .vm
+            .get_builtin_by_name(BuiltinName::bitwise)
+            .unwrap();
+        let (used_cells, allocated_size) = bitwise
+            .get_used_cells_and_allocated_size(&cairo_runner.vm)
+            .unwrap();
+        assert!(used_cells < allocated_size);
+        for offset in 0..allocated_size {
+            let address = Relocatable::from((bitwise.base() as isize, offset));
+            assert!(cairo_runner.vm.segments.memory.get(&address).is_some());
+        }
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn finalize_segments_run_ended_emptyproof_mode() {
@@ -4388,7 +5666,18 @@ mod tests {
             cairo_runner.initialize_builtins(false),
             Err(RunnerError::NoBuiltinForInstance(Box::new((
                 HashSet::from([BuiltinName::output]),
-                LayoutName::plain
+                LayoutName::plain,
+                vec![
+                    LayoutName::small,
+                    LayoutName::dex,
+                    LayoutName::recursive,
+                    LayoutName::starknet,
+                    LayoutName::starknet_with_keccak,
+                    LayoutName::recursive_large_output,
+                    LayoutName::recursive_with_poseidon,
+                    LayoutName::all_solidity,
+                    LayoutName::all_cairo,
+                ]
             ))))
         );
     }
@@ -4402,7 +5691,18 @@ mod tests {
             cairo_runner.initialize_builtins(false),
             Err(RunnerError::NoBuiltinForInstance(Box::new((
                 HashSet::from([BuiltinName::output, BuiltinName::pedersen]),
-                LayoutName::plain
+                LayoutName::plain,
+                vec![
+                    LayoutName::small,
+                    LayoutName::dex,
+                    LayoutName::recursive,
+                    LayoutName::starknet,
+                    LayoutName::starknet_with_keccak,
+                    LayoutName::recursive_large_output,
+                    LayoutName::recursive_with_poseidon,
+                    LayoutName::all_solidity,
+                    LayoutName::all_cairo,
+                ]
             ))))
         );
     }
@@ -4417,6 +5717,15 @@ mod tests {
             Err(RunnerError::NoBuiltinForInstance(Box::new((
                 HashSet::from([BuiltinName::bitwise]),
                 LayoutName::small,
+                vec![
+                    LayoutName::recursive,
+                    LayoutName::starknet,
+                    LayoutName::starknet_with_keccak,
+                    LayoutName::recursive_large_output,
+                    LayoutName::recursive_with_poseidon,
+                    LayoutName::all_solidity,
+                    LayoutName::all_cairo,
+                ]
             ))))
         );
     }
@@ -4839,6 +6148,121 @@ mod tests {
         assert!(cairo_runner.get_memory_holes().unwrap().is_zero());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_function_by_name_resolves_implicit_builtins() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/example_program.json"),
+            None,
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_function_by_name("main", &[], true, None, &mut hint_processor),
+            Ok(return_values) if return_values.is_empty()
+        );
+        // main uses the range_check builtin six times
+        assert_eq!(cairo_runner.vm.builtin_runners[0].stop_ptr(), Some(6));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_function_by_name_twice_on_the_same_runner() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/example_program.json"),
+            None,
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_function_by_name("main", &[], true, None, &mut hint_processor),
+            Ok(return_values) if return_values.is_empty()
+        );
+        // The second run starts using the range_check builtin where the first one stopped
+        assert_matches!(
+            cairo_runner.run_function_by_name("main", &[], true, None, &mut hint_processor),
+            Ok(return_values) if return_values.is_empty()
+        );
+        assert_eq!(cairo_runner.vm.builtin_runners[0].stop_ptr(), Some(12));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_function_by_name_returns_values() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/example_program.json"),
+            None,
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_function_by_name(
+                "__main__.fib",
+                &[
+                    &mayberelocatable!(1).into(),
+                    &mayberelocatable!(1).into(),
+                    &mayberelocatable!(10).into()
+                ],
+                true,
+                None,
+                &mut hint_processor,
+            ),
+            Ok(return_values) if return_values == vec![mayberelocatable!(144)]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_function_by_name_args_count_mismatch() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/example_program.json"),
+            None,
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_function_by_name(
+                "fib",
+                &[&mayberelocatable!(1).into()],
+                true,
+                None,
+                &mut hint_processor,
+            ),
+            Err(CairoRunError::Runner(RunnerError::FunctionArgsCountMismatch(bx)))
+                if *bx == ("__main__.fib".to_string(), 3, 1)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_function_by_name_unknown_function() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/example_program.json"),
+            None,
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program, LayoutName::all_cairo, false, true);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner().unwrap();
+
+        assert_matches!(
+            cairo_runner.run_function_by_name("missing", &[], true, None, &mut hint_processor),
+            Err(CairoRunError::Program(ProgramError::EntrypointNotFound(name))) if name == "missing"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn cairo_arg_from_single() {
@@ -5179,6 +6603,81 @@ mod tests {
         assert_eq!(hint_processor.run_resources(), &RunResources::new(0));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_run_resources_consume_steps() {
+        let mut run_resources = RunResources::new(10);
+        run_resources.consume_steps(4);
+        assert_eq!(run_resources, RunResources::new(6));
+        run_resources.consume_steps(20);
+        assert!(run_resources.consumed());
+
+        let mut run_resources = RunResources::default();
+        run_resources.consume_steps(20);
+        assert!(!run_resources.consumed());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_run_resources_shared_with_hints() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut runner = cairo_runner!(program);
+        let end = runner.initialize(false).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new(HashMap::new(), RunResources::new(80));
+        // e.g. iterations done by the hints of a previous call within the same transaction
+        hint_processor.consume_steps(1);
+        assert_matches!(
+            runner.run_until_pc(end, &mut hint_processor),
+            Err(VirtualMachineError::UnfinishedExecution)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_run_resources_charged_by_hints() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/squash_dict.json"),
+            Some("main"),
+        )
+        .unwrap();
+        let mut runner = cairo_runner!(program);
+        let end = runner.initialize(false).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new(
+            HashMap::new(),
+            RunResources::new(10_000).with_hint_iterations(),
+        );
+        runner.run_until_pc(end, &mut hint_processor).unwrap();
+        let n_steps = runner.vm.current_step;
+        // The squash_dict hint loops over the 6 accesses of the dictionary
+        assert_eq!(
+            hint_processor.run_resources().get_n_steps(),
+            Some(10_000 - n_steps - 6)
+        );
+
+        // Without charging the hints, the VM steps are enough
+        let mut runner = cairo_runner!(program);
+        let end = runner.initialize(false).unwrap();
+        let mut hint_processor =
+            BuiltinHintProcessor::new(HashMap::new(), RunResources::new(n_steps));
+        assert_matches!(runner.run_until_pc(end, &mut hint_processor), Ok(()));
+
+        // Resources enough for the VM steps alone are used up by the hints
+        let mut runner = cairo_runner!(program);
+        let end = runner.initialize(false).unwrap();
+        let mut hint_processor = BuiltinHintProcessor::new(
+            HashMap::new(),
+            RunResources::new(n_steps).with_hint_iterations(),
+        );
+        assert_matches!(
+            runner.run_until_pc(end, &mut hint_processor),
+            Err(VirtualMachineError::UnfinishedExecution)
+        );
+    }
+
     #[test]
     fn get_cairo_pie_no_program_base() {
         let runner = cairo_runner!(Default::default());
@@ -0,0 +1,111 @@
+use crate::stdlib::collections::HashMap;
+use crate::types::builtin_name::BuiltinName;
+
+use super::cairo_runner::ExecutionResources;
+
+/// The weight of each resource used by a run, used to turn [ExecutionResources] into a single
+/// cost figure (e.g. for fee estimation).
+///
+/// Builtins without a weight don't add to the cost.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CostModel {
+    pub step_weight: u64,
+    pub memory_hole_weight: u64,
+    pub builtin_weights: HashMap<BuiltinName, u64>,
+}
+
+/// The cost of a run according to a [CostModel], with its breakdown per resource.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cost {
+    pub total: u64,
+    pub steps: u64,
+    pub memory_holes: u64,
+    pub builtins: HashMap<BuiltinName, u64>,
+}
+
+impl CostModel {
+    /// Computes the cost of the given resources.
+    /// Saturates at `u64::MAX` instead of overflowing.
+    pub fn cost(&self, resources: &ExecutionResources) -> Cost {
+        let weigh = |amount: usize, weight: u64| (amount as u64).saturating_mul(weight);
+        let steps = weigh(resources.n_steps, self.step_weight);
+        let memory_holes = weigh(resources.n_memory_holes, self.memory_hole_weight);
+        let builtins: HashMap<BuiltinName, u64> = resources
+            .builtin_instance_counter
+            .iter()
+            .filter_map(|(name, instances)| {
+                let weight = self.builtin_weights.get(name)?;
+                Some((*name, weigh(*instances, *weight)))
+            })
+            .collect();
+        let total = builtins
+            .values()
+            .fold(steps.saturating_add(memory_holes), |total, cost| {
+                total.saturating_add(*cost)
+            });
+        Cost {
+            total,
+            steps,
+            memory_holes,
+            builtins,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cost_with_breakdown() {
+        let model = CostModel {
+            step_weight: 2,
+            memory_hole_weight: 1,
+            builtin_weights: HashMap::from([
+                (BuiltinName::pedersen, 100),
+                (BuiltinName::range_check, 10),
+            ]),
+        };
+        let resources = ExecutionResources {
+            n_steps: 50,
+            n_memory_holes: 7,
+            builtin_instance_counter: HashMap::from([
+                (BuiltinName::pedersen, 3),
+                (BuiltinName::range_check, 4),
+                (BuiltinName::output, 5),
+            ]),
+        };
+        assert_eq!(
+            model.cost(&resources),
+            Cost {
+                total: 100 + 7 + 300 + 40,
+                steps: 100,
+                memory_holes: 7,
+                builtins: HashMap::from([
+                    (BuiltinName::pedersen, 300),
+                    (BuiltinName::range_check, 40)
+                ]),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cost_saturates() {
+        let model = CostModel {
+            step_weight: u64::MAX,
+            memory_hole_weight: 1,
+            ..Default::default()
+        };
+        let resources = ExecutionResources {
+            n_steps: 2,
+            n_memory_holes: 1,
+            builtin_instance_counter: HashMap::new(),
+        };
+        assert_eq!(model.cost(&resources).total, u64::MAX);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod builtin_runner;
 pub mod cairo_pie;
 pub mod cairo_runner;
+pub mod cost_model;
@@ -29,7 +29,15 @@ pub fn verify_secure_runner(
         false => Vec::new(),
     };
     // Check builtin segment out of bounds.
-    for (index, stop_ptr) in builtins_segment_info {
+    for (builtin, (index, stop_ptr)) in runner.vm.builtin_runners.iter().zip(builtins_segment_info)
+    {
+        // Filling the holes extends the segment up to the size allocated for the builtin
+        let stop_ptr = if runner.builtin_holes_filled {
+            let (_, allocated_size) = builtin.get_used_cells_and_allocated_size(&runner.vm)?;
+            stop_ptr.max(allocated_size)
+        } else {
+            stop_ptr
+        };
         let current_size = runner
             .vm
             .segments
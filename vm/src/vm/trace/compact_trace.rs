@@ -0,0 +1,196 @@
+use crate::{stdlib::prelude::*, types::relocatable::Relocatable};
+
+use super::trace_entry::TraceEntry;
+
+/// A compact representation of the execution trace.
+///
+/// Each entry is stored as the difference of its registers (pc segment index, pc offset, ap & fp)
+/// with respect to the previous entry, encoded as zigzag varints. As consecutive entries tend
+/// to be close to each other, most registers take a single byte instead of a full `usize`.
+/// The full entries can be recovered via [CompactTrace::iter] or [CompactTrace::to_trace_entries].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompactTrace {
+    data: Vec<u8>,
+    len: usize,
+    last: Option<TraceEntry>,
+}
+
+impl CompactTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an entry to the trace
+    pub fn push(&mut self, entry: &TraceEntry) {
+        let (pc_segment_index, pc_offset, ap, fp) = match &self.last {
+            Some(last) => (
+                last.pc.segment_index as i64,
+                last.pc.offset as i64,
+                last.ap as i64,
+                last.fp as i64,
+            ),
+            None => (0, 0, 0, 0),
+        };
+        write_varint(
+            &mut self.data,
+            (entry.pc.segment_index as i64).wrapping_sub(pc_segment_index),
+        );
+        write_varint(
+            &mut self.data,
+            (entry.pc.offset as i64).wrapping_sub(pc_offset),
+        );
+        write_varint(&mut self.data, (entry.ap as i64).wrapping_sub(ap));
+        write_varint(&mut self.data, (entry.fp as i64).wrapping_sub(fp));
+        self.last = Some(entry.clone());
+        self.len += 1;
+    }
+
+    /// Returns the amount of entries in the trace
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the size in bytes of the encoded trace
+    pub fn encoded_size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns an iterator over the decoded entries
+    pub fn iter(&self) -> CompactTraceIter<'_> {
+        CompactTraceIter {
+            data: &self.data,
+            position: 0,
+            last: (0, 0, 0, 0),
+        }
+    }
+
+    /// Decodes the whole trace
+    pub fn to_trace_entries(&self) -> Vec<TraceEntry> {
+        let mut entries = Vec::with_capacity(self.len);
+        entries.extend(self.iter());
+        entries
+    }
+}
+
+impl From<&[TraceEntry]> for CompactTrace {
+    fn from(entries: &[TraceEntry]) -> Self {
+        let mut compact_trace = CompactTrace::new();
+        for entry in entries {
+            compact_trace.push(entry);
+        }
+        compact_trace
+    }
+}
+
+pub struct CompactTraceIter<'a> {
+    data: &'a [u8],
+    position: usize,
+    // (pc segment index, pc offset, ap, fp) of the last decoded entry
+    last: (i64, i64, i64, i64),
+}
+
+impl Iterator for CompactTraceIter<'_> {
+    type Item = TraceEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.data.len() {
+            return None;
+        }
+        let pc_segment_index = self
+            .last
+            .0
+            .wrapping_add(read_varint(self.data, &mut self.position));
+        let pc_offset = self
+            .last
+            .1
+            .wrapping_add(read_varint(self.data, &mut self.position));
+        let ap = self
+            .last
+            .2
+            .wrapping_add(read_varint(self.data, &mut self.position));
+        let fp = self
+            .last
+            .3
+            .wrapping_add(read_varint(self.data, &mut self.position));
+        self.last = (pc_segment_index, pc_offset, ap, fp);
+        Some(TraceEntry {
+            pc: Relocatable::from((pc_segment_index as isize, pc_offset as usize)),
+            ap: ap as usize,
+            fp: fp as usize,
+        })
+    }
+}
+
+fn write_varint(data: &mut Vec<u8>, value: i64) {
+    // Zigzag encoding so that small negative deltas also take few bytes
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+    while value >= 0x80 {
+        data.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    data.push(value as u8);
+}
+
+// The data is only ever written by `write_varint`, so it is always well-formed
+fn read_varint(data: &[u8], position: &mut usize) -> i64 {
+    let mut value = 0_u64;
+    let mut shift = 0;
+    while let Some(byte) = data.get(*position) {
+        *position += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn entry(pc: (isize, usize), ap: usize, fp: usize) -> TraceEntry {
+        TraceEntry {
+            pc: pc.into(),
+            ap,
+            fp,
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compact_trace_roundtrip() {
+        let entries = vec![
+            entry((0, 3), 2, 2),
+            entry((0, 5), 3, 2),
+            entry((0, 0), 5, 5),
+            entry((0, 2), 6, 5),
+            entry((4, 7), 6, 2),
+            entry((0, usize::MAX >> 1), usize::MAX >> 1, 0),
+        ];
+        let compact_trace = CompactTrace::from(entries.as_slice());
+        assert_eq!(compact_trace.len(), entries.len());
+        assert_eq!(compact_trace.to_trace_entries(), entries);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compact_trace_small_deltas_take_one_byte_per_register() {
+        let mut compact_trace = CompactTrace::new();
+        assert!(compact_trace.is_empty());
+        for i in 0..100 {
+            compact_trace.push(&entry((0, i), 100 + i, 100));
+        }
+        // The first entry takes 6 bytes, the rest of them 4 bytes each
+        assert_eq!(compact_trace.encoded_size(), 6 + 99 * 4);
+        assert_eq!(compact_trace.iter().nth(42), Some(entry((0, 42), 142, 100)));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod compact_trace;
+
 pub mod trace_entry {
     use serde::{Deserialize, Serialize};
 
@@ -1,36 +1,41 @@
 use crate::math_utils::signed_felt;
-use crate::stdlib::{any::Any, borrow::Cow, collections::HashMap, prelude::*};
+use crate::stdlib::{
+    any::Any, borrow::Cow, collections::HashMap, ops::Range, prelude::*, sync::Arc,
+};
 use crate::types::builtin_name::BuiltinName;
 #[cfg(feature = "extensive_hints")]
 use crate::types::program::HintRange;
+use crate::utils::as_printable_short_string;
 use crate::{
     hint_processor::hint_processor_definition::HintProcessor,
     types::{
+        cairo_arg::{CairoArg, CairoReturnType},
         errors::math_errors::MathError,
         exec_scope::ExecutionScopes,
         instruction::{
             is_call_instruction, ApUpdate, FpUpdate, Instruction, Opcode, PcUpdate, Res,
         },
-        relocatable::{MaybeRelocatable, Relocatable},
+        relocatable::{relocate_address, MaybeRelocatable, Relocatable},
     },
     vm::{
-        context::run_context::RunContext,
-        decoding::decoder::decode_instruction,
+        checkpoint::Checkpoint,
+        context::run_context::{RegisterState, RunContext},
+        decoding::decoder::{CairoInstructionDecoder, InstructionDecoder},
         errors::{
             exec_scope_errors::ExecScopeError, memory_errors::MemoryError,
             vm_errors::VirtualMachineError,
         },
         runners::builtin_runner::{
-            BuiltinRunner, OutputBuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner,
+            BuiltinRunner, BuiltinRunnerVariant, OutputBuiltinRunner, RangeCheckBuiltinRunner,
+            SignatureBuiltinRunner,
         },
-        trace::trace_entry::TraceEntry,
-        vm_memory::memory_segments::MemorySegmentManager,
+        trace::{compact_trace::CompactTrace, trace_entry::TraceEntry},
+        vm_memory::{memory::MemoryCell, memory_segments::MemorySegmentManager},
     },
 };
 
 use crate::Felt252;
 use core::cmp::Ordering;
-#[cfg(feature = "extensive_hints")]
 use core::num::NonZeroUsize;
 use num_traits::{ToPrimitive, Zero};
 
@@ -48,6 +53,24 @@ pub struct Operands {
     op1: MaybeRelocatable,
 }
 
+impl Operands {
+    pub fn dst(&self) -> &MaybeRelocatable {
+        &self.dst
+    }
+
+    pub fn res(&self) -> Option<&MaybeRelocatable> {
+        self.res.as_ref()
+    }
+
+    pub fn op0(&self) -> &MaybeRelocatable {
+        &self.op0
+    }
+
+    pub fn op1(&self) -> &MaybeRelocatable {
+        &self.op1
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct OperandsAddresses {
     dst_addr: Relocatable,
@@ -55,6 +78,12 @@ pub struct OperandsAddresses {
     op1_addr: Relocatable,
 }
 
+/// A consumer of [TraceEntry]s, called once per executed instruction.
+///
+/// Can be used to stream the trace (to disk, a compressor, etc) instead of
+/// accumulating it in memory. See [VirtualMachine::set_trace_sink].
+pub type TraceSink = Box<dyn FnMut(TraceEntry) + Send>;
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct DeducedOperands(u8);
 
@@ -80,47 +109,137 @@ impl DeducedOperands {
     }
 }
 
+/// Options used to build a [VirtualMachine], see [VirtualMachine::with_config].
+///
+/// New options are added as builder methods, so adding one is not a breaking change.
+#[derive(Clone, Default)]
+pub struct VmConfig {
+    trace_enabled: bool,
+    compact_trace: bool,
+    trace_pc_filter: Option<Vec<Range<Relocatable>>>,
+    checkpoint_interval: Option<NonZeroUsize>,
+    instruction_decoder: Option<Arc<dyn InstructionDecoder>>,
+    disable_auto_deductions: bool,
+    #[cfg(feature = "hooks")]
+    hooks: crate::vm::hooks::Hooks,
+}
+
+impl VmConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables the execution trace.
+    pub fn trace_enabled(mut self, trace_enabled: bool) -> Self {
+        self.trace_enabled = trace_enabled;
+        self
+    }
+
+    /// Stores the trace in compact mode, see [VirtualMachine::enable_compact_trace].
+    /// Has no effect unless the trace is enabled.
+    pub fn compact_trace(mut self, compact_trace: bool) -> Self {
+        self.compact_trace = compact_trace;
+        self
+    }
+
+    /// See [VirtualMachine::set_trace_pc_filter].
+    pub fn trace_pc_filter(mut self, ranges: Vec<Range<Relocatable>>) -> Self {
+        self.trace_pc_filter = Some(ranges);
+        self
+    }
+
+    /// Takes a [Checkpoint] every `interval` steps, see [VirtualMachine::get_checkpoints].
+    pub fn checkpoint_interval(mut self, interval: NonZeroUsize) -> Self {
+        self.checkpoint_interval = Some(interval);
+        self
+    }
+
+    /// Decodes instructions with the given decoder instead of [CairoInstructionDecoder].
+    pub fn instruction_decoder(mut self, decoder: Arc<dyn InstructionDecoder>) -> Self {
+        self.instruction_decoder = Some(decoder);
+        self
+    }
+
+    /// Stops deducing the output cells of the builtins while computing the operands, which then
+    /// have to be present in memory (e.g. when replaying an externally produced memory). Their
+    /// consistency can still be checked with [VirtualMachine::verify_auto_deductions].
+    pub fn disable_auto_deductions(mut self, disable_auto_deductions: bool) -> Self {
+        self.disable_auto_deductions = disable_auto_deductions;
+        self
+    }
+
+    /// Sets the [Hooks](crate::vm::hooks::Hooks) executed during the run.
+    #[cfg(feature = "hooks")]
+    pub fn hooks(mut self, hooks: crate::vm::hooks::Hooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+}
+
 pub struct VirtualMachine {
     pub(crate) run_context: RunContext,
     pub builtin_runners: Vec<BuiltinRunner>,
     pub segments: MemorySegmentManager,
     pub(crate) trace: Option<Vec<TraceEntry>>,
+    pub(crate) trace_sink: Option<TraceSink>,
+    pub(crate) compact_trace: Option<CompactTrace>,
+    pub(crate) trace_pc_filter: Option<Vec<Range<Relocatable>>>,
+    // Set once the trace pc filter drops an entry, so that the trace isn't exported as complete
+    pub(crate) trace_filtered: bool,
     pub(crate) current_step: usize,
     pub(crate) rc_limits: Option<(isize, isize)>,
+    pub(crate) checkpoint_interval: Option<NonZeroUsize>,
+    pub(crate) checkpoints: Vec<Checkpoint>,
     skip_instruction_execution: bool,
     run_finished: bool,
     instruction_cache: Vec<Option<Instruction>>,
-    #[cfg(feature = "test_utils")]
+    instruction_decoder: Arc<dyn InstructionDecoder>,
+    disable_auto_deductions: bool,
+    #[cfg(feature = "hooks")]
     pub(crate) hooks: crate::vm::hooks::Hooks,
     pub(crate) relocation_table: Option<Vec<usize>>,
 }
 
 impl VirtualMachine {
     pub fn new(trace_enabled: bool) -> VirtualMachine {
+        Self::with_config(VmConfig::new().trace_enabled(trace_enabled))
+    }
+
+    pub fn with_config(config: VmConfig) -> VirtualMachine {
         let run_context = RunContext {
             pc: Relocatable::from((0, 0)),
             ap: 0,
             fp: 0,
         };
 
-        let trace = if trace_enabled {
-            Some(Vec::<TraceEntry>::new())
-        } else {
-            None
+        let (trace, compact_trace) = match (config.trace_enabled, config.compact_trace) {
+            (false, _) => (None, None),
+            (true, false) => (Some(Vec::<TraceEntry>::new()), None),
+            (true, true) => (None, Some(CompactTrace::default())),
         };
 
         VirtualMachine {
             run_context,
             builtin_runners: Vec::new(),
             trace,
+            trace_sink: None,
+            compact_trace,
+            trace_pc_filter: config.trace_pc_filter,
+            trace_filtered: false,
+            checkpoint_interval: config.checkpoint_interval,
+            checkpoints: Vec::new(),
             current_step: 0,
             skip_instruction_execution: false,
             segments: MemorySegmentManager::new(),
             rc_limits: None,
             run_finished: false,
             instruction_cache: Vec::new(),
-            #[cfg(feature = "test_utils")]
-            hooks: Default::default(),
+            instruction_decoder: config
+                .instruction_decoder
+                .unwrap_or_else(|| Arc::new(CairoInstructionDecoder)),
+            disable_auto_deductions: config.disable_auto_deductions,
+            #[cfg(feature = "hooks")]
+            hooks: config.hooks,
             relocation_table: None,
         }
     }
@@ -293,6 +412,15 @@ impl VirtualMachine {
     ) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
         for builtin in self.builtin_runners.iter() {
             if builtin.base() as isize == address.segment_index {
+                if self.disable_auto_deductions {
+                    if builtin.is_output_cell(address.offset) {
+                        return Err(VirtualMachineError::MissingBuiltinOutput(Box::new((
+                            builtin.name(),
+                            address,
+                        ))));
+                    }
+                    return Ok(None);
+                }
                 match builtin.deduce_memory_cell(address, &self.segments.memory) {
                     Ok(maybe_reloc) => return Ok(maybe_reloc),
                     Err(error) => return Err(VirtualMachineError::RunnerError(error)),
@@ -407,12 +535,29 @@ impl VirtualMachine {
         self.insert_deduced_operands(deduced_operands, &operands, &operands_addresses)?;
         self.opcode_assertions(instruction, &operands)?;
 
-        if let Some(ref mut trace) = &mut self.trace {
-            trace.push(TraceEntry {
-                pc: self.run_context.pc,
-                ap: self.run_context.ap,
-                fp: self.run_context.fp,
-            });
+        if self.trace.is_some() || self.trace_sink.is_some() || self.compact_trace.is_some() {
+            if self.trace_pc_filter.as_ref().map_or(true, |ranges| {
+                ranges
+                    .iter()
+                    .any(|range| range.contains(&self.run_context.pc))
+            }) {
+                let entry = TraceEntry {
+                    pc: self.run_context.pc,
+                    ap: self.run_context.ap,
+                    fp: self.run_context.fp,
+                };
+                if let Some(ref mut trace_sink) = &mut self.trace_sink {
+                    trace_sink(entry.clone());
+                }
+                if let Some(ref mut compact_trace) = &mut self.compact_trace {
+                    compact_trace.push(&entry);
+                }
+                if let Some(ref mut trace) = &mut self.trace {
+                    trace.push(entry);
+                }
+            } else {
+                self.trace_filtered = true;
+            }
         }
 
         // Update range check limits
@@ -440,18 +585,18 @@ impl VirtualMachine {
 
         self.update_registers(instruction, operands)?;
         self.current_step += 1;
+        if let Some(interval) = self.checkpoint_interval {
+            if self.current_step % interval == 0 {
+                self.checkpoints.push(self.checkpoint());
+            }
+        }
 
         Ok(())
     }
 
-    fn decode_current_instruction(&self) -> Result<Instruction, VirtualMachineError> {
-        let instruction = self
-            .segments
-            .memory
-            .get_integer(self.run_context.pc)?
-            .to_u64()
-            .ok_or(VirtualMachineError::InvalidInstructionEncoding)?;
-        decode_instruction(instruction)
+    pub(crate) fn decode_current_instruction(&self) -> Result<Instruction, VirtualMachineError> {
+        let instruction = self.segments.memory.get_integer(self.run_context.pc)?;
+        self.instruction_decoder.decode(&instruction)
     }
 
     #[cfg(not(feature = "extensive_hints"))]
@@ -524,7 +669,11 @@ impl VirtualMachine {
             let instruction = instruction.as_ref().unwrap();
 
             if !self.skip_instruction_execution {
+                #[cfg(feature = "hooks")]
+                self.execute_pre_step(instruction)?;
                 self.run_instruction(instruction)?;
+                #[cfg(feature = "hooks")]
+                self.execute_post_step(instruction)?;
             } else {
                 self.run_context.pc += instruction.size();
                 self.skip_instruction_execution = false;
@@ -535,7 +684,11 @@ impl VirtualMachine {
             let instruction = self.decode_current_instruction()?;
 
             if !self.skip_instruction_execution {
+                #[cfg(feature = "hooks")]
+                self.execute_pre_step(&instruction)?;
                 self.run_instruction(&instruction)?;
+                #[cfg(feature = "hooks")]
+                self.execute_post_step(&instruction)?;
             } else {
                 self.run_context.pc += instruction.size();
                 self.skip_instruction_execution = false;
@@ -562,10 +715,10 @@ impl VirtualMachine {
             constants,
         )?;
 
-        #[cfg(feature = "test_utils")]
+        #[cfg(feature = "hooks")]
         self.execute_pre_step_instruction(hint_processor, exec_scopes, hint_datas, constants)?;
         self.step_instruction()?;
-        #[cfg(feature = "test_utils")]
+        #[cfg(feature = "hooks")]
         self.execute_post_step_instruction(hint_processor, exec_scopes, hint_datas, constants)?;
 
         Ok(())
@@ -830,6 +983,23 @@ impl VirtualMachine {
         self.run_context.get_pc()
     }
 
+    /// Returns the current values of the pc, ap and fp registers.
+    pub fn get_registers(&self) -> RegisterState {
+        RegisterState {
+            pc: self.run_context.pc,
+            ap: self.run_context.ap,
+            fp: self.run_context.fp,
+        }
+    }
+
+    /// Sets the pc, ap and fp registers at once, e.g. to restore a snapshot taken with
+    /// [get_registers](Self::get_registers).
+    pub fn set_registers(&mut self, registers: RegisterState) {
+        self.run_context.pc = registers.pc;
+        self.run_context.ap = registers.ap;
+        self.run_context.fp = registers.fp;
+    }
+
     ///Gets the integer value corresponding to the Relocatable address
     pub fn get_integer(&self, key: Relocatable) -> Result<Cow<Felt252>, MemoryError> {
         self.segments.memory.get_integer(key)
@@ -903,6 +1073,76 @@ impl VirtualMachine {
         self.segments.memory.get_continuous_range(addr, n_ret)
     }
 
+    /// Gets the return values described by `return_types` from memory, following the pointers
+    /// of arrays and spans to fetch their elements
+    pub fn get_typed_return_values(
+        &self,
+        return_types: &[CairoReturnType],
+    ) -> Result<Vec<CairoArg>, MemoryError> {
+        let n_ret = return_types.iter().map(CairoReturnType::size).sum();
+        let mut addr = (self.run_context.get_ap() - n_ret)
+            .map_err(|_| MemoryError::FailedToGetReturnValues(Box::new((n_ret, self.get_ap()))))?;
+        return_types
+            .iter()
+            .map(|return_type| self.get_typed_value(return_type, &mut addr))
+            .collect()
+    }
+
+    /// Reads a value of type `return_type` at `addr`, advancing `addr` past it
+    fn get_typed_value(
+        &self,
+        return_type: &CairoReturnType,
+        addr: &mut Relocatable,
+    ) -> Result<CairoArg, MemoryError> {
+        let memory = &self.segments.memory;
+        let value = match return_type {
+            CairoReturnType::Single => CairoArg::Single(
+                memory
+                    .get(addr)
+                    .ok_or_else(|| MemoryError::UnknownMemoryCell(Box::new(*addr)))?
+                    .into_owned(),
+            ),
+            CairoReturnType::Array => {
+                let len = memory.get_usize(*addr)?;
+                let ptr = memory.get_relocatable((*addr + 1)?)?;
+                CairoArg::Array(self.get_typed_array(ptr, len)?)
+            }
+            CairoReturnType::Span => {
+                let start = memory.get_relocatable(*addr)?;
+                let end = memory.get_relocatable((*addr + 1)?)?;
+                CairoArg::Array(self.get_typed_array(start, (end - start)?)?)
+            }
+            CairoReturnType::Composed(members) => {
+                return members
+                    .iter()
+                    .map(|member| self.get_typed_value(member, addr))
+                    .collect::<Result<_, _>>()
+                    .map(CairoArg::Composed)
+            }
+        };
+        *addr = (*addr + return_type.size())?;
+        Ok(value)
+    }
+
+    /// Gets the `len` elements of the array at `ptr`.
+    /// As `len` is read from memory, it is checked against the size of the segment before
+    /// allocating the array
+    fn get_typed_array(
+        &self,
+        ptr: Relocatable,
+        len: usize,
+    ) -> Result<Vec<MaybeRelocatable>, MemoryError> {
+        let memory = &self.segments.memory;
+        let segment_size = memory.segment_len(ptr.segment_index).unwrap_or_default();
+        if ptr.offset.saturating_add(len) > segment_size {
+            let missing = Relocatable::from((ptr.segment_index, ptr.offset.max(segment_size)));
+            return Err(MemoryError::GetRangeMemoryGap(Box::new((
+                ptr, len, missing,
+            ))));
+        }
+        memory.get_continuous_range(ptr, len)
+    }
+
     ///Gets n elements from memory starting from addr (n being size)
     pub fn get_range(&self, addr: Relocatable, size: usize) -> Vec<Option<Cow<MaybeRelocatable>>> {
         self.segments.memory.get_range(addr, size)
@@ -917,6 +1157,34 @@ impl VirtualMachine {
         self.segments.memory.get_continuous_range(addr, size)
     }
 
+    /// Iterates over the values stored in the segment `segment_index`, skipping empty cells
+    pub fn iter_segment(
+        &self,
+        segment_index: isize,
+    ) -> impl Iterator<Item = (Relocatable, MaybeRelocatable)> + '_ {
+        self.segments.memory.iter_segment(segment_index)
+    }
+
+    /// Iterates over the values stored in the real (non-temporary) segments, in address order
+    pub fn iter_memory(&self) -> impl Iterator<Item = (Relocatable, MaybeRelocatable)> + '_ {
+        self.segments.memory.iter()
+    }
+
+    /// Returns the unaccessed addresses of the segment `segment_index`, within its size.
+    /// Requires the segment sizes to be computed, see [MemorySegmentManager::compute_effective_sizes].
+    pub fn get_memory_holes_in_segment(
+        &self,
+        segment_index: usize,
+    ) -> Result<Vec<Relocatable>, MemoryError> {
+        let segment_size = self
+            .get_segment_size(segment_index)
+            .ok_or(MemoryError::MissingSegmentUsedSizes)?;
+        Ok(self
+            .segments
+            .memory
+            .get_unaccessed_addresses(segment_index, segment_size))
+    }
+
     ///Gets n integer values from memory starting from addr (n being size),
     pub fn get_integer_range(
         &self,
@@ -926,39 +1194,120 @@ impl VirtualMachine {
         self.segments.memory.get_integer_range(addr, size)
     }
 
+    ///Iterates over n integer values from memory starting from addr (n being size), see
+    ///[Memory::get_felt_range](crate::vm::vm_memory::memory::Memory::get_felt_range)
+    pub fn get_felt_range(
+        &self,
+        addr: Relocatable,
+        size: usize,
+    ) -> Result<impl ExactSizeIterator<Item = Felt252> + '_, MemoryError> {
+        self.segments.memory.get_felt_range(addr, size)
+    }
+
+    /// Returns the first builtin runner of type `T`, e.g.
+    /// `vm.get_builtin::<HashBuiltinRunner>()`.
+    pub fn get_builtin<T: BuiltinRunnerVariant>(&self) -> Option<&T> {
+        self.builtin_runners.iter().find_map(T::from_builtin_runner)
+    }
+
+    /// Returns a mutable reference to the first builtin runner of type `T`.
+    pub fn get_builtin_mut<T: BuiltinRunnerVariant>(&mut self) -> Option<&mut T> {
+        self.builtin_runners
+            .iter_mut()
+            .find_map(T::from_builtin_runner_mut)
+    }
+
+    /// Returns the builtin runner with the given name, if present.
+    pub fn get_builtin_by_name(&self, name: BuiltinName) -> Option<&BuiltinRunner> {
+        self.builtin_runners
+            .iter()
+            .find(|builtin| builtin.name() == name)
+    }
+
+    /// Returns a mutable reference to the builtin runner with the given name, if present.
+    pub fn get_builtin_by_name_mut(&mut self, name: BuiltinName) -> Option<&mut BuiltinRunner> {
+        self.builtin_runners
+            .iter_mut()
+            .find(|builtin| builtin.name() == name)
+    }
+
     pub fn get_range_check_builtin(
         &self,
     ) -> Result<&RangeCheckBuiltinRunner<RC_N_PARTS_STANDARD>, VirtualMachineError> {
-        for builtin in &self.builtin_runners {
-            if let BuiltinRunner::RangeCheck(range_check_builtin) = builtin {
-                return Ok(range_check_builtin);
-            };
-        }
-        Err(VirtualMachineError::NoRangeCheckBuiltin)
+        self.get_builtin()
+            .ok_or(VirtualMachineError::NoRangeCheckBuiltin)
     }
 
     pub fn get_signature_builtin(
         &mut self,
     ) -> Result<&mut SignatureBuiltinRunner, VirtualMachineError> {
-        for builtin in self.get_builtin_runners_as_mut() {
-            if let BuiltinRunner::Signature(signature_builtin) = builtin {
-                return Ok(signature_builtin);
-            };
-        }
-
-        Err(VirtualMachineError::NoSignatureBuiltin)
+        self.get_builtin_mut()
+            .ok_or(VirtualMachineError::NoSignatureBuiltin)
     }
 
     pub fn get_output_builtin_mut(
         &mut self,
     ) -> Result<&mut OutputBuiltinRunner, VirtualMachineError> {
-        for builtin in self.get_builtin_runners_as_mut() {
-            if let BuiltinRunner::Output(output_builtin) = builtin {
-                return Ok(output_builtin);
-            };
+        self.get_builtin_mut()
+            .ok_or(VirtualMachineError::NoOutputBuiltin)
+    }
+
+    /// Sets a sink that will receive every [TraceEntry] as soon as it is generated.
+    ///
+    /// The sink works independently of the stored trace: to stream the trace without keeping
+    /// it in memory, create the VM with the trace disabled and set a sink.
+    pub fn set_trace_sink(&mut self, trace_sink: impl FnMut(TraceEntry) + Send + 'static) {
+        self.trace_sink = Some(Box::new(trace_sink));
+    }
+
+    /// Removes the current trace sink (if any) and returns it.
+    pub fn take_trace_sink(&mut self) -> Option<TraceSink> {
+        self.trace_sink.take()
+    }
+
+    /// Switches the trace to compact mode: entries are stored delta-encoded in a [CompactTrace]
+    /// instead of a `Vec<TraceEntry>`. Entries already in the trace are moved into the compact trace.
+    ///
+    /// The compact trace is decoded when relocating the trace, so the usual trace outputs are
+    /// still available after the run.
+    pub fn enable_compact_trace(&mut self) {
+        let mut compact_trace = self.compact_trace.take().unwrap_or_default();
+        for entry in self.trace.take().into_iter().flatten() {
+            compact_trace.push(&entry);
         }
+        self.compact_trace = Some(compact_trace);
+    }
+
+    /// Restricts the trace to the entries whose pc falls within any of the given ranges.
+    ///
+    /// Applies to the stored trace, the compact trace and the trace sink. A whole segment can be
+    /// selected with a range such as `(2, 0).into()..(3, 0).into()`.
+    /// Note that a filtered trace is not suitable for proving: once an entry was dropped,
+    /// relocating or encoding the trace fails with `TraceError::FilteredTrace`. The recorded
+    /// entries can still be read through the trace sink or the compact trace.
+    pub fn set_trace_pc_filter(&mut self, ranges: Vec<Range<Relocatable>>) {
+        self.trace_pc_filter = Some(ranges);
+    }
 
-        Err(VirtualMachineError::NoOutputBuiltin)
+    /// Removes the trace pc filter, so that every entry is recorded again.
+    pub fn clear_trace_pc_filter(&mut self) {
+        self.trace_pc_filter = None;
+    }
+
+    /// Returns a [Checkpoint] of the current state of the VM.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint::new(self)
+    }
+
+    /// Returns the checkpoints taken so far every `checkpoint_interval` steps,
+    /// see [VmConfig::checkpoint_interval].
+    pub fn get_checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    /// Returns the compact trace, if the compact trace mode is enabled
+    pub fn get_compact_trace(&self) -> Option<&CompactTrace> {
+        self.compact_trace.as_ref()
     }
 
     #[cfg(feature = "tracer")]
@@ -1027,11 +1376,7 @@ impl VirtualMachine {
         &mut self,
         writer: &mut impl core::fmt::Write,
     ) -> Result<(), VirtualMachineError> {
-        let builtin = match self
-            .builtin_runners
-            .iter()
-            .find(|b| b.name() == BuiltinName::output)
-        {
+        let builtin = match self.get_builtin_by_name(BuiltinName::output) {
             Some(x) => x,
             _ => return Ok(()),
         };
@@ -1057,6 +1402,46 @@ impl VirtualMachine {
         Ok(())
     }
 
+    /// Writes a human readable dump of the memory, grouped by segment.
+    ///
+    /// Each segment header shows the segment's name (or the builtin that owns it) along with its
+    /// occupied ranges. Its values are listed below, with markers for the gaps between them.
+    /// Pointers are prefixed with `->`, and integers that encode a short string are shown next
+    /// to their string representation.
+    pub fn dump_memory(&self, writer: &mut impl core::fmt::Write) -> core::fmt::Result {
+        for (index, segment) in self.segments.memory.temp_data.iter().enumerate() {
+            dump_memory_segment(writer, -(index as isize) - 1, "temporary", segment)?;
+        }
+        for (index, segment) in self.segments.memory.data.iter().enumerate() {
+            let name = match index {
+                0 => "program".to_string(),
+                1 => "execution".to_string(),
+                _ => match self.builtin_runners.iter().find(|b| b.base() == index) {
+                    Some(builtin) => builtin.name().to_string(),
+                    None => "user".to_string(),
+                },
+            };
+            dump_memory_segment(writer, index as isize, &name, segment)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the relocation table computed when relocating the run, which holds the final
+    /// (linear) base address of each segment, indexed by segment index.
+    /// Returns `None` if the run hasn't been relocated yet.
+    pub fn get_relocation_table(&self) -> Option<&[usize]> {
+        self.relocation_table.as_deref()
+    }
+
+    /// Returns the final (linear) address of a relocatable address, according to the relocation table.
+    pub fn get_relocated_address(&self, addr: Relocatable) -> Result<usize, MemoryError> {
+        let relocation_table = self
+            .relocation_table
+            .as_ref()
+            .ok_or(MemoryError::UnrelocatedMemory)?;
+        relocate_address(addr, relocation_table)
+    }
+
     /// Returns a list of addresses of memory cells that constitute the public memory.
     pub fn get_public_memory_addresses(&self) -> Result<Vec<(usize, usize)>, VirtualMachineError> {
         if let Some(relocation_table) = &self.relocation_table {
@@ -1117,12 +1502,8 @@ impl VirtualMachine {
         > {
             if let Some((ptr, n)) = mod_params {
                 let mod_builtin = self
-                    .builtin_runners
-                    .iter()
-                    .find_map(|b| match b {
-                        BuiltinRunner::Mod(b) if b.name() == mod_name => Some(b),
-                        _ => None,
-                    })
+                    .get_builtin_by_name(mod_name)
+                    .and_then(ModBuiltinRunner::from_builtin_runner)
                     .ok_or_else(|| VirtualMachineError::NoModBuiltin(mod_name))?;
                 if let Some(batch_size) = batch_size {
                     if mod_builtin.batch_size() != batch_size {
@@ -1162,15 +1543,73 @@ impl VirtualMachine {
     }
 }
 
+fn dump_memory_segment(
+    writer: &mut impl core::fmt::Write,
+    segment_index: isize,
+    name: &str,
+    segment: &[MemoryCell],
+) -> core::fmt::Result {
+    let mut occupied_ranges = Vec::new();
+    let mut range_start = None;
+    for (offset, cell) in segment.iter().enumerate() {
+        match (cell.is_some(), range_start) {
+            (true, None) => range_start = Some(offset),
+            (false, Some(start)) => {
+                occupied_ranges.push(format!("[{start}, {offset})"));
+                range_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = range_start {
+        occupied_ranges.push(format!("[{start}, {})", segment.len()));
+    }
+    writeln!(
+        writer,
+        "Segment {segment_index} ({name}), occupied: {}",
+        occupied_ranges.join(", ")
+    )?;
+
+    let mut gap = 0;
+    for (offset, cell) in segment.iter().enumerate() {
+        let Some(value) = cell.get_value() else {
+            gap += 1;
+            continue;
+        };
+        if gap != 0 {
+            writeln!(writer, "  ... {gap} empty cell(s)")?;
+            gap = 0;
+        }
+        match value {
+            MaybeRelocatable::RelocatableValue(rel) => {
+                writeln!(writer, "  {segment_index}:{offset}\t-> {rel}")?
+            }
+            MaybeRelocatable::Int(num) => match as_printable_short_string(&num) {
+                Some(short_string) => writeln!(
+                    writer,
+                    "  {segment_index}:{offset}\t{} ('{short_string}')",
+                    signed_felt(num)
+                )?,
+                None => writeln!(writer, "  {segment_index}:{offset}\t{}", signed_felt(num))?,
+            },
+        }
+    }
+    if gap != 0 {
+        writeln!(writer, "  ... {gap} empty cell(s)")?;
+    }
+    Ok(())
+}
+
 pub struct VirtualMachineBuilder {
     pub(crate) run_context: RunContext,
     pub(crate) builtin_runners: Vec<BuiltinRunner>,
     pub(crate) segments: MemorySegmentManager,
     pub(crate) trace: Option<Vec<TraceEntry>>,
+    pub(crate) trace_sink: Option<TraceSink>,
     pub(crate) current_step: usize,
     skip_instruction_execution: bool,
     run_finished: bool,
-    #[cfg(feature = "test_utils")]
+    #[cfg(feature = "hooks")]
     pub(crate) hooks: crate::vm::hooks::Hooks,
 }
 
@@ -1186,11 +1625,12 @@ impl Default for VirtualMachineBuilder {
             run_context,
             builtin_runners: Vec::new(),
             trace: None,
+            trace_sink: None,
             current_step: 0,
             skip_instruction_execution: false,
             segments: MemorySegmentManager::new(),
             run_finished: false,
-            #[cfg(feature = "test_utils")]
+            #[cfg(feature = "hooks")]
             hooks: Default::default(),
         }
     }
@@ -1217,6 +1657,14 @@ impl VirtualMachineBuilder {
         self
     }
 
+    pub fn trace_sink(
+        mut self,
+        trace_sink: impl FnMut(TraceEntry) + Send + 'static,
+    ) -> VirtualMachineBuilder {
+        self.trace_sink = Some(Box::new(trace_sink));
+        self
+    }
+
     pub fn current_step(mut self, current_step: usize) -> VirtualMachineBuilder {
         self.current_step = current_step;
         self
@@ -1235,7 +1683,7 @@ impl VirtualMachineBuilder {
         self
     }
 
-    #[cfg(feature = "test_utils")]
+    #[cfg(feature = "hooks")]
     pub fn hooks(mut self, hooks: crate::vm::hooks::Hooks) -> VirtualMachineBuilder {
         self.hooks = hooks;
         self
@@ -1246,13 +1694,21 @@ impl VirtualMachineBuilder {
             run_context: self.run_context,
             builtin_runners: self.builtin_runners,
             trace: self.trace,
+            trace_sink: self.trace_sink,
+            compact_trace: None,
+            trace_pc_filter: None,
+            trace_filtered: false,
+            checkpoint_interval: None,
+            checkpoints: Vec::new(),
             current_step: self.current_step,
             skip_instruction_execution: self.skip_instruction_execution,
             segments: self.segments,
             rc_limits: None,
             run_finished: self.run_finished,
             instruction_cache: Vec::new(),
-            #[cfg(feature = "test_utils")]
+            instruction_decoder: Arc::new(CairoInstructionDecoder),
+            disable_auto_deductions: false,
+            #[cfg(feature = "hooks")]
             hooks: self.hooks,
             relocation_table: None,
         }
@@ -3162,6 +3618,191 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_step_with_trace_pc_filter() {
+        let mut vm = vm!(true);
+        // Only record the entries executed by `myfunc`
+        vm.set_trace_pc_filter(vec![(0, 0).into()..(0, 3).into()]);
+
+        run_context!(vm, 3, 2, 2);
+
+        vm.segments.memory =
+            memory![
+            ((0, 0), 5207990763031199744_i64),
+            ((0, 1), 2),
+            ((0, 2), 2345108766317314046_i64),
+            ((0, 3), 5189976364521848832_i64),
+            ((0, 4), 1),
+            ((0, 5), 1226245742482522112_i64),
+            (
+                (0, 6),
+                ("3618502788666131213697322783095070105623107215331596699973092056135872020476",10)
+            ),
+            ((0, 7), 2345108766317314046_i64),
+            ((1, 0), (2, 0)),
+            ((1, 1), (3, 0))
+        ];
+
+        let final_pc = Relocatable::from((3, 0));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        while vm.run_context.pc != final_pc {
+            assert_matches!(
+                vm.step(
+                    &mut hint_processor,
+                    exec_scopes_ref!(),
+                    &mut Vec::new(),
+                    #[cfg(feature = "extensive_hints")]
+                    &mut HashMap::new(),
+                    &HashMap::new()
+                ),
+                Ok(())
+            );
+        }
+
+        assert_eq!(vm.current_step, 5);
+        assert!(vm.trace_filtered);
+        let trace = vm.trace.unwrap();
+        trace_check(&trace, &[((0, 0).into(), 5, 5), ((0, 2).into(), 6, 5)]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_step_with_trace_sink_and_trace_disabled() {
+        use crate::stdlib::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut vm = vm!(false);
+        let n_entries = Arc::new(AtomicUsize::new(0));
+        let pc_offsets_sum = Arc::new(AtomicUsize::new(0));
+        let (n_entries_sink, pc_offsets_sum_sink) = (n_entries.clone(), pc_offsets_sum.clone());
+        vm.set_trace_sink(move |entry: TraceEntry| {
+            n_entries_sink.fetch_add(1, Ordering::Relaxed);
+            pc_offsets_sum_sink.fetch_add(entry.pc.offset, Ordering::Relaxed);
+        });
+
+        run_context!(vm, 3, 2, 2);
+
+        vm.segments.memory =
+            memory![
+            ((0, 0), 5207990763031199744_i64),
+            ((0, 1), 2),
+            ((0, 2), 2345108766317314046_i64),
+            ((0, 3), 5189976364521848832_i64),
+            ((0, 4), 1),
+            ((0, 5), 1226245742482522112_i64),
+            (
+                (0, 6),
+                ("3618502788666131213697322783095070105623107215331596699973092056135872020476",10)
+            ),
+            ((0, 7), 2345108766317314046_i64),
+            ((1, 0), (2, 0)),
+            ((1, 1), (3, 0))
+        ];
+
+        let final_pc = Relocatable::from((3, 0));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        while vm.run_context.pc != final_pc {
+            assert_matches!(
+                vm.step(
+                    &mut hint_processor,
+                    exec_scopes_ref!(),
+                    &mut Vec::new(),
+                    #[cfg(feature = "extensive_hints")]
+                    &mut HashMap::new(),
+                    &HashMap::new()
+                ),
+                Ok(())
+            );
+        }
+
+        // The entries were streamed to the sink but not stored
+        assert!(vm.trace.is_none());
+        assert_eq!(n_entries.load(Ordering::Relaxed), 5);
+        // pcs: 3, 5, 0, 2, 7
+        assert_eq!(pc_offsets_sum.load(Ordering::Relaxed), 17);
+        assert!(vm.take_trace_sink().is_some());
+        assert!(vm.take_trace_sink().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn with_config_trace_options() {
+        let vm = VirtualMachine::with_config(VmConfig::new());
+        assert!(vm.trace.is_none());
+        assert!(vm.get_compact_trace().is_none());
+
+        let vm = VirtualMachine::with_config(VmConfig::new().trace_enabled(true));
+        assert_eq!(vm.trace, Some(Vec::new()));
+        assert!(vm.get_compact_trace().is_none());
+
+        let vm = VirtualMachine::with_config(
+            VmConfig::new()
+                .trace_enabled(true)
+                .compact_trace(true)
+                .trace_pc_filter(vec![(0, 0).into()..(1, 0).into()]),
+        );
+        assert!(vm.trace.is_none());
+        assert_eq!(vm.get_compact_trace(), Some(&CompactTrace::default()));
+        assert_eq!(vm.trace_pc_filter, Some(vec![(0, 0).into()..(1, 0).into()]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_and_set_registers() {
+        let mut vm = vm!();
+        run_context!(vm, 3, 2, 1);
+        let registers = vm.get_registers();
+        assert_eq!(
+            registers,
+            RegisterState {
+                pc: (0, 3).into(),
+                ap: 2,
+                fp: 1
+            }
+        );
+        assert_eq!(registers.pc, vm.get_pc());
+        assert_eq!(relocatable!(1, registers.ap), vm.get_ap());
+        assert_eq!(relocatable!(1, registers.fp), vm.get_fp());
+
+        vm.set_registers(RegisterState {
+            pc: (2, 0).into(),
+            ap: 7,
+            fp: 5,
+        });
+        assert_eq!(vm.get_pc(), relocatable!(2, 0));
+        assert_eq!(vm.get_ap(), relocatable!(1, 7));
+        assert_eq!(vm.get_fp(), relocatable!(1, 5));
+        vm.set_registers(registers);
+        assert_eq!(vm.get_registers(), registers);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_builtin_by_type_and_name() {
+        let mut vm = vm!();
+        vm.builtin_runners = vec![
+            OutputBuiltinRunner::new(true).into(),
+            HashBuiltinRunner::new(Some(8), true).into(),
+        ];
+        assert!(vm.get_builtin::<HashBuiltinRunner>().is_some());
+        assert!(vm.get_builtin::<BitwiseBuiltinRunner>().is_none());
+        assert!(vm.get_builtin_mut::<OutputBuiltinRunner>().is_some());
+        assert_matches!(
+            vm.get_builtin_by_name(BuiltinName::pedersen),
+            Some(BuiltinRunner::Hash(_))
+        );
+        assert!(vm.get_builtin_by_name(BuiltinName::ecdsa).is_none());
+        assert_matches!(
+            vm.get_builtin_by_name_mut(BuiltinName::output),
+            Some(BuiltinRunner::Output(_))
+        );
+        assert_matches!(
+            vm.get_range_check_builtin(),
+            Err(VirtualMachineError::NoRangeCheckBuiltin)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /// Test the following program:
@@ -3466,6 +4107,22 @@ mod tests {
         assert_eq!(operands_mem_address, expected_operands_mem_addresses);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell_auto_deductions_disabled() {
+        let mut vm = VirtualMachine::with_config(VmConfig::new().disable_auto_deductions(true));
+        let builtin = BitwiseBuiltinRunner::new(Some(256), true);
+        vm.builtin_runners.push(builtin.into());
+        vm.segments = segments![((0, 0), 12), ((0, 1), 10)];
+        assert_matches!(
+            vm.deduce_memory_cell(Relocatable::from((0, 2))),
+            Err(VirtualMachineError::MissingBuiltinOutput(bx))
+                if *bx == (BuiltinName::bitwise, Relocatable::from((0, 2)))
+        );
+        // Input cells are left to the instruction deductions
+        assert_matches!(vm.deduce_memory_cell(Relocatable::from((0, 5))), Ok(None));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn deduce_memory_cell_ec_op_builtin_valid() {
@@ -3745,6 +4402,83 @@ mod tests {
         assert_eq!(vm.get_return_values(4).unwrap(), expected);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn can_get_typed_return_values() {
+        let mut vm = vm!();
+        vm.set_ap(6);
+        vm.segments = segments![
+            ((1, 0), 7),
+            ((1, 1), 2),
+            ((1, 2), (2, 0)),
+            ((1, 3), (2, 1)),
+            ((1, 4), (2, 3)),
+            ((1, 5), 9),
+            ((2, 0), 10),
+            ((2, 1), 11),
+            ((2, 2), 12)
+        ];
+        let return_types = [
+            CairoReturnType::Single,
+            CairoReturnType::Composed(vec![CairoReturnType::Array, CairoReturnType::Span]),
+            CairoReturnType::Single,
+        ];
+        assert_eq!(
+            vm.get_typed_return_values(&return_types).unwrap(),
+            vec![
+                CairoArg::Single(7.into()),
+                CairoArg::Composed(vec![
+                    CairoArg::Array(vec![10.into(), 11.into()]),
+                    CairoArg::Array(vec![11.into(), 12.into()]),
+                ]),
+                CairoArg::Single(9.into()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_typed_return_values_fails_when_ap_is_too_low() {
+        let mut vm = vm!();
+        vm.set_ap(1);
+        vm.segments = segments![((1, 0), 1)];
+        assert_matches!(
+            vm.get_typed_return_values(&[CairoReturnType::Array]),
+            Err(MemoryError::FailedToGetReturnValues(bx))
+            if *bx == (2, Relocatable::from((1, 1)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_typed_return_values_fails_when_length_exceeds_segment() {
+        let mut vm = vm!();
+        vm.set_ap(2);
+        vm.segments = segments![((1, 0), 1000), ((1, 1), (2, 1)), ((2, 0), 7), ((2, 1), 8)];
+        assert_matches!(
+            vm.get_typed_return_values(&[CairoReturnType::Array]),
+            Err(MemoryError::GetRangeMemoryGap(bx))
+            if *bx == (Relocatable::from((2, 1)), 1000, Relocatable::from((2, 2)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_memory_holes_in_segment() {
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((0, 2), 3)];
+        vm.segments.memory.mark_as_accessed((0, 0).into());
+        assert_matches!(
+            vm.get_memory_holes_in_segment(0),
+            Err(MemoryError::MissingSegmentUsedSizes)
+        );
+        vm.segments.segment_used_sizes = Some(vec![4]);
+        assert_eq!(
+            vm.get_memory_holes_in_segment(0).unwrap(),
+            vec![(0, 1).into(), (0, 2).into(), (0, 3).into()]
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_return_values_fails_when_ap_is_0() {
@@ -3881,6 +4615,25 @@ mod tests {
         assert_eq!(builtins[1].name(), BuiltinName::bitwise);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn dump_memory_grouped_by_segment() {
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 5), ((1, 0), (2, 0)), ((1, 3), 18533), ((2, 0), 7)];
+        let mut output_builtin = OutputBuiltinRunner::new(true);
+        output_builtin.new_state(2, true);
+        vm.builtin_runners.push(output_builtin.into());
+
+        let mut dump = String::new();
+        vm.dump_memory(&mut dump).unwrap();
+        assert_eq!(
+            dump,
+            "Segment 0 (program), occupied: [0, 1)\n  0:0\t5\n\
+             Segment 1 (execution), occupied: [0, 1), [3, 4)\n  1:0\t-> 2:0\n  ... 2 empty cell(s)\n  1:3\t18533 ('He')\n\
+             Segment 2 (output_builtin), occupied: [0, 1)\n  2:0\t7\n"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn test_get_output_builtin_mut() {
@@ -3967,7 +4720,11 @@ mod tests {
 
         assert_eq!(
             vm.get_continuous_range(Relocatable::from((1, 0)), 3),
-            Err(MemoryError::GetRangeMemoryGap(Box::new(((1, 0).into(), 3))))
+            Err(MemoryError::GetRangeMemoryGap(Box::new((
+                (1, 0).into(),
+                3,
+                (1, 2).into()
+            ))))
         );
     }
 
@@ -4129,6 +4886,28 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn decode_current_instruction_custom_decoder() {
+        struct FailingDecoder;
+        impl InstructionDecoder for FailingDecoder {
+            fn decode(&self, _: &Felt252) -> Result<Instruction, VirtualMachineError> {
+                Err(VirtualMachineError::Unexpected)
+            }
+        }
+
+        let mut vm = VirtualMachine::with_config(
+            VmConfig::new().instruction_decoder(Arc::new(FailingDecoder)),
+        );
+        vm.segments = segments![((0, 0), 0x14A7800080008000_u64)];
+        assert_matches!(
+            vm.decode_current_instruction(),
+            Err(VirtualMachineError::Unexpected)
+        );
+        vm.instruction_decoder = Arc::new(CairoInstructionDecoder);
+        assert_matches!(vm.decode_current_instruction(), Ok(_));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn add_relocation_rule_test() {
@@ -4380,16 +5159,16 @@ mod tests {
                 fp: 1,
             }]));
 
-        #[cfg(feature = "test_utils")]
+        #[cfg(feature = "hooks")]
         fn before_first_step_hook(
             _vm: &mut VirtualMachine,
             _hint_data: &[Box<dyn Any>],
         ) -> Result<(), VirtualMachineError> {
             Err(VirtualMachineError::Unexpected)
         }
-        #[cfg(feature = "test_utils")]
+        #[cfg(feature = "hooks")]
         let virtual_machine_builder = virtual_machine_builder.hooks(crate::vm::hooks::Hooks::new(
-            Some(std::sync::Arc::new(before_first_step_hook)),
+            Some(crate::stdlib::sync::Arc::new(before_first_step_hook)),
             None,
             None,
         ));
@@ -4421,7 +5200,7 @@ mod tests {
                 fp: 1,
             }])
         );
-        #[cfg(feature = "test_utils")]
+        #[cfg(feature = "hooks")]
         {
             let program = crate::types::program::Program::from_bytes(
                 include_bytes!("../../../cairo_programs/sqrt.json"),
@@ -587,8 +587,21 @@ impl Memory {
         values
     }
 
+    /// Returns the current size of the segment `segment_index`, including its gaps.
+    /// Returns None if the segment doesn't exist
+    pub(crate) fn segment_len(&self, segment_index: isize) -> Option<usize> {
+        let (i, _) = from_relocatable_to_indexes((segment_index, 0).into());
+        let data = if segment_index.is_negative() {
+            &self.temp_data
+        } else {
+            &self.data
+        };
+        data.get(i).map(Vec::len)
+    }
+
     /// Gets a range of memory values from addr to addr + size
-    /// Fails if there if any of the values inside the range is missing (memory gap)
+    /// Fails if there if any of the values inside the range is missing (memory gap),
+    /// reporting the first missing address
     pub fn get_continuous_range(
         &self,
         addr: Relocatable,
@@ -597,9 +610,14 @@ impl Memory {
         let mut values = Vec::with_capacity(size);
 
         for i in 0..size {
-            values.push(match self.get(&(addr + i)?) {
+            let elem_addr = (addr + i)?;
+            values.push(match self.get(&elem_addr) {
                 Some(elem) => elem.into_owned(),
-                None => return Err(MemoryError::GetRangeMemoryGap(Box::new((addr, size)))),
+                None => {
+                    return Err(MemoryError::GetRangeMemoryGap(Box::new((
+                        addr, size, elem_addr,
+                    ))))
+                }
             });
         }
 
@@ -623,6 +641,50 @@ impl Memory {
         Ok(values)
     }
 
+    /// Gets a range of Felt252 memory values from addr to addr + size, without collecting them
+    /// The whole range is checked before returning, failing like [`get_integer_range`](Self::get_integer_range)
+    /// if any of the values is missing or is not a Felt252, so the values can then be read
+    /// from memory one by one without any allocation
+    pub fn get_felt_range(
+        &self,
+        addr: Relocatable,
+        size: usize,
+    ) -> Result<impl ExactSizeIterator<Item = Felt252> + '_, MemoryError> {
+        let data = if addr.segment_index.is_negative() {
+            &self.temp_data
+        } else {
+            &self.data
+        };
+        let (i, j) = from_relocatable_to_indexes(addr);
+        let cells = data
+            .get(i)
+            .and_then(|segment| segment.get(j..))
+            .unwrap_or_default();
+        for offset in 0..size {
+            let cell_addr = (addr + offset)?;
+            let value = cells
+                .get(offset)
+                .and_then(MemoryCell::get_value)
+                .ok_or_else(|| MemoryError::UnknownMemoryCell(Box::new(cell_addr)))?;
+            if self.cell_value_to_felt(value).is_none() {
+                return Err(MemoryError::ExpectedInteger(Box::new(cell_addr)));
+            }
+        }
+        Ok(cells[..size].iter().map(move |cell| {
+            cell.get_value()
+                .and_then(|value| self.cell_value_to_felt(value))
+                .unwrap_or_default()
+        }))
+    }
+
+    // Applies the relocation rules to a value read from memory, returning it if it is a Felt252
+    fn cell_value_to_felt(&self, value: MaybeRelocatable) -> Option<Felt252> {
+        match value {
+            MaybeRelocatable::Int(num) => Some(num),
+            value => self.relocate_value(&value).ok()?.get_int(),
+        }
+    }
+
     pub fn mark_as_accessed(&mut self, addr: Relocatable) {
         let (i, j) = from_relocatable_to_indexes(addr);
         let data = if addr.segment_index < 0 {
@@ -649,6 +711,54 @@ impl Memory {
         )
     }
 
+    /// Iterates over the values stored in the segment `segment_index`, skipping empty cells.
+    /// Negative indexes refer to temporary segments. Values are relocated like in [Memory::get].
+    pub fn iter_segment(
+        &self,
+        segment_index: isize,
+    ) -> impl Iterator<Item = (Relocatable, MaybeRelocatable)> + '_ {
+        let data = if segment_index.is_negative() {
+            &self.temp_data
+        } else {
+            &self.data
+        };
+        let (i, _) = from_relocatable_to_indexes((segment_index, 0).into());
+        data.get(i)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(move |(offset, cell)| {
+                let value = cell.get_value()?;
+                let value = self.relocate_value(&value).ok()?.into_owned();
+                Some(((segment_index, offset).into(), value))
+            })
+    }
+
+    /// Iterates over the values stored in the real (non-temporary) segments, in address order
+    pub fn iter(&self) -> impl Iterator<Item = (Relocatable, MaybeRelocatable)> + '_ {
+        (0..self.data.len()).flat_map(|segment_index| self.iter_segment(segment_index as isize))
+    }
+
+    /// Returns the addresses of the segment `segment_index` below `segment_size` that haven't
+    /// been accessed, either because they are empty or because no instruction read them.
+    /// For segments other than the builtin ones, these are the memory holes counted by
+    /// `MemorySegmentManager::get_memory_holes`.
+    pub fn get_unaccessed_addresses(
+        &self,
+        segment_index: usize,
+        segment_size: usize,
+    ) -> Vec<Relocatable> {
+        let segment = self.data.get(segment_index);
+        (0..segment_size)
+            .filter(|offset| {
+                !segment
+                    .and_then(|segment| segment.get(*offset))
+                    .is_some_and(|cell| cell.is_some() && cell.is_accessed())
+            })
+            .map(|offset| (segment_index as isize, offset).into())
+            .collect()
+    }
+
     // Inserts a value into memory & inmediately marks it as accessed if insertion was succesful
     // Used by ModBuiltinRunner, as it accesses memory outside of it's segment when operating
     pub(crate) fn insert_as_accessed<V>(
@@ -1346,7 +1456,67 @@ mod memory_tests {
 
         assert_eq!(
             memory.get_continuous_range(Relocatable::from((1, 0)), 3),
-            Err(MemoryError::GetRangeMemoryGap(Box::new(((1, 0).into(), 3))))
+            Err(MemoryError::GetRangeMemoryGap(Box::new((
+                (1, 0).into(),
+                3,
+                (1, 2).into()
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_felt_range_for_continuous_memory() {
+        let memory = memory![((1, 0), 2), ((1, 1), 3), ((1, 2), 4), ((1, 3), 5)];
+
+        let range = memory.get_felt_range(Relocatable::from((1, 1)), 3).unwrap();
+        assert_eq!(range.len(), 3);
+        assert_eq!(
+            range.collect::<Vec<_>>(),
+            vec![Felt252::from(3), Felt252::from(4), Felt252::from(5)]
+        );
+        assert_eq!(
+            memory
+                .get_felt_range(Relocatable::from((1, 4)), 0)
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_felt_range_fails_like_get_integer_range() {
+        let memory = memory![((1, 0), 2), ((1, 1), (2, 0)), ((1, 3), 4)];
+
+        for (addr, size) in [((1, 0), 2), ((1, 2), 1), ((1, 3), 2), ((3, 0), 1)] {
+            let addr = Relocatable::from(addr);
+            assert_eq!(
+                memory.get_felt_range(addr, size).err(),
+                memory.get_integer_range(addr, size).err()
+            );
+        }
+        assert_eq!(
+            memory.get_felt_range(Relocatable::from((1, 2)), 1).err(),
+            Some(MemoryError::UnknownMemoryCell(Box::new((1, 2).into())))
+        );
+        assert_eq!(
+            memory.get_felt_range(Relocatable::from((1, 0)), 2).err(),
+            Some(MemoryError::ExpectedInteger(Box::new((1, 1).into())))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_felt_range_temporary_segment() {
+        let memory = memory![((-1, 0), 7), ((-1, 1), 8)];
+
+        assert_eq!(
+            memory
+                .get_felt_range(Relocatable::from((-1, 0)), 2)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![Felt252::from(7), Felt252::from(8)]
         );
     }
 
@@ -1883,4 +2053,45 @@ mod memory_tests {
             ])
         )
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_memory() {
+        let mut memory = memory![((0, 0), 1), ((0, 2), (-1, 0)), ((1, 1), 5), ((-1, 0), 7)];
+        memory
+            .add_relocation_rule((-1, 0).into(), (1, 3).into())
+            .unwrap();
+
+        assert_eq!(
+            memory.iter().collect::<Vec<_>>(),
+            vec![
+                ((0, 0).into(), MaybeRelocatable::from(1)),
+                ((0, 2).into(), MaybeRelocatable::from((1, 3))),
+                ((1, 1).into(), MaybeRelocatable::from(5)),
+            ]
+        );
+        assert_eq!(
+            memory.iter_segment(-1).collect::<Vec<_>>(),
+            vec![((-1, 0).into(), MaybeRelocatable::from(7))]
+        );
+        assert_eq!(memory.iter_segment(2).count(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_unaccessed_addresses() {
+        let mut memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 3), 4)];
+        memory.mark_as_accessed((0, 0).into());
+        memory.mark_as_accessed((0, 3).into());
+
+        assert_eq!(
+            memory.get_unaccessed_addresses(0, 5),
+            vec![(0, 1).into(), (0, 2).into(), (0, 4).into()]
+        );
+        assert_eq!(
+            memory.get_unaccessed_addresses(1, 2),
+            vec![(1, 0).into(), (1, 1).into()]
+        );
+    }
+
 }
@@ -8,7 +8,7 @@ use num_traits::Zero;
 
 use crate::stdlib::prelude::*;
 use crate::stdlib::{any::Any, collections::HashMap};
-use crate::vm::runners::cairo_runner::CairoArg;
+use crate::types::cairo_arg::CairoArg;
 
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},